@@ -1,5 +1,5 @@
 #import bevy_pbr::{
-    mesh_view_bindings::view,
+    mesh_view_bindings::{view, globals},
     forward_io::VertexOutput,
 }
 
@@ -13,13 +13,13 @@ struct SdfSphere {
     stretch_factor: f32,
     ripple_phase: f32,
     ripple_amplitude: f32,
-    spike_amount: f32,
+    glow: f32,          // Additive emission (0 = none, 1 = full flash)
     digit_value: u32,
+    spike: f32,         // Spiky displacement for invalid nodes (0 = smooth)
 }
 
 struct SdfCylinder {
     start: vec3<f32>,
-    _padding1: f32,
     end: vec3<f32>,
     radius: f32,
     color: vec4<f32>,
@@ -28,18 +28,47 @@ struct SdfCylinder {
     node_a_idx: u32,
     node_b_idx: u32,
 
-    // Tension wave animation
-    wave_phase: f32,      // Where the wave is (0-1), -1 = no wave
-    wave_amplitude: f32,  // Strength of squeeze
+    // Tension wave animation (up to 3 at once, xyz used, w unused)
+    wave_phases: vec4<f32>,      // Where each wave is (0-1), -1 = no wave
+    wave_amplitudes: vec4<f32>,  // Strength of each squeeze
+
+    // Melt-in animation: multiplier on blend_smoothness (0 = hard seam, 1 = full goo)
+    blend: f32,
+
+    // Draw-in animation: fraction of the start→end length that is visible (0-1)
+    grow_progress: f32,
+
+    // Spring stretch (0 = relaxed, 1 = white-hot) for tension coloring
+    tension: f32,
+
+    // Position in draw order (0 = first edge of the trail, 1 = most recent)
+    order_t: f32,
+
+    // Animated current: 1 = flows start→end, -1 = end→start, 0 = still
+    flow_direction: f32,
+}
+
+struct SdfParticle {
+    position: vec3<f32>,
+    radius: f32,
+    color: vec4<f32>,  // Glow color, alpha = current brightness
 }
 
 struct SdfSceneUniform {
     num_spheres: u32,
     num_cylinders: u32,
-    _padding1: u32,
-    _padding2: u32,
+    blend_smoothness: f32,
+    num_particles: u32,
+    shockwave_center: vec3<f32>,  // Where the completion shockwave started
+    shockwave_radius: f32,        // Current ring radius (world units)
+    shockwave_strength: f32,      // Glow boost of the ring (0 = none)
+    presentation: u32,            // 0 = through the board plane, 1 = full-screen orbit
+    orbit_fov: f32,               // Vertical field of view of the orbit camera (radians)
+    orbit_eye: vec3<f32>,         // Where the orbit camera sits
+    orbit_target: vec3<f32>,      // What the orbit camera looks at
     spheres: array<SdfSphere, 9>,
     cylinders: array<SdfCylinder, 17>,
+    particles: array<SdfParticle, 72>,
 }
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0)
@@ -58,6 +87,37 @@ struct DigitUvs {
 @group(#{MATERIAL_BIND_GROUP}) @binding(3)
 var<uniform> digit_uvs: DigitUvs;
 
+struct SdfBackground {
+    intensity: f32,  // 0 = flat base color, 1 = full environment
+    color_a: vec4<f32>,  // Deep color (bottom)
+    color_b: vec4<f32>,  // Light color (top)
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(4)
+var<uniform> background: SdfBackground;
+
+struct SdfTheme {
+    light_direction: vec3<f32>,  // Key light direction (normalized on CPU)
+    glow_intensity: f32,         // Multiplier on additive glow and rim light
+    background_tint: vec4<f32>,  // Tint over the environment (w = amount)
+    edge_tint: vec4<f32>,        // Tint blended into edges (w = amount)
+    ambient: f32,                // Brightness of the unlit side
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(5)
+var<uniform> theme: SdfTheme;
+
+struct SdfQuality {
+    march_steps: u32,        // Raymarch iterations looking for a surface
+    ao_samples: u32,         // Ambient occlusion taps along the normal (0 = none)
+    wave_slots: u32,         // Tension waves drawn per edge (of the 3 tracked)
+    background_detail: u32,  // 0 = plain gradient, 1 = + noise and grid warp, 2 = + blobs
+    edge_softness: f32,      // Silhouette fade width in pixels (0 = hard edges)
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(6)
+var<uniform> quality: SdfQuality;
+
 /// Sample a digit from the MSDF atlas
 /// Returns alpha value (0.0 = transparent, 1.0 = opaque)
 fn sample_digit(digit_value: u32, local_uv: vec2<f32>) -> f32 {
@@ -118,6 +178,24 @@ fn sdf_ellipsoid(p: vec3<f32>, center: vec3<f32>, radius: f32,
     return length(deformed) - radius;
 }
 
+/// Spiky displacement for invalid nodes: sharp cones poking out of the surface
+fn apply_spikes(base_sdf: f32, p: vec3<f32>, center: vec3<f32>, radius: f32, spike: f32) -> f32 {
+    if spike < 0.01 {
+        return base_sdf;
+    }
+
+    let dir = normalize(p - center + vec3<f32>(1e-5));
+    let theta = atan2(dir.y, dir.x);
+    let phi = acos(clamp(dir.z, -1.0, 1.0));
+
+    // Peaks of |sin·sin| become narrow cones when raised to a high power
+    let pattern = pow(abs(sin(theta * 4.0) * sin(phi * 4.0)), 6.0);
+    let displaced = base_sdf - pattern * spike * radius * 0.35;
+
+    // Displacement breaks the distance bound, so take shorter steps near spiky nodes
+    return displaced * mix(1.0, 0.6, spike);
+}
+
 // fn sdf_ellipsoid(p: vec3<f32>, center: vec3<f32>, radius: f32,
 //     stretch_dir: vec3<f32>, stretch: f32) -> f32 {
 //     let local_p = p - center;
@@ -131,6 +209,11 @@ fn sdf_ellipsoid(p: vec3<f32>, center: vec3<f32>, radius: f32,
 //     return length(deformed) - radius;
 // }
 
+/// Visible end point of a cylinder while it grows out of its start node
+fn cylinder_end(cyl: SdfCylinder) -> vec3<f32> {
+    return mix(cyl.start, cyl.end, clamp(cyl.grow_progress, 0.0, 1.0));
+}
+
 /// SDF for a regular cylinder (constant radius)
 fn sdf_cylinder(p: vec3<f32>, a: vec3<f32>, b: vec3<f32>, radius: f32) -> f32 {
     let pa = p - a;
@@ -146,12 +229,12 @@ fn sdf_rubber_band(
     a: vec3<f32>,
     b: vec3<f32>,
     base_radius: f32,
-    wave_phase: f32,      // Where the wave is (0-1), -1 = no wave
-    wave_amplitude: f32   // Strength of squeeze
+    wave_phases: vec4<f32>,     // Where each wave is (0-1), -1 = no wave
+    wave_amplitudes: vec4<f32>  // Strength of each squeeze
 ) -> f32 {
     let pa = p - a;
     let ba = b - a;
-    let h = clamp(dot(pa, ba) / dot(ba, ba), 0.0, 1.0);
+    let h = clamp(dot(pa, ba) / max(dot(ba, ba), 1e-5), 0.0, 1.0);
 
     // Base rubber band shape (parabola)
     let center_dist = abs(h - 0.5) * 2.0;  // 0 at center, 1 at ends
@@ -160,9 +243,15 @@ fn sdf_rubber_band(
     let base_thickness = min_thickness + (max_thickness - min_thickness) * center_dist * center_dist;
 
     // === TENSION WAVE EFFECT ===
-    var wave_effect = 1.0;
+    // Sum the squeeze of every active wave so stacked waves don't cancel each other out
+    var total_squeeze = 0.0;
+
+    for (var w = 0u; w < min(quality.wave_slots, 3u); w++) {
+        let wave_phase = wave_phases[w];
+        if wave_phase < 0.0 {  // Wave slot is empty
+            continue;
+        }
 
-    if wave_phase >= 0.0 {  // Wave is active
         // Distance from wave position
         let dist_from_wave = abs(h - wave_phase);
 
@@ -170,11 +259,13 @@ fn sdf_rubber_band(
         let wave_width = 0.15;  // How wide the squeeze is
         let squeeze = exp(-dist_from_wave * dist_from_wave / (wave_width * wave_width));
 
-        // Squeeze factor: 1.0 = normal, 0.4 = very squeezed
-        let min_squeeze = 0.4;  // How much to squeeze (lower = more squeeze)
-        wave_effect = mix(1.0, min_squeeze, squeeze * wave_amplitude);
+        total_squeeze += squeeze * wave_amplitudes[w];
     }
 
+    // Squeeze factor: 1.0 = normal, 0.4 = very squeezed
+    let min_squeeze = 0.4;  // How much to squeeze (lower = more squeeze)
+    let wave_effect = mix(1.0, min_squeeze, clamp(total_squeeze, 0.0, 1.0));
+
     let thickness_curve = base_thickness * wave_effect;
     let radius = base_radius * thickness_curve;
 
@@ -307,6 +398,8 @@ fn sdf_scene(p: vec3<f32>) -> vec3<f32> {  // Returns (distance, sphere_idx, is_
             d = apply_ripple(d, p, sphere.center, sphere.ripple_phase, sphere.ripple_amplitude);
         }
         //d = apply_ripple(d, p, sphere.center, sphere.ripple_phase, sphere.ripple_amplitude);
+
+        d = apply_spikes(d, p, sphere.center, sphere.radius, sphere.spike);
         // d = apply_ripple_pop(d, p, sphere.center, sphere.ripple_phase, sphere.ripple_amplitude);
 
         if d < min_dist {
@@ -328,12 +421,13 @@ fn sdf_scene(p: vec3<f32>) -> vec3<f32> {  // Returns (distance, sphere_idx, is_
             d = sdf_cylinder(p, cyl.start, cyl.end, cyl.radius);
         } else {
             // Regular edge: rubber band shape with tension wave
-            d = sdf_rubber_band(p, cyl.start, cyl.end, cyl.radius, cyl.wave_phase, cyl.wave_amplitude);
+            d = sdf_rubber_band(p, cyl.start, cylinder_end(cyl), cyl.radius, cyl.wave_phases, cyl.wave_amplitudes);
         }
 
-        // Smooth blend
+        // Smooth blend (per-edge melt scales the scene-wide smoothness)
         let old_dist = min_dist;
-        min_dist = smin(min_dist, d, 0.15);
+        let k = max(data.blend_smoothness * cyl.blend, 0.0001);
+        min_dist = smin(min_dist, d, k);
 
         // If cylinder is now closest, mark it
         if d < old_dist - 0.05 {
@@ -345,14 +439,22 @@ fn sdf_scene(p: vec3<f32>) -> vec3<f32> {  // Returns (distance, sphere_idx, is_
     return vec3<f32>(min_dist, closest_sphere_idx, is_sphere);
 }
 
+/// Where the last `raymarch` came closest to the scene: (distance, t, sphere_idx,
+/// is_sphere), for smoothing the silhouettes of rays that just miss
+var<private> closest_approach: vec4<f32>;
+
 fn raymarch(ro: vec3<f32>, rd: vec3<f32>) -> vec3<f32> {  // Returns (t, sphere_idx, is_sphere)
     var t = 0.0;
     var sphere_idx = -1.0;
     var is_sphere = 0.0;
+    closest_approach = vec4<f32>(1e9, 0.0, -1.0, 0.0);
 
-    for (var i = 0; i < 128; i++) {
+    for (var i = 0u; i < quality.march_steps; i++) {
         let result = sdf_scene(ro + rd * t);
         let d = result.x;
+        if d < closest_approach.x {
+            closest_approach = vec4<f32>(d, t, result.y, result.z);
+        }
 
         if d < 0.001 {
             sphere_idx = result.y;
@@ -374,6 +476,22 @@ fn normal_at(p: vec3<f32>) -> vec3<f32> {
     return normalize(vec3<f32>(dx, dy, dz));
 }
 
+/// Ambient occlusion: how much nearby geometry crowds the surface (1 = open)
+///
+/// Samples the scene at growing distances along the normal; where blobs meet, the field
+/// there is closer than the step, and the surface darkens.
+fn ambient_occlusion(p: vec3<f32>, n: vec3<f32>) -> f32 {
+    var occlusion = 0.0;
+    var weight = 1.0;
+    for (var i = 1u; i <= quality.ao_samples; i++) {
+        let step = 0.04 * f32(i);
+        let d = sdf_scene(p + n * step).x;
+        occlusion += (step - d) * weight;
+        weight *= 0.6;
+    }
+    return clamp(1.0 - occlusion * 3.0, 0.0, 1.0);
+}
+
 /// Convert RGB to HSV for color wheel blending
 fn rgb_to_hsv(c: vec3<f32>) -> vec3<f32> {
     let K = vec4<f32>(0.0, -1.0 / 3.0, 2.0 / 3.0, -1.0);
@@ -489,6 +607,87 @@ fn apply_ripple_warp(p: vec3<f32>) -> vec3<f32> {
     return warped;
 }
 
+/// How much the completion shockwave boosts a point (0 = untouched)
+fn shockwave_boost(p: vec3<f32>) -> f32 {
+    if data.shockwave_strength <= 0.0 {
+        return 0.0;
+    }
+    let ring_width = 0.6;
+    let d = (length(p.xy - data.shockwave_center.xy) - data.shockwave_radius) / ring_width;
+    return data.shockwave_strength * exp(-d * d);
+}
+
+/// Additive glow of all particles along a ray, up to distance max_t
+fn particle_glow(ro: vec3<f32>, rd: vec3<f32>, max_t: f32) -> vec3<f32> {
+    var glow = vec3<f32>(0.0);
+    for (var i = 0u; i < data.num_particles; i++) {
+        let particle = data.particles[i];
+        let t = dot(particle.position - ro, rd);
+        if t < 0.0 || t > max_t {
+            continue;  // Behind the camera or hidden by a surface
+        }
+
+        // Distance from the particle center to the closest point on the ray
+        let d = length(particle.position - (ro + rd * t));
+        let core = 1.0 - smoothstep(0.0, particle.radius, d);
+        let halo = exp(-d * d / (particle.radius * particle.radius * 4.0)) * 0.5;
+
+        glow += particle.color.rgb * particle.color.a * (core + halo);
+    }
+    return glow;
+}
+
+/// Cheap 2D hash for value noise
+fn hash2(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+/// Smooth value noise in [0, 1]
+fn value_noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+
+    let a = hash2(i);
+    let b = hash2(i + vec2<f32>(1.0, 0.0));
+    let c = hash2(i + vec2<f32>(0.0, 1.0));
+    let d = hash2(i + vec2<f32>(1.0, 1.0));
+
+    return mix(mix(a, b, u.x), mix(c, d, u.x), u.y);
+}
+
+/// Slow noise-displaced gradient with a few distant drifting blobs
+fn render_environment(world_pos: vec3<f32>) -> vec3<f32> {
+    let t = globals.time;
+
+    // Two octaves of drifting noise bend the vertical gradient
+    var n = 0.75;  // The noise's average, for a plain gradient
+    if quality.background_detail >= 1u {
+        let p = world_pos.xy * 0.35;
+        n = value_noise(p + vec2<f32>(t * 0.05, -t * 0.035))
+            + 0.5 * value_noise(p * 2.1 - vec2<f32>(t * 0.065, t * 0.05));
+    }
+    let gradient = clamp(world_pos.y / 8.0 + (n - 0.75) * 0.35, 0.0, 1.0);
+
+    var color = mix(background.color_a.rgb, background.color_b.rgb, gradient);
+    if quality.background_detail < 2u {
+        return mix(background.color_a.rgb, color, background.intensity);
+    }
+
+    // Distant blobs wandering behind the board
+    var blobs = 0.0;
+    for (var i = 0; i < 3; i++) {
+        let fi = f32(i);
+        let center = vec2<f32>(2.25, 4.0)
+            + vec2<f32>(sin(t * 0.07 + fi * 2.1), cos(t * 0.05 + fi * 1.7)) * vec2<f32>(2.0, 3.0);
+        let d = length(world_pos.xy - center);
+        blobs += exp(-d * d / 2.25);
+    }
+    color += background.color_b.rgb * blobs * 0.35;
+
+    return mix(background.color_a.rgb, color, background.intensity);
+}
+
 /// Render warped grid background
 fn render_background_ripples(world_pos: vec3<f32>) -> vec4<f32> {
     // Ripple expansion
@@ -505,7 +704,10 @@ fn render_background_ripples(world_pos: vec3<f32>) -> vec4<f32> {
     let grid_glow = 0.5;          // How much grid lights up (0.2-1.0)
 
     // === WARP THE SPACE ===
-    let warped_pos = apply_ripple_warp(world_pos);
+    var warped_pos = world_pos;
+    if quality.background_detail >= 1u {
+        warped_pos = apply_ripple_warp(world_pos);
+    }
 
     // === GRID PATTERN (sampled at warped position) ===
     let grid_spacing = 0.5;
@@ -545,14 +747,17 @@ fn render_background_ripples(world_pos: vec3<f32>) -> vec4<f32> {
     }
 
     // === GRID COLORING ===
-    let base_color = vec3<f32>(0.05, 0.08, 0.12);      // Dark background
+    let environment = render_environment(world_pos);
+    let base_color = mix(environment, theme.background_tint.rgb, theme.background_tint.w);
     let grid_base = vec3<f32>(0.15, 0.18, 0.22);       // Grid lines
 
-    let grid_with_glow = grid_base + ripple_color * grid_glow;
+    let shock = shockwave_boost(world_pos);
+    let grid_with_glow = grid_base + ripple_color * grid_glow + grid_base * shock * 2.0;
     let background_color = select(base_color, grid_with_glow, is_grid);
     let final_color = background_color + ripple_color * color_strength;
 
-    let base_alpha = select(0.2, 0.5, is_grid);
+    let environment_alpha = mix(0.2, 0.9, background.intensity);
+    let base_alpha = select(environment_alpha, max(environment_alpha, 0.5), is_grid);
     let ripple_alpha = ripple_intensity * 0.6;
     let alpha = base_alpha + ripple_alpha;
 
@@ -560,211 +765,319 @@ fn render_background_ripples(world_pos: vec3<f32>) -> vec4<f32> {
 }
 
 
-@fragment
-fn fragment(in: VertexOutput) -> FragOut {
-    let cam = view.world_position;
-    let ro = cam;
-    let rd = normalize(in.world_position.xyz - cam);
-
-    let result = raymarch(ro, rd);
-    let t = result.x;
-    let idx = i32(result.y);
-    let is_sphere = result.z > 0.5;
-
-    if t > 0.0 && idx >= 0 {
-        let hit = ro + rd * t;
-        let n = normal_at(hit);
-
-        // === LIGHTING ===
-        let light_dir = normalize(vec3<f32>(1.0, 1.0, 1.0));
-        let view_dir = normalize(cam - hit);
-
-        // Cel-shaded diffuse
-        let diffuse_raw = max(dot(n, light_dir), 0.0);
-        let diffuse_stepped = smoothstep(0.3, 0.35, diffuse_raw);
-
-        // Sharp specular highlight
-        let half_dir = normalize(light_dir + view_dir);
-        let spec_raw = pow(max(dot(n, half_dir), 0.0), 64.0);
-        let specular = step(0.8, spec_raw) * 1.5;
-
-        let lit = mix(0.6, 1.2, diffuse_stepped);
-        let lighting = lit + specular;
-
-        // === COLOR ===
-        var base_color: vec4<f32>;
-        var position_along_cylinder: f32 = 0.5;
-        var glow: f32 = 0.0;  // Track glow intensity (multi-purpose)
-
-        if is_sphere {
-            let sphere = data.spheres[idx];
-            base_color = sphere.color;
-            glow = sphere.spike_amount;  // Get glow from spike_amount field
-        } else {
-            let cyl = data.cylinders[idx];
-            let to_hit = hit - cyl.start;
-            let cyl_dir = cyl.end - cyl.start;
-            let t_cyl = clamp(dot(to_hit, cyl_dir) / dot(cyl_dir, cyl_dir), 0.0, 1.0);
-
-            position_along_cylinder = t_cyl;
-
-            let sphere_a = data.spheres[cyl.node_a_idx];
-            let sphere_b = data.spheres[cyl.node_b_idx];
-
-            // === SHARPER GRADIENT WITH POWER CURVE ===
-            // Push colors toward the ends, narrow the middle transition
-            let sharpness = 3.0;  // Higher = sharper (try 2.0-5.0)
-            let sharp_t = pow(t_cyl, sharpness) / (pow(t_cyl, sharpness) + pow(1.0 - t_cyl, sharpness));
-
-            // Blend with the sharpened gradient
-            let strength_a = 1.0 - sharp_t;
-            let strength_b = sharp_t;
-
-            // Use regular RGB mix to preserve node colors
-            let mixed_color = sphere_a.color.rgb * strength_a + sphere_b.color.rgb * strength_b;
+/// Light and color the surface a ray hits `t` along its way (`plane_depth` is the
+/// depth of the pixel on the plane, used where the game camera can't place the hit)
+fn shade_surface(
+    ro: vec3<f32>,
+    rd: vec3<f32>,
+    t: f32,
+    idx: i32,
+    is_sphere: bool,
+    plane_depth: f32,
+) -> FragOut {
+    let cam = ro;
+    let orbiting = data.presentation == 1u;
+    let hit = ro + rd * t;
+    let n = normal_at(hit);
+
+    // === LIGHTING ===
+    let light_dir = theme.light_direction;
+    let view_dir = normalize(cam - hit);
+
+    // Cel-shaded diffuse
+    let diffuse_raw = max(dot(n, light_dir), 0.0);
+    let diffuse_stepped = smoothstep(0.3, 0.35, diffuse_raw);
+
+    // Sharp specular highlight
+    let half_dir = normalize(light_dir + view_dir);
+    let spec_raw = pow(max(dot(n, half_dir), 0.0), 64.0);
+    let specular = step(0.8, spec_raw) * 1.5;
+
+    let lit = mix(theme.ambient, 1.2, diffuse_stepped) * ambient_occlusion(hit, n);
+    let lighting = lit + specular;
+
+    // === COLOR ===
+    var base_color: vec4<f32>;
+    var position_along_cylinder: f32 = 0.5;
+    var glow: f32 = 0.0;  // Emissive strength (hover, last added, hint, celebration)
+
+    if is_sphere {
+        let sphere = data.spheres[idx];
+        base_color = sphere.color;
+        glow = sphere.glow;
+    } else {
+        let cyl = data.cylinders[idx];
+        let to_hit = hit - cyl.start;
+        let cyl_dir = cylinder_end(cyl) - cyl.start;
+        let t_cyl = clamp(dot(to_hit, cyl_dir) / max(dot(cyl_dir, cyl_dir), 1e-5), 0.0, 1.0);
+
+        position_along_cylinder = t_cyl;
+
+        let sphere_a = data.spheres[cyl.node_a_idx];
+        let sphere_b = data.spheres[cyl.node_b_idx];
+
+        // === SHARPER GRADIENT WITH POWER CURVE ===
+        // Push colors toward the ends, narrow the middle transition
+        let sharpness = 3.0;  // Higher = sharper (try 2.0-5.0)
+        let sharp_t = pow(t_cyl, sharpness) / (pow(t_cyl, sharpness) + pow(1.0 - t_cyl, sharpness));
+
+        // Blend with the sharpened gradient
+        let strength_a = 1.0 - sharp_t;
+        let strength_b = sharp_t;
+
+        // Use regular RGB mix to preserve node colors
+        let node_mix = sphere_a.color.rgb * strength_a + sphere_b.color.rgb * strength_b;
+        let tinted_color = mix(node_mix, theme.edge_tint.rgb, theme.edge_tint.w);
+
+        // === TRAIL ORDER GRADIENT ===
+        // Early edges lean cool, recent edges lean warm (subtle, so node colors still read)
+        let cool = vec3<f32>(0.35, 0.55, 1.0);
+        let warm = vec3<f32>(1.0, 0.6, 0.3);
+        let order_tint = mix(cool, warm, clamp(cyl.order_t, 0.0, 1.0));
+        var mixed_color = mix(tinted_color, tinted_color * order_tint * 1.3, 0.25);
+
+        // === PREVIEW VALIDITY ===
+        // Preview edges carry a green/red validity tint, alpha = how much it overrides
+        if cyl.node_a_idx == cyl.node_b_idx {
+            mixed_color = mix(mixed_color, cyl.color.rgb, cyl.color.a);
+        }
 
-            // Thickness brightness (independent of color)
-            let dist_from_center = abs(t_cyl - 0.5) * 2.0;
-            let thickness_brightness = mix(0.6, 1.0, dist_from_center * dist_from_center);
+        // Thickness brightness (independent of color)
+        let dist_from_center = abs(t_cyl - 0.5) * 2.0;
+        let thickness_brightness = mix(0.6, 1.0, dist_from_center * dist_from_center);
+
+        // === TENSION HEAT ===
+        // Stretched edges glow red, then white-hot at full stretch
+        let heat = clamp(cyl.tension, 0.0, 1.0);
+        let hot_red = vec3<f32>(1.0, 0.15, 0.05);
+        let white_hot = vec3<f32>(1.0, 0.95, 0.85);
+        let heat_color = mix(hot_red, white_hot, smoothstep(0.5, 1.0, heat));
+        let heated_color = mix(mixed_color, heat_color, smoothstep(0.0, 0.6, heat) * 0.8);
+
+        // === DIRECTIONAL FLOW ===
+        // Noise scrolled along the edge in draw direction, stronger near the trail head
+        let edge_length = length(cylinder_end(cyl) - cyl.start);
+        let flow_uv = vec2<f32>(
+            t_cyl * edge_length * 4.0 - globals.time * 1.5 * cyl.flow_direction,
+            n.z * 2.0
+        );
+        let current = smoothstep(0.55, 0.85, value_noise(flow_uv)) * abs(cyl.flow_direction);
+        let flow_strength = current * mix(0.15, 0.35, clamp(cyl.order_t, 0.0, 1.0));
+        let flowing_color = heated_color * (1.0 + flow_strength) + vec3<f32>(flow_strength * 0.1);
 
-            base_color = vec4<f32>(mixed_color * thickness_brightness, 1.0);
-        }
+        base_color = vec4<f32>(flowing_color * thickness_brightness, 1.0);
+    }
 
-        // === COLOR BOOST ===
-        let saturation_boost = 1.6;
-        let brightness_boost = 1.4;
-
-        let gray = dot(base_color.rgb, vec3<f32>(0.299, 0.587, 0.114));
-        let boosted_color = mix(vec3<f32>(gray), base_color.rgb, saturation_boost) * brightness_boost;
-        var clamped_color = clamp(boosted_color, vec3<f32>(0.0), vec3<f32>(1.0));
-
-        // === RENDER DIGIT (INSIDE SPHERE) ===
-        if is_sphere {
-            let sphere = data.spheres[idx];
-            let to_cam = normalize(cam - sphere.center);
-            let is_front_face = to_cam.z > 0.5;
-
-            if is_front_face {
-                let right = vec3<f32>(1.0, 0.0, 0.0);
-                let up = vec3<f32>(0.0, 1.0, 0.0);
-
-                // === VELOCITY-BASED LAG EFFECT ===
-                // When sphere moves/stretches, digit lags behind
-                let is_moving = sphere.stretch_factor > 1.05;
-                let lag_amount = max(sphere.stretch_factor - 1.0, 0.0) * 0.7;
-
-                // Digit lags opposite to stretch direction (feels suspended)
-                let digit_offset = select(
-                    vec3<f32>(0.0),
-                    -sphere.stretch_direction * sphere.radius * lag_amount,
-                    is_moving
+    // === COLOR BOOST ===
+    let saturation_boost = 1.6;
+    let brightness_boost = 1.4;
+
+    let gray = dot(base_color.rgb, vec3<f32>(0.299, 0.587, 0.114));
+    let boosted_color = mix(vec3<f32>(gray), base_color.rgb, saturation_boost) * brightness_boost;
+    var clamped_color = clamp(boosted_color, vec3<f32>(0.0), vec3<f32>(1.0));
+
+    // === RENDER DIGIT (INSIDE SPHERE) ===
+    if is_sphere {
+        let sphere = data.spheres[idx];
+        let to_cam = normalize(cam - sphere.center);
+        let is_front_face = to_cam.z > 0.5;
+
+        if is_front_face {
+            let right = vec3<f32>(1.0, 0.0, 0.0);
+            let up = vec3<f32>(0.0, 1.0, 0.0);
+
+            // === VELOCITY-BASED LAG EFFECT ===
+            // When sphere moves/stretches, digit lags behind
+            let is_moving = sphere.stretch_factor > 1.05;
+            let lag_amount = max(sphere.stretch_factor - 1.0, 0.0) * 0.7;
+
+            // Digit lags opposite to stretch direction (feels suspended)
+            let digit_offset = select(
+                vec3<f32>(0.0),
+                -sphere.stretch_direction * sphere.radius * lag_amount,
+                is_moving
+            );
+
+            // Clamp to keep digit inside sphere (max 50% of radius for visibility)
+            let offset_length = length(digit_offset);
+            let max_offset = sphere.radius * 0.9;
+            let clamped_offset = select(
+                digit_offset,
+                normalize(digit_offset) * max_offset,
+                offset_length > max_offset
+            );
+
+            let digit_center = sphere.center + clamped_offset;
+
+            let plane_z = digit_center.z;
+            let t_to_plane = (plane_z - ro.z) / rd.z;
+            let plane_hit = ro + rd * t_to_plane;
+
+            let to_plane_hit = plane_hit - digit_center;
+            let u = dot(to_plane_hit, right) / (sphere.radius * 0.6);
+            let v = dot(to_plane_hit, up) / (sphere.radius * 0.6);
+
+            if abs(u) < 1.0 && abs(v) < 1.0 {
+                let digit_uv = vec2<f32>(
+                    (u + 1.0) * 0.5,
+                    1.0 - (v + 1.0) * 0.5
                 );
+                let digit_alpha = sample_digit(sphere.digit_value, digit_uv);
 
-                // Clamp to keep digit inside sphere (max 50% of radius for visibility)
-                let offset_length = length(digit_offset);
-                let max_offset = sphere.radius * 0.9;
-                let clamped_offset = select(
-                    digit_offset,
-                    normalize(digit_offset) * max_offset,
-                    offset_length > max_offset
-                );
+                if digit_alpha > 0.01 {
+                    // Sharpen the digit edge
+                    let sharp_alpha = smoothstep(0.35, 0.65, digit_alpha);
 
-                let digit_center = sphere.center + clamped_offset;
+                    // 🔧 SIMPLIFIED: Just blend black digit, high visibility
+                    let digit_color = vec3<f32>(0.0, 0.0, 0.0);
+                    clamped_color = mix(clamped_color, digit_color, sharp_alpha * 0.98);
 
-                let plane_z = digit_center.z;
-                let t_to_plane = (plane_z - ro.z) / rd.z;
-                let plane_hit = ro + rd * t_to_plane;
+                    // 🔧 REMOVED: No opacity manipulation here!
+                    // The opacity is set once below based on stretch
+                }
+            }
+        }
+    }
 
-                let to_plane_hit = plane_hit - digit_center;
-                let u = dot(to_plane_hit, right) / (sphere.radius * 0.6);
-                let v = dot(to_plane_hit, up) / (sphere.radius * 0.6);
+    // === OPACITY (SET ONCE, STRETCH-AWARE) ===
+    var opacity: f32;
+    if is_sphere {
+        let sphere = data.spheres[idx];
+
+        // 🔧 Base opacity depends on stretch
+        // When fleeing (stretched), make it more opaque so it stays visible
+        let stretch_amount = sphere.stretch_factor - 1.0;  // 0 = no stretch, 0.2 = 20% stretch
+        let base_opacity = 0.70;  // Normal transparency
+        let stretched_opacity = 0.88;  // More solid when fleeing
+
+        opacity = mix(base_opacity, stretched_opacity, clamp(stretch_amount * 2.5, 0.0, 1.0));
+    } else {
+        // Cylinders: solid at ends, transparent in middle
+        let dist_from_center = abs(position_along_cylinder - 0.5) * 2.0;
+        opacity = mix(0.5, 0.95, dist_from_center * dist_from_center);
+    }
 
-                if abs(u) < 1.0 && abs(v) < 1.0 {
-                    let digit_uv = vec2<f32>(
-                        (u + 1.0) * 0.5,
-                        1.0 - (v + 1.0) * 0.5
-                    );
-                    let digit_alpha = sample_digit(sphere.digit_value, digit_uv);
+    // === SUBSURFACE GLOW (cylinders only) ===
+    var subsurface_glow = vec3<f32>(0.0);
+    if !is_sphere {
+        // Middle of cylinder glows when backlit
+        let dist_from_center = abs(position_along_cylinder - 0.5) * 2.0;
+        let glow_amount = (1.0 - dist_from_center) * 0.5;  // Peaks at center
+        let backlight = max(dot(-n, light_dir), 0.0);
+        subsurface_glow = clamped_color * glow_amount * backlight;
+    }
 
-                    if digit_alpha > 0.01 {
-                        // Sharpen the digit edge
-                        let sharp_alpha = smoothstep(0.35, 0.65, digit_alpha);
+    // === FINAL COLOR ===
+    let surface_color = clamped_color * lighting;
+    let final_color = surface_color + subsurface_glow;
 
-                        // 🔧 SIMPLIFIED: Just blend black digit, high visibility
-                        let digit_color = vec3<f32>(0.0, 0.0, 0.0);
-                        clamped_color = mix(clamped_color, digit_color, sharp_alpha * 0.98);
+    // === RIM LIGHT ===
+    let fresnel_raw = pow(1.0 - abs(dot(n, view_dir)), 2.0);
+    let fresnel_stepped = smoothstep(0.65, 0.75, fresnel_raw);
 
-                        // 🔧 REMOVED: No opacity manipulation here!
-                        // The opacity is set once below based on stretch
-                    }
-                }
-            }
-        }
+    let distance_to_cam = length(hit - cam);
+    let distance_fade = 1.0 - smoothstep(5.0, 15.0, distance_to_cam);
 
-        // === OPACITY (SET ONCE, STRETCH-AWARE) ===
-        var opacity: f32;
-        if is_sphere {
-            let sphere = data.spheres[idx];
+    let rim_strength = 0.6 * theme.glow_intensity;
+    let rim_glow = clamped_color * fresnel_stepped * rim_strength * distance_fade;
+    var with_rim = final_color + rim_glow;
 
-            // 🔧 Base opacity depends on stretch
-            // When fleeing (stretched), make it more opaque so it stays visible
-            let stretch_amount = sphere.stretch_factor - 1.0;  // 0 = no stretch, 0.2 = 20% stretch
-            let base_opacity = 0.70;  // Normal transparency
-            let stretched_opacity = 0.88;  // More solid when fleeing
+    // === GLOW EFFECT (additive emission) ===
+    // Add glow AFTER all lighting so it's visible on any color!
+    if is_sphere && glow > 0.01 {
+        // Additive glow in the node's own color, washing toward white at full strength
+        let core = glow * glow * glow * 0.25;
+        let emission = (clamped_color * glow * 0.6 + vec3<f32>(core)) * theme.glow_intensity;
+        with_rim = with_rim + emission;  // Don't clamp - let it glow!
+    }
 
-            opacity = mix(base_opacity, stretched_opacity, clamp(stretch_amount * 2.5, 0.0, 1.0));
-        } else {
-            // Cylinders: solid at ends, transparent in middle
-            let dist_from_center = abs(position_along_cylinder - 0.5) * 2.0;
-            opacity = mix(0.5, 0.95, dist_from_center * dist_from_center);
-        }
+    // === COMPLETION SHOCKWAVE ===
+    // Everything the ring passes flares up in its own color, with a white-hot core
+    let shock = shockwave_boost(hit);
+    with_rim = with_rim + clamped_color * shock * 1.5 + vec3<f32>(shock * 0.3);
 
-        // === SUBSURFACE GLOW (cylinders only) ===
-        var subsurface_glow = vec3<f32>(0.0);
-        if !is_sphere {
-            // Middle of cylinder glows when backlit
-            let dist_from_center = abs(position_along_cylinder - 0.5) * 2.0;
-            let glow_amount = (1.0 - dist_from_center) * 0.5;  // Peaks at center
-            let backlight = max(dot(-n, light_dir), 0.0);
-            subsurface_glow = clamped_color * glow_amount * backlight;
-        }
+    // === PARTICLES (in front of the surface) ===
+    with_rim = with_rim + particle_glow(ro, rd, t);
 
-        // === FINAL COLOR ===
-        let surface_color = clamped_color * lighting;
-        let final_color = surface_color + subsurface_glow;
+    let clip = view.clip_from_world * vec4<f32>(hit, 1.0);
+    // The game camera can't place the orbit camera's hits; they sit on the plane
+    let depth = select(clip.z / clip.w, plane_depth, orbiting);
 
-        // === RIM LIGHT ===
-        let fresnel_raw = pow(1.0 - abs(dot(n, view_dir)), 2.0);
-        let fresnel_stepped = smoothstep(0.65, 0.75, fresnel_raw);
+    return FragOut(
+        vec4<f32>(with_rim, opacity),
+        depth
+    );
+}
 
-        let distance_to_cam = length(hit - cam);
-        let distance_fade = 1.0 - smoothstep(5.0, 15.0, distance_to_cam);
+/// Ray through a screen pixel from the orbiting camera (the 3D presentation), with +Z up
+fn orbit_ray_direction(frag_coord: vec2<f32>) -> vec3<f32> {
+    let uv = (frag_coord - view.viewport.xy) / view.viewport.zw;
+    let ndc = vec2<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0);
+    let aspect = view.viewport.z / view.viewport.w;
+
+    let forward = normalize(data.orbit_target - data.orbit_eye);
+    let right = normalize(cross(forward, vec3<f32>(0.0, 0.0, 1.0)));
+    let up = cross(right, forward);
+    let half_height = tan(data.orbit_fov * 0.5);
+    return normalize(forward + (right * ndc.x * aspect + up * ndc.y) * half_height);
+}
 
-        let rim_strength = 0.6;
-        let rim_glow = clamped_color * fresnel_stepped * rim_strength * distance_fade;
-        var with_rim = final_color + rim_glow;
+@fragment
+fn fragment(in: VertexOutput) -> FragOut {
+    // Flat: rays from the game camera through this point of the board plane
+    // Orbit: the plane is just a screen, and rays come from the orbiting camera
+    let orbiting = data.presentation == 1u;
+    var cam = view.world_position;
+    var rd = normalize(in.world_position.xyz - cam);
+    if orbiting {
+        cam = data.orbit_eye;
+        rd = orbit_ray_direction(in.position.xy);
+    }
+    let ro = cam;
 
-        // === GLOW EFFECT (additive emission) ===
-        // Add glow AFTER all lighting so it's visible on any color!
-        if is_sphere && glow > 0.01 {
-            // Additive glow in the node's own color
-            let emission = clamped_color * glow * 0.6;
-            with_rim = with_rim + emission;  // Don't clamp - let it glow!
-        }
+    // One pixel's width on the board plane (taken before any branching, as derivatives
+    // need every pixel in step)
+    let pixel_size = length(fwidth(in.world_position.xy));
 
-        let clip = view.clip_from_world * vec4<f32>(hit, 1.0);
-        let depth = clip.z / clip.w;
+    let result = raymarch(ro, rd);
+    let t = result.x;
+    let idx = i32(result.y);
+    let is_sphere = result.z > 0.5;
 
-        return FragOut(
-            vec4<f32>(with_rim, opacity),
-            depth
-        );
+    if t > 0.0 && idx >= 0 {
+        return shade_surface(ro, rd, t, idx, is_sphere, in.position.z);
     }
 
     // === WARPED GRID BACKGROUND (Geometry Wars style!) ===
     // If we didn't hit any nodes/edges, render warped grid
-    let background = render_background_ripples(in.world_position.xyz);
+    // Orbiting, the grid becomes a floor under the blobs (where the ray comes down to it)
+    var floor_pos = in.world_position.xyz;
+    if orbiting && rd.z < -0.001 {
+        floor_pos = ro + rd * (-ro.z / rd.z);
+    }
+    let background = render_background_ripples(floor_pos);
+
+    // Particles over the background brighten and solidify it where they glow
+    let sparks = particle_glow(ro, rd, 1000.0);
+    let spark_alpha = clamp(max(sparks.r, max(sparks.g, sparks.b)), 0.0, 1.0);
+    let with_sparks = vec4<f32>(background.rgb + sparks, max(background.a, spark_alpha));
+
+    // === SILHOUETTE SMOOTHING ===
+    // A ray that only just missed a blob takes on its color, fading out over a pixel or
+    // so, instead of snapping between blob and background from one frame to the next
+    let softness = pixel_size * quality.edge_softness;
+    let near_idx = i32(closest_approach.z);
+    if softness > 0.0 && near_idx >= 0 && closest_approach.x < softness {
+        let surface = shade_surface(
+            ro, rd, closest_approach.y, near_idx, closest_approach.w > 0.5, in.position.z
+        );
+        let coverage = (1.0 - smoothstep(0.0, softness, closest_approach.x)) * surface.color.a;
+        return FragOut(
+            vec4<f32>(mix(with_sparks.rgb, surface.color.rgb, coverage), max(with_sparks.a, coverage)),
+            surface.depth
+        );
+    }
 
     // Use a far depth value so background is always behind
-    return FragOut(background, 0.9999);
+    return FragOut(with_sparks, 0.9999);
 }
@@ -18,26 +18,26 @@ struct Flow {
     share: f32,
 }
 
-/// A single HUD element instance (digit or slash)
+/// A single HUD element instance (a glyph or a pip)
 struct HudInstance {
-    kind: u32,               // 0 = digit, 1 = slash
-    mask: u32,               // Current/target mask
+    kind: u32,               // 0 = glyph, 1 = pip
+    mask: u32,               // Current/target glyph mask (segments, then punctuation)
     from_mask: u32,          // Previous mask (for transitions)
     transition_progress: f32,// 0.0 = from_mask, 1.0 = mask
     pos: vec2<f32>,          // Position in world XY space
     scale: f32,
-    _pad1: u32,
-    _pad2: u32,
-    _pad3: u32,
-    _pad4: u32,
+    flow_seed: u32,          // Seeds organic flows (0 for the other styles)
+    easing: u32,             // Blob easing (0 = per-blob mix, else `Easing::shader_value`)
+    stagger: f32,            // Share of each phase the starts cascade across
+    _pad4: u32,              // Pads the array stride to 48 (a multiple of 16)
 }
 
 struct SevenSegmentData {
     time: f32,
     hud_count: u32,
-    _padding1: u32,
-    _padding2: u32,
-    hud: array<HudInstance, 12>,
+    edge_softness: f32,  // Edge fade width in pixels (0 = the fixed world-space edge)
+    transition_style: u32, // 0 = deterministic, 1 = excitement, 2 = organic
+    hud: array<HudInstance, 128>,
 }
 
 @group(#{MATERIAL_BIND_GROUP}) @binding(0)
@@ -143,6 +143,29 @@ fn ease_varied(t: f32, blob_id: u32) -> f32 {
     }
 }
 
+// Mirrors `Easing::apply` in visual/utils.rs (0 falls back to the per-blob mix)
+fn ease(t: f32, easing: u32, blob_id: u32) -> f32 {
+    let x = clamp(t, 0.0, 1.0);
+    switch easing {
+        case 1u: { return x; }
+        case 2u: { return x * x * x; }
+        case 3u: { return ease_out_cubic(x); }
+        case 4u: {
+            if x < 0.5 { return 2.0 * x * x; }
+            let u = 1.0 - x;
+            return 1.0 - 2.0 * u * u;
+        }
+        default: { return ease_varied(x, blob_id); }
+    }
+}
+
+// Mirrors `staggered` in visual/utils.rs: slot 0 leads, every slot finishes at 1.0
+fn staggered(progress: f32, slot: u32, slots: u32, stagger: f32) -> f32 {
+    let spread = clamp(stagger, 0.0, 0.95);
+    let delay = spread * f32(slot) / f32(max(slots, 2u) - 1u);
+    return clamp((progress - delay) / (1.0 - spread), 0.0, 1.0);
+}
+
 fn blend_crease_mask(d1: f32, d2: f32, k: f32) -> f32 {
     // d1 = smallest SDF, d2 = second smallest
     // When d2 ~= d1, we are near an overlap/crease.
@@ -301,9 +324,22 @@ fn hash(n: u32) -> f32 {
     return f32(x) / f32(0x7fffffffu);
 }
 
+// Organic transitions reseed every per-flow hash (0 keeps the fixed pattern)
+var<private> flow_jitter: u32 = 0u;
+
+// The transition being drawn's blob easing and cascade (see `HudInstance`)
+var<private> flow_easing: u32 = 0u;
+var<private> flow_stagger: f32 = 0.0;
+
 // Get a pseudo-random value for a specific flow
 fn hash_flow(flow_idx: u32, seed: u32) -> f32 {
-    return hash(flow_idx * 73u + seed * 37u);
+    return hash(flow_idx * 73u + seed * 37u + flow_jitter * 101u);
+}
+
+// Seeded pick in 0..count, salted per segment (mirrors `flows::pick`)
+fn pick(seed: u32, salt: u32, count: u32) -> u32 {
+    let n = seed ^ (salt * 73u);
+    return ((n * 1103515245u + 12345u) & 0x7fffffffu) % max(count, 1u);
 }
 
 // Approximate gaussian from uniform (Box-Muller lite)
@@ -360,7 +396,8 @@ fn segment_distance(from_seg: u32, to_seg: u32) -> u32 {
     return 2u;
 }
 
-fn compute_flows(from_mask: u32, to_mask: u32) -> array<Flow, MAX_FLOWS> {
+// Mirrors `seven_segment::flows::compute_flows` (keep the two in step)
+fn compute_flows(from_mask: u32, to_mask: u32, style: u32, seed: u32) -> array<Flow, MAX_FLOWS> {
     var flows: array<Flow, MAX_FLOWS>;
     var flow_count = 0u;
 
@@ -391,33 +428,61 @@ fn compute_flows(from_mask: u32, to_mask: u32) -> array<Flow, MAX_FLOWS> {
                 }
             }
 
-            // Pass 3: create flows with equal share
-            let share = 1.0 / f32(max(nearest_count, 1u));
+            // Pass 3: create flows with equal share (organic: the whole mass to one of them)
+            let organic = style == 2u;
+            let chosen = pick(seed, from_seg, nearest_count);
+            let share = select(1.0 / f32(max(nearest_count, 1u)), 1.0, organic);
+            var nearest_idx = 0u;
 
             for (var to_seg = 0u; to_seg < 7u; to_seg++) {
                 if to_active[to_seg] == 1u && flow_count < MAX_FLOWS {
                     let dist = segment_distance(from_seg, to_seg);
                     if dist == min_dist {
-                        flows[flow_count] = Flow(from_seg, to_seg, share);
-                        flow_count++;
+                        if !organic || nearest_idx == chosen {
+                            flows[flow_count] = Flow(from_seg, to_seg, share);
+                            flow_count++;
+                        }
+                        nearest_idx++;
                     }
                 }
             }
         }
     }
 
+    if style == 0u {
+        return flows;
+    }
+
     // “Excitement flows”: stable -> appearing
+    var steady_count = 0u;
+    for (var seg = 0u; seg < 7u; seg++) {
+        if from_active[seg] == 1u && to_active[seg] == 1u {
+            steady_count++;
+        }
+    }
+
     for (var to_seg = 0u; to_seg < 7u; to_seg++) {
         if from_active[to_seg] == 0u && to_active[to_seg] == 1u && flow_count < MAX_FLOWS {
             var min_dist = 999u;
             var closest_stable = 0u;
+            let chosen = pick(seed, to_seg + 7u, steady_count);
+            var steady_idx = 0u;
 
             for (var stable_seg = 0u; stable_seg < 7u; stable_seg++) {
                 if from_active[stable_seg] == 1u && to_active[stable_seg] == 1u {
-                    let dist = segment_distance(stable_seg, to_seg);
-                    if dist < min_dist {
-                        min_dist = dist;
-                        closest_stable = stable_seg;
+                    if style == 2u {
+                        // Organic: any steady segment, picked by the seed
+                        if steady_idx == chosen {
+                            min_dist = 0u;
+                            closest_stable = stable_seg;
+                        }
+                        steady_idx++;
+                    } else {
+                        let dist = segment_distance(stable_seg, to_seg);
+                        if dist < min_dist {
+                            min_dist = dist;
+                            closest_stable = stable_seg;
+                        }
                     }
                 }
             }
@@ -521,11 +586,20 @@ fn render_static_digit(p: vec2<f32>, mask: u32) -> f32 {
     return d;
 }
 
-fn render_transition(p: vec2<f32>, from_mask: u32, to_mask: u32, t: f32) -> f32 {
+fn render_transition(p: vec2<f32>, inst: HudInstance) -> f32 {
+    let from_mask = inst.from_mask & SEGMENT_BITS;
+    let to_mask = inst.mask & SEGMENT_BITS;
+    let t = inst.transition_progress;
+    let flow_seed = inst.flow_seed;
+    flow_easing = inst.easing;
+    flow_stagger = inst.stagger;
+
     if t <= 0.0 { return render_static_digit(p, from_mask); }
     if t >= 1.0 { return render_static_digit(p, to_mask); }
 
-    let flows = compute_flows(from_mask, to_mask);
+    let style = data.transition_style;
+    flow_jitter = select(0u, flow_seed, style == 2u);
+    let flows = compute_flows(from_mask, to_mask, style, flow_seed);
     let flow_count = count_flows(flows);
 
     let phase = get_current_phase(t);
@@ -560,8 +634,8 @@ fn render_split_phase(
                 let seg_sdf = render_segment_with_effects(p, seg_id, base_radius, 1.0, 0.0);
                 d = smin(d, seg_sdf, 0.18);
             } else {
-                // shrinking
-                let shrink_factor = 1.0 - (phase_progress * 0.7);
+                // shrinking, segment by segment
+                let shrink_factor = 1.0 - (staggered(phase_progress, seg_id, 7u, flow_stagger) * 0.7);
                 if shrink_factor > 0.01 {
                     let seg_sdf = render_segment_with_effects(p, seg_id, base_radius, shrink_factor, 0.0);
                     d = smin(d, seg_sdf, 0.18);
@@ -593,12 +667,13 @@ fn render_anticipation_phase(
                 d = smin(d, seg_sdf, 0.18);
             } else {
                 // shrink + wobble
-                let shrink = 0.3 - (phase_progress * 0.2);
+                let seg_progress = staggered(phase_progress, seg_id, 7u, flow_stagger);
+                let shrink = 0.3 - (seg_progress * 0.2);
 
                 let wobble = vec2<f32>(
                     sin(data.time * 5.0 + f32(seg_id)),
                     cos(data.time * 4.0 + f32(seg_id))
-                ) * 0.02 * phase_progress;
+                ) * 0.02 * seg_progress;
 
                 let wobbled_p = p + wobble;
 
@@ -640,14 +715,18 @@ fn render_jump_phase(
         let from_pos = seg_target_point(flow.from_seg, i);
         let to_pos = seg_target_point(flow.to_seg, i + 100u);
 
-        let start_offset = hash_flow(i, 1u) * 0.15;
-        let adjusted_progress = clamp(
-            (phase_progress - start_offset) / (1.0 - start_offset),
-            0.0,
-            1.0
-        );
+        // Departures cascade in flow order, or jitter a little without a stagger
+        var adjusted_progress = staggered(phase_progress, i, flow_count, flow_stagger);
+        if flow_stagger <= 0.0 {
+            let start_offset = hash_flow(i, 1u) * 0.15;
+            adjusted_progress = clamp(
+                (phase_progress - start_offset) / (1.0 - start_offset),
+                0.0,
+                1.0
+            );
+        }
 
-        let eased = ease_varied(adjusted_progress, i);
+        let eased = ease(adjusted_progress, flow_easing, i);
         let blob_pos = mix(from_pos, to_pos, eased);
 
         let distortion_strength = sin(adjusted_progress * 3.14159) * 0.3;
@@ -731,7 +810,9 @@ fn render_settle_phase(
         let target_seg = get_segment_geometry(flow.to_seg);
         let blob_pos = seg_target_point(flow.to_seg, i + 100u);
 
-        let eased_progress = phase_progress * phase_progress; // ease-in quadratic
+        // Arrivals cascade like the departures did (ease-in quadratic)
+        let arrival = staggered(phase_progress, i, flow_count, flow_stagger);
+        let eased_progress = arrival * arrival;
 
         let start_pos = mix(blob_pos, target_seg.start, eased_progress);
         let end_pos = mix(blob_pos, target_seg.end, eased_progress);
@@ -740,10 +821,40 @@ fn render_settle_phase(
         d = smin(d, morphing_sdf, 0.18);
     }
 
+    // Appearing segments nothing flows into (a minus out of a blank sign slot) grow
+    // out from their middle instead of popping in at the end
+    for (var seg_id = 0u; seg_id < 7u; seg_id++) {
+        let appearing = (from_mask & (1u << seg_id)) == 0u && (to_mask & (1u << seg_id)) != 0u;
+        if !appearing {
+            continue;
+        }
+
+        var fed = false;
+        for (var i = 0u; i < flow_count; i++) {
+            fed = fed || flows[i].to_seg == seg_id;
+        }
+        if fed {
+            continue;
+        }
+
+        let seg = get_segment_geometry(seg_id);
+        let mid = seg_mid(seg_id);
+        let grow = ease_out_cubic(phase_progress);
+        let growing_sdf = sd_capsule(p, mix(mid, seg.start, grow), mix(mid, seg.end, grow), base_radius * grow);
+        d = smin(d, growing_sdf, 0.18);
+    }
+
     return d;
 }
 
-// ===== SLASH RENDERING =====
+// ===== PUNCTUATION RENDERING =====
+// Glyph mask bits past the segments (`SLASH_MASK` etc. in digit.rs)
+const SEGMENT_BITS: u32 = 0x7fu;
+const SLASH_BIT: u32 = 7u;
+const COLON_BIT: u32 = 8u;
+const PERIOD_BIT: u32 = 9u;
+const DOT_RADIUS: f32 = 0.14;
+
 fn sd_capsule_2d(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, r: f32) -> f32 {
     let pa = p - a;
     let ba = b - a;
@@ -751,33 +862,68 @@ fn sd_capsule_2d(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, r: f32) -> f32 {
     return length(pa - ba * h) - r;
 }
 
-fn render_slash(p: vec2<f32>) -> f32 {
+fn render_slash(p: vec2<f32>, r: f32) -> f32 {
     let a = vec2<f32>(-0.4, -0.6);
     let b = vec2<f32>(0.4, 0.6);
-    let r = 0.12;
     return sd_capsule_2d(p, a, b, r);
 }
 
+/// How much of a mark shows: whole if both masks have it, else grown in or shrunk
+/// away over the transition
+fn mark_size(inst: HudInstance, bit: u32) -> f32 {
+    let from = f32((inst.from_mask >> bit) & 1u);
+    let to = f32((inst.mask >> bit) & 1u);
+    return mix(from, to, ease_out_cubic(clamp(inst.transition_progress, 0.0, 1.0)));
+}
+
+/// Distance to the instance's punctuation marks (slash, colon dots, period)
+fn render_marks(p: vec2<f32>, inst: HudInstance) -> f32 {
+    var d = 1e9;
+    if ((inst.mask | inst.from_mask) & ~SEGMENT_BITS) == 0u {
+        return d;
+    }
+
+    let slash = mark_size(inst, SLASH_BIT);
+    if slash > 0.0 {
+        d = min(d, render_slash(p, 0.12 * slash));
+    }
+    let colon = mark_size(inst, COLON_BIT);
+    if colon > 0.0 {
+        let upper = length(p - vec2<f32>(0.0, 0.35)) - DOT_RADIUS * colon;
+        let lower = length(p - vec2<f32>(0.0, -0.35)) - DOT_RADIUS * colon;
+        d = min(d, min(upper, lower));
+    }
+    let period = mark_size(inst, PERIOD_BIT);
+    if period > 0.0 {
+        d = min(d, length(p - vec2<f32>(0.0, -0.7)) - DOT_RADIUS * period);
+    }
+    return d;
+}
+
+// ===== PIP RENDERING =====
+// A dot that swells when transition_progress resets to 0 and settles back by 1
+fn render_pip(p: vec2<f32>, t: f32) -> f32 {
+    let swell = (1.0 - ease_out_cubic(t)) * 0.35;
+    let breathe = 0.03 * sin(data.time * 3.0);
+    return length(p) - (0.3 + swell + breathe);
+}
+
+/// Distance to one instance, in world units (`local_p` is in the instance's units)
+fn instance_sdf(local_p: vec2<f32>, inst: HudInstance) -> f32 {
+    if inst.kind == 1u {
+        return render_pip(local_p, inst.transition_progress) * inst.scale;
+    }
+    let segments = render_transition(local_p / 1.2, inst);
+    return min(segments, render_marks(local_p, inst)) * inst.scale;
+}
+
 fn scene_sdf(p_world: vec2<f32>) -> f32 {
     var min_d = 1e9;
 
     for (var i = 0u; i < data.hud_count; i++) {
         let inst = data.hud[i];
         let local_p = (p_world - inst.pos) / max(inst.scale, 0.001);
-
-        var d: f32;
-        if inst.kind == 1u {
-            d = render_slash(local_p) * inst.scale;
-        } else {
-            d = render_transition(
-                local_p / 1.2,
-                inst.from_mask,
-                inst.mask,
-                inst.transition_progress
-            ) * inst.scale;
-        }
-
-        min_d = min(min_d, d);
+        min_d = min(min_d, instance_sdf(local_p, inst));
     }
 
     return min_d;
@@ -795,6 +941,10 @@ fn fragment(in: VertexOutput) -> FragOut {
     let world_pos = in.world_position.xyz;
     let p = vec2<f32>(world_pos.x, world_pos.y);
 
+    // One pixel's width in world units, for edges that stay a pixel or so soft at any
+    // zoom (taken before any branching, as derivatives need every pixel in step)
+    let pixel_size = length(fwidth(p));
+
     // Compute min distances for FG + shadow (single pass over instances)
     var min_d = 1e9;
     var min_shadow_d = 1e9;
@@ -804,24 +954,11 @@ fn fragment(in: VertexOutput) -> FragOut {
         let local_p = (p - inst.pos) / max(inst.scale, 0.001);
 
         // FG
-        var d: f32;
-        if inst.kind == 1u {
-            d = render_slash(local_p) * inst.scale;
-        } else {
-            d = render_transition(local_p / 1.2, inst.from_mask, inst.mask, inst.transition_progress) * inst.scale;
-        }
-        min_d = min(min_d, d);
+        min_d = min(min_d, instance_sdf(local_p, inst));
 
         // Shadow: sample shifted local coords
         let shadow_local_p = local_p - SHADOW_OFFSET;
-
-        var sd: f32;
-        if inst.kind == 1u {
-            sd = render_slash(shadow_local_p) * inst.scale;
-        } else {
-            sd = render_transition(shadow_local_p / 1.2, inst.from_mask, inst.mask, inst.transition_progress) * inst.scale;
-        }
-        min_shadow_d = min(min_shadow_d, sd);
+        min_shadow_d = min(min_shadow_d, instance_sdf(shadow_local_p, inst));
     }
 
     // Shading should be computed ONCE for the whole scene, not per-instance
@@ -848,7 +985,11 @@ fn fragment(in: VertexOutput) -> FragOut {
 
 
     // Alpha edges
-    let fg_a = smoothstep(0.02, 0.0, min_d);
+    var fg_a = smoothstep(0.02, 0.0, min_d);
+    if data.edge_softness > 0.0 {
+        let soft = pixel_size * data.edge_softness;
+        fg_a = 1.0 - smoothstep(-soft, soft, min_d);
+    }
     let shadow_a_raw = smoothstep(SHADOW_SOFTNESS, 0.0, min_shadow_d);
     let shadow_a = shadow_a_raw * SHADOW_OPACITY * (1.0 - fg_a);
 
@@ -0,0 +1,171 @@
+//! Criterion benches for the solver and the per-frame hot paths (uniform sync, springs).
+//!
+//! Run with `cargo bench`; compare against a saved baseline with
+//! `cargo bench -- --save-baseline main` / `--baseline main`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+
+use bevy::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use valence_sdf::{
+    game::session::PuzzleSession,
+    graph::{Edge, NodeId, Valences, solver},
+    visual::{
+        edges::{dying::DyingEdges, intro::EdgeIntro, waves::EdgeWaves},
+        interactions::{
+            pointer::{DragState, HoverState},
+            preview::PreviewEndpoint,
+        },
+        nodes::{GraphNode, NodeVisual},
+        physics::{
+            EdgeTension, NodePhysics, apply_edge_spring_forces,
+            forces::edge_spring_forces::accumulate_spring_forces,
+        },
+        sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
+        sdf::sync::{CylinderSlots, sdf_sync_systems},
+        setup::SceneMetrics,
+    },
+};
+
+/// Library puzzles spanning the complexity range (complexity = solutions × edges)
+const PUZZLES: [(&str, [usize; 9]); 4] = [
+    ("c1", [0, 0, 0, 0, 0, 0, 0, 1, 1]),
+    ("c24", [0, 0, 0, 2, 1, 2, 2, 3, 2]),
+    ("c120", [0, 0, 1, 2, 4, 2, 2, 3, 2]),
+    ("c960", [1, 2, 2, 2, 4, 2, 2, 3, 2]),
+];
+
+fn bench_solver(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solver/count_solutions");
+    for (name, values) in PUZZLES {
+        let valences = Valences::from_array(values);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &valences, |b, v| {
+            b.iter(|| solver::count_solutions(black_box(v)))
+        });
+    }
+    group.finish();
+}
+
+/// A world with the 3×3 board spawned and every resource the sync/spring systems read
+fn board_world(valences: Valences, trail_len: usize) -> World {
+    let mut world = World::new();
+
+    let mut session = PuzzleSession::new(valences.clone(), 1);
+    let trail = solver::find_solution_trail(&valences).expect("bench puzzles are solvable");
+    for &node in trail.iter().take(trail_len) {
+        session.add_node(node);
+    }
+
+    let mut materials = Assets::<SdfSceneMaterial>::default();
+    let handle = materials.add(SdfSceneMaterial::default());
+
+    world.insert_resource(session);
+    world.insert_resource(materials);
+    world.insert_resource(SceneMaterialHandle(handle));
+    world.insert_resource(SceneMetrics::new(1.0));
+    world.init_resource::<HoverState>();
+    world.init_resource::<DragState>();
+    world.init_resource::<PreviewEndpoint>();
+    world.init_resource::<EdgeWaves>();
+    world.init_resource::<EdgeIntro>();
+    world.init_resource::<DyingEdges>();
+    world.init_resource::<EdgeTension>();
+    world.init_resource::<CylinderSlots>();
+
+    for index in 0..9 {
+        // Displace from rest so springs have something to do
+        let rest = Vec3::new((index % 3) as f32, (index / 3) as f32, 0.0);
+        let mut physics = NodePhysics::at_rest(rest);
+        physics.position = rest * 1.1;
+        world.spawn((
+            GraphNode {
+                node_id: NodeId(index),
+            },
+            physics,
+            NodeVisual::default(),
+        ));
+    }
+
+    world
+}
+
+fn bench_sync(c: &mut Criterion) {
+    let valences = Valences::from_array(PUZZLES[3].1);
+    let edge_total = valences.total() / 2;
+
+    let mut group = c.benchmark_group("sdf_sync");
+    for trail_len in [0, edge_total / 2 + 1, edge_total + 1] {
+        let mut world = board_world(valences.clone(), trail_len);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sdf_sync_systems());
+        schedule.run(&mut world);
+
+        // Session change forces a full rewrite of every sphere and cylinder
+        group.bench_function(BenchmarkId::new("dirty", trail_len), |b| {
+            b.iter(|| {
+                world.resource_mut::<PuzzleSession>().set_changed();
+                schedule.run(&mut world);
+            })
+        });
+
+        // Nothing changed since the last run: only the run conditions are checked
+        group.bench_function(BenchmarkId::new("idle", trail_len), |b| {
+            b.iter(|| schedule.run(&mut world))
+        });
+    }
+    group.finish();
+}
+
+/// A `size`×`size` grid stretched 10% from rest, with a king's-move edge to each
+/// right/down/diagonal neighbor
+fn stretched_grid(size: usize) -> (HashMap<NodeId, (Vec3, Vec3)>, Vec<Edge>) {
+    let mut nodes = HashMap::new();
+    let mut edges = Vec::new();
+    for row in 0..size {
+        for col in 0..size {
+            let rest = Vec3::new(col as f32, row as f32, 0.0);
+            let id = row * size + col;
+            nodes.insert(NodeId(id), (rest * 1.1, rest));
+
+            if col + 1 < size {
+                edges.push(Edge::new(NodeId(id), NodeId(id + 1)));
+            }
+            if row + 1 < size {
+                edges.push(Edge::new(NodeId(id), NodeId(id + size)));
+                if col + 1 < size {
+                    edges.push(Edge::new(NodeId(id), NodeId(id + size + 1)));
+                }
+            }
+        }
+    }
+    (nodes, edges)
+}
+
+fn bench_springs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("edge_springs/accumulate");
+    for size in [3, 10, 30, 60] {
+        let (nodes, edges) = stretched_grid(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| {
+                let mut tension = HashMap::new();
+                accumulate_spring_forces(&edges, black_box(&nodes), 2.0, 1.0, &mut tension)
+            })
+        });
+    }
+    group.finish();
+
+    // The full system on the real board with a completed trail
+    let valences = Valences::from_array(PUZZLES[3].1);
+    let mut world = board_world(valences.clone(), valences.total() / 2 + 1);
+    let mut system = IntoSystem::into_system(apply_edge_spring_forces);
+    system.initialize(&mut world);
+
+    c.bench_function("edge_springs/system_3x3", |b| {
+        b.iter(|| system.run((), &mut world).unwrap())
+    });
+}
+
+criterion_group!(benches, bench_solver, bench_sync, bench_springs);
+criterion_main!(benches);
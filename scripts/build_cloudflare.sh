@@ -13,7 +13,7 @@ cargo install wasm-bindgen-cli --version 0.2.108
 apt-get update && apt-get install -y binaryen
 
 # Build
-cargo build --target wasm32-unknown-unknown --profile wasm-release
+cargo build --target wasm32-unknown-unknown --profile wasm-release --features wasm
 
 # Generate bindings
 wasm-bindgen --out-dir ./wasm --target web \
@@ -2,7 +2,7 @@
 set -e
 
 echo "🔨 Building WASM..."
-cargo build --target wasm32-unknown-unknown --profile wasm-release
+cargo build --target wasm32-unknown-unknown --profile wasm-release --features wasm
 
 echo "🔗 Generating bindings..."
 wasm-bindgen --out-dir ./wasm --target web \
@@ -0,0 +1,310 @@
+// config.rs - server settings from defaults < TOML file < environment < CLI flags
+
+use clap::Parser;
+use figment::{
+    Figment,
+    providers::{Env, Format, Serialized, Toml},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+/// Config file read when `--config` isn't given (skipped if missing)
+const DEFAULT_CONFIG_FILE: &str = "valence.toml";
+
+/// Prefix for environment overrides, e.g. `VALENCE_PORT`, `VALENCE_COMPRESSION__GZIP`
+const ENV_PREFIX: &str = "VALENCE_";
+
+/// Unprefixed variables the server read before it had a config layer (fly.toml, Dockerfile)
+const LEGACY_ENV: [&str; 3] = ["PORT", "STATIC_DIR", "DATA_DIR"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// Interface to listen on
+    pub bind: String,
+    pub port: u16,
+    /// Built web client (`dist/`)
+    pub static_dir: PathBuf,
+    /// sled database for leaderboards and telemetry
+    pub data_dir: PathBuf,
+    pub tls: TlsConfig,
+    pub compression: CompressionConfig,
+    pub security: SecurityConfig,
+    pub log_format: LogFormat,
+}
+
+/// Certificate and key for serving HTTPS directly (both or neither)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    pub cert: Option<PathBuf>,
+    pub key: Option<PathBuf>,
+    /// Plain-HTTP port that redirects to HTTPS (none by default)
+    pub redirect_port: Option<u16>,
+    /// Strict-Transport-Security max-age in seconds while serving HTTPS (0 = no header)
+    pub hsts_max_age: u64,
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            cert: None,
+            key: None,
+            redirect_port: None,
+            hsts_max_age: 31_536_000,
+        }
+    }
+}
+
+impl TlsConfig {
+    /// Cert and key paths when HTTPS is configured
+    pub fn paths(&self) -> Result<Option<(&Path, &Path)>, String> {
+        match (&self.cert, &self.key) {
+            (Some(cert), Some(key)) => Ok(Some((cert, key))),
+            (None, None) => Ok(None),
+            _ => Err("TLS needs both a certificate and a key".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CompressionConfig {
+    pub br: bool,
+    pub gzip: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SecurityConfig {
+    /// Send COOP/COEP so the page may use SharedArrayBuffer (threaded wasm)
+    pub cross_origin_isolation: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable, one line per event
+    Full,
+    /// Shorter lines without span context
+    Compact,
+    /// One JSON object per line, for log shippers
+    Json,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            bind: "0.0.0.0".to_string(),
+            port: 8080,
+            static_dir: PathBuf::from("../dist"),
+            data_dir: PathBuf::from("./data"),
+            tls: TlsConfig::default(),
+            compression: CompressionConfig {
+                br: true,
+                gzip: true,
+            },
+            security: SecurityConfig {
+                cross_origin_isolation: true,
+            },
+            log_format: LogFormat::Full,
+        }
+    }
+}
+
+/// Command-line flags; anything set here wins over the file and environment
+#[derive(Debug, Default, Parser, Serialize)]
+#[command(
+    name = "valence-server",
+    about = "Serves the Valence SDF web build and its API"
+)]
+pub struct Cli {
+    /// TOML config file [default: valence.toml, if present]
+    #[arg(long, short)]
+    #[serde(skip)]
+    pub config: Option<PathBuf>,
+
+    /// Interface to listen on [default: 0.0.0.0]
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bind: Option<String>,
+
+    /// [default: 8080]
+    #[arg(long, short)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    /// Built web client [default: ../dist]
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub static_dir: Option<PathBuf>,
+
+    /// Database directory [default: ./data]
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_dir: Option<PathBuf>,
+
+    /// [default: full]
+    #[arg(long)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_format: Option<LogFormat>,
+
+    /// PEM certificate chain for HTTPS
+    #[arg(long)]
+    #[serde(skip)]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key for HTTPS
+    #[arg(long)]
+    #[serde(skip)]
+    pub tls_key: Option<PathBuf>,
+
+    /// Also listen for plain HTTP on this port and redirect it to HTTPS
+    #[arg(long)]
+    #[serde(skip)]
+    pub tls_redirect_port: Option<u16>,
+
+    /// Turn off brotli responses
+    #[arg(long)]
+    #[serde(skip)]
+    pub no_brotli: bool,
+
+    /// Turn off gzip responses
+    #[arg(long)]
+    #[serde(skip)]
+    pub no_gzip: bool,
+}
+
+impl Config {
+    /// Resolve the config from every source
+    pub fn load() -> Result<Self, Box<figment::Error>> {
+        Self::from_sources(Cli::parse(), Env::prefixed(ENV_PREFIX).split("__"))
+    }
+
+    fn from_sources(cli: Cli, env: Env) -> Result<Self, Box<figment::Error>> {
+        let file = match &cli.config {
+            Some(path) => Toml::file_exact(path),
+            None => Toml::file(DEFAULT_CONFIG_FILE),
+        };
+
+        let mut config: Config = Figment::from(Serialized::defaults(Config::default()))
+            .merge(file)
+            .merge(Env::raw().only(&LEGACY_ENV))
+            .merge(env)
+            .merge(Serialized::defaults(&cli))
+            .extract()
+            .map_err(Box::new)?;
+
+        // Nested or negated flags don't map onto the figment tree; apply them last
+        if cli.tls_cert.is_some() {
+            config.tls.cert = cli.tls_cert;
+        }
+        if cli.tls_key.is_some() {
+            config.tls.key = cli.tls_key;
+        }
+        if cli.tls_redirect_port.is_some() {
+            config.tls.redirect_port = cli.tls_redirect_port;
+        }
+        config.compression.br &= !cli.no_brotli;
+        config.compression.gzip &= !cli.no_gzip;
+
+        Ok(config)
+    }
+
+    pub fn addr(&self) -> Result<SocketAddr, std::net::AddrParseError> {
+        format!("{}:{}", self.bind, self.port).parse()
+    }
+
+    pub fn index_path(&self) -> PathBuf {
+        self.static_dir.join("index.html")
+    }
+}
+
+/// Start logging in the configured format (RUST_LOG filters, defaulting to info)
+pub fn init_logging(format: LogFormat) {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match format {
+        LogFormat::Full => builder.init(),
+        LogFormat::Compact => builder.compact().init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+#[cfg(test)]
+// `Jail` closures return `figment::Error` by value
+#[allow(clippy::result_large_err)]
+mod tests {
+    use super::*;
+    use figment::Jail;
+
+    fn cli(args: &[&str]) -> Cli {
+        Cli::parse_from(std::iter::once("valence-server").chain(args.iter().copied()))
+    }
+
+    fn load(args: &[&str]) -> Config {
+        Config::from_sources(cli(args), Env::prefixed(ENV_PREFIX).split("__")).unwrap()
+    }
+
+    #[test]
+    fn test_defaults() {
+        Jail::expect_with(|_| {
+            assert_eq!(load(&[]), Config::default());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_layer_precedence() {
+        Jail::expect_with(|jail| {
+            jail.create_file(
+                "valence.toml",
+                r#"
+                    port = 7000
+                    bind = "127.0.0.1"
+                    log_format = "json"
+
+                    [compression]
+                    br = false
+                "#,
+            )?;
+            jail.set_env("PORT", "7100");
+            jail.set_env("VALENCE_BIND", "10.0.0.1");
+            jail.set_env("VALENCE_TLS__CERT", "/certs/cert.pem");
+
+            let config = load(&["--port", "7200", "--no-gzip", "--tls-key", "/certs/key.pem"]);
+            assert_eq!(config.port, 7200);
+            assert_eq!(config.bind, "10.0.0.1");
+            assert_eq!(config.log_format, LogFormat::Json);
+            assert!(!config.compression.br && !config.compression.gzip);
+            assert_eq!(config.tls.cert, Some(PathBuf::from("/certs/cert.pem")));
+            assert_eq!(config.tls.key, Some(PathBuf::from("/certs/key.pem")));
+            assert!(config.tls.paths().unwrap().is_some());
+            assert_eq!(config.addr().unwrap().to_string(), "10.0.0.1:7200");
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_tls_needs_cert_and_key() {
+        let tls = TlsConfig {
+            cert: Some(PathBuf::from("cert.pem")),
+            ..TlsConfig::default()
+        };
+        assert!(tls.paths().is_err());
+        assert_eq!(TlsConfig::default().paths(), Ok(None));
+    }
+
+    #[test]
+    fn test_explicit_config_file_must_exist() {
+        Jail::expect_with(|_| {
+            let result = Config::from_sources(
+                cli(&["--config", "missing.toml"]),
+                Env::prefixed(ENV_PREFIX),
+            );
+            assert!(result.is_err());
+            Ok(())
+        });
+    }
+}
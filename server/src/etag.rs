@@ -0,0 +1,194 @@
+// etag.rs - strong ETags for static files, hashed once at startup
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderMap, HeaderValue, Method, Request, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Content hash of every file under the static dir, keyed by URL path
+#[derive(Debug, Default)]
+pub struct EtagIndex {
+    tags: HashMap<String, String>,
+}
+
+impl EtagIndex {
+    /// Hash every file under `static_dir` (the dist is immutable while the server runs)
+    pub fn build(static_dir: &Path) -> io::Result<Self> {
+        let mut index = Self::default();
+        let mut dirs = vec![PathBuf::from(static_dir)];
+
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+
+                let Ok(relative) = path.strip_prefix(static_dir) else {
+                    continue;
+                };
+                let url_path = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .fold(String::new(), |url, part| url + "/" + &part);
+
+                let digest = Sha256::digest(std::fs::read(&path)?);
+                let hash: String = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+                index.tags.insert(url_path, hash);
+            }
+        }
+
+        Ok(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Hash for a request path ("/" is served as index.html)
+    fn hash_for(&self, path: &str) -> Option<&str> {
+        let path = if path.ends_with('/') {
+            format!("{path}index.html")
+        } else {
+            path.to_string()
+        };
+        self.tags.get(&path).map(String::as_str)
+    }
+}
+
+/// Middleware: Add ETags and answer matching If-None-Match with 304
+///
+/// Sits outside the compression layer, so the tag gets the encoding appended ("-br",
+/// "-gzip") - a compressed body is a different representation from the file on disk.
+/// The suffix is ignored when comparing, so any encoding revalidates. Cache-control
+/// wraps this layer, so 304s carry the same Cache-Control as full responses.
+pub async fn etag(State(index): State<Arc<EtagIndex>>, req: Request<Body>, next: Next) -> Response {
+    let hash = match *req.method() {
+        Method::GET | Method::HEAD => index.hash_for(req.uri().path()).map(str::to_owned),
+        _ => None,
+    };
+    let Some(hash) = hash else {
+        return next.run(req).await;
+    };
+
+    if let Some(matched) = matching_tag(req.headers(), &hash) {
+        let mut res = StatusCode::NOT_MODIFIED.into_response();
+        if let Ok(value) = HeaderValue::from_str(&matched) {
+            res.headers_mut().insert(header::ETAG, value);
+        }
+        return res;
+    }
+
+    let mut res = next.run(req).await;
+    if res.status() != StatusCode::OK {
+        return res;
+    }
+
+    let tag = match res.headers().get(header::CONTENT_ENCODING) {
+        Some(encoding) => format!("\"{hash}-{}\"", encoding.to_str().unwrap_or("encoded")),
+        None => format!("\"{hash}\""),
+    };
+    if let Ok(value) = HeaderValue::from_str(&tag) {
+        res.headers_mut().insert(header::ETAG, value);
+    }
+    res
+}
+
+/// The If-None-Match entry that matches `hash`, if any
+fn matching_tag(headers: &HeaderMap, hash: &str) -> Option<String> {
+    let if_none_match = headers.get(header::IF_NONE_MATCH)?.to_str().ok()?;
+
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .find(|tag| {
+            if *tag == "*" {
+                return true;
+            }
+            // Weak comparison, as If-None-Match requires
+            let opaque = tag.trim_start_matches("W/").trim_matches('"');
+            opaque == hash
+                || opaque
+                    .strip_prefix(hash)
+                    .is_some_and(|suffix| suffix.starts_with('-'))
+        })
+        .map(|tag| {
+            if tag == "*" {
+                format!("\"{hash}\"")
+            } else {
+                tag.to_string()
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware};
+    use tower::ServiceExt;
+    use tower_http::services::ServeDir;
+
+    fn static_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("valence-etag-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        std::fs::write(dir.join("index.html"), "<html></html>").unwrap();
+        std::fs::write(dir.join("assets/app.js"), "console.log(1)").unwrap();
+        dir
+    }
+
+    fn app(dir: &Path) -> Router {
+        let index = Arc::new(EtagIndex::build(dir).unwrap());
+        Router::new()
+            .fallback_service(ServeDir::new(dir))
+            .layer(middleware::from_fn_with_state(index, etag))
+    }
+
+    async fn get(app: Router, path: &str, if_none_match: Option<&str>) -> Response {
+        let mut req = Request::get(path);
+        if let Some(tag) = if_none_match {
+            req = req.header(header::IF_NONE_MATCH, tag);
+        }
+        app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_etag_revalidation() {
+        let dir = static_dir();
+
+        let res = get(app(&dir), "/assets/app.js", None).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let tag = res.headers()[header::ETAG].to_str().unwrap().to_string();
+        assert!(res.headers().contains_key(header::LAST_MODIFIED));
+
+        let res = get(app(&dir), "/assets/app.js", Some(&tag)).await;
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(res.headers()[header::ETAG], tag.as_str());
+
+        // A compressed variant's tag still revalidates
+        let br_tag = format!("{}-br\"", tag.trim_end_matches('"'));
+        let res = get(app(&dir), "/assets/app.js", Some(&br_tag)).await;
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+
+        let res = get(app(&dir), "/assets/app.js", Some("\"stale\"")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // "/" shares index.html's tag
+        let res = get(app(&dir), "/", None).await;
+        let index_tag = res.headers()[header::ETAG].clone();
+        let res = get(app(&dir), "/index.html", None).await;
+        assert_eq!(res.headers()[header::ETAG], index_tag);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
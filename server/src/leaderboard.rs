@@ -0,0 +1,388 @@
+// leaderboard.rs - daily puzzle scores, one best entry per player per day
+
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing,
+};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::sync_rules::{MAX_ID_LEN, is_id};
+
+/// How many entries GET returns for a day (and the most a page can hold)
+const MAX_ENTRIES: usize = 100;
+
+/// Entries on a leaderboard page when the client doesn't say
+const DEFAULT_PAGE_LIMIT: usize = 10;
+
+/// sled tree holding every day's scores, keyed by "<date>/<player>"
+const SCORES_TREE: &str = "daily_scores";
+
+#[derive(Clone)]
+pub struct Leaderboard {
+    scores: sled::Tree,
+    /// Most solutions any catalog puzzle has: no honest score finds more
+    max_solutions: u32,
+}
+
+/// Body of `POST /api/daily/{date}/scores`
+#[derive(Debug, Deserialize)]
+pub struct ScoreSubmission {
+    pub player: String,
+    /// Milliseconds from the puzzle appearing to the submitted solve
+    pub time_ms: u64,
+    pub solutions_found: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub player: String,
+    pub time_ms: u64,
+    pub solutions_found: u32,
+    /// Unix seconds of the submission that set this entry
+    pub submitted_at: u64,
+}
+
+impl ScoreEntry {
+    /// More solutions wins; ties go to the faster time
+    fn beats(&self, other: &ScoreEntry) -> bool {
+        (self.solutions_found, std::cmp::Reverse(self.time_ms))
+            > (other.solutions_found, std::cmp::Reverse(other.time_ms))
+    }
+}
+
+/// Query of `GET /api/daily/{date}/leaderboard`
+#[derive(Debug, Default, Deserialize)]
+pub struct PageQuery {
+    /// Rank of the first entry, from 0
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+    /// A player to center the page on instead ("around me"); ignored if they have no entry
+    pub around: Option<String>,
+}
+
+/// One page of a day's ranking
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScorePage {
+    /// Entries ranked for the day
+    pub total: usize,
+    /// Rank of the first entry, from 0
+    pub offset: usize,
+    pub entries: Vec<ScoreEntry>,
+    /// The `around` player's rank, from 0 (None if they weren't asked about or have no entry)
+    pub player_rank: Option<usize>,
+}
+
+type ApiError = (StatusCode, String);
+
+impl Leaderboard {
+    pub fn open(db: &sled::Db, max_solutions: u32) -> sled::Result<Self> {
+        Ok(Self {
+            scores: db.open_tree(SCORES_TREE)?,
+            max_solutions,
+        })
+    }
+
+    pub fn router(self) -> Router {
+        Router::new()
+            .route(
+                "/api/daily/{date}/scores",
+                routing::get(get_scores).post(post_score),
+            )
+            .route("/api/daily/{date}/leaderboard", routing::get(get_page))
+            .with_state(self)
+    }
+
+    /// Store the entry if it beats the player's previous one for that day
+    ///
+    /// Returns the player's best entry after the update.
+    fn submit(&self, date: &str, entry: ScoreEntry) -> sled::Result<ScoreEntry> {
+        let key = format!("{date}/{}", entry.player);
+        let mut best = entry.clone();
+
+        self.scores.fetch_and_update(key.as_bytes(), |old| {
+            let previous = old.and_then(|bytes| serde_json::from_slice::<ScoreEntry>(bytes).ok());
+            best = match previous {
+                Some(previous) if !entry.beats(&previous) => previous,
+                _ => entry.clone(),
+            };
+            serde_json::to_vec(&best).ok()
+        })?;
+
+        Ok(best)
+    }
+
+    /// A day's top entries, best first
+    fn day(&self, date: &str) -> sled::Result<Vec<ScoreEntry>> {
+        let mut entries = self.ranked(date)?;
+        entries.truncate(MAX_ENTRIES);
+        Ok(entries)
+    }
+
+    /// A page of a day's ranking: from `offset`, or around a player's entry
+    ///
+    /// A page centered on a player near the end is moved up so it stays full.
+    fn page(&self, date: &str, query: &PageQuery) -> sled::Result<ScorePage> {
+        let ranked = self.ranked(date)?;
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_PAGE_LIMIT)
+            .clamp(1, MAX_ENTRIES);
+
+        let player_rank = query
+            .around
+            .as_ref()
+            .and_then(|player| ranked.iter().position(|entry| entry.player == *player));
+        let offset = match player_rank {
+            Some(rank) => rank
+                .saturating_sub(limit / 2)
+                .min(ranked.len().saturating_sub(limit)),
+            None => query.offset.min(ranked.len()),
+        };
+
+        Ok(ScorePage {
+            total: ranked.len(),
+            offset,
+            entries: ranked.iter().skip(offset).take(limit).cloned().collect(),
+            player_rank,
+        })
+    }
+
+    /// All of a day's entries, best first
+    fn ranked(&self, date: &str) -> sled::Result<Vec<ScoreEntry>> {
+        let mut entries = Vec::new();
+        for item in self.scores.scan_prefix(format!("{date}/").as_bytes()) {
+            let (_, value) = item?;
+            match serde_json::from_slice::<ScoreEntry>(&value) {
+                Ok(entry) => entries.push(entry),
+                Err(err) => tracing::warn!("Skipping unreadable score for {date}: {err}"),
+            }
+        }
+
+        entries.sort_by(|a, b| {
+            b.solutions_found
+                .cmp(&a.solutions_found)
+                .then(a.time_ms.cmp(&b.time_ms))
+                .then(a.submitted_at.cmp(&b.submitted_at))
+        });
+        Ok(entries)
+    }
+}
+
+async fn get_scores(
+    State(board): State<Leaderboard>,
+    Path(date): Path<String>,
+) -> Result<Json<Vec<ScoreEntry>>, ApiError> {
+    validate_date(&date)?;
+    board.day(&date).map(Json).map_err(storage_error)
+}
+
+async fn get_page(
+    State(board): State<Leaderboard>,
+    Path(date): Path<String>,
+    Query(query): Query<PageQuery>,
+) -> Result<Json<ScorePage>, ApiError> {
+    validate_date(&date)?;
+    if let Some(player) = &query.around {
+        validate_player(player)?;
+    }
+    board.page(&date, &query).map(Json).map_err(storage_error)
+}
+
+async fn post_score(
+    State(board): State<Leaderboard>,
+    Path(date): Path<String>,
+    Json(submission): Json<ScoreSubmission>,
+) -> Result<(StatusCode, Json<ScoreEntry>), ApiError> {
+    validate_date(&date)?;
+    validate_submission(&submission, board.max_solutions)?;
+
+    let entry = ScoreEntry {
+        player: submission.player,
+        time_ms: submission.time_ms,
+        solutions_found: submission.solutions_found,
+        submitted_at: unix_now(),
+    };
+    let best = board.submit(&date, entry).map_err(storage_error)?;
+    Ok((StatusCode::CREATED, Json(best)))
+}
+
+/// Dates are "YYYY-MM-DD" (the client's UTC day)
+fn validate_date(date: &str) -> Result<(), ApiError> {
+    let bytes = date.as_bytes();
+    let well_formed = bytes.len() == 10
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            4 | 7 => *b == b'-',
+            _ => b.is_ascii_digit(),
+        });
+
+    let month = date.get(5..7).and_then(|m| m.parse::<u8>().ok());
+    let day = date.get(8..10).and_then(|d| d.parse::<u8>().ok());
+    let in_range = matches!(month, Some(1..=12)) && matches!(day, Some(1..=31));
+
+    if well_formed && in_range {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid date {date:?}, expected YYYY-MM-DD"),
+        ))
+    }
+}
+
+fn validate_player(player: &str) -> Result<(), ApiError> {
+    if is_id(player) {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::BAD_REQUEST,
+            format!("Player id must be 1-{MAX_ID_LEN} characters of [A-Za-z0-9_-]"),
+        ))
+    }
+}
+
+fn validate_submission(submission: &ScoreSubmission, max_solutions: u32) -> Result<(), ApiError> {
+    validate_player(&submission.player)?;
+    if submission.time_ms == 0 || submission.solutions_found == 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A score needs a non-zero time and at least one solution".to_string(),
+        ));
+    }
+    if submission.solutions_found > max_solutions {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("No puzzle has more than {max_solutions} solutions"),
+        ));
+    }
+    Ok(())
+}
+
+fn storage_error(err: sled::Error) -> ApiError {
+    tracing::error!("Leaderboard storage error: {err}");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Leaderboard storage error".to_string(),
+    )
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_board() -> Leaderboard {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        Leaderboard::open(&db, 12).unwrap()
+    }
+
+    fn entry(player: &str, time_ms: u64, solutions_found: u32) -> ScoreEntry {
+        ScoreEntry {
+            player: player.to_string(),
+            time_ms,
+            solutions_found,
+            submitted_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_keeps_best_entry_per_player() {
+        let board = temp_board();
+        board.submit("2026-01-01", entry("amy", 9000, 1)).unwrap();
+        board.submit("2026-01-01", entry("amy", 12000, 2)).unwrap();
+        let best = board.submit("2026-01-01", entry("amy", 5000, 1)).unwrap();
+
+        assert_eq!(best, entry("amy", 12000, 2));
+        assert_eq!(
+            board.day("2026-01-01").unwrap(),
+            vec![entry("amy", 12000, 2)]
+        );
+    }
+
+    #[test]
+    fn test_day_is_ranked_and_isolated() {
+        let board = temp_board();
+        board.submit("2026-01-01", entry("slow", 20000, 2)).unwrap();
+        board.submit("2026-01-01", entry("fast", 8000, 2)).unwrap();
+        board.submit("2026-01-01", entry("one", 1000, 1)).unwrap();
+        board.submit("2026-01-02", entry("other", 1000, 3)).unwrap();
+
+        let players: Vec<_> = board
+            .day("2026-01-01")
+            .unwrap()
+            .into_iter()
+            .map(|e| e.player)
+            .collect();
+        assert_eq!(players, ["fast", "slow", "one"]);
+    }
+
+    #[test]
+    fn test_pages_from_offset_or_around_a_player() {
+        let board = temp_board();
+        for (i, player) in ["a", "b", "c", "d", "e", "f"].iter().enumerate() {
+            board
+                .submit("2026-01-01", entry(player, 1000 * (i as u64 + 1), 1))
+                .unwrap();
+        }
+        let players = |page: &ScorePage| -> Vec<String> {
+            page.entries.iter().map(|e| e.player.clone()).collect()
+        };
+
+        let second = board
+            .page(
+                "2026-01-01",
+                &PageQuery {
+                    offset: 2,
+                    limit: Some(2),
+                    around: None,
+                },
+            )
+            .unwrap();
+        assert_eq!((second.total, second.offset), (6, 2));
+        assert_eq!(players(&second), ["c", "d"]);
+
+        let around = |player: &str| PageQuery {
+            limit: Some(3),
+            around: Some(player.to_string()),
+            ..PageQuery::default()
+        };
+        let middle = board.page("2026-01-01", &around("c")).unwrap();
+        assert_eq!((middle.offset, middle.player_rank), (1, Some(2)));
+        assert_eq!(players(&middle), ["b", "c", "d"]);
+        // Near the end, the page moves up to stay full
+        let last = board.page("2026-01-01", &around("f")).unwrap();
+        assert_eq!(players(&last), ["d", "e", "f"]);
+        // Someone without an entry gets the top of the board
+        let stranger = board.page("2026-01-01", &around("zed")).unwrap();
+        assert_eq!((stranger.offset, stranger.player_rank), (0, None));
+    }
+
+    #[test]
+    fn test_validate_date() {
+        assert!(validate_date("2026-10-17").is_ok());
+        assert!(validate_date("2026-13-01").is_err());
+        assert!(validate_date("2026-1-017").is_err());
+    }
+
+    #[test]
+    fn test_validate_submission() {
+        let submission = |solutions_found| ScoreSubmission {
+            player: "ada".to_string(),
+            time_ms: 1000,
+            solutions_found,
+        };
+        assert!(validate_submission(&submission(12), 12).is_ok());
+        assert!(validate_submission(&submission(0), 12).is_err());
+        assert!(validate_submission(&submission(13), 12).is_err());
+        assert!(validate_submission(&submission(u32::MAX), 12).is_err());
+    }
+}
@@ -1,3 +1,17 @@
+mod config;
+mod etag;
+mod leaderboard;
+mod monitoring;
+mod puzzles;
+mod rooms;
+mod saves;
+mod security;
+// Shared with the game, which applies the same rules to the saves it sends
+#[path = "../../src/sync_rules.rs"]
+mod sync_rules;
+mod telemetry;
+mod tls;
+
 use axum::{
     Router,
     body::Body,
@@ -6,10 +20,18 @@ use axum::{
     response::Response,
     routing,
 };
+use std::{net::SocketAddr, sync::Arc};
 use tower::ServiceBuilder;
 use tower_http::{
     compression::CompressionLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
     services::{ServeDir, ServeFile},
+    trace::{DefaultOnResponse, TraceLayer},
+};
+
+use crate::{
+    config::Config, etag::EtagIndex, leaderboard::Leaderboard, puzzles::PuzzleCatalog,
+    rooms::Rooms, saves::Saves, security::SecurityHeaders, telemetry::Telemetry,
 };
 
 async fn healthz() -> &'static str {
@@ -18,34 +40,135 @@ async fn healthz() -> &'static str {
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Invalid configuration: {err}");
+            std::process::exit(2);
+        }
+    };
+    config::init_logging(config.log_format);
+    tracing::debug!("{config:?}");
+    let metrics = monitoring::install();
+
+    let static_dir = &config.static_dir;
+    let static_files =
+        ServeDir::new(static_dir).not_found_service(ServeFile::new(config.index_path()));
+    let etags = EtagIndex::build(static_dir).unwrap_or_else(|err| {
+        tracing::warn!("No ETags: failed to hash {}: {err}", static_dir.display());
+        EtagIndex::default()
+    });
+    tracing::info!("ETags: {} static files hashed", etags.len());
 
-    let static_dir = std::env::var("STATIC_DIR").unwrap_or_else(|_| "../dist".to_string());
-    let index_path = format!("{}/index.html", static_dir);
+    let db = sled::open(&config.data_dir).expect("failed to open data dir");
+    let catalog = PuzzleCatalog::load().expect("failed to load puzzle catalog");
+    tracing::info!("Puzzle catalog: {} puzzles", catalog.total_puzzle_count());
+    let max_solutions = u32::try_from(catalog.most_solutions()).unwrap_or(u32::MAX);
+    let leaderboard = Leaderboard::open(&db, max_solutions).expect("failed to open leaderboard");
+    let telemetry = Telemetry::open(&db).expect("failed to open telemetry");
+    let saves = Saves::open(&db).expect("failed to open saves");
 
-    let static_files = ServeDir::new(&static_dir).not_found_service(ServeFile::new(&index_path));
+    let security =
+        SecurityHeaders::new(&config.index_path(), config.security.cross_origin_isolation);
+
+    let compression = CompressionLayer::new()
+        .br(config.compression.br)
+        .gzip(config.compression.gzip);
 
     let app = Router::new()
         .route("/healthz", routing::get(healthz))
+        .merge(leaderboard.router())
+        .merge(catalog.router())
+        .merge(telemetry.router())
+        .merge(saves.router())
+        .merge(Rooms::default().router())
+        .merge(monitoring::router(metrics))
         .fallback_service(static_files)
         .layer(
             ServiceBuilder::new()
-                .layer(CompressionLayer::new().br(true).gzip(true))
-                .layer(middleware::from_fn(cache_control)),
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(request_span)
+                        .on_response(DefaultOnResponse::new().level(tracing::Level::INFO)),
+                )
+                .layer(PropagateRequestIdLayer::x_request_id())
+                .layer(middleware::from_fn_with_state(
+                    Arc::new(security),
+                    security::security_headers,
+                ))
+                .layer(middleware::from_fn(monitoring::track))
+                .layer(middleware::from_fn(cache_control))
+                .layer(middleware::from_fn_with_state(Arc::new(etags), etag::etag))
+                .layer(compression),
         );
 
-    let port = std::env::var("PORT").unwrap_or_else(|_| "8080".to_string());
-    let addr = format!("0.0.0.0:{port}");
-    tracing::info!("Server running on http://{addr}");
+    let addr = config.addr().expect("invalid bind address");
 
-    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    let tls_paths = match config.tls.paths() {
+        Ok(paths) => paths,
+        Err(err) => {
+            tracing::error!("{err}");
+            std::process::exit(2);
+        }
+    };
+    let Some((cert, key)) = tls_paths else {
+        tracing::info!("Server running on http://{addr}");
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        axum::serve(listener, app).await.unwrap();
+        return;
+    };
+
+    let app = if config.tls.hsts_max_age > 0 {
+        app.layer(middleware::from_fn_with_state(
+            tls::hsts_value(config.tls.hsts_max_age),
+            tls::hsts,
+        ))
+    } else {
+        app
+    };
+
+    if let Some(redirect_port) = config.tls.redirect_port {
+        let redirect_addr = SocketAddr::new(addr.ip(), redirect_port);
+        tokio::spawn(async move {
+            if let Err(err) = tls::serve_redirect(redirect_addr, addr.port()).await {
+                tracing::error!("HTTP redirect listener failed: {err}");
+            }
+        });
+    }
+
+    tls::serve(app, addr, cert, key)
+        .await
+        .expect("HTTPS server failed");
+}
+
+/// One span per request, tagged with the id `SetRequestIdLayer` assigned (echoed back
+/// to the client as `x-request-id`)
+fn request_span(req: &Request<Body>) -> tracing::Span {
+    let request_id = req
+        .headers()
+        .get("x-request-id")
+        .and_then(|id| id.to_str().ok())
+        .unwrap_or("-");
+    tracing::info_span!(
+        "request",
+        method = %req.method(),
+        uri = %req.uri(),
+        request_id,
+    )
 }
 
 async fn cache_control(req: Request<Body>, next: Next) -> Response {
     let path = req.uri().path().to_owned(); // <- avoid borrowing req
     let mut res = next.run(req).await;
 
+    // API: live data, never cache
+    if path.starts_with("/api/") {
+        res.headers_mut()
+            .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        return res;
+    }
+
     // HTML: always revalidate (lets you change index each load)
     if path == "/" || path.ends_with(".html") {
         res.headers_mut().insert(
@@ -0,0 +1,82 @@
+// monitoring.rs - Prometheus metrics for requests, latency and static-asset revalidation
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::Response,
+    routing,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::time::Instant;
+
+const REQUESTS_TOTAL: &str = "http_requests_total";
+const REQUEST_DURATION: &str = "http_request_duration_seconds";
+
+/// Static-file responses by result; `not_modified / all` is the browser cache hit ratio
+const STATIC_RESPONSES_TOTAL: &str = "static_asset_responses_total";
+
+/// Latency buckets (seconds): static files are sub-millisecond, API calls hit sled
+const DURATION_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+/// Install the global recorder; the handle renders the `/metrics` page
+pub fn install() -> PrometheusHandle {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(REQUEST_DURATION.to_string()),
+            DURATION_BUCKETS,
+        )
+        .expect("duration buckets are non-empty")
+        .install_recorder()
+        .expect("metrics recorder installs once")
+}
+
+pub fn router(handle: PrometheusHandle) -> Router {
+    Router::new()
+        .route("/metrics", routing::get(render))
+        .with_state(handle)
+}
+
+async fn render(State(handle): State<PrometheusHandle>) -> String {
+    handle.render()
+}
+
+/// Middleware: Count and time every request
+///
+/// Routes are labelled by their pattern (`/api/daily/{date}/scores`); everything the
+/// static fallback serves shares the "static" label to keep label cardinality fixed.
+pub async fn track(req: Request<Body>, next: Next) -> Response {
+    let started = Instant::now();
+    let method = req.method().to_string();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned());
+
+    let res = next.run(req).await;
+    let status = res.status();
+    let is_static = route.is_none();
+    let labels = [
+        ("method", method),
+        ("route", route.unwrap_or_else(|| "static".to_string())),
+        ("status", status.as_u16().to_string()),
+    ];
+
+    metrics::counter!(REQUESTS_TOTAL, &labels).increment(1);
+    metrics::histogram!(REQUEST_DURATION, &labels).record(started.elapsed().as_secs_f64());
+
+    if is_static {
+        let result = match status {
+            StatusCode::NOT_MODIFIED => "not_modified",
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => "full",
+            _ => "other",
+        };
+        metrics::counter!(STATIC_RESPONSES_TOTAL, "result" => result).increment(1);
+    }
+
+    res
+}
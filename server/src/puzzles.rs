@@ -0,0 +1,186 @@
+// puzzles.rs - the puzzle catalog, served to clients that fetch puzzles at runtime
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Same file the game embeds; the server is the place to grow it without a client rebuild
+const PUZZLES_CSV: &str = include_str!("../../assets/puzzles_symmetric.csv");
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CatalogPuzzle {
+    /// Row-major valences of the 3x3 grid
+    pub valences: [usize; 9],
+    /// Distinct solutions (edge sets) as the puzzle set lists them: complexity ÷ edges,
+    /// not re-solved here
+    pub listed_solutions: usize,
+}
+
+/// Body of `GET /api/puzzles?complexity=N`
+#[derive(Debug, Serialize)]
+pub struct CatalogPage<'a> {
+    pub complexity: usize,
+    pub puzzles: &'a [CatalogPuzzle],
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogQuery {
+    complexity: usize,
+}
+
+#[derive(Clone)]
+pub struct PuzzleCatalog {
+    by_complexity: Arc<BTreeMap<usize, Vec<CatalogPuzzle>>>,
+}
+
+type ApiError = (StatusCode, String);
+
+impl PuzzleCatalog {
+    /// Parse the embedded CSV
+    pub fn load() -> Result<Self, String> {
+        Self::from_csv(PUZZLES_CSV)
+    }
+
+    /// CSV format: 9 valence values followed by complexity
+    ///
+    /// Rows whose complexity isn't a whole multiple of the edge count can't have a valid
+    /// solution count, so they're rejected rather than served.
+    fn from_csv(csv_data: &str) -> Result<Self, String> {
+        let mut by_complexity: BTreeMap<usize, Vec<CatalogPuzzle>> = BTreeMap::new();
+
+        for (line_num, line) in csv_data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let values: Vec<usize> = line
+                .split(',')
+                .map(|s| s.trim().parse::<usize>())
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("Parse error on line {}: {}", line_num + 1, e))?;
+
+            let Ok(row) = <[usize; 10]>::try_from(values.as_slice()) else {
+                return Err(format!(
+                    "Line {} has {} values, expected 10 (9 valences + 1 complexity)",
+                    line_num + 1,
+                    values.len()
+                ));
+            };
+
+            let mut valences = [0; 9];
+            valences.copy_from_slice(&row[..9]);
+            let complexity = row[9];
+
+            let edges = valences.iter().sum::<usize>() / 2;
+            if edges == 0 || complexity % edges != 0 {
+                return Err(format!(
+                    "Line {}: complexity {} isn't a multiple of its {} edges",
+                    line_num + 1,
+                    complexity,
+                    edges
+                ));
+            }
+
+            by_complexity
+                .entry(complexity)
+                .or_default()
+                .push(CatalogPuzzle {
+                    valences,
+                    listed_solutions: complexity / edges,
+                });
+        }
+
+        if by_complexity.is_empty() {
+            return Err("No puzzles loaded from CSV".to_string());
+        }
+
+        Ok(Self {
+            by_complexity: Arc::new(by_complexity),
+        })
+    }
+
+    pub fn total_puzzle_count(&self) -> usize {
+        self.by_complexity.values().map(Vec::len).sum()
+    }
+
+    /// The largest solution count of any puzzle
+    pub fn most_solutions(&self) -> usize {
+        self.by_complexity
+            .values()
+            .flatten()
+            .map(|p| p.listed_solutions)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/api/puzzles", routing::get(get_puzzles))
+            .with_state(self)
+    }
+}
+
+async fn get_puzzles(
+    State(catalog): State<PuzzleCatalog>,
+    Query(query): Query<CatalogQuery>,
+) -> Result<Response, ApiError> {
+    let Some(puzzles) = catalog.by_complexity.get(&query.complexity) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("No puzzles with complexity {}", query.complexity),
+        ));
+    };
+
+    let page = CatalogPage {
+        complexity: query.complexity,
+        puzzles,
+    };
+    Ok(Json(page).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_catalog_loads() {
+        let catalog = PuzzleCatalog::load().unwrap();
+        assert!(catalog.total_puzzle_count() > 0);
+
+        // Complexity 1 is a single edge with a single solution
+        let simplest = &catalog.by_complexity[&1];
+        assert!(simplest.iter().all(|p| p.listed_solutions == 1));
+    }
+
+    #[test]
+    fn test_most_solutions() {
+        let catalog = PuzzleCatalog::from_csv(
+            "0,0,0,0,0,0,1,2,1,2\n1,1,0,0,0,0,0,0,0,1\n0,0,0,1,2,1,0,0,0,6",
+        )
+        .unwrap();
+        assert_eq!(catalog.most_solutions(), 3);
+    }
+
+    #[test]
+    fn test_solution_counts() {
+        let catalog = PuzzleCatalog::from_csv("0,0,0,0,0,0,1,2,1,2").unwrap();
+        let puzzle = &catalog.by_complexity[&2][0];
+        assert_eq!(puzzle.listed_solutions, 1);
+        assert_eq!(puzzle.valences, [0, 0, 0, 0, 0, 0, 1, 2, 1]);
+    }
+
+    #[test]
+    fn test_rejects_bad_rows() {
+        assert!(PuzzleCatalog::from_csv("0,0,0,0,0,0,0,1,1").is_err());
+        assert!(PuzzleCatalog::from_csv("0,0,0,0,0,0,0,1,1,x").is_err());
+        // 2 edges can't make complexity 3
+        assert!(PuzzleCatalog::from_csv("0,0,0,0,0,0,1,2,1,3").is_err());
+        assert!(PuzzleCatalog::from_csv("").is_err());
+    }
+}
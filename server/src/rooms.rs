@@ -0,0 +1,310 @@
+// rooms.rs - WebSocket rooms that relay JSON messages between the players in them
+
+use axum::{
+    Router,
+    extract::{
+        Path, State, WebSocketUpgrade,
+        ws::{Message, WebSocket},
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use serde_json::{Value, json};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tokio::sync::broadcast;
+
+use crate::sync_rules::{MAX_ID_LEN, is_id};
+
+/// Players (or spectators) sharing one room
+const MAX_PEERS: usize = 8;
+
+/// Largest text frame relayed; a board with every solution found is a few kilobytes
+const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+
+/// Relayed messages a slow client may fall behind by before it skips ahead
+const CHANNEL_CAPACITY: usize = 64;
+
+/// Message types clients may send: the shared board (co-op) and race progress
+///
+/// Anything else is dropped, which also bounds a room's replayed `latest` to one message
+/// of each of these.
+const CLIENT_TYPES: [&str; 2] = ["board", "race"];
+
+/// Live rooms, created on first join and dropped when the last peer leaves
+#[derive(Clone, Default)]
+pub struct Rooms {
+    rooms: Arc<Mutex<HashMap<String, Room>>>,
+    next_peer: Arc<AtomicU64>,
+}
+
+struct Room {
+    tx: broadcast::Sender<Relay>,
+    /// Latest message of each client type, replayed to whoever joins next
+    latest: BTreeMap<&'static str, Value>,
+    peers: usize,
+}
+
+/// A message on its way to every peer except the sender
+#[derive(Debug, Clone)]
+struct Relay {
+    from: u64,
+    text: Arc<str>,
+}
+
+/// A peer's place in a room
+struct Membership {
+    peer: u64,
+    rx: broadcast::Receiver<Relay>,
+    /// `{"type": "welcome", "peers": <others>, "latest": [...]}`
+    welcome: String,
+}
+
+impl Rooms {
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/ws/room/{id}", routing::get(join_room))
+            .with_state(self)
+    }
+
+    fn is_full(&self, room_id: &str) -> bool {
+        let rooms = self.rooms.lock().unwrap();
+        rooms
+            .get(room_id)
+            .is_some_and(|room| room.peers >= MAX_PEERS)
+    }
+
+    fn join(&self, room_id: &str) -> Result<Membership, String> {
+        let mut rooms = self.rooms.lock().unwrap();
+        let room = rooms.entry(room_id.to_string()).or_insert_with(|| Room {
+            tx: broadcast::channel(CHANNEL_CAPACITY).0,
+            latest: BTreeMap::new(),
+            peers: 0,
+        });
+        if room.peers >= MAX_PEERS {
+            return Err(format!("Room is full ({MAX_PEERS} peers)"));
+        }
+
+        let peer = self.next_peer.fetch_add(1, Ordering::Relaxed);
+        let welcome = json!({
+            "type": "welcome",
+            "peers": room.peers,
+            "latest": room.latest.values().collect::<Vec<_>>(),
+        });
+        room.peers += 1;
+        announce_peers(room, peer);
+
+        Ok(Membership {
+            peer,
+            rx: room.tx.subscribe(),
+            welcome: welcome.to_string(),
+        })
+    }
+
+    /// Relay a peer's message to the rest of the room
+    fn publish(&self, room_id: &str, peer: u64, text: &str) -> Result<(), String> {
+        let (kind, message) = validate_message(text)?;
+
+        let mut rooms = self.rooms.lock().unwrap();
+        let Some(room) = rooms.get_mut(room_id) else {
+            return Err("Room closed".to_string());
+        };
+        room.latest.insert(kind, message);
+        // No receivers just means the peer is alone for now
+        let _ = room.tx.send(Relay {
+            from: peer,
+            text: text.into(),
+        });
+        Ok(())
+    }
+
+    fn leave(&self, room_id: &str, peer: u64) {
+        let mut rooms = self.rooms.lock().unwrap();
+        let Some(room) = rooms.get_mut(room_id) else {
+            return;
+        };
+
+        room.peers -= 1;
+        if room.peers == 0 {
+            rooms.remove(room_id);
+        } else {
+            announce_peers(room, peer);
+        }
+    }
+}
+
+/// Tell everyone but `peer` how many others are now in the room
+fn announce_peers(room: &Room, peer: u64) {
+    let text = json!({ "type": "peers", "count": room.peers - 1 }).to_string();
+    let _ = room.tx.send(Relay {
+        from: peer,
+        text: text.into(),
+    });
+}
+
+async fn join_room(
+    State(rooms): State<Rooms>,
+    Path(room_id): Path<String>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    if let Err(err) = validate_room_id(&room_id) {
+        return err.into_response();
+    }
+    if rooms.is_full(&room_id) {
+        return (
+            StatusCode::CONFLICT,
+            format!("Room is full ({MAX_PEERS} peers)"),
+        )
+            .into_response();
+    }
+
+    upgrade
+        .max_message_size(MAX_MESSAGE_BYTES)
+        .on_upgrade(move |socket| async move {
+            // Joined only once upgraded, so abandoned handshakes never hold a seat
+            let Ok(membership) = rooms.join(&room_id) else {
+                return;
+            };
+            let peer = membership.peer;
+            run_peer(&rooms, &room_id, socket, membership).await;
+            rooms.leave(&room_id, peer);
+        })
+}
+
+/// Pump messages between one socket and its room until either side closes
+async fn run_peer(rooms: &Rooms, room_id: &str, mut socket: WebSocket, membership: Membership) {
+    let Membership {
+        peer,
+        mut rx,
+        welcome,
+    } = membership;
+    if socket.send(Message::Text(welcome.into())).await.is_err() {
+        return;
+    }
+    tracing::debug!("Peer {peer} joined room {room_id}");
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    if let Err(err) = rooms.publish(room_id, peer, text.as_str()) {
+                        tracing::debug!("Dropped message from peer {peer}: {err}");
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by axum; binary frames aren't part of the protocol
+                Some(Ok(_)) => {}
+            },
+            relayed = rx.recv() => match relayed {
+                Ok(relay) if relay.from == peer => {}
+                Ok(relay) => {
+                    if socket.send(Message::Text(relay.text.as_ref().into())).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Peer {peer} skipped {skipped} messages");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+    tracing::debug!("Peer {peer} left room {room_id}");
+}
+
+fn validate_room_id(room_id: &str) -> Result<(), (StatusCode, String)> {
+    if is_id(room_id) {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::BAD_REQUEST,
+            format!("Room id must be 1-{MAX_ID_LEN} characters of [A-Za-z0-9_-]"),
+        ))
+    }
+}
+
+/// Messages are JSON objects with one of the client "type"s; the payload is the clients'
+/// business
+fn validate_message(text: &str) -> Result<(&'static str, Value), String> {
+    if text.len() > MAX_MESSAGE_BYTES {
+        return Err(format!("Message over {MAX_MESSAGE_BYTES} bytes"));
+    }
+    let message: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let Some(kind) = message.get("type").and_then(Value::as_str) else {
+        return Err("Message needs a \"type\"".to_string());
+    };
+    match CLIENT_TYPES.iter().find(|&&known| known == kind) {
+        Some(&kind) => Ok((kind, message)),
+        None => Err(format!("Clients can't send {kind:?} messages")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn welcome(membership: &Membership) -> Value {
+        serde_json::from_str(&membership.welcome).unwrap()
+    }
+
+    #[test]
+    fn test_relay_skips_sender_and_replays_latest() {
+        let rooms = Rooms::default();
+        let mut first = rooms.join("lobby").unwrap();
+        assert_eq!(welcome(&first)["peers"], 0);
+
+        rooms
+            .publish("lobby", first.peer, r#"{"type":"board","trail":[1]}"#)
+            .unwrap();
+        rooms
+            .publish("lobby", first.peer, r#"{"type":"board","trail":[1,2]}"#)
+            .unwrap();
+
+        // A later joiner gets only the latest board
+        let second = rooms.join("lobby").unwrap();
+        assert_eq!(welcome(&second)["peers"], 1);
+        assert_eq!(
+            welcome(&second)["latest"],
+            json!([{"type": "board", "trail": [1, 2]}])
+        );
+
+        // The first peer sees its own messages tagged as its own, then the join notice
+        let own = first.rx.try_recv().unwrap();
+        assert_eq!(own.from, first.peer);
+        first.rx.try_recv().unwrap();
+        let joined = first.rx.try_recv().unwrap();
+        assert_eq!(joined.from, second.peer);
+        assert_eq!(&*joined.text, r#"{"count":1,"type":"peers"}"#);
+    }
+
+    #[test]
+    fn test_empty_room_is_dropped() {
+        let rooms = Rooms::default();
+        let peer = rooms.join("lobby").unwrap().peer;
+        rooms.publish("lobby", peer, r#"{"type":"board"}"#).unwrap();
+        rooms.leave("lobby", peer);
+
+        assert!(rooms.rooms.lock().unwrap().is_empty());
+        assert_eq!(welcome(&rooms.join("lobby").unwrap())["latest"], json!([]));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_room_id("game-42_b").is_ok());
+        assert!(validate_room_id("").is_err());
+
+        assert!(validate_message(r#"{"type":"board"}"#).is_ok());
+        assert!(validate_message(r#"{"type":"race","found":2}"#).is_ok());
+        assert!(validate_message(r#"{"type":"welcome"}"#).is_err());
+        // Made-up types would pile up in the room's replay, so they aren't relayed at all
+        assert!(validate_message(r#"{"type":"junk-1"}"#).is_err());
+        assert!(validate_message(r#"{"trail":[]}"#).is_err());
+        assert!(validate_message("not json").is_err());
+    }
+}
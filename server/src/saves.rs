@@ -0,0 +1,290 @@
+// saves.rs - profile saves kept for players, so progress follows them between devices
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header},
+    routing,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::sync_rules::{FoundSolutions, MAX_ID_LEN, SyncedSave, is_id, is_token, merge};
+
+/// A profile is a few KB; anything much bigger isn't one
+const MAX_SAVE_BYTES: usize = 64 * 1024;
+
+/// sled tree holding each profile's save, keyed by profile id
+const SAVES_TREE: &str = "saves";
+
+/// sled tree holding the SHA-256 of each profile's token, keyed by profile id
+const TOKENS_TREE: &str = "save_tokens";
+
+#[derive(Clone)]
+pub struct Saves {
+    saves: sled::Tree,
+    tokens: sled::Tree,
+}
+
+/// Mirrors the client's `Profile`: the fields saves are merged by, with the rest kept
+/// as the client sent it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    /// Unix milliseconds of the client's last change
+    pub updated_at: u64,
+    #[serde(default)]
+    pub board: Option<SavedBoard>,
+    #[serde(flatten)]
+    pub rest: Map<String, Value>,
+}
+
+/// Mirrors the client's `BoardState`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedBoard {
+    pub valences: [usize; 9],
+    /// Each found solution as its edges, sorted
+    pub found: FoundSolutions,
+    #[serde(flatten)]
+    pub rest: Map<String, Value>,
+}
+
+impl SyncedSave for SaveData {
+    fn updated_at(&self) -> u64 {
+        self.updated_at
+    }
+
+    fn board(&self) -> Option<(&[usize; 9], &FoundSolutions)> {
+        self.board
+            .as_ref()
+            .map(|board| (&board.valences, &board.found))
+    }
+
+    fn board_mut(&mut self) -> Option<(&[usize; 9], &mut FoundSolutions)> {
+        self.board
+            .as_mut()
+            .map(|board| (&board.valences, &mut board.found))
+    }
+}
+
+type ApiError = (StatusCode, String);
+
+impl Saves {
+    pub fn open(db: &sled::Db) -> sled::Result<Self> {
+        Ok(Self {
+            saves: db.open_tree(SAVES_TREE)?,
+            tokens: db.open_tree(TOKENS_TREE)?,
+        })
+    }
+
+    pub fn router(self) -> Router {
+        Router::new()
+            .route(
+                "/api/save/{profile_id}",
+                routing::get(get_save).put(put_save),
+            )
+            .with_state(self)
+    }
+
+    /// Check a request's token against the profile's; the first token seen claims it
+    fn authorize(&self, profile_id: &str, token: &str) -> Result<(), ApiError> {
+        let hash = Sha256::digest(token.as_bytes()).to_vec();
+        let claimed = self
+            .tokens
+            .compare_and_swap(profile_id.as_bytes(), None::<&[u8]>, Some(hash.as_slice()))
+            .map_err(storage_error)?;
+        match claimed {
+            Ok(()) => Ok(()),
+            Err(held) if held.current.as_deref() == Some(hash.as_slice()) => Ok(()),
+            Err(_) => Err((
+                StatusCode::FORBIDDEN,
+                format!("Profile {profile_id} belongs to another token"),
+            )),
+        }
+    }
+
+    fn load(&self, profile_id: &str) -> sled::Result<Option<SaveData>> {
+        let Some(bytes) = self.saves.get(profile_id.as_bytes())? else {
+            return Ok(None);
+        };
+        match serde_json::from_slice(&bytes) {
+            Ok(save) => Ok(Some(save)),
+            Err(err) => {
+                tracing::warn!("Replacing unreadable save for {profile_id}: {err}");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Merge the save into the stored one, returning the save as it now stands
+    fn store(&self, profile_id: &str, save: SaveData) -> sled::Result<SaveData> {
+        let mut merged = save.clone();
+        self.saves.fetch_and_update(profile_id.as_bytes(), |old| {
+            let stored = old.and_then(|bytes| serde_json::from_slice::<SaveData>(bytes).ok());
+            merged = match stored {
+                Some(stored) => merge(&stored, &save),
+                None => save.clone(),
+            };
+            serde_json::to_vec(&merged).ok()
+        })?;
+        Ok(merged)
+    }
+}
+
+async fn get_save(
+    State(saves): State<Saves>,
+    Path(profile_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<SaveData>, ApiError> {
+    validate_profile_id(&profile_id)?;
+    saves.authorize(&profile_id, bearer_token(&headers)?)?;
+    match saves.load(&profile_id).map_err(storage_error)? {
+        Some(save) => Ok(Json(save)),
+        None => Err((StatusCode::NOT_FOUND, format!("No save for {profile_id}"))),
+    }
+}
+
+async fn put_save(
+    State(saves): State<Saves>,
+    Path(profile_id): Path<String>,
+    headers: HeaderMap,
+    Json(save): Json<SaveData>,
+) -> Result<Json<SaveData>, ApiError> {
+    validate_profile_id(&profile_id)?;
+    validate_save(&save)?;
+    saves.authorize(&profile_id, bearer_token(&headers)?)?;
+    saves
+        .store(&profile_id, save)
+        .map(Json)
+        .map_err(storage_error)
+}
+
+fn validate_profile_id(profile_id: &str) -> Result<(), ApiError> {
+    if is_id(profile_id) {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::BAD_REQUEST,
+            format!("Profile id must be 1-{MAX_ID_LEN} characters of [A-Za-z0-9_-]"),
+        ))
+    }
+}
+
+/// The profile's token, from `Authorization: Bearer <token>`
+fn bearer_token(headers: &HeaderMap) -> Result<&str, ApiError> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .filter(|token| is_token(token))
+        .ok_or((
+            StatusCode::UNAUTHORIZED,
+            "Saves need the profile's token as a bearer token".to_string(),
+        ))
+}
+
+fn validate_save(save: &SaveData) -> Result<(), ApiError> {
+    let size = serde_json::to_vec(save)
+        .map(|bytes| bytes.len())
+        .unwrap_or(0);
+    if size > MAX_SAVE_BYTES {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("A save can be at most {MAX_SAVE_BYTES} bytes"),
+        ));
+    }
+    Ok(())
+}
+
+fn storage_error(err: sled::Error) -> ApiError {
+    tracing::error!("Save storage error: {err}");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "Save storage error".to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_saves() -> Saves {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        Saves::open(&db).unwrap()
+    }
+
+    fn save(updated_at: u64, level: usize, found: &[&[[usize; 2]]]) -> SaveData {
+        serde_json::from_value(serde_json::json!({
+            "name": "Sam",
+            "level": level,
+            "updated_at": updated_at,
+            "board": {
+                "level": level,
+                "valences": [2, 2, 0, 2, 2, 0, 0, 0, 0],
+                "total_solutions": 3,
+                "trail": [],
+                "found": found,
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_later_save_wins_and_keeps_both_solutions() {
+        let saves = temp_saves();
+        saves.store("sam", save(2000, 4, &[&[[0, 1]]])).unwrap();
+        let merged = saves.store("sam", save(1000, 3, &[&[[1, 4]]])).unwrap();
+
+        assert_eq!(merged.updated_at, 2000);
+        assert_eq!(merged.rest["level"], 4);
+        assert_eq!(
+            merged.board.unwrap().found,
+            vec![vec![[0, 1]], vec![[1, 4]]]
+        );
+        assert_eq!(saves.load("sam").unwrap().unwrap().updated_at, 2000);
+    }
+
+    #[test]
+    fn test_unknown_fields_round_trip() {
+        let saved = save(1000, 3, &[]);
+        let json = serde_json::to_value(&saved).unwrap();
+        assert_eq!(json["name"], "Sam");
+        assert_eq!(json["board"]["total_solutions"], 3);
+    }
+
+    #[test]
+    fn test_first_token_claims_the_profile() {
+        let saves = temp_saves();
+        let ours = "0123456789abcdef0123456789abcdef";
+        saves.authorize("sam", ours).unwrap();
+        saves.authorize("sam", ours).unwrap();
+
+        let (status, _) = saves
+            .authorize("sam", "fedcba9876543210fedcba9876543210")
+            .unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        // Other profiles are claimed separately
+        saves
+            .authorize("kim", "fedcba9876543210fedcba9876543210")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            bearer_token(&headers).unwrap_err().0,
+            StatusCode::UNAUTHORIZED
+        );
+
+        headers.insert(header::AUTHORIZATION, "Bearer short".parse().unwrap());
+        assert!(bearer_token(&headers).is_err());
+
+        let token = "0123456789abcdef0123456789abcdef";
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {token}").parse().unwrap(),
+        );
+        assert_eq!(bearer_token(&headers).unwrap(), token);
+    }
+}
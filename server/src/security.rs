@@ -0,0 +1,190 @@
+// security.rs - CSP, nosniff, referrer and cross-origin isolation headers on every response
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderMap, HeaderName, HeaderValue, Request, header},
+    middleware::Next,
+    response::Response,
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use sha2::{Digest, Sha256};
+use std::{path::Path, sync::Arc};
+
+/// Headers added to every response, built once at startup
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders {
+    headers: HeaderMap,
+}
+
+impl SecurityHeaders {
+    /// Build the header set for the web client served from `index`
+    ///
+    /// index.html boots the wasm from an inline `<script>` and styles the page from an
+    /// inline `<style>`; both are allowed by hash so the policy needs no 'unsafe-inline'.
+    pub fn new(index: &Path, cross_origin_isolation: bool) -> Self {
+        let html = std::fs::read_to_string(index).unwrap_or_else(|err| {
+            tracing::warn!(
+                "CSP: failed to read {}: {err}, inline scripts will be blocked",
+                index.display()
+            );
+            String::new()
+        });
+        let policy = content_security_policy(&html);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_str(&policy).expect("CSP is ASCII"),
+        );
+        headers.insert(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        );
+        headers.insert(
+            header::REFERRER_POLICY,
+            HeaderValue::from_static("strict-origin-when-cross-origin"),
+        );
+
+        // Makes the page cross-origin isolated, which SharedArrayBuffer (and so a
+        // threaded wasm build) requires. Everything the client loads is same-origin.
+        if cross_origin_isolation {
+            headers.insert(
+                HeaderName::from_static("cross-origin-opener-policy"),
+                HeaderValue::from_static("same-origin"),
+            );
+            headers.insert(
+                HeaderName::from_static("cross-origin-embedder-policy"),
+                HeaderValue::from_static("require-corp"),
+            );
+        }
+
+        Self { headers }
+    }
+}
+
+/// Middleware: Add the security headers (a handler's own values win)
+pub async fn security_headers(
+    State(security): State<Arc<SecurityHeaders>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let mut res = next.run(req).await;
+    for (name, value) in &security.headers {
+        if !res.headers().contains_key(name) {
+            res.headers_mut().insert(name.clone(), value.clone());
+        }
+    }
+    res
+}
+
+/// Same-origin only, plus 'wasm-unsafe-eval' to compile the wasm module and hashes of
+/// index.html's inline blocks
+fn content_security_policy(html: &str) -> String {
+    let scripts = inline_hashes(html, "script");
+    let styles = inline_hashes(html, "style");
+
+    [
+        "default-src 'self'".to_string(),
+        format!("script-src 'self' 'wasm-unsafe-eval'{scripts}"),
+        format!("style-src 'self'{styles}"),
+        "img-src 'self' data: blob:".to_string(),
+        "connect-src 'self'".to_string(),
+        "worker-src 'self' blob:".to_string(),
+        "object-src 'none'".to_string(),
+        "base-uri 'self'".to_string(),
+        "form-action 'none'".to_string(),
+        "frame-ancestors 'self'".to_string(),
+    ]
+    .join("; ")
+}
+
+/// " 'sha256-…'" for each inline `<tag>` body in `html` (skipping `src=` scripts)
+fn inline_hashes(html: &str, tag: &str) -> String {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut hashes = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let after_open = &rest[start + open.len()..];
+        let Some(attrs_end) = after_open.find('>') else {
+            break;
+        };
+        let attrs = &after_open[..attrs_end];
+        let body_start = &after_open[attrs_end + 1..];
+        let Some(body_len) = body_start.find(&close) else {
+            break;
+        };
+
+        // "<scripts" or "<styles" aren't this tag
+        let is_tag = attrs.is_empty() || attrs.starts_with(char::is_whitespace);
+        if is_tag && !attrs.contains("src=") {
+            let digest = Sha256::digest(&body_start.as_bytes()[..body_len]);
+            hashes.push_str(&format!(" 'sha256-{}'", STANDARD.encode(digest)));
+        }
+        rest = &body_start[body_len + close.len()..];
+    }
+
+    hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, http::StatusCode, middleware, routing};
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_inline_blocks_are_hashed() {
+        let html = r#"<html><head><style>
+            body { margin: 0; }
+        </style></head><body>
+        <script src="./external.js"></script>
+        <script type="module">init();</script>
+        </body></html>"#;
+
+        let expected = STANDARD.encode(Sha256::digest("init();"));
+        assert_eq!(
+            inline_hashes(html, "script"),
+            format!(" 'sha256-{expected}'")
+        );
+        assert_eq!(inline_hashes(html, "style").matches("'sha256-").count(), 1);
+
+        let policy = content_security_policy(html);
+        assert!(policy.contains("script-src 'self' 'wasm-unsafe-eval' 'sha256-"));
+        assert!(!policy.contains("unsafe-inline"));
+    }
+
+    #[tokio::test]
+    async fn test_headers_added_to_responses() {
+        let security = Arc::new(SecurityHeaders::new(Path::new("missing.html"), true));
+        let app = Router::new()
+            .route("/", routing::get(|| async { "ok" }))
+            .route(
+                "/custom",
+                routing::get(|| async { ([(header::REFERRER_POLICY, "no-referrer")], "ok") }),
+            )
+            .layer(middleware::from_fn_with_state(security, security_headers));
+
+        let res = app
+            .clone()
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+        assert_eq!(res.headers()["cross-origin-opener-policy"], "same-origin");
+        assert_eq!(
+            res.headers()["cross-origin-embedder-policy"],
+            "require-corp"
+        );
+        assert!(res.headers().contains_key(header::CONTENT_SECURITY_POLICY));
+
+        let res = app
+            .oneshot(Request::get("/custom").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.headers()[header::REFERRER_POLICY], "no-referrer");
+    }
+}
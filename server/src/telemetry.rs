@@ -0,0 +1,206 @@
+// telemetry.rs - anonymous, opt-in gameplay events used to tune difficulty
+
+use axum::{Json, Router, extract::State, http::StatusCode, routing};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Events accepted in one batch (the client sends at most 50)
+const MAX_EVENTS: usize = 100;
+
+/// Session ids are random per game run; nothing ties them to a player
+const MAX_SESSION_LEN: usize = 32;
+
+/// sled tree of stored batches, keyed by a monotonic id
+const TELEMETRY_TREE: &str = "telemetry";
+
+/// Batches kept; past this the oldest are dropped (a batch is at most a few KB)
+const MAX_STORED_BATCHES: usize = 100_000;
+
+#[derive(Clone)]
+pub struct Telemetry {
+    batches: sled::Tree,
+    ids: sled::Db,
+    /// Batches in the tree (sled counts by walking it, so it's counted once at startup)
+    stored: Arc<AtomicUsize>,
+    max_stored: usize,
+}
+
+/// Mirrors the client's `TelemetryEvent`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    LevelComplete {
+        level: usize,
+        complexity: usize,
+        seconds: f32,
+    },
+    InvalidMoves {
+        level: usize,
+        count: u32,
+    },
+    Abandoned {
+        level: usize,
+        edges_drawn: usize,
+        solutions_found: usize,
+        seconds: f32,
+    },
+}
+
+/// Body of `POST /api/telemetry`
+#[derive(Debug, Deserialize)]
+pub struct TelemetryBatch {
+    pub session: String,
+    pub events: Vec<TelemetryEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredBatch {
+    session: String,
+    /// Unix seconds when the server received the batch
+    received_at: u64,
+    events: Vec<TelemetryEvent>,
+}
+
+type ApiError = (StatusCode, String);
+
+impl Telemetry {
+    pub fn open(db: &sled::Db) -> sled::Result<Self> {
+        let batches = db.open_tree(TELEMETRY_TREE)?;
+        Ok(Self {
+            stored: Arc::new(AtomicUsize::new(batches.len())),
+            batches,
+            ids: db.clone(),
+            max_stored: MAX_STORED_BATCHES,
+        })
+    }
+
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/api/telemetry", routing::post(post_batch))
+            .with_state(self)
+    }
+
+    fn store(&self, batch: TelemetryBatch) -> sled::Result<()> {
+        let stored = StoredBatch {
+            session: batch.session,
+            received_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            events: batch.events,
+        };
+        let value = serde_json::to_vec(&stored).expect("telemetry batch serializes");
+
+        let id = self.ids.generate_id()?;
+        self.batches.insert(id.to_be_bytes(), value)?;
+        // Keys are big-endian ids, so the first one is the oldest batch
+        if self.stored.fetch_add(1, Ordering::Relaxed) >= self.max_stored
+            && self.batches.pop_min()?.is_some()
+        {
+            self.stored.fetch_sub(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+async fn post_batch(
+    State(telemetry): State<Telemetry>,
+    Json(batch): Json<TelemetryBatch>,
+) -> Result<StatusCode, ApiError> {
+    validate_batch(&batch)?;
+
+    telemetry.store(batch).map_err(|err| {
+        tracing::error!("Telemetry storage error: {err}");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Telemetry storage error".to_string(),
+        )
+    })?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn validate_batch(batch: &TelemetryBatch) -> Result<(), ApiError> {
+    let session_ok = !batch.session.is_empty()
+        && batch.session.len() <= MAX_SESSION_LEN
+        && batch.session.chars().all(|c| c.is_ascii_alphanumeric());
+    if !session_ok {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Session id must be 1-{MAX_SESSION_LEN} alphanumeric characters"),
+        ));
+    }
+    if batch.events.is_empty() || batch.events.len() > MAX_EVENTS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("A batch holds 1-{MAX_EVENTS} events"),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_client_batch() {
+        let batch: TelemetryBatch = serde_json::from_str(
+            r#"{"session":"a1b2","events":[
+                {"kind":"level_complete","level":3,"complexity":3,"seconds":12.5},
+                {"kind":"invalid_moves","level":3,"count":4},
+                {"kind":"abandoned","level":4,"edges_drawn":2,"solutions_found":0,"seconds":30.0}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert!(validate_batch(&batch).is_ok());
+        assert_eq!(
+            batch.events[1],
+            TelemetryEvent::InvalidMoves { level: 3, count: 4 }
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_batches() {
+        let batch = |session: &str, events: usize| TelemetryBatch {
+            session: session.to_string(),
+            events: vec![TelemetryEvent::InvalidMoves { level: 1, count: 1 }; events],
+        };
+
+        assert!(validate_batch(&batch("", 1)).is_err());
+        assert!(validate_batch(&batch("not/a/session", 1)).is_err());
+        assert!(validate_batch(&batch("abc", 0)).is_err());
+        assert!(validate_batch(&batch("abc", MAX_EVENTS + 1)).is_err());
+    }
+
+    #[test]
+    fn test_stores_batches_up_to_the_cap() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let mut telemetry = Telemetry::open(&db).unwrap();
+        telemetry.max_stored = 2;
+
+        for count in 1..=3 {
+            telemetry
+                .store(TelemetryBatch {
+                    session: "abc".to_string(),
+                    events: vec![TelemetryEvent::InvalidMoves { level: 1, count }],
+                })
+                .unwrap();
+        }
+        assert_eq!(telemetry.batches.len(), 2);
+
+        // The oldest batch made way
+        let (_, oldest) = telemetry.batches.first().unwrap().unwrap();
+        let oldest: StoredBatch = serde_json::from_slice(&oldest).unwrap();
+        assert_eq!(
+            oldest.events,
+            vec![TelemetryEvent::InvalidMoves { level: 1, count: 2 }]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+// tls.rs - optional HTTPS termination, HTTP->HTTPS redirects and HSTS
+
+use axum::{
+    Router,
+    body::Body,
+    extract::State,
+    http::{HeaderValue, Request, StatusCode, Uri, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_server::tls_rustls::RustlsConfig;
+use std::{net::SocketAddr, path::Path};
+
+/// Serve `app` over HTTPS with the given PEM cert chain and key
+pub async fn serve(app: Router, addr: SocketAddr, cert: &Path, key: &Path) -> std::io::Result<()> {
+    // Pick the crypto backend explicitly; it's compiled in without aws-lc
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    let tls = RustlsConfig::from_pem_file(cert, key).await?;
+    tracing::info!("Server running on https://{addr}");
+    axum_server::bind_rustls(addr, tls)
+        .serve(app.into_make_service())
+        .await
+}
+
+/// Listen for plain HTTP on `addr` and send every request to the HTTPS port
+pub async fn serve_redirect(addr: SocketAddr, https_port: u16) -> std::io::Result<()> {
+    let redirect = Router::new()
+        .fallback(move |req: Request<Body>| async move { redirect_to_https(&req, https_port) });
+
+    tracing::info!("Redirecting http://{addr} to HTTPS");
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, redirect).await
+}
+
+fn redirect_to_https(req: &Request<Body>, https_port: u16) -> Response {
+    let Some(host) = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+    else {
+        return (StatusCode::BAD_REQUEST, "Missing Host header").into_response();
+    };
+
+    // Drop any port from Host (bracketed IPv6 keeps its colons)
+    let hostname = match host.rsplit_once(':') {
+        Some((name, port)) if !port.contains(']') => name,
+        _ => host,
+    };
+    let authority = if https_port == 443 {
+        hostname.to_string()
+    } else {
+        format!("{hostname}:{https_port}")
+    };
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+
+    match format!("https://{authority}{path}").parse::<Uri>() {
+        Ok(location) => (
+            StatusCode::PERMANENT_REDIRECT,
+            [(header::LOCATION, location.to_string())],
+        )
+            .into_response(),
+        Err(_) => (StatusCode::BAD_REQUEST, "Invalid Host header").into_response(),
+    }
+}
+
+/// Middleware: Tell browsers to stick to HTTPS (only layered when serving TLS)
+pub async fn hsts(State(value): State<HeaderValue>, req: Request<Body>, next: Next) -> Response {
+    let mut res = next.run(req).await;
+    res.headers_mut()
+        .insert(header::STRICT_TRANSPORT_SECURITY, value);
+    res
+}
+
+pub fn hsts_value(max_age: u64) -> HeaderValue {
+    HeaderValue::from_str(&format!("max-age={max_age}; includeSubDomains"))
+        .expect("HSTS header is ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(host: &str, uri: &str, https_port: u16) -> String {
+        let req = Request::get(uri)
+            .header(header::HOST, host)
+            .body(Body::empty())
+            .unwrap();
+        let res = redirect_to_https(&req, https_port);
+        assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+        res.headers()[header::LOCATION]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_redirect_keeps_path_and_query() {
+        assert_eq!(
+            location("valence.example:80", "/api/puzzles?complexity=4", 443),
+            "https://valence.example/api/puzzles?complexity=4"
+        );
+        assert_eq!(
+            location("localhost:8080", "/", 8443),
+            "https://localhost:8443/"
+        );
+        assert_eq!(location("[::1]", "/x", 443), "https://[::1]/x");
+    }
+
+    #[test]
+    fn test_redirect_needs_host() {
+        let req = Request::get("/").body(Body::empty()).unwrap();
+        assert_eq!(
+            redirect_to_https(&req, 443).status(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -0,0 +1,165 @@
+// autosave.rs - keep the board in progress in the active profile, so a game closed
+// mid-level picks up there
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::{
+    editor::Editor,
+    game::{error::AppError, progression::ProgressionTracker, session::PuzzleSession},
+    graph::Valences,
+    net::{BoardState, RoomLink, Spectator},
+    profile::Profiles,
+};
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+use crate::web::DailyRun;
+
+/// Rebuild the session a board was captured from
+///
+/// Found solutions and the trail are replayed under the game's rules, so a stale or
+/// hand-edited save is rejected rather than corrupting the board.
+pub fn resume(board: &BoardState) -> Result<PuzzleSession, String> {
+    if !board.is_well_formed() {
+        return Err("malformed board".to_string());
+    }
+
+    let mut session =
+        PuzzleSession::new(Valences::from_array(board.valences), board.total_solutions);
+    if session.merge_solutions(board.solutions()) != board.found.len() {
+        return Err("found solutions don't solve the puzzle".to_string());
+    }
+    session
+        .replace_trail(&board.trail_nodes())
+        .map_err(|err| format!("trail doesn't replay: {}", err))?;
+
+    Ok(session)
+}
+
+/// Whoever else has a say in the board: a room, a spectated player or the daily puzzle
+#[derive(SystemParam)]
+pub struct SharedBoard<'w> {
+    room: Option<Res<'w, RoomLink>>,
+    spectator: Option<Res<'w, Spectator>>,
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    daily: Option<Res<'w, DailyRun>>,
+}
+
+impl SharedBoard<'_> {
+    fn is_shared(&self) -> bool {
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        let daily = self.daily.is_some();
+        #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+        let daily = false;
+
+        daily || self.room.is_some() || self.spectator.is_some()
+    }
+}
+
+/// Run condition: The board is the player's own, not a room's, someone else's being
+/// watched or the daily puzzle
+pub fn own_board(shared: SharedBoard) -> bool {
+    !shared.is_shared()
+}
+
+/// Run condition: Playing a board of our own (rooms, spectating, the daily puzzle, the
+/// editor and error screens are left out of the save)
+pub fn autosaving(
+    shared: SharedBoard,
+    editor: Option<Res<Editor>>,
+    error: Option<Res<AppError>>,
+) -> bool {
+    !shared.is_shared() && editor.is_none() && error.is_none()
+}
+
+/// System: Put back the board saved when the game was last closed
+///
+/// Runs before the scene is spawned, so the nodes come up with the saved valences and
+/// the first scene sync draws the saved trail's edges.
+pub fn resume_saved_board(
+    profiles: Res<Profiles>,
+    mut session: ResMut<PuzzleSession>,
+    mut tracker: ResMut<ProgressionTracker>,
+) {
+    let Some(board) = &profiles.active().board else {
+        return;
+    };
+
+    match resume(board) {
+        Ok(resumed) => {
+            info!(
+                "💾 Resumed level {}: {} solutions found, {} nodes in the trail",
+                board.level,
+                board.found.len(),
+                board.trail.len()
+            );
+            *session = resumed;
+            *tracker = ProgressionTracker::at_level(board.level);
+        }
+        Err(err) => warn!("Ignoring the saved board: {}", err),
+    }
+}
+
+/// System: Save the board to the active profile whenever it changes
+pub fn save_board(
+    session: Res<PuzzleSession>,
+    tracker: Res<ProgressionTracker>,
+    mut profiles: ResMut<Profiles>,
+) {
+    if !(session.is_changed() || tracker.is_changed()) {
+        return;
+    }
+    let board = BoardState::capture(&session, &tracker);
+    if profiles.active().board.as_ref() == Some(&board) {
+        return;
+    }
+
+    let profile = profiles.active_mut();
+    profile.level = board.level;
+    profile.board = Some(board);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::NodeId;
+
+    fn square_with_diagonals() -> PuzzleSession {
+        // Nodes 0, 1, 3 and 4 all touch each other: a square with both diagonals
+        PuzzleSession::new(Valences::new(vec![2, 2, 0, 2, 2, 0, 0, 0, 0]), 3)
+    }
+
+    #[test]
+    fn test_resume_restores_solutions_and_trail() {
+        let mut session = square_with_diagonals();
+        for node in [0, 1, 4, 3, 0] {
+            session.add_node(NodeId(node));
+        }
+        session.reset();
+        session.add_node(NodeId(0));
+        session.add_node(NodeId(4));
+
+        let board = BoardState::capture(&session, &ProgressionTracker::at_level(4));
+        let resumed = resume(&board).unwrap();
+
+        assert_eq!(resumed.found_solutions(), session.found_solutions());
+        assert_eq!(resumed.current_trail(), &[NodeId(0), NodeId(4)]);
+        assert_eq!(resumed.current_valences(), session.current_valences());
+        assert_eq!(
+            BoardState::capture(&resumed, &ProgressionTracker::at_level(4)),
+            board
+        );
+    }
+
+    #[test]
+    fn test_resume_rejects_boards_that_dont_replay() {
+        let session = square_with_diagonals();
+        let mut board = BoardState::capture(&session, &ProgressionTracker::default());
+        board.found = vec![vec![[0, 1]]];
+        assert!(resume(&board).is_err());
+
+        board.found.clear();
+        board.trail = vec![0, 2];
+        assert!(resume(&board).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! Puzzle library tools: score each puzzle's difficulty, and report it level by level.
+//!
+//! ```bash
+//! cargo run --release --bin puzzle_tools -- difficulty >> assets/puzzle_difficulty.csv
+//! cargo run --release --bin puzzle_tools -- levels
+//! ```
+//!
+//! `difficulty` scores the puzzles in `assets/puzzles_symmetric.csv` (or another CSV named
+//! after the command) that `assets/puzzle_difficulty.csv` doesn't have yet, so its output
+//! appends to the scores file. `levels` orders the complexities by median difficulty, for
+//! the level table.
+
+use std::collections::{BTreeMap, HashMap};
+use std::process::ExitCode;
+
+use valence_sdf::graph::{Difficulty, Valences};
+
+const DEFAULT_CSV: &str = "assets/puzzles_symmetric.csv";
+const DIFFICULTY_CSV: &str = "assets/puzzle_difficulty.csv";
+
+/// One library row: 9 valences and the complexity
+struct Row {
+    valences: [usize; 9],
+    complexity: usize,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let path = args.get(1).map(String::as_str).unwrap_or(DEFAULT_CSV);
+
+    let result = read_rows(path).and_then(|rows| {
+        let scores = read_scores(DIFFICULTY_CSV)?;
+        match args.first().map(String::as_str) {
+            Some("difficulty") => {
+                print_difficulty(&rows, &scores);
+                Ok(())
+            }
+            Some("levels") => print_levels(&rows, &scores),
+            _ => Err("usage: puzzle_tools <difficulty|levels> [puzzles.csv]".to_string()),
+        }
+    });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Split a CSV line into `expected` fields, parsed
+fn parse_line<T: std::str::FromStr>(
+    line: &str,
+    line_num: usize,
+    expected: usize,
+) -> Result<Vec<T>, String>
+where
+    T::Err: std::fmt::Display,
+{
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    if fields.len() != expected {
+        return Err(format!(
+            "Line {} has {} values, expected {}",
+            line_num + 1,
+            fields.len(),
+            expected
+        ));
+    }
+    fields
+        .iter()
+        .map(|field| field.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Line {}: {}", line_num + 1, e))
+}
+
+fn read_rows(path: &str) -> Result<Vec<Row>, String> {
+    let csv = std::fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+
+    let mut rows = Vec::new();
+    for (line_num, line) in csv.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let values: Vec<usize> = parse_line(line, line_num, 10)?;
+        let mut valences = [0; 9];
+        valences.copy_from_slice(&values[..9]);
+        rows.push(Row {
+            valences,
+            complexity: values[9],
+        });
+    }
+    Ok(rows)
+}
+
+/// The scores already in the difficulty CSV, by valences (none if there's no file yet)
+fn read_scores(path: &str) -> Result<HashMap<[usize; 9], f32>, String> {
+    let Ok(csv) = std::fs::read_to_string(path) else {
+        return Ok(HashMap::new());
+    };
+
+    let mut scores = HashMap::new();
+    for (line_num, line) in csv.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let values: Vec<f32> = parse_line(line, line_num, 10)?;
+        let mut valences = [0; 9];
+        for (valence, value) in valences.iter_mut().zip(&values) {
+            *valence = *value as usize;
+        }
+        scores.insert(valences, values[9]);
+    }
+    Ok(scores)
+}
+
+/// Print a difficulty row for every puzzle that hasn't been scored yet
+fn print_difficulty(rows: &[Row], scores: &HashMap<[usize; 9], f32>) {
+    let unscored: Vec<&Row> = rows
+        .iter()
+        .filter(|row| !scores.contains_key(&row.valences))
+        .collect();
+
+    for (done, row) in unscored.iter().enumerate() {
+        let score = Difficulty::estimate(&Valences::from_array(row.valences)).score();
+        let valences: Vec<String> = row.valences.iter().map(usize::to_string).collect();
+        println!("{},{:.2}", valences.join(","), score);
+
+        if (done + 1) % 500 == 0 {
+            eprintln!("Scored {}/{} puzzles", done + 1, unscored.len());
+        }
+    }
+    eprintln!("{} new scores", unscored.len());
+}
+
+/// Print the level order: complexities from easiest to hardest median difficulty (ties
+/// by complexity), ready to paste into `LEVEL_TO_COMPLEXITY`, then each level's details
+fn print_levels(rows: &[Row], scores: &HashMap<[usize; 9], f32>) -> Result<(), String> {
+    let mut by_complexity: BTreeMap<usize, Vec<f32>> = BTreeMap::new();
+    for row in rows {
+        let score = scores
+            .get(&row.valences)
+            .ok_or_else(|| "Puzzles need difficulty scores first (run `difficulty`)".to_string())?;
+        by_complexity
+            .entry(row.complexity)
+            .or_default()
+            .push(*score);
+    }
+
+    let mut levels: Vec<(usize, usize, f32)> = by_complexity
+        .into_iter()
+        .map(|(complexity, mut scores)| {
+            scores.sort_by(f32::total_cmp);
+            (complexity, scores.len(), median(&scores))
+        })
+        .collect();
+    levels.sort_by(|a, b| a.2.total_cmp(&b.2).then(a.0.cmp(&b.0)));
+
+    let order: Vec<String> = levels.iter().map(|level| level.0.to_string()).collect();
+    println!("// LEVEL_TO_COMPLEXITY");
+    println!("{},", order.join(", "));
+    println!();
+    println!("level,complexity,puzzles,median_difficulty");
+    for (level, (complexity, puzzles, difficulty)) in levels.iter().enumerate() {
+        println!("{},{},{},{:.2}", level + 1, complexity, puzzles, difficulty);
+    }
+    Ok(())
+}
+
+/// Median of sorted, non-empty scores
+fn median(sorted: &[f32]) -> f32 {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
@@ -1,19 +1,72 @@
 // camera.rs
 
 use bevy::camera::{ScalingMode, Viewport};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::touch::Touches;
 use bevy::prelude::*;
 use bevy::window::WindowResized;
 
+use crate::{input::window_to_world, settings::Settings};
+
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<GameCamera>()
+            .init_resource::<CameraTilt>()
             .add_systems(Startup, setup_camera)
-            .add_systems(Update, update_camera_viewport);
+            .add_systems(
+                Update,
+                (
+                    update_camera_viewport,
+                    (
+                        toggle_perspective,
+                        (tilt_toward_cursor, ease_camera_tilt).chain(),
+                        ease_camera_frame,
+                        zoom_and_pan_camera,
+                        apply_camera_view,
+                        apply_camera_shake,
+                    )
+                        .chain(),
+                ),
+            );
     }
 }
 
+/// Largest camera offset at full trauma (world units)
+const MAX_SHAKE_OFFSET: f32 = 0.12;
+
+/// How quickly shake trauma wears off (per second)
+const SHAKE_DECAY: f32 = 1.8;
+
+/// Closest the camera zooms in: the view's size as a fraction of the frame's
+pub const MIN_CAMERA_SCALE: f32 = 0.2;
+
+/// How fast the frame eases toward a new layout (fraction of the gap per second)
+const FRAME_EASE_SPEED: f32 = 4.0;
+
+/// The frame snaps the rest of the way once every edge is this close (world units)
+const FRAME_SNAP_DISTANCE: f32 = 0.001;
+
+/// Zoom factor per wheel line
+const WHEEL_ZOOM_STEP: f32 = 1.15;
+
+/// Vertical field of view of the perspective camera (radians)
+const PERSPECTIVE_FOV: f32 = 0.5;
+
+/// Furthest the perspective camera swings toward the pointer or the phone's tilt
+/// (radians)
+const MAX_TILT: f32 = 0.12;
+
+/// How fast the tilt follows its target (fraction of the gap per second)
+const TILT_FOLLOW_SPEED: f32 = 5.0;
+
+/// Height the orthographic camera looks down from (anything in front of the board)
+const ORTHOGRAPHIC_HEIGHT: f32 = 10.0;
+
+/// Wheel pixels that count as one line (trackpads scroll in pixels)
+const WHEEL_PIXELS_PER_LINE: f32 = 40.0;
+
 // 🔧 FIXED ASPECT RATIO - This never changes!
 // Bottom-left origin: (0, 0) to (GAME_WIDTH, GAME_HEIGHT)
 const GAME_HEIGHT: f32 = 8.0; // World units
@@ -22,11 +75,21 @@ const GAME_WIDTH: f32 = GAME_HEIGHT * GAME_ASPECT_RATIO; // 4.5 world units
 
 #[derive(Resource)]
 pub struct GameCamera {
+    /// The board area the layout and physics walls are built in
     pub bounds: CameraBounds,
     pub entity: Option<Entity>,
+    /// What the camera frames when zoomed all the way out, easing toward `frame_target`
+    pub frame: CameraBounds,
+    frame_target: CameraBounds,
+    /// The view's size as a fraction of the frame's (1 = the whole frame, smaller is
+    /// zoomed in)
+    pub scale: f32,
+    /// The view's center, relative to the frame's, as a fraction of the frame's size (so
+    /// a view pushed against an edge stays there as the frame eases)
+    pub offset: Vec2,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct CameraBounds {
     pub left: f32,
     pub right: f32,
@@ -39,10 +102,86 @@ impl Default for GameCamera {
         Self {
             bounds: CameraBounds::from_fixed_aspect(),
             entity: None,
+            frame: CameraBounds::from_fixed_aspect(),
+            frame_target: CameraBounds::from_fixed_aspect(),
+            scale: 1.0,
+            offset: Vec2::ZERO,
         }
     }
 }
 
+impl GameCamera {
+    /// What's on screen: the frame zoomed and panned (the HUD anchors to this)
+    pub fn view(&self) -> CameraBounds {
+        self.frame
+            .zoomed(self.scale, self.offset * self.frame.size())
+    }
+
+    /// Ease the frame over to `target` (see `CameraBounds::framing`)
+    pub fn frame_to(&mut self, target: CameraBounds) {
+        self.frame_target = target;
+    }
+
+    /// Frame `target` straight away, with no easing
+    pub fn snap_frame_to(&mut self, target: CameraBounds) {
+        self.frame_target = target;
+        self.frame = target;
+    }
+
+    /// Whether the frame has reached its target
+    pub fn frame_settled(&self) -> bool {
+        self.frame == self.frame_target
+    }
+
+    /// Move the frame `dt` seconds closer to its target
+    fn ease_frame(&mut self, dt: f32) {
+        let t = (FRAME_EASE_SPEED * dt).min(1.0);
+        let (from, to) = (self.frame, self.frame_target);
+        let ease = |from: f32, to: f32| {
+            let eased = from + (to - from) * t;
+            if (to - eased).abs() < FRAME_SNAP_DISTANCE {
+                to
+            } else {
+                eased
+            }
+        };
+        self.frame = CameraBounds {
+            left: ease(from.left, to.left),
+            right: ease(from.right, to.right),
+            bottom: ease(from.bottom, to.bottom),
+            top: ease(from.top, to.top),
+        };
+    }
+
+    /// Zoom in (factor above 1) or out, keeping the world point `focus` where it is on
+    /// screen
+    pub fn zoom_about(&mut self, factor: f32, focus: Vec2) {
+        let scale = (self.scale / factor).clamp(MIN_CAMERA_SCALE, 1.0);
+        let center = self.view().center();
+        let shift = (focus - center) * (1.0 - scale / self.scale);
+        self.scale = scale;
+        self.set_offset(self.offset + shift / self.frame.size());
+    }
+
+    /// Move the view by `delta` world units
+    pub fn pan_by(&mut self, delta: Vec2) {
+        self.set_offset(self.offset + delta / self.frame.size());
+    }
+
+    /// Put the view back on the whole frame
+    pub fn reset_view(&mut self) {
+        self.scale = 1.0;
+        self.offset = Vec2::ZERO;
+    }
+
+    /// Move the view's center (in fractions of the frame), clamped so the view stays
+    /// inside the frame
+    fn set_offset(&mut self, offset: Vec2) {
+        let slack = Vec2::splat(0.5 * (1.0 - self.scale));
+        self.offset = offset.clamp(-slack, slack);
+    }
+}
+
 impl CameraBounds {
     /// Create bounds with FIXED aspect ratio, bottom-left origin at (0, 0)
     pub fn from_fixed_aspect() -> Self {
@@ -62,6 +201,44 @@ impl CameraBounds {
         self.top - self.bottom
     }
 
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.width(), self.height())
+    }
+
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.left + self.right, self.bottom + self.top) * 0.5
+    }
+
+    /// The frame for a layout: `extents` grown by `margin` on every side, then widened to
+    /// the game's aspect ratio
+    ///
+    /// A frame that needs more height gets it above the layout, where the HUD sits.
+    pub fn framing(extents: Rect, margin: f32) -> CameraBounds {
+        let padded = extents.inflate(margin);
+        let width = padded.width().max(padded.height() * GAME_ASPECT_RATIO);
+        let height = padded.height().max(padded.width() / GAME_ASPECT_RATIO);
+        let center_x = padded.center().x;
+        CameraBounds {
+            left: center_x - width * 0.5,
+            right: center_x + width * 0.5,
+            bottom: padded.min.y,
+            top: padded.min.y + height,
+        }
+    }
+
+    /// These bounds shrunk to `scale` of their size about their center, then moved by
+    /// `offset`
+    pub fn zoomed(&self, scale: f32, offset: Vec2) -> CameraBounds {
+        let center = self.center() + offset;
+        let half = Vec2::new(self.width(), self.height()) * 0.5 * scale;
+        CameraBounds {
+            left: center.x - half.x,
+            right: center.x + half.x,
+            bottom: center.y - half.y,
+            top: center.y + half.y,
+        }
+    }
+
     /// CSS-style anchor positioning in XY plane
     pub fn anchor(&self, horizontal: f32, vertical: f32, padding: f32) -> Vec3 {
         let padded_left = self.left + self.width() * padding;
@@ -101,25 +278,58 @@ impl CameraBounds {
 #[derive(Component)]
 pub struct MainCamera;
 
+/// Decaying positional shake applied around the camera's resting position
+#[derive(Component, Debug)]
+pub struct CameraShake {
+    /// Where the camera sits when not shaking
+    pub anchor: Vec3,
+    /// Current shake energy (0.0 = still, 1.0 = maximum)
+    pub trauma: f32,
+    /// Multiplier on the player's shake setting for this session (spectating turns it up)
+    pub boost: f32,
+    /// Running clock that drives the noise pattern
+    time: f32,
+}
+
+impl CameraShake {
+    pub fn new(anchor: Vec3) -> Self {
+        Self {
+            anchor,
+            trauma: 0.0,
+            boost: 1.0,
+            time: 0.0,
+        }
+    }
+
+    /// Add shake energy (capped at 1.0)
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+
+    /// Current offset from the anchor (squared trauma keeps small hits subtle)
+    pub fn offset(&self) -> Vec3 {
+        let shake = self.trauma * self.trauma * MAX_SHAKE_OFFSET;
+        let t = self.time;
+        // Sum of incommensurate sines: cheap, smooth, non-repeating-looking noise
+        let x = ((t * 37.0).sin() + (t * 23.0 + 1.3).sin()) * 0.5;
+        let y = ((t * 31.0 + 0.7).sin() + (t * 19.0 + 2.1).sin()) * 0.5;
+        Vec3::new(x, y, 0.0) * shake
+    }
+}
+
 fn setup_camera(mut commands: Commands, game_camera: Res<GameCamera>) {
-    let projection = Projection::Orthographic(OrthographicProjection {
-        scaling_mode: ScalingMode::FixedVertical {
-            viewport_height: GAME_HEIGHT,
-        },
-        near: -1000.0,
-        far: 1000.0,
-        ..OrthographicProjection::default_3d()
-    });
+    let projection = orthographic_projection(game_camera.view().height());
 
     // Position camera at center of game area, looking down -Z onto XY plane
-    let cx = GAME_WIDTH * 0.5;
-    let cy = GAME_HEIGHT * 0.5;
+    let Vec2 { x: cx, y: cy } = game_camera.view().center();
 
     commands.spawn((
         Camera3d::default(),
         projection,
-        Transform::from_xyz(cx, cy, 10.0).looking_at(Vec3::new(cx, cy, 0.0), Vec3::Y),
+        Transform::from_xyz(cx, cy, ORTHOGRAPHIC_HEIGHT)
+            .looking_at(Vec3::new(cx, cy, 0.0), Vec3::Y),
         MainCamera,
+        CameraShake::new(Vec3::new(cx, cy, ORTHOGRAPHIC_HEIGHT)),
     ));
 
     info!("📷 Camera setup: XY plane, bottom-left origin (0,0)");
@@ -132,6 +342,102 @@ fn setup_camera(mut commands: Commands, game_camera: Res<GameCamera>) {
     );
 }
 
+/// Resource: Which way the perspective camera leans, each axis from -1 to 1 (x toward
+/// the right edge, y toward the top)
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct CameraTilt {
+    /// Where the pointer or the phone's tilt asks to lean
+    pub target: Vec2,
+    /// Where the camera leans now, following `target`
+    pub current: Vec2,
+    /// The phone's motion sensor drives the target (the web build sets this on the first
+    /// reading), so the cursor is ignored
+    pub from_motion: bool,
+}
+
+impl CameraTilt {
+    /// Move `current` `dt` seconds closer to the target
+    fn follow(&mut self, dt: f32) {
+        let t = (TILT_FOLLOW_SPEED * dt).min(1.0);
+        let next = self.current.lerp(self.target, t);
+        self.current = if next.distance(self.target) < 1e-4 {
+            self.target
+        } else {
+            next
+        };
+    }
+}
+
+/// Where the camera sits and which way it looks to show `view` (None: straight down, the
+/// orthographic top-down camera)
+///
+/// The perspective camera stands back far enough for `view` to fill the screen at the
+/// board plane, then swings about the view's center by the tilt.
+fn camera_pose(view: &CameraBounds, tilt: Option<Vec2>) -> (Vec3, Quat) {
+    let center = view.center().extend(0.0);
+    let Some(tilt) = tilt else {
+        return (
+            center.with_z(ORTHOGRAPHIC_HEIGHT),
+            Quat::IDENTITY, // Looking down -Z with +Y up
+        );
+    };
+
+    let distance = view.height() * 0.5 / (PERSPECTIVE_FOV * 0.5).tan();
+    let swing =
+        Quat::from_rotation_y(tilt.x * MAX_TILT) * Quat::from_rotation_x(-tilt.y * MAX_TILT);
+    let position = center + swing * Vec3::Z * distance;
+    let rotation = Transform::from_translation(position)
+        .looking_at(center, Vec3::Y)
+        .rotation;
+    (position, rotation)
+}
+
+/// System: V switches between the flat top-down camera and the tilting perspective one
+fn toggle_perspective(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if !keys.just_pressed(KeyCode::KeyV) {
+        return;
+    }
+    settings.perspective = !settings.perspective;
+    info!(
+        "📷 Perspective camera {}",
+        if settings.perspective { "on" } else { "off" }
+    );
+}
+
+/// System: Lean the perspective camera toward the cursor (unless the phone's tilt is
+/// driving it), or level it when tilting is off
+fn tilt_toward_cursor(
+    settings: Res<Settings>,
+    windows: Query<&Window>,
+    mut tilt: ResMut<CameraTilt>,
+) {
+    let target = if !settings.perspective || settings.reduced_motion {
+        Vec2::ZERO
+    } else if tilt.from_motion {
+        return;
+    } else {
+        let Some((window, cursor)) = windows
+            .single()
+            .ok()
+            .and_then(|window| Some((window, window.cursor_position()?)))
+        else {
+            return;
+        };
+        // Window coords run down from the top-left
+        let size = Vec2::new(window.width(), window.height()).max(Vec2::ONE);
+        let from_center = cursor / size * 2.0 - Vec2::ONE;
+        Vec2::new(from_center.x, -from_center.y).clamp(Vec2::NEG_ONE, Vec2::ONE)
+    };
+    tilt.set_if_neq(CameraTilt { target, ..*tilt });
+}
+
+/// System: Ease the tilt toward its target
+fn ease_camera_tilt(time: Res<Time<Real>>, mut tilt: ResMut<CameraTilt>) {
+    if tilt.current != tilt.target {
+        tilt.follow(time.delta_secs());
+    }
+}
+
 /// Update camera viewport to maintain aspect ratio with letterboxing
 fn update_camera_viewport(
     mut cameras: Query<&mut Camera, With<MainCamera>>,
@@ -182,3 +488,260 @@ fn update_camera_viewport(
         );
     }
 }
+
+/// System: Scroll or pinch to zoom, and drag with the right or middle mouse button (or
+/// two fingers) to pan
+///
+/// Zooming keeps the point under the cursor (or between the fingers) where it is.
+pub fn zoom_and_pan_camera(
+    mut wheel: MessageReader<MouseWheel>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut game_camera: ResMut<GameCamera>,
+    mut last_cursor: Local<Option<Vec2>>,
+) {
+    let Ok((camera, camera_transform)) = cameras.single() else {
+        return;
+    };
+    let to_world = |position: Vec2| {
+        window_to_world(position, camera, camera_transform).map(|world| world.truncate())
+    };
+    let cursor = windows
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+
+    let lines: f32 = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / WHEEL_PIXELS_PER_LINE,
+        })
+        .sum();
+    if lines != 0.0 {
+        let focus = cursor
+            .and_then(to_world)
+            .unwrap_or(game_camera.view().center());
+        game_camera.zoom_about(WHEEL_ZOOM_STEP.powf(lines), focus);
+    }
+
+    let dragging = mouse_buttons.any_pressed([MouseButton::Right, MouseButton::Middle]);
+    if dragging
+        && let (Some(from), Some(to)) = (*last_cursor, cursor)
+        && let (Some(from), Some(to)) = (to_world(from), to_world(to))
+    {
+        game_camera.pan_by(from - to);
+    }
+    *last_cursor = cursor;
+
+    let fingers: Vec<_> = touches.iter().collect();
+    if let [a, b] = fingers[..] {
+        let (from_mid, to_mid) = (
+            (a.previous_position() + b.previous_position()) * 0.5,
+            (a.position() + b.position()) * 0.5,
+        );
+        let from_spread = a.previous_position().distance(b.previous_position());
+        let to_spread = a.position().distance(b.position());
+        if let (Some(from), Some(to)) = (to_world(from_mid), to_world(to_mid)) {
+            if from_spread > 0.0 && to_spread > 0.0 {
+                game_camera.zoom_about(to_spread / from_spread, to);
+            }
+            game_camera.pan_by(from - to);
+        }
+    }
+}
+
+/// System: Ease the frame toward the layout it was last given
+pub fn ease_camera_frame(time: Res<Time<Real>>, mut game_camera: ResMut<GameCamera>) {
+    if game_camera.frame_settled() {
+        return;
+    }
+    game_camera.ease_frame(time.delta_secs());
+}
+
+/// System: Point the camera at the `GameCamera` view, top-down or in tilted perspective
+pub fn apply_camera_view(
+    game_camera: Res<GameCamera>,
+    settings: Res<Settings>,
+    tilt: Res<CameraTilt>,
+    mut cameras: Query<(&mut Projection, &mut CameraShake, &mut Transform), With<MainCamera>>,
+) {
+    if !game_camera.is_changed() && !settings.is_changed() && !tilt.is_changed() {
+        return;
+    }
+
+    let view = game_camera.view();
+    let perspective = settings.perspective.then_some(tilt.current);
+    let (position, rotation) = camera_pose(&view, perspective);
+    for (mut projection, mut shake, mut transform) in &mut cameras {
+        match (&mut *projection, settings.perspective) {
+            (Projection::Orthographic(ortho), false) => {
+                ortho.scaling_mode = ScalingMode::FixedVertical {
+                    viewport_height: view.height(),
+                };
+            }
+            (Projection::Perspective(_), true) => {}
+            (_, false) => *projection = orthographic_projection(view.height()),
+            (_, true) => {
+                *projection = Projection::Perspective(PerspectiveProjection {
+                    fov: PERSPECTIVE_FOV,
+                    ..default()
+                })
+            }
+        }
+        shake.anchor = position;
+        transform.rotation = rotation;
+    }
+}
+
+fn orthographic_projection(viewport_height: f32) -> Projection {
+    Projection::Orthographic(OrthographicProjection {
+        scaling_mode: ScalingMode::FixedVertical { viewport_height },
+        near: -1000.0,
+        far: 1000.0,
+        ..OrthographicProjection::default_3d()
+    })
+}
+
+/// Offset the camera by its current shake, decaying trauma over time
+fn apply_camera_shake(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut cameras: Query<(&mut Transform, &mut CameraShake), With<MainCamera>>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut transform, mut shake) in &mut cameras {
+        shake.time += dt;
+        shake.trauma = (shake.trauma - SHAKE_DECAY * dt).max(0.0);
+
+        transform.translation =
+            shake.anchor + shake.offset() * shake.boost * settings.shake_scale();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_stays_between_the_whole_board_and_the_limit() {
+        let mut camera = GameCamera::default();
+        camera.zoom_about(0.5, Vec2::ZERO);
+        assert_eq!(camera.scale, 1.0);
+        camera.zoom_about(100.0, Vec2::ZERO);
+        assert_eq!(camera.scale, MIN_CAMERA_SCALE);
+    }
+
+    #[test]
+    fn test_zoom_keeps_the_focus_in_place() {
+        let mut camera = GameCamera::default();
+        let focus = Vec2::new(1.0, 3.0);
+        let before = camera.view();
+        let on_screen = |view: CameraBounds| {
+            (focus - Vec2::new(view.left, view.bottom)) / Vec2::new(view.width(), view.height())
+        };
+
+        camera.zoom_about(2.0, focus);
+        assert_eq!(camera.scale, 0.5);
+        assert!((on_screen(camera.view()) - on_screen(before)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_panning_keeps_the_view_on_the_board() {
+        let mut camera = GameCamera::default();
+        let board = camera.frame;
+        // With the whole board in view there's nowhere to pan
+        camera.pan_by(Vec2::new(1.0, 1.0));
+        assert_eq!(camera.offset, Vec2::ZERO);
+
+        camera.zoom_about(2.0, board.center());
+        camera.pan_by(Vec2::new(-100.0, 100.0));
+        let view = camera.view();
+        assert_eq!((view.left, view.top), (board.left, board.top));
+
+        // Zooming back out pulls the view back over the board
+        camera.zoom_about(0.5, board.center());
+        assert_eq!(camera.offset, Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_framing_widens_to_the_game_aspect() {
+        // A wide layout gets extra height above it
+        let wide = CameraBounds::framing(Rect::new(1.0, 1.0, 3.0, 2.0), 0.5);
+        assert_eq!((wide.left, wide.right, wide.bottom), (0.5, 3.5, 0.5));
+        assert!((wide.width() / wide.height() - GAME_ASPECT_RATIO).abs() < 1e-5);
+
+        // A tall one is widened evenly on both sides
+        let tall = CameraBounds::framing(Rect::new(0.0, 0.0, 1.0, 10.0), 0.0);
+        assert_eq!((tall.bottom, tall.top), (0.0, 10.0));
+        assert_eq!(tall.center().x, 0.5);
+        assert!((tall.width() - 10.0 * GAME_ASPECT_RATIO).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_frame_eases_to_its_target_and_keeps_the_view_inside() {
+        let mut camera = GameCamera::default();
+        let target = CameraBounds::framing(Rect::new(0.0, 0.0, 9.0, 9.0), 1.0);
+        camera.zoom_about(2.0, camera.frame.center());
+        camera.pan_by(Vec2::new(-100.0, 0.0));
+
+        camera.frame_to(target);
+        camera.ease_frame(0.1);
+        assert!(!camera.frame_settled());
+        assert!(camera.frame.width() > camera.bounds.width());
+        for _ in 0..100 {
+            camera.ease_frame(0.1);
+        }
+        assert!(camera.frame_settled());
+        assert!((camera.view().left - target.left).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_perspective_camera_frames_the_view_at_the_board() {
+        let view = GameCamera::default().view();
+        let (position, rotation) = camera_pose(&view, Some(Vec2::ZERO));
+        assert_eq!(position.truncate(), view.center());
+        let half_height = position.z * (PERSPECTIVE_FOV * 0.5).tan();
+        assert!((half_height * 2.0 - view.height()).abs() < 1e-4);
+        assert!((rotation * Vec3::NEG_Z - Vec3::NEG_Z).length() < 1e-5);
+
+        // Leaning right swings the camera right, still looking at the view's center
+        let (leaning, rotation) = camera_pose(&view, Some(Vec2::X));
+        assert!(leaning.x > view.center().x);
+        let sight = (view.center().extend(0.0) - leaning).normalize();
+        assert!((rotation * Vec3::NEG_Z - sight).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_tilt_follows_its_target() {
+        let mut tilt = CameraTilt {
+            target: Vec2::ONE,
+            ..default()
+        };
+        tilt.follow(0.05);
+        assert!(tilt.current.x > 0.0 && tilt.current.x < 1.0);
+        for _ in 0..200 {
+            tilt.follow(0.05);
+        }
+        assert_eq!(tilt.current, tilt.target);
+    }
+
+    #[test]
+    fn test_still_camera_has_no_offset() {
+        let shake = CameraShake::new(Vec3::ZERO);
+        assert_eq!(shake.offset(), Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_trauma_is_capped() {
+        let mut shake = CameraShake::new(Vec3::ZERO);
+        shake.add_trauma(0.7);
+        shake.add_trauma(0.7);
+        assert_eq!(shake.trauma, 1.0);
+        shake.time = 0.3;
+        assert!(shake.offset().length() <= MAX_SHAKE_OFFSET * 2.0_f32.sqrt());
+    }
+}
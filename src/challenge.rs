@@ -0,0 +1,150 @@
+// challenge.rs - share codes for finished boards, and boards started from a friend's code
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{error::no_app_error, progression::CompletionPolicy, session::PuzzleSession},
+    graph::{PuzzleCode, solver},
+    visual::{interactions::handle_pointer_input, setup::check_level_progression},
+};
+
+/// Environment variable holding a puzzle code to start on (native only)
+pub const PUZZLE_ENV_VAR: &str = "VALENCE_PUZZLE";
+
+pub struct ChallengePlugin;
+
+impl Plugin for ChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Challenge>().add_systems(
+            Update,
+            (
+                start_challenge.before(handle_pointer_input),
+                remember_share_code
+                    .after(handle_pointer_input)
+                    .before(check_level_progression),
+            )
+                .run_if(no_app_error),
+        );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(PreStartup, challenge_from_env);
+
+        #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+        app.add_systems(Update, share_board);
+    }
+}
+
+/// Resource: Codes coming in from friends and going out to them
+#[derive(Resource, Debug, Default)]
+pub struct Challenge {
+    /// A board waiting to replace the current one (from the URL, environment or paste box)
+    pub pending: Option<PuzzleCode>,
+    /// Code for the last finished board, with one of its solutions
+    pub last_code: Option<String>,
+}
+
+impl Challenge {
+    /// Queue a pasted or linked code, warning when it can't be read
+    pub fn accept(&mut self, code: &str) {
+        match PuzzleCode::decode(code) {
+            Ok(code) => self.pending = Some(code),
+            Err(err) => warn!("Ignoring puzzle code {:?}: {}", code, err),
+        }
+    }
+
+    /// The code worth sharing: the last finished board's, or the current board's before any
+    pub fn share_code(&self, session: &PuzzleSession) -> String {
+        self.last_code
+            .clone()
+            .unwrap_or_else(|| current_code(session).encode())
+    }
+}
+
+/// The code for the current board, carrying a found solution if there is one
+pub fn current_code(session: &PuzzleSession) -> PuzzleCode {
+    // Solutions are unordered; pick one deterministically
+    let solution = session
+        .found_solutions()
+        .iter()
+        .min_by_key(|solution| solution.canonical_string())
+        .cloned();
+    PuzzleCode::for_board(session.puzzle_valences(), solution)
+}
+
+/// System: Queue the code from the environment before the first puzzle is shown
+#[cfg(not(target_arch = "wasm32"))]
+fn challenge_from_env(mut challenge: ResMut<Challenge>) {
+    if let Ok(code) = std::env::var(PUZZLE_ENV_VAR) {
+        challenge.accept(&code);
+    }
+}
+
+/// System: Swap in a queued board; a solution that came with it counts as found
+fn start_challenge(mut challenge: ResMut<Challenge>, mut session: ResMut<PuzzleSession>) {
+    let Some(code) = challenge.pending.take() else {
+        return;
+    };
+
+    let board = code.board();
+    let total_solutions = solver::count_solutions(&board);
+    info!(
+        "🤝 Challenge board: {} solutions expected{}",
+        total_solutions,
+        if code.solution.is_some() {
+            " (one shown by your friend)"
+        } else {
+            ""
+        }
+    );
+
+    *session = PuzzleSession::new(board, total_solutions);
+    session.merge_solutions(code.solution);
+}
+
+/// System: Note the code for a board as it's finished, before the next level replaces it
+fn remember_share_code(
+    session: Res<PuzzleSession>,
+    policy: Res<CompletionPolicy>,
+    mut challenge: ResMut<Challenge>,
+) {
+    if !session.is_changed() || !policy.is_met(&session.progress()) {
+        return;
+    }
+
+    let code = current_code(&session).encode();
+    info!("🔗 Challenge a friend with this board: {}", code);
+    challenge.last_code = Some(code);
+}
+
+/// System: L logs the code to share (the web build shows it in a paste box instead)
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+fn share_board(
+    keys: Res<ButtonInput<KeyCode>>,
+    session: Res<PuzzleSession>,
+    challenge: Res<Challenge>,
+) {
+    if keys.just_pressed(KeyCode::KeyL) {
+        info!("🔗 Puzzle code: {}", challenge.share_code(&session));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{NodeId, Valences};
+
+    #[test]
+    fn test_current_code_carries_a_found_solution() {
+        let mut session = PuzzleSession::new(Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]), 1);
+        assert_eq!(current_code(&session).solution, None);
+
+        session.add_node(NodeId(0));
+        session.add_node(NodeId(1));
+        let code = PuzzleCode::decode(&current_code(&session).encode()).unwrap();
+        assert_eq!(code.board(), *session.puzzle_valences());
+        assert_eq!(
+            code.solution.as_ref(),
+            session.found_solutions().iter().next()
+        );
+    }
+}
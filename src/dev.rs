@@ -0,0 +1,60 @@
+// dev.rs - development-only tooling (enabled with `--features dev`)
+
+use bevy::prelude::*;
+use bevy::render::{
+    Render, RenderApp, RenderSystems,
+    render_resource::{CachedPipelineState, PipelineCache},
+};
+
+pub struct DevPlugin;
+
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, log_shader_reloads);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                report_pipeline_failures.in_set(RenderSystems::Cleanup),
+            );
+        }
+    }
+}
+
+/// Log every shader that was reloaded from disk
+fn log_shader_reloads(
+    mut events: MessageReader<AssetEvent<Shader>>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in events.read() {
+        if let AssetEvent::Modified { id } = event {
+            let path = asset_server
+                .get_path(*id)
+                .map(|path| path.to_string())
+                .unwrap_or_else(|| format!("{id:?}"));
+            info!("🔁 Shader reloaded: {}", path);
+        }
+    }
+}
+
+/// Render world: warn once when reloaded shaders break pipelines, and again when they recover
+///
+/// Bevy already logs the compile error itself and re-queues the pipeline on the next save,
+/// so all this needs to do is make the broken/fixed transitions easy to spot in the log.
+fn report_pipeline_failures(pipeline_cache: Res<PipelineCache>, mut failing: Local<usize>) {
+    let count = pipeline_cache
+        .pipelines()
+        .filter(|pipeline| matches!(pipeline.state, CachedPipelineState::Err(_)))
+        .count();
+
+    if count > *failing {
+        warn!(
+            "🧨 {} pipeline(s) failed to compile - affected materials won't draw until the shader is fixed and saved",
+            count
+        );
+    } else if count < *failing {
+        info!("✅ Shader pipelines recovered ({} still failing)", count);
+    }
+
+    *failing = count;
+}
@@ -0,0 +1,310 @@
+// editor.rs - build puzzles on the board: click nodes to set valences, play-test, save
+
+use bevy::prelude::*;
+
+use crate::{
+    autosave::own_board,
+    game::{
+        error::no_app_error,
+        puzzle::{CustomPuzzle, PuzzleLibrary},
+        session::PuzzleSession,
+    },
+    graph::{NodeId, Valences, solver},
+    input::{PointerEventType, WorldPointerEvent},
+    visual::{
+        interactions::handle_pointer_input, nodes::GraphNode, physics::NodePhysics,
+        ui::board_uncovered,
+    },
+};
+
+/// Search moves the solver may spend per edit before calling the board uncountable
+const SOLVER_BUDGET: usize = 200_000;
+
+/// Highest valence a node can be given (the center's degree in the king's graph)
+const MAX_VALENCE: usize = 8;
+
+/// How close (world units) a click must land to a node to edit it
+const CLICK_RADIUS: f32 = 0.5;
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                // Not on a shared board: edits would reach the room, or be overwritten
+                toggle_editor
+                    .run_if(own_board)
+                    .run_if(no_app_error)
+                    .run_if(board_uncovered),
+                (edit_valences, toggle_playtest, save_edited_puzzle)
+                    .chain()
+                    .run_if(resource_exists::<Editor>),
+            )
+                .chain()
+                .before(handle_pointer_input),
+        );
+    }
+}
+
+/// What the solver made of the board being edited
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solvability {
+    Solutions(usize),
+    /// Fails `Valences::is_feasible`, with its reason
+    Infeasible(String),
+    Unsolvable,
+    /// Too many trails to count within `SOLVER_BUDGET`
+    TooMany,
+}
+
+impl Solvability {
+    pub fn of(valences: &Valences) -> Self {
+        if let Err(reason) = valences.is_feasible() {
+            return Self::Infeasible(reason);
+        }
+        match solver::count_solutions_within(valences, SOLVER_BUDGET) {
+            Some(0) => Self::Unsolvable,
+            Some(count) => Self::Solutions(count),
+            None => Self::TooMany,
+        }
+    }
+
+    /// Solution count a session can be played with (0 when there isn't one)
+    fn playable_count(&self) -> usize {
+        match self {
+            Self::Solutions(count) => *count,
+            Self::Infeasible(_) | Self::Unsolvable | Self::TooMany => 0,
+        }
+    }
+}
+
+/// Resource: Present while the editor is open
+///
+/// The board being edited is shown through `PuzzleSession` like any puzzle, so the SDF
+/// scene and HUD (0/N solutions) need nothing editor-specific.
+#[derive(Resource, Debug)]
+pub struct Editor {
+    pub valences: [usize; 9],
+    pub solvability: Solvability,
+    /// Drawing trails on the edited board instead of editing it
+    pub playtesting: bool,
+    /// The puzzle being played when the editor opened, restored on close
+    previous: PuzzleSession,
+}
+
+impl Editor {
+    pub fn open(previous: PuzzleSession) -> Self {
+        let puzzle = previous.puzzle_valences();
+        let valences = std::array::from_fn(|i| puzzle.get(NodeId(i)));
+        Self {
+            valences,
+            solvability: Solvability::of(puzzle),
+            playtesting: false,
+            previous,
+        }
+    }
+
+    /// Step a node's valence up (or down), wrapping between 0 and 8
+    pub fn cycle(&mut self, node: NodeId, down: bool) {
+        let valence = &mut self.valences[node.index()];
+        *valence = if down {
+            valence.checked_sub(1).unwrap_or(MAX_VALENCE)
+        } else {
+            (*valence + 1) % (MAX_VALENCE + 1)
+        };
+        self.solvability = Solvability::of(&self.board());
+    }
+
+    pub fn board(&self) -> Valences {
+        Valences::from_array(self.valences)
+    }
+
+    /// A fresh session on the edited board
+    fn session(&self) -> PuzzleSession {
+        PuzzleSession::new(self.board(), self.solvability.playable_count())
+    }
+}
+
+/// Run condition: Pointer input draws trails (the editor is closed, or play-testing)
+pub fn not_editing(editor: Option<Res<Editor>>) -> bool {
+    editor.is_none_or(|editor| editor.playtesting)
+}
+
+/// Run condition: The editor is closed (level progression waits while it's open)
+pub fn outside_editor(editor: Option<Res<Editor>>) -> bool {
+    editor.is_none()
+}
+
+fn log_solvability(editor: &Editor) {
+    match &editor.solvability {
+        Solvability::Solutions(count) => info!("✏️ {}: {} solutions", editor.board(), count),
+        Solvability::Infeasible(reason) => info!("✏️ {}: board {}", editor.board(), reason),
+        Solvability::Unsolvable => info!("✏️ {}: unsolvable", editor.board()),
+        Solvability::TooMany => info!("✏️ {}: too many solutions to count", editor.board()),
+    }
+}
+
+/// System: B opens the editor on the current puzzle, or closes it and resumes play
+fn toggle_editor(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    editor: Option<Res<Editor>>,
+    mut session: ResMut<PuzzleSession>,
+) {
+    if !keys.just_pressed(KeyCode::KeyB) {
+        return;
+    }
+
+    match editor {
+        Some(editor) => {
+            info!("✏️ Editor closed");
+            *session = editor.previous.clone();
+            commands.remove_resource::<Editor>();
+        }
+        None => {
+            let editor = Editor::open(session.clone());
+            info!("✏️ Editor open: click a node to raise its valence (Shift lowers it)");
+            log_solvability(&editor);
+            *session = editor.session();
+            commands.insert_resource(editor);
+        }
+    }
+}
+
+/// System: Clicking a node cycles its valence and re-solves the board
+fn edit_valences(
+    mut pointer_events: MessageReader<WorldPointerEvent>,
+    keys: Res<ButtonInput<KeyCode>>,
+    nodes: Query<(&GraphNode, &NodePhysics)>,
+    mut editor: ResMut<Editor>,
+    mut session: ResMut<PuzzleSession>,
+) {
+    if editor.playtesting {
+        pointer_events.clear();
+        return;
+    }
+
+    let down = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    for event in pointer_events.read() {
+        if event.event_type != PointerEventType::Down {
+            continue;
+        }
+        let Some((node, _)) = nodes
+            .iter()
+            .find(|(_, physics)| event.world_pos.distance(physics.position) < CLICK_RADIUS)
+        else {
+            continue;
+        };
+
+        editor.cycle(node.node_id, down);
+        log_solvability(&editor);
+        *session = editor.session();
+    }
+}
+
+/// System: Enter switches between editing and play-testing the board
+fn toggle_playtest(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut editor: ResMut<Editor>,
+    mut session: ResMut<PuzzleSession>,
+) {
+    if !keys.just_pressed(KeyCode::Enter) {
+        return;
+    }
+
+    editor.playtesting = !editor.playtesting;
+    info!(
+        "✏️ {}",
+        if editor.playtesting {
+            "Play-testing (Enter to edit again)"
+        } else {
+            "Editing"
+        }
+    );
+    *session = editor.session();
+}
+
+/// System: S adds the board to the custom puzzles (and the custom puzzles file)
+fn save_edited_puzzle(
+    keys: Res<ButtonInput<KeyCode>>,
+    editor: Res<Editor>,
+    mut library: ResMut<PuzzleLibrary>,
+) {
+    if !keys.just_pressed(KeyCode::KeyS) || editor.playtesting {
+        return;
+    }
+    if !matches!(editor.solvability, Solvability::Solutions(_)) {
+        warn!("✏️ Only boards with a countable number of solutions can be saved");
+        return;
+    }
+
+    let puzzle = CustomPuzzle {
+        name: None,
+        valences: editor.valences,
+    };
+    match library.merge_custom(std::slice::from_ref(&puzzle)) {
+        Ok(0) => info!("✏️ Already saved"),
+        Ok(_) => {
+            let name = library
+                .custom_puzzles()
+                .last()
+                .map(|entry| entry.name.clone())
+                .unwrap_or_default();
+            info!("✏️ Saved as \"{}\" (C plays custom puzzles)", name);
+            persist(&library, &name, &puzzle);
+        }
+        Err(err) => warn!("✏️ Not saved: {}", err),
+    }
+}
+
+/// Keep a saved puzzle for later runs: appended to the TOML file, or in localStorage
+fn persist(library: &PuzzleLibrary, name: &str, puzzle: &CustomPuzzle) {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = library;
+        if let Err(err) = crate::game::puzzle::append_custom_puzzle(name, puzzle) {
+            warn!(
+                "Failed to write {}: {}",
+                crate::game::puzzle::CUSTOM_PUZZLES_FILE,
+                err
+            );
+        }
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        let _ = (name, puzzle);
+        crate::web::save_custom_puzzles(library);
+    }
+
+    #[cfg(all(not(feature = "wasm"), target_arch = "wasm32"))]
+    let _ = (library, name, puzzle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycling_wraps_and_re_solves() {
+        let mut editor = Editor::open(PuzzleSession::new(
+            Valences::new(vec![1, 0, 0, 0, 0, 0, 0, 0, 0]),
+            0,
+        ));
+        assert_eq!(
+            editor.solvability,
+            Solvability::Infeasible("has an odd valence sum (1)".to_string())
+        );
+
+        editor.cycle(NodeId(1), false);
+        assert_eq!(editor.valences[1], 1);
+        assert_eq!(editor.solvability, Solvability::Solutions(1));
+
+        editor.cycle(NodeId(2), true);
+        assert_eq!(editor.valences[2], MAX_VALENCE);
+        editor.cycle(NodeId(2), false);
+        assert_eq!(editor.valences[2], 0);
+    }
+}
@@ -0,0 +1,403 @@
+// event_log.rs - ring buffer of notable game events, with a debug console for bug reports
+
+use bevy::asset::AssetLoadFailedEvent;
+use bevy::prelude::*;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write as _;
+
+#[cfg(debug_assertions)]
+use crate::game::session::PuzzleSession;
+use crate::{
+    game::error::AppError,
+    graph::NodeId,
+    visual::{
+        interactions::{InvalidMove, SolutionFound},
+        nodes::GraphNode,
+        physics::NodePhysics,
+        plugin::InputSet,
+    },
+};
+
+/// Entries kept before the oldest are dropped
+const EVENT_LOG_CAPACITY: usize = 256;
+
+/// Entries shown when the console opens
+const CONSOLE_LINES: usize = 20;
+
+/// Speed (world units/s) above which a node is reported as runaway
+const RUNAWAY_SPEED: f32 = 50.0;
+
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(EventLog::with_capacity(EVENT_LOG_CAPACITY))
+            .init_resource::<ConsoleState>()
+            .add_systems(
+                Update,
+                (
+                    log_session_events,
+                    log_physics_anomalies,
+                    log_shader_failures,
+                    log_app_errors,
+                    toggle_console,
+                    export_event_log,
+                )
+                    .chain()
+                    // Log this frame's moves, not last frame's
+                    .after(InputSet),
+            );
+
+        #[cfg(debug_assertions)]
+        app.add_systems(Update, verify_session_invariants.after(log_session_events));
+
+        #[cfg(feature = "console")]
+        app.add_systems(Startup, overlay::spawn_console)
+            .add_systems(Update, overlay::update_console.after(export_event_log));
+    }
+}
+
+/// What part of the game an entry came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogCategory {
+    Session,
+    Physics,
+    Shader,
+    App,
+}
+
+impl LogCategory {
+    fn label(self) -> &'static str {
+        match self {
+            LogCategory::Session => "session",
+            LogCategory::Physics => "physics",
+            LogCategory::Shader => "shader",
+            LogCategory::App => "app",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Real seconds since startup
+    pub time: f32,
+    pub category: LogCategory,
+    pub message: String,
+}
+
+/// Resource: The most recent notable events (oldest dropped first)
+#[derive(Resource, Debug)]
+pub struct EventLog {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+}
+
+impl EventLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, time: f32, category: LogCategory, message: impl Into<String>) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            time,
+            category,
+            message: message.into(),
+        });
+    }
+
+    /// The last `count` entries, oldest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &LogEntry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(count))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Plain-text dump of the whole log (one entry per line)
+    pub fn export(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            let _ = writeln!(
+                out,
+                "[{:>9.3}] {:<7} {}",
+                entry.time,
+                entry.category.label(),
+                entry.message
+            );
+        }
+        out
+    }
+}
+
+/// Resource: Whether the debug console is open (toggled with backtick)
+#[derive(Resource, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+}
+
+/// System: Record solution and invalid-move feedback
+fn log_session_events(
+    time: Res<Time<Real>>,
+    mut solutions: MessageReader<SolutionFound>,
+    mut invalid_moves: MessageReader<InvalidMove>,
+    mut log: ResMut<EventLog>,
+) {
+    let now = time.elapsed_secs();
+
+    for solution in solutions.read() {
+        let kind = if solution.is_new { "new" } else { "repeat" };
+        log.push(
+            now,
+            LogCategory::Session,
+            format!("Solution found ({kind})"),
+        );
+    }
+    for _ in invalid_moves.read() {
+        log.push(now, LogCategory::Session, "Invalid move");
+    }
+}
+
+/// System: Record nodes that go non-finite or fly off (reported once until they recover)
+fn log_physics_anomalies(
+    time: Res<Time<Real>>,
+    nodes: Query<(&GraphNode, &NodePhysics)>,
+    mut reported: Local<HashSet<NodeId>>,
+    mut log: ResMut<EventLog>,
+) {
+    for (graph_node, physics) in &nodes {
+        let anomaly = if !physics.position.is_finite() || !physics.velocity.is_finite() {
+            Some("non-finite position/velocity".to_string())
+        } else if physics.velocity.length() > RUNAWAY_SPEED {
+            Some(format!("runaway speed {:.1}", physics.velocity.length()))
+        } else {
+            None
+        };
+
+        match anomaly {
+            Some(description) if reported.insert(graph_node.node_id) => {
+                let message = format!("Node {}: {}", graph_node.node_id.0, description);
+                warn!("⚠️ {}", message);
+                log.push(time.elapsed_secs(), LogCategory::Physics, message);
+            }
+            Some(_) => {}
+            None => {
+                reported.remove(&graph_node.node_id);
+            }
+        }
+    }
+}
+
+/// System: Check the session's bookkeeping every frame in debug builds (each distinct
+/// problem reported once until it recovers)
+#[cfg(debug_assertions)]
+fn verify_session_invariants(
+    time: Res<Time<Real>>,
+    session: Res<PuzzleSession>,
+    mut reported: Local<Option<String>>,
+    mut log: ResMut<EventLog>,
+) {
+    match session.verify_invariants() {
+        Ok(()) => *reported = None,
+        Err(problem) if reported.as_ref() != Some(&problem) => {
+            error!("🧮 Session state corrupted: {}", problem);
+            log.push(
+                time.elapsed_secs(),
+                LogCategory::Session,
+                format!("State corrupted: {}", problem),
+            );
+            *reported = Some(problem);
+        }
+        Err(_) => {}
+    }
+}
+
+/// System: Record shaders that failed to load (their materials fall back to not drawing)
+fn log_shader_failures(
+    time: Res<Time<Real>>,
+    mut failures: MessageReader<AssetLoadFailedEvent<Shader>>,
+    mut log: ResMut<EventLog>,
+) {
+    for failure in failures.read() {
+        log.push(
+            time.elapsed_secs(),
+            LogCategory::Shader,
+            format!("{} failed to load: {}", failure.path, failure.error),
+        );
+    }
+}
+
+/// System: Record why the game stopped (each new or changed `AppError`)
+fn log_app_errors(time: Res<Time<Real>>, error: Option<Res<AppError>>, mut log: ResMut<EventLog>) {
+    let Some(error) = error else {
+        return;
+    };
+    if error.is_changed() {
+        log.push(time.elapsed_secs(), LogCategory::App, error.reason.clone());
+    }
+}
+
+/// System: Backtick opens/closes the console (and echoes recent entries to the log)
+fn toggle_console(
+    keys: Res<ButtonInput<KeyCode>>,
+    log: Res<EventLog>,
+    mut console: ResMut<ConsoleState>,
+) {
+    if !keys.just_pressed(KeyCode::Backquote) {
+        return;
+    }
+
+    console.open = !console.open;
+    if console.open {
+        info!("🧾 Event log ({} entries):", log.len());
+        for entry in log.recent(CONSOLE_LINES) {
+            info!(
+                "  [{:.3}] {} {}",
+                entry.time,
+                entry.category.label(),
+                entry.message
+            );
+        }
+    }
+}
+
+/// System: F12 with the console open exports the log for a bug report
+fn export_event_log(
+    keys: Res<ButtonInput<KeyCode>>,
+    console: Res<ConsoleState>,
+    log: Res<EventLog>,
+) {
+    if !console.open || !keys.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    let contents = log.export();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = format!("valence_event_log_{stamp}.txt");
+        match std::fs::write(&path, contents) {
+            Ok(()) => info!("💾 Event log exported to {}", path),
+            Err(err) => warn!("Failed to export event log: {}", err),
+        }
+    }
+
+    // No filesystem on the web: dump to the browser console instead
+    #[cfg(target_arch = "wasm32")]
+    info!("🧾 Event log export:\n{}", contents);
+}
+
+/// On-screen console overlay (needs Bevy's UI/text stack: `--features console`)
+#[cfg(feature = "console")]
+mod overlay {
+    use bevy::prelude::*;
+
+    use super::{CONSOLE_LINES, ConsoleState, EventLog};
+
+    #[derive(Component)]
+    pub struct ConsoleText;
+
+    pub fn spawn_console(mut commands: Commands) {
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(8.0),
+                top: Val::Px(8.0),
+                max_width: Val::Percent(90.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            Text::new(""),
+            TextFont {
+                font_size: 13.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.8, 1.0, 0.8)),
+            Visibility::Hidden,
+            ConsoleText,
+            Name::new("Event Console"),
+        ));
+    }
+
+    pub fn update_console(
+        console: Res<ConsoleState>,
+        log: Res<EventLog>,
+        mut overlay: Query<(&mut Text, &mut Visibility), With<ConsoleText>>,
+    ) {
+        if !(console.is_changed() || (console.open && log.is_changed())) {
+            return;
+        }
+
+        for (mut text, mut visibility) in &mut overlay {
+            *visibility = if console.open {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+
+            if console.open {
+                let lines: Vec<String> = log
+                    .recent(CONSOLE_LINES)
+                    .map(|e| format!("[{:.1}] {} {}", e.time, e.category.label(), e.message))
+                    .collect();
+                text.0 = lines.join("\n");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_drops_oldest() {
+        let mut log = EventLog::with_capacity(3);
+        for i in 0..5 {
+            log.push(i as f32, LogCategory::Session, format!("event {i}"));
+        }
+
+        let messages: Vec<&str> = log.recent(10).map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["event 2", "event 3", "event 4"]);
+    }
+
+    #[test]
+    fn test_recent_returns_newest_in_order() {
+        let mut log = EventLog::with_capacity(10);
+        for i in 0..5 {
+            log.push(i as f32, LogCategory::Physics, format!("event {i}"));
+        }
+
+        let messages: Vec<&str> = log.recent(2).map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["event 3", "event 4"]);
+    }
+
+    #[test]
+    fn test_export_has_one_line_per_entry() {
+        let mut log = EventLog::with_capacity(10);
+        log.push(1.5, LogCategory::Shader, "sdf_scene.wgsl failed");
+        log.push(2.0, LogCategory::Session, "Invalid move");
+
+        let export = log.export();
+        assert_eq!(export.lines().count(), 2);
+        assert!(export.contains("shader"));
+        assert!(export.contains("sdf_scene.wgsl failed"));
+    }
+}
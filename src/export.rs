@@ -0,0 +1,257 @@
+// export.rs - the board as a shareable image: SVG always, PNG with `--features png-export`
+
+use bevy::prelude::*;
+use std::fmt::Write as _;
+
+use crate::{
+    game::session::PuzzleSession,
+    graph::{Edge, NodeId, Valences},
+    visual::{sdf::seven_segment::Digit, theme::Theme},
+};
+
+/// Pixels between neighbouring nodes
+const NODE_SPACING: f32 = 120.0;
+
+/// Pixels between the outer nodes' centers and the image edge
+const MARGIN: f32 = 80.0;
+
+const NODE_RADIUS: f32 = 42.0;
+
+const EDGE_WIDTH: f32 = 14.0;
+
+/// Height of a valence label (drawn as seven segments, like in game, so no fonts needed)
+const DIGIT_HEIGHT: f32 = 36.0;
+
+/// PNG pixels per SVG unit
+#[cfg(feature = "png-export")]
+const PNG_SCALE: f32 = 2.0;
+
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, export_board_image);
+    }
+}
+
+/// Segment endpoints of a digit `h` tall centered on the origin (y down), in mask bit order
+fn segments(h: f32) -> [(Vec2, Vec2); 7] {
+    let (x, y) = (h * 0.275, h * 0.5);
+    [
+        (Vec2::new(-x, -y), Vec2::new(x, -y)),   // Top
+        (Vec2::new(x, -y), Vec2::new(x, 0.0)),   // Top right
+        (Vec2::new(x, 0.0), Vec2::new(x, y)),    // Bottom right
+        (Vec2::new(-x, y), Vec2::new(x, y)),     // Bottom
+        (Vec2::new(-x, 0.0), Vec2::new(-x, y)),  // Bottom left
+        (Vec2::new(-x, -y), Vec2::new(-x, 0.0)), // Top left
+        (Vec2::new(-x, 0.0), Vec2::new(x, 0.0)), // Middle
+    ]
+}
+
+/// Valences are 0-8, so every label is a single digit
+fn digit(value: usize) -> Digit {
+    Digit::try_from(value.min(8) as u8).unwrap_or(Digit::Eight)
+}
+
+/// Image coordinates of a node (row 0 is the bottom row in game)
+fn node_center(node: NodeId) -> Vec2 {
+    let (row, col) = (node.0 / 3, node.0 % 3);
+    Vec2::new(
+        MARGIN + col as f32 * NODE_SPACING,
+        MARGIN + (2 - row) as f32 * NODE_SPACING,
+    )
+}
+
+fn hex(color: Vec4) -> String {
+    Srgba::new(color.x, color.y, color.z, 1.0).to_hex()
+}
+
+/// The edges worth sharing: the trail being drawn, or with none drawn a found solution
+pub fn exported_edges(session: &PuzzleSession) -> Vec<Edge> {
+    if !session.edges().is_empty() {
+        return session.edges().edges_in_order().to_vec();
+    }
+
+    // Solutions are unordered; pick one deterministically
+    let mut edges: Vec<Edge> = session
+        .found_solutions()
+        .iter()
+        .min_by_key(|solution| solution.canonical_string())
+        .map(|solution| solution.edges().iter().copied().collect())
+        .unwrap_or_default();
+    edges.sort_by_key(|edge| (edge.from, edge.to));
+    edges
+}
+
+/// Draw the board as an SVG: edges in the blend of their nodes' colors, nodes colored by
+/// remaining valence and labeled with the puzzle's valences
+pub fn board_svg(puzzle: &Valences, edges: &[Edge], theme: &Theme) -> String {
+    let size = MARGIN * 2.0 + NODE_SPACING * 2.0;
+    let nodes = (0..9).map(NodeId);
+    let remaining = |node: NodeId| {
+        let used = edges.iter().filter(|edge| edge.contains_node(node)).count();
+        puzzle.get(node).saturating_sub(used)
+    };
+    let color = |node: NodeId| theme.node_color(remaining(node));
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        theme.background.to_srgba().to_hex()
+    );
+
+    for edge in edges {
+        let (a, b) = (node_center(edge.from), node_center(edge.to));
+        let blend = (color(edge.from) + color(edge.to)) * 0.5;
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{EDGE_WIDTH}" stroke-linecap="round"/>"#,
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+            hex(blend)
+        );
+    }
+
+    for node in nodes {
+        let center = node_center(node);
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{}" cy="{}" r="{NODE_RADIUS}" fill="{}"/>"#,
+            center.x,
+            center.y,
+            hex(color(node))
+        );
+
+        let mask = digit(puzzle.get(node)).mask();
+        let mut path = String::new();
+        for (bit, (from, to)) in segments(DIGIT_HEIGHT).into_iter().enumerate() {
+            if mask & (1 << bit) != 0 {
+                let (from, to) = (center + from, center + to);
+                let _ = write!(path, "M{} {}L{} {}", from.x, from.y, to.x, to.y);
+            }
+        }
+        // A soft dark outline keeps the white label readable on light node colors
+        for (stroke, width) in [
+            ("#00000059", DIGIT_HEIGHT * 0.22),
+            ("#ffffff", DIGIT_HEIGHT * 0.12),
+        ] {
+            let _ = writeln!(
+                svg,
+                r#"<path d="{path}" fill="none" stroke="{stroke}" stroke-width="{width}" stroke-linecap="round" stroke-linejoin="round"/>"#
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Rasterize an SVG from `board_svg`
+#[cfg(feature = "png-export")]
+pub fn svg_to_png(svg: &str) -> Result<Vec<u8>, String> {
+    use resvg::{tiny_skia, usvg};
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).map_err(|e| e.to_string())?;
+    let size = tree
+        .size()
+        .to_int_size()
+        .scale_by(PNG_SCALE)
+        .ok_or("image too large")?;
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("image too large")?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(PNG_SCALE, PNG_SCALE),
+        &mut pixmap.as_mut(),
+    );
+    pixmap.encode_png().map_err(|e| e.to_string())
+}
+
+/// System: E exports the board (saved next to the game natively, downloaded on the web)
+fn export_board_image(
+    keys: Res<ButtonInput<KeyCode>>,
+    session: Res<PuzzleSession>,
+    theme: Res<Theme>,
+) {
+    if !keys.just_pressed(KeyCode::KeyE) {
+        return;
+    }
+
+    let svg = board_svg(session.puzzle_valences(), &exported_edges(&session), &theme);
+    save("svg", svg.as_bytes(), "image/svg+xml");
+
+    #[cfg(feature = "png-export")]
+    match svg_to_png(&svg) {
+        Ok(png) => save("png", &png, "image/png"),
+        Err(err) => warn!("Failed to render PNG: {}", err),
+    }
+}
+
+fn save(extension: &str, contents: &[u8], mime: &str) {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = mime;
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = format!("valence_board_{stamp}.{extension}");
+        match std::fs::write(&path, contents) {
+            Ok(()) => info!("🖼 Board exported to {}", path),
+            Err(err) => warn!("Failed to export board: {}", err),
+        }
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    crate::web::download(&format!("valence_board.{extension}"), contents, mime);
+
+    #[cfg(all(not(feature = "wasm"), target_arch = "wasm32"))]
+    let _ = (extension, contents, mime);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_draws_every_node_and_edge() {
+        let puzzle = Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]);
+        let edges = [Edge::new(NodeId(0), NodeId(1))];
+        let svg = board_svg(&puzzle, &edges, &Theme::default());
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<circle").count(), 9);
+        assert_eq!(svg.matches("<line").count(), 1);
+        // Both ends are used up, so they share the valence-0 color
+        let done = hex(Theme::default().node_color(0));
+        assert_eq!(svg.matches(&format!(r#"fill="{done}""#)).count(), 9);
+    }
+
+    #[test]
+    fn test_found_solution_exported_when_no_trail() {
+        let mut session = PuzzleSession::new(Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]), 1);
+        assert!(exported_edges(&session).is_empty());
+
+        session.add_node(NodeId(0));
+        session.add_node(NodeId(1));
+        assert_eq!(
+            exported_edges(&session),
+            vec![Edge::new(NodeId(0), NodeId(1))]
+        );
+    }
+
+    #[cfg(feature = "png-export")]
+    #[test]
+    fn test_png_renders() {
+        let puzzle = Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]);
+        let png = svg_to_png(&board_svg(&puzzle, &[], &Theme::default())).unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
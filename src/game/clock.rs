@@ -0,0 +1,105 @@
+// game/clock.rs
+
+use bevy::prelude::*;
+
+/// Slowest allowed time scale (keeps slow-mo from freezing the game outright)
+const MIN_TIME_SCALE: f32 = 0.05;
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameClock>()
+            .add_systems(PreUpdate, (toggle_pause, apply_game_clock).chain());
+    }
+}
+
+/// Gameplay clock: pause and time scale on top of Bevy's virtual time
+///
+/// Gameplay systems read `Res<Time>` (virtual time in `Update` and `FixedUpdate`), so
+/// pausing or scaling here freezes or slows physics, waves and effects together. Anything
+/// that should keep moving while paused (HUD transitions, input filtering) reads `Time<Real>`.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct GameClock {
+    pub paused: bool,
+    /// Gameplay speed multiplier (1.0 = normal, 0.5 = half-speed slow-mo)
+    pub scale: f32,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            scale: 1.0,
+        }
+    }
+}
+
+impl GameClock {
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.max(MIN_TIME_SCALE);
+    }
+}
+
+/// Run condition: gameplay input is ignored while paused
+pub fn not_paused(clock: Res<GameClock>) -> bool {
+    !clock.paused
+}
+
+/// System: P toggles pause
+fn toggle_pause(keys: Res<ButtonInput<KeyCode>>, mut clock: ResMut<GameClock>) {
+    if keys.just_pressed(KeyCode::KeyP) {
+        clock.toggle_pause();
+        info!("⏸ Paused: {}", clock.paused);
+    }
+}
+
+/// System: Push the clock into Bevy's virtual time
+fn apply_game_clock(clock: Res<GameClock>, mut virtual_time: ResMut<Time<Virtual>>) {
+    if !clock.is_changed() {
+        return;
+    }
+
+    if clock.paused {
+        virtual_time.pause();
+    } else {
+        virtual_time.unpause();
+    }
+    virtual_time.set_relative_speed(clock.scale);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_is_clamped() {
+        let mut clock = GameClock::default();
+        clock.set_scale(0.0);
+        assert_eq!(clock.scale, MIN_TIME_SCALE);
+        clock.set_scale(0.5);
+        assert_eq!(clock.scale, 0.5);
+    }
+
+    #[test]
+    fn test_pause_freezes_virtual_time() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::input::InputPlugin, ClockPlugin));
+        app.update();
+
+        app.world_mut().resource_mut::<GameClock>().paused = true;
+        app.update();
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+
+        app.world_mut().resource_mut::<GameClock>().set_scale(0.5);
+        app.world_mut().resource_mut::<GameClock>().paused = false;
+        app.update();
+        let virtual_time = app.world().resource::<Time<Virtual>>();
+        assert!(!virtual_time.is_paused());
+        assert_eq!(virtual_time.relative_speed(), 0.5);
+    }
+}
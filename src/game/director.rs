@@ -0,0 +1,143 @@
+// game/director.rs - adaptive difficulty: once the player is clearly stuck on a level,
+// hints light up and the next puzzles drawn come from the easier ones
+
+use bevy::prelude::*;
+
+use crate::settings::Settings;
+
+/// Gameplay seconds on a level without a solution before the player counts as stuck
+const STUCK_SECS: f32 = 150.0;
+
+/// Invalid moves on a level before their rate is judged (a few slips aren't a struggle)
+const STUCK_MIN_INVALID: u32 = 8;
+
+/// Share of a level's moves that were invalid before the player counts as stuck
+const STUCK_INVALID_RATE: f32 = 0.4;
+
+/// How play on a level is going, as the director judges it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Struggle {
+    /// Gameplay seconds on the level
+    pub seconds: f32,
+    pub invalid_moves: u32,
+    /// Share of the level's moves that were invalid
+    pub invalid_rate: f32,
+    pub solutions_found: usize,
+}
+
+impl Struggle {
+    /// Clearly stuck: long on the level without a solution, or mostly invalid moves
+    pub fn stuck(&self) -> bool {
+        let stalled = self.solutions_found == 0 && self.seconds >= STUCK_SECS;
+        let flailing =
+            self.invalid_moves >= STUCK_MIN_INVALID && self.invalid_rate >= STUCK_INVALID_RATE;
+        stalled || flailing
+    }
+}
+
+/// Resource: The level the player got stuck on, while adaptive difficulty is on
+///
+/// A level stays marked until another one is: hints show for the rest of it, and the
+/// puzzle drawn after it (or a skip on it) comes from the easier half of the untried ones.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct Director {
+    stuck_on: Option<usize>,
+}
+
+impl Director {
+    /// Whether the player got stuck on `level`
+    pub fn stuck_on(&self, level: usize) -> bool {
+        self.stuck_on == Some(level)
+    }
+
+    /// Take in how `level` is going; true the moment the player gets stuck on it
+    pub fn assess(&mut self, level: usize, struggle: &Struggle) -> bool {
+        if self.stuck_on(level) || !struggle.stuck() {
+            return false;
+        }
+        self.stuck_on = Some(level);
+        true
+    }
+}
+
+/// System: A turns adaptive difficulty on or off
+pub fn adaptive_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<Settings>,
+    mut director: ResMut<Director>,
+) {
+    if !keys.just_pressed(KeyCode::KeyA) {
+        return;
+    }
+    settings.adaptive = !settings.adaptive;
+    if !settings.adaptive {
+        *director = Director::default();
+    }
+    info!(
+        "🧭 Adaptive difficulty {}",
+        if settings.adaptive { "on" } else { "off" }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stuck_by_time_or_invalid_moves() {
+        let calm = Struggle {
+            seconds: 60.0,
+            invalid_moves: 3,
+            invalid_rate: 0.5,
+            solutions_found: 0,
+        };
+        assert!(!calm.stuck());
+
+        let stalled = Struggle {
+            seconds: STUCK_SECS,
+            ..calm
+        };
+        assert!(stalled.stuck());
+        // A solution shows they're getting somewhere
+        assert!(
+            !Struggle {
+                solutions_found: 1,
+                ..stalled
+            }
+            .stuck()
+        );
+
+        let flailing = Struggle {
+            invalid_moves: STUCK_MIN_INVALID,
+            ..calm
+        };
+        assert!(flailing.stuck());
+        assert!(
+            !Struggle {
+                invalid_rate: 0.2,
+                ..flailing
+            }
+            .stuck()
+        );
+    }
+
+    #[test]
+    fn test_director_marks_a_level_once() {
+        let stuck = Struggle {
+            seconds: STUCK_SECS,
+            ..default()
+        };
+        let mut director = Director::default();
+
+        assert!(!director.assess(3, &Struggle::default()));
+        assert!(director.assess(3, &stuck));
+        assert!(!director.assess(3, &stuck));
+        assert!(director.stuck_on(3));
+
+        // Moving on doesn't clear it until the next level is a struggle too
+        assert!(!director.assess(4, &Struggle::default()));
+        assert!(director.stuck_on(3));
+        assert!(director.assess(4, &stuck));
+        assert!(!director.stuck_on(3));
+    }
+}
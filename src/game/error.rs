@@ -0,0 +1,25 @@
+// game/error.rs - failures the game can't play through, shown until a retry clears them
+
+use bevy::prelude::*;
+
+/// Resource: Present while the game can't run (e.g. the puzzle library failed to load)
+///
+/// Startup leaves an empty board behind it instead of panicking; the background turns to
+/// the error palette and R retries (see `retry_after_error`).
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct AppError {
+    pub reason: String,
+}
+
+impl AppError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        let reason = reason.into();
+        error!("❌ {} (press R to retry)", reason);
+        Self { reason }
+    }
+}
+
+/// Run condition: Nothing has failed (gameplay and level progression wait otherwise)
+pub fn no_app_error(error: Option<Res<AppError>>) -> bool {
+    error.is_none()
+}
@@ -0,0 +1,166 @@
+// game/hints.rs - hints toward a solution the player hasn't found: the engine that
+// searches for them, and the tokens players earn and spend on them
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::session::PuzzleSession;
+use crate::graph::{NodeId, Valences, solver};
+
+/// Search moves spent looking for a hint
+const HINT_BUDGET: usize = 50_000;
+
+/// Resource: Hint tokens, earned by finishing levels and spent with H
+///
+/// Kept with the player's profile.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HintWallet {
+    pub tokens: u32,
+}
+
+/// The board a hint is for: puzzle, trail drawn and solutions found
+type HintBoard = (Valences, Vec<NodeId>, usize);
+
+fn hint_board(session: &PuzzleSession) -> HintBoard {
+    (
+        session.puzzle_valences().clone(),
+        session.current_trail().to_vec(),
+        session.found_solutions().len(),
+    )
+}
+
+/// Resource: Finds the next move toward a new solution, and keeps a bought hint up until
+/// the board moves on
+#[derive(Resource, Debug, Default)]
+pub struct HintEngine {
+    /// The board last searched, and the hint found for it
+    searched: Option<(HintBoard, Option<NodeId>)>,
+    /// The board a token was spent on
+    bought_for: Option<HintBoard>,
+}
+
+impl HintEngine {
+    /// The node to play next toward a solution the player hasn't found (with an empty
+    /// trail, where to start)
+    ///
+    /// The search runs again only when the board has changed.
+    pub fn next_node(&mut self, session: &PuzzleSession) -> Option<NodeId> {
+        let board = hint_board(session);
+        if let Some((searched, node)) = &self.searched
+            && *searched == board
+        {
+            return *node;
+        }
+
+        let node =
+            solver::hint_next_node(&board.0, &board.1, session.found_solutions(), HINT_BUDGET);
+        self.searched = Some((board, node));
+        node
+    }
+
+    /// Whether a token bought the hint for the board as it stands (a purchase lapses once
+    /// the board moves on)
+    pub fn bought(&mut self, session: &PuzzleSession) -> bool {
+        let current = self
+            .bought_for
+            .as_ref()
+            .is_some_and(|board| *board == hint_board(session));
+        if !current {
+            self.bought_for = None;
+        }
+        current
+    }
+
+    /// Spend a token on a hint for the board
+    ///
+    /// None, with nothing spent, if the wallet is empty or there's no hint to give.
+    pub fn buy(&mut self, session: &PuzzleSession, wallet: &mut HintWallet) -> Option<NodeId> {
+        if wallet.tokens == 0 {
+            return None;
+        }
+        let node = self.next_node(session)?;
+        wallet.tokens -= 1;
+        self.bought_for = Some(hint_board(session));
+        Some(node)
+    }
+}
+
+/// Tokens a finished level earns: one for finishing it, and one per solution found beyond
+/// what it required (a partner's finds can carry a shared board past it)
+pub fn tokens_earned(solutions_found: usize, required: usize) -> u32 {
+    1 + solutions_found.saturating_sub(required) as u32
+}
+
+/// System: H spends a hint token to show the next move
+pub fn spend_hint_token(
+    keys: Res<ButtonInput<KeyCode>>,
+    session: Res<PuzzleSession>,
+    mut wallet: ResMut<HintWallet>,
+    mut engine: ResMut<HintEngine>,
+) {
+    if !keys.just_pressed(KeyCode::KeyH) || engine.bought(&session) {
+        return;
+    }
+    if wallet.tokens == 0 {
+        info!("💡 No hint tokens; finish a level to earn one");
+        return;
+    }
+
+    match engine.buy(&session, &mut wallet) {
+        Some(node) => info!(
+            "💡 Hint: node {} next ({} tokens left)",
+            node.0, wallet.tokens
+        ),
+        None => info!("💡 No new solution from this trail; nothing spent"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> PuzzleSession {
+        PuzzleSession::new(Valences::new(vec![0, 0, 0, 0, 0, 1, 0, 1, 2]), 1)
+    }
+
+    #[test]
+    fn test_bought_hint_lasts_until_the_board_moves_on() {
+        let mut session = session();
+        let mut engine = HintEngine::default();
+        let mut wallet = HintWallet { tokens: 1 };
+
+        let node = engine.buy(&session, &mut wallet).unwrap();
+        assert_eq!(wallet.tokens, 0);
+        assert!(engine.bought(&session));
+
+        session.add_node(node);
+        assert!(!engine.bought(&session));
+        // Back where it was bought: the purchase has lapsed
+        session.reset();
+        assert!(!engine.bought(&session));
+    }
+
+    #[test]
+    fn test_nothing_is_spent_without_a_hint() {
+        let session = session();
+        let mut engine = HintEngine::default();
+
+        let mut empty = HintWallet::default();
+        assert_eq!(engine.buy(&session, &mut empty), None);
+
+        let mut solved = PuzzleSession::new(Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]), 1);
+        solved.add_node(NodeId(0));
+        solved.add_node(NodeId(1));
+        let mut wallet = HintWallet { tokens: 2 };
+        assert_eq!(engine.buy(&solved, &mut wallet), None);
+        assert_eq!(wallet.tokens, 2);
+    }
+
+    #[test]
+    fn test_finishing_earns_a_token_and_extra_solutions_more() {
+        assert_eq!(tokens_earned(1, 1), 1);
+        assert_eq!(tokens_earned(4, 1), 4);
+        assert_eq!(tokens_earned(2, 5), 1);
+    }
+}
@@ -1,3 +1,10 @@
+pub mod clock;
+pub mod director;
+pub mod error;
+pub mod hints;
 pub mod progression;
 pub mod puzzle;
+pub mod rng;
 pub mod session;
+pub mod skip;
+pub mod tour;
@@ -1,21 +1,27 @@
 // game/progression.rs
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::session::ProgressInfo;
 
 /// Maps level number (1-217) to complexity value
-/// Generated from the unique complexity values in the symmetric puzzles CSV
+///
+/// Each unique complexity in the symmetric puzzles CSV is a level, ordered by the median
+/// difficulty score of its puzzles (ties by complexity) so levels ramp up more smoothly
+/// than raw complexity does. Regenerate with `puzzle_tools levels`.
 const LEVEL_TO_COMPLEXITY: &[usize] = &[
-    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 20, 21, 22, 24, 25, 26, 27, 28,
-    30, 32, 33, 34, 35, 36, 39, 40, 42, 44, 45, 48, 49, 50, 52, 54, 55, 56, 60, 63, 64, 65, 66, 70,
-    72, 75, 77, 78, 80, 81, 84, 88, 90, 91, 96, 98, 99, 100, 104, 105, 108, 110, 112, 117, 120,
-    121, 126, 128, 130, 132, 135, 136, 140, 143, 144, 147, 150, 152, 153, 154, 156, 160, 161, 162,
-    165, 168, 169, 170, 171, 175, 176, 180, 182, 184, 187, 189, 190, 192, 195, 196, 198, 200, 204,
-    207, 208, 209, 210, 216, 220, 221, 224, 225, 228, 230, 231, 232, 234, 240, 242, 248, 250, 252,
-    253, 260, 261, 264, 270, 279, 280, 285, 286, 288, 294, 297, 299, 300, 304, 306, 308, 310, 312,
-    319, 320, 322, 325, 330, 333, 336, 338, 342, 348, 350, 351, 352, 360, 363, 364, 368, 370, 372,
-    374, 376, 378, 384, 390, 392, 418, 420, 423, 429, 430, 432, 440, 450, 470, 494, 500, 504, 517,
-    532, 533, 540, 550, 570, 576, 583, 594, 600, 624, 630, 650, 663, 671, 672, 676, 684, 696, 700,
-    708, 728, 732, 740, 792, 810, 832, 852, 858, 880, 924, 936, 960,
+    1, 2, 3, 4, 5, 25, 6, 7, 49, 175, 21, 35, 14, 28, 98, 42, 147, 161, 8, 16, 32, 248, 136, 56,
+    152, 184, 368, 64, 96, 376, 232, 128, 304, 9, 18, 24, 72, 63, 54, 45, 27, 126, 153, 171, 200,
+    81, 40, 162, 144, 342, 10, 210, 135, 225, 351, 207, 450, 70, 261, 279, 189, 378, 504, 810, 423,
+    333, 936, 297, 430, 306, 20, 30, 90, 190, 50, 77, 470, 170, 36, 100, 160, 140, 500, 48, 80,
+    270, 150, 374, 650, 280, 310, 198, 250, 120, 320, 88, 110, 360, 121, 209, 242, 60, 130, 319,
+    220, 11, 168, 570, 33, 240, 370, 55, 108, 187, 99, 517, 66, 960, 740, 165, 22, 44, 230, 12,
+    330, 600, 117, 352, 440, 583, 176, 348, 550, 858, 231, 594, 216, 363, 418, 671, 204, 264, 154,
+    300, 192, 143, 252, 336, 84, 540, 78, 792, 432, 924, 384, 221, 260, 234, 13, 880, 132, 308,
+    288, 684, 228, 420, 624, 286, 180, 429, 708, 533, 732, 672, 372, 253, 576, 852, 156, 696, 728,
+    26, 364, 676, 299, 224, 39, 494, 663, 338, 52, 112, 630, 832, 104, 390, 392, 325, 350, 322,
+    294, 65, 700, 91, 195, 532, 208, 182, 196, 312, 15, 169, 285, 75, 105, 17, 34,
 ];
 
 const MAX_LEVEL: usize = 217;
@@ -39,6 +45,14 @@ impl Default for ProgressionTracker {
 }
 
 impl ProgressionTracker {
+    /// Start at a specific level (clamped to 1..=217), e.g. from saved progress
+    pub fn at_level(level: usize) -> Self {
+        Self {
+            current_level: level.clamp(1, MAX_LEVEL),
+            completed_at_level: 0,
+        }
+    }
+
     /// Get the complexity value for the current level
     pub fn current_complexity(&self) -> usize {
         LEVEL_TO_COMPLEXITY[self.current_level - 1]
@@ -70,9 +84,60 @@ impl ProgressionTracker {
     }
 }
 
+/// Resource: How much of a level must be solved before the next one starts
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompletionPolicy {
+    /// Any one solution (casual play)
+    FirstSolution,
+    /// Half of the solutions, rounded up
+    HalfSolutions,
+    /// Every solution
+    #[default]
+    AllSolutions,
+}
+
+impl CompletionPolicy {
+    /// The policy after this one (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            CompletionPolicy::FirstSolution => CompletionPolicy::HalfSolutions,
+            CompletionPolicy::HalfSolutions => CompletionPolicy::AllSolutions,
+            CompletionPolicy::AllSolutions => CompletionPolicy::FirstSolution,
+        }
+    }
+
+    /// Solutions to find on a puzzle with `total` of them
+    pub fn required(self, total: usize) -> usize {
+        match self {
+            CompletionPolicy::FirstSolution => total.min(1),
+            CompletionPolicy::HalfSolutions => total.div_ceil(2),
+            CompletionPolicy::AllSolutions => total,
+        }
+    }
+
+    /// Has enough of the level been solved to move on?
+    pub fn is_met(self, progress: &ProgressInfo) -> bool {
+        progress
+            .total_solutions
+            .is_some_and(|total| progress.solutions_found >= self.required(total))
+    }
+}
+
+/// System: G steps through the completion policies
+pub fn cycle_completion_policy(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut policy: ResMut<CompletionPolicy>,
+) {
+    if keys.just_pressed(KeyCode::KeyG) {
+        *policy = policy.next();
+        info!("🏁 Levels now need: {:?}", *policy);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::puzzle::PuzzleLibrary;
 
     #[test]
     fn test_level_to_complexity_has_217_entries() {
@@ -80,11 +145,38 @@ mod tests {
     }
 
     #[test]
-    fn test_level_to_complexity_is_sorted() {
-        for i in 1..LEVEL_TO_COMPLEXITY.len() {
+    fn test_every_complexity_is_a_level_once() {
+        let library = PuzzleLibrary::load().unwrap();
+        let mut levels = LEVEL_TO_COMPLEXITY.to_vec();
+        levels.sort_unstable();
+        assert_eq!(levels, library.available_complexities());
+    }
+
+    #[test]
+    fn test_levels_rise_in_median_difficulty() {
+        let library = PuzzleLibrary::load().unwrap();
+        let median = |complexity| {
+            let mut scores: Vec<f32> = (0..library.puzzle_count(complexity))
+                .map(|index| library.difficulty(complexity, index).unwrap())
+                .collect();
+            scores.sort_by(f32::total_cmp);
+            let mid = scores.len() / 2;
+            if scores.len().is_multiple_of(2) {
+                (scores[mid - 1] + scores[mid]) / 2.0
+            } else {
+                scores[mid]
+            }
+        };
+
+        let medians: Vec<f32> = LEVEL_TO_COMPLEXITY.iter().map(|&c| median(c)).collect();
+        for (level, pair) in medians.windows(2).enumerate() {
             assert!(
-                LEVEL_TO_COMPLEXITY[i] >= LEVEL_TO_COMPLEXITY[i - 1],
-                "Complexity values should be non-decreasing"
+                pair[1] >= pair[0],
+                "Level {} (median {}) plays easier than level {} ({})",
+                level + 2,
+                pair[1],
+                level + 1,
+                pair[0]
             );
         }
     }
@@ -96,6 +188,13 @@ mod tests {
         assert_eq!(tracker.current_complexity(), 1);
     }
 
+    #[test]
+    fn test_at_level_clamps() {
+        assert_eq!(ProgressionTracker::at_level(0).current_level, 1);
+        assert_eq!(ProgressionTracker::at_level(42).current_level, 42);
+        assert_eq!(ProgressionTracker::at_level(999).current_level, MAX_LEVEL);
+    }
+
     #[test]
     fn test_advance_level() {
         let mut tracker = ProgressionTracker::default();
@@ -151,12 +250,28 @@ mod tests {
         };
         assert_eq!(tracker.current_complexity(), 1);
 
-        // Level 19 should be complexity 20 (gap at 19)
-        tracker.current_level = 19;
-        assert_eq!(tracker.current_complexity(), 20);
+        // Complexity 25's few puzzles play easier than complexity 6's
+        tracker.current_level = 6;
+        assert_eq!(tracker.current_complexity(), 25);
 
-        // Level 217 should be complexity 960 (highest)
-        tracker.current_level = 217;
+        // The highest complexity lands mid-table
+        tracker.current_level = 118;
         assert_eq!(tracker.current_complexity(), 960);
     }
+
+    #[test]
+    fn test_completion_policies() {
+        let progress = |solutions_found| ProgressInfo {
+            solutions_found,
+            total_solutions: Some(5),
+            current_edges: 0,
+        };
+
+        assert!(!CompletionPolicy::FirstSolution.is_met(&progress(0)));
+        assert!(CompletionPolicy::FirstSolution.is_met(&progress(1)));
+        assert!(!CompletionPolicy::HalfSolutions.is_met(&progress(2)));
+        assert!(CompletionPolicy::HalfSolutions.is_met(&progress(3)));
+        assert!(!CompletionPolicy::AllSolutions.is_met(&progress(4)));
+        assert!(CompletionPolicy::AllSolutions.is_met(&progress(5)));
+    }
 }
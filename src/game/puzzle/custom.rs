@@ -0,0 +1,206 @@
+// game/puzzle/custom.rs - player-made puzzles from custom_puzzles.toml (or pasted JSON on the web)
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::PuzzleLibrary;
+use crate::game::session::PuzzleSession;
+
+/// File next to the game that custom puzzles are read from (native only)
+pub const CUSTOM_PUZZLES_FILE: &str = "custom_puzzles.toml";
+
+/// One puzzle as written by a player
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CustomPuzzle {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub valences: [usize; 9],
+}
+
+/// A custom puzzles file: `[[puzzle]]` tables in TOML, `{"puzzle": [...]}` in JSON
+#[derive(Debug, Clone, Deserialize)]
+struct CustomPuzzleFile {
+    #[serde(default)]
+    puzzle: Vec<CustomPuzzle>,
+}
+
+/// A custom puzzle that passed validation, as listed in the Custom tab
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomEntry {
+    pub name: String,
+    pub valences: [usize; 9],
+    pub total_solutions: usize,
+}
+
+/// Somewhere custom puzzles can be read from
+pub trait PuzzleSource {
+    /// Where the puzzles came from, for logs
+    fn label(&self) -> String;
+
+    fn puzzles(&self) -> Result<Vec<CustomPuzzle>, String>;
+}
+
+/// A TOML file on disk
+#[cfg(not(target_arch = "wasm32"))]
+pub struct TomlFile(pub std::path::PathBuf);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PuzzleSource for TomlFile {
+    fn label(&self) -> String {
+        self.0.display().to_string()
+    }
+
+    fn puzzles(&self) -> Result<Vec<CustomPuzzle>, String> {
+        let text = std::fs::read_to_string(&self.0).map_err(|e| e.to_string())?;
+        toml::from_str::<CustomPuzzleFile>(&text)
+            .map(|file| file.puzzle)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Append a puzzle to `custom_puzzles.toml` (as the editor saves it)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn append_custom_puzzle(name: &str, puzzle: &CustomPuzzle) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(CUSTOM_PUZZLES_FILE)?;
+    writeln!(
+        file,
+        "\n[[puzzle]]\nname = {:?}\nvalences = {:?}",
+        name, puzzle.valences
+    )
+}
+
+/// JSON text, e.g. pasted into the web build's import box
+pub struct JsonText(pub String);
+
+impl PuzzleSource for JsonText {
+    fn label(&self) -> String {
+        "pasted JSON".to_string()
+    }
+
+    fn puzzles(&self) -> Result<Vec<CustomPuzzle>, String> {
+        serde_json::from_str::<CustomPuzzleFile>(&self.0)
+            .map(|file| file.puzzle)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Read a source into the library, logging the outcome; returns how many puzzles were added
+pub fn import_custom_puzzles(library: &mut PuzzleLibrary, source: &impl PuzzleSource) -> usize {
+    match source
+        .puzzles()
+        .and_then(|puzzles| library.merge_custom(&puzzles))
+    {
+        Ok(added) => {
+            info!("🧩 Added {} custom puzzles from {}", added, source.label());
+            added
+        }
+        Err(err) => {
+            warn!("Custom puzzles from {} skipped: {}", source.label(), err);
+            0
+        }
+    }
+}
+
+/// System: Load `custom_puzzles.toml` if the player dropped one next to the game
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_custom_puzzles(mut library: ResMut<PuzzleLibrary>) {
+    let source = TomlFile(CUSTOM_PUZZLES_FILE.into());
+    if source.0.exists() {
+        import_custom_puzzles(&mut library, &source);
+    }
+}
+
+/// Message asking for the next custom puzzle (the Custom tab: C, or its button on the web)
+#[derive(Message, Debug, Clone, Copy)]
+pub struct NextCustomPuzzle;
+
+/// System: C opens the Custom tab's next puzzle
+pub fn custom_keys(keys: Res<ButtonInput<KeyCode>>, mut next: MessageWriter<NextCustomPuzzle>) {
+    if keys.just_pressed(KeyCode::KeyC) {
+        next.write(NextCustomPuzzle);
+    }
+}
+
+/// System: Play the next custom puzzle (cycling back to the first) when one is asked for
+pub fn play_custom_puzzle(
+    mut requests: MessageReader<NextCustomPuzzle>,
+    library: Res<PuzzleLibrary>,
+    mut session: ResMut<PuzzleSession>,
+    mut next: Local<usize>,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+    let custom = library.custom_puzzles();
+    if custom.is_empty() {
+        info!("🧩 No custom puzzles (add some to {})", CUSTOM_PUZZLES_FILE);
+        return;
+    }
+
+    let entry = &custom[*next % custom.len()];
+    *next = (*next + 1) % custom.len();
+    info!(
+        "🧩 Custom puzzle \"{}\": {} solutions expected",
+        entry.name, entry.total_solutions
+    );
+    *session = PuzzleSession::new(
+        crate::graph::Valences::from_array(entry.valences),
+        entry.total_solutions,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_and_json_read_the_same_puzzles() {
+        let toml = r#"
+            [[puzzle]]
+            name = "Corner"
+            valences = [1, 1, 0, 0, 0, 0, 0, 0, 0]
+
+            [[puzzle]]
+            valences = [2, 2, 0, 0, 2, 0, 0, 0, 0]
+        "#;
+        let json = r#"{"puzzle": [
+            {"name": "Corner", "valences": [1, 1, 0, 0, 0, 0, 0, 0, 0]},
+            {"valences": [2, 2, 0, 0, 2, 0, 0, 0, 0]}
+        ]}"#;
+
+        let from_toml = toml::from_str::<CustomPuzzleFile>(toml).unwrap().puzzle;
+        assert_eq!(JsonText(json.to_string()).puzzles().unwrap(), from_toml);
+        assert_eq!(from_toml[0].name.as_deref(), Some("Corner"));
+        assert_eq!(from_toml[1].name, None);
+    }
+
+    #[test]
+    fn test_import_validates_and_names_puzzles() {
+        let mut library = PuzzleLibrary::from_csv("0,0,0,0,0,0,0,1,1,1").unwrap();
+        let good = JsonText(
+            r#"{"puzzle": [
+                {"name": "Corner", "valences": [1, 1, 0, 0, 0, 0, 0, 0, 0]},
+                {"valences": [2, 2, 0, 0, 2, 0, 0, 0, 0]}
+            ]}"#
+            .to_string(),
+        );
+        assert_eq!(import_custom_puzzles(&mut library, &good), 2);
+        assert_eq!(library.puzzle_count(1), 2);
+
+        let custom = library.custom_puzzles();
+        assert_eq!(custom[0].name, "Corner");
+        assert_eq!(custom[1].name, "Custom 2");
+        assert_eq!(custom[1].total_solutions, 1);
+
+        // Importing again changes nothing; an unsolvable puzzle rejects the whole file
+        assert_eq!(import_custom_puzzles(&mut library, &good), 0);
+        let unsolvable = JsonText(r#"{"puzzle": [{"valences": [1,0,0,0,0,0,0,0,1]}]}"#.into());
+        assert_eq!(import_custom_puzzles(&mut library, &unsolvable), 0);
+        assert_eq!(library.custom_puzzles().len(), 2);
+    }
+}
@@ -1,24 +1,41 @@
-mod transforms;
+mod custom;
+mod remote;
 
-use crate::graph::Valences;
+use crate::game::error::AppError;
+use crate::graph::{NodeId, Valences, solver};
+pub use crate::graph::{Symmetry, apply_symmetry};
 use bevy::prelude::*;
+pub use custom::{
+    CUSTOM_PUZZLES_FILE, CustomEntry, CustomPuzzle, JsonText, NextCustomPuzzle, PuzzleSource,
+    custom_keys, import_custom_puzzles, play_custom_puzzle,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use custom::{TomlFile, append_custom_puzzle, load_custom_puzzles};
 use rand::prelude::*;
-use rand::rng;
+pub use remote::{
+    CatalogPage, CatalogPuzzle, RemotePuzzleSource, receive_remote_puzzles, request_remote_puzzles,
+};
 use std::collections::HashMap;
-pub use transforms::{Symmetry, apply_symmetry};
 
 const PUZZLES_CSV: &str = include_str!("../../../assets/puzzles_symmetric.csv");
 
+/// Difficulty scores for the embedded puzzles, from `puzzle_tools difficulty`
+const DIFFICULTY_CSV: &str = include_str!("../../../assets/puzzle_difficulty.csv");
+
 /// Resource containing all base puzzles organized by complexity
 #[derive(Resource, Debug)]
 pub struct PuzzleLibrary {
     puzzles_by_complexity: HashMap<usize, Vec<BasePuzzle>>,
+    /// Player-made puzzles, in import order (also merged into their complexity)
+    custom: Vec<CustomEntry>,
 }
 
 /// A base puzzle before geometric transformations are applied
 #[derive(Debug, Clone)]
 struct BasePuzzle {
     valences: Valences,
+    /// `Difficulty` score from the difficulty CSV (puzzles added at runtime go without)
+    difficulty: Option<f32>,
 }
 
 /// Configuration for a single puzzle instance
@@ -32,14 +49,39 @@ pub struct PuzzleConfig {
 impl PuzzleLibrary {
     /// Load the puzzle library from embedded CSV data
     pub fn load() -> Result<Self, String> {
-        Self::from_csv(PUZZLES_CSV)
+        let mut library = Self::from_csv(PUZZLES_CSV)?;
+        library.add_difficulty(DIFFICULTY_CSV)?;
+        Ok(library)
+    }
+
+    /// No puzzles at all (stands in while the embedded library can't be loaded)
+    pub fn empty() -> Self {
+        PuzzleLibrary {
+            puzzles_by_complexity: HashMap::new(),
+            custom: Vec::new(),
+        }
+    }
+
+    /// Load the embedded library again, keeping the custom puzzles merged into this one
+    pub fn reload(&self) -> Result<Self, String> {
+        let mut library = Self::load()?;
+        let custom: Vec<_> = self
+            .custom
+            .iter()
+            .map(|entry| CustomPuzzle {
+                name: Some(entry.name.clone()),
+                valences: entry.valences,
+            })
+            .collect();
+        library.merge_custom(&custom)?;
+        Ok(library)
     }
 
     /// Parse CSV data into puzzle library
     ///
     /// CSV format: 9 valence values followed by complexity
     /// Example: 0,0,0,0,0,0,0,1,1,1
-    fn from_csv(csv_data: &str) -> Result<Self, String> {
+    pub(crate) fn from_csv(csv_data: &str) -> Result<Self, String> {
         let mut puzzles_by_complexity: HashMap<usize, Vec<BasePuzzle>> = HashMap::new();
 
         for (line_num, line) in csv_data.lines().enumerate() {
@@ -64,11 +106,17 @@ impl PuzzleLibrary {
 
             let complexity = values[9];
             let valences = Valences::new(values[0..9].to_vec());
+            valences
+                .is_feasible()
+                .map_err(|e| format!("Puzzle on line {} {}", line_num + 1, e))?;
 
             puzzles_by_complexity
                 .entry(complexity)
                 .or_default()
-                .push(BasePuzzle { valences });
+                .push(BasePuzzle {
+                    valences,
+                    difficulty: None,
+                });
         }
 
         if puzzles_by_complexity.is_empty() {
@@ -77,16 +125,159 @@ impl PuzzleLibrary {
 
         Ok(PuzzleLibrary {
             puzzles_by_complexity,
+            custom: Vec::new(),
         })
     }
 
+    /// Attach difficulty scores to the library's base puzzles
+    ///
+    /// CSV format: 9 valence values followed by the `Difficulty` score (see
+    /// `puzzle_tools difficulty`). Rows for puzzles the library doesn't have are ignored.
+    /// Example: 0,0,0,0,0,0,0,1,1,0.00
+    pub(crate) fn add_difficulty(&mut self, csv_data: &str) -> Result<(), String> {
+        let mut scores: HashMap<Vec<usize>, f32> = HashMap::new();
+        for (line_num, line) in csv_data.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() != 10 {
+                return Err(format!(
+                    "Line {} has {} values, expected 10 (9 valences + 1 difficulty)",
+                    line_num + 1,
+                    fields.len()
+                ));
+            }
+            let parse_error =
+                |e: &dyn std::fmt::Display| format!("Parse error on line {}: {}", line_num + 1, e);
+            let valences = fields[..9]
+                .iter()
+                .map(|s| s.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| parse_error(&e))?;
+            let score = fields[9].parse::<f32>().map_err(|e| parse_error(&e))?;
+            scores.insert(valences, score);
+        }
+
+        for base in self.puzzles_by_complexity.values_mut().flatten() {
+            let key: Vec<usize> = (0..9).map(|i| base.valences.get(NodeId(i))).collect();
+            if let Some(&score) = scores.get(&key) {
+                base.difficulty = Some(score);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a server catalog page's puzzles, skipping ones already in the library
+    ///
+    /// The server only lists the counts its CSV implies, so each new puzzle is checked
+    /// here: it must be feasible, and the solver must find the listed number of solutions,
+    /// which times its edges must be the page's complexity. Puzzles that fail are dropped.
+    /// Known puzzles are skipped before solving, so a page of the embedded set costs
+    /// nothing. Returns how many were added.
+    pub fn merge_catalog(&mut self, page: &CatalogPage) -> usize {
+        let mut added = 0;
+        for remote in &page.puzzles {
+            let valences = Valences::from_array(remote.valences);
+            let known = self
+                .puzzles_by_complexity
+                .get(&page.complexity)
+                .is_some_and(|puzzles| puzzles.iter().any(|known| known.valences == valences));
+            if known {
+                continue;
+            }
+            if let Err(err) = valences.is_feasible() {
+                warn!("Dropping catalog puzzle {}: it {}", valences, err);
+                continue;
+            }
+            let solutions = solver::count_solutions(&valences);
+            if solutions != remote.listed_solutions
+                || solutions * (valences.total() / 2) != page.complexity
+            {
+                warn!(
+                    "Dropping catalog puzzle {}: {} solutions, listed as {} at complexity {}",
+                    valences, solutions, remote.listed_solutions, page.complexity
+                );
+                continue;
+            }
+
+            self.puzzles_by_complexity
+                .entry(page.complexity)
+                .or_default()
+                .push(BasePuzzle {
+                    valences,
+                    difficulty: None,
+                });
+            added += 1;
+        }
+        added
+    }
+
+    /// Add player-made puzzles, skipping ones already imported
+    ///
+    /// Every puzzle must be feasible and solvable; its solution count (from the solver) sets its
+    /// complexity. Unnamed puzzles are numbered. Returns how many were added.
+    pub fn merge_custom(&mut self, puzzles: &[CustomPuzzle]) -> Result<usize, String> {
+        let mut incoming = Vec::with_capacity(puzzles.len());
+        for puzzle in puzzles {
+            let valences = Valences::from_array(puzzle.valences);
+            valences
+                .is_feasible()
+                .map_err(|e| format!("Custom puzzle {} {}", valences, e))?;
+            let total_solutions = solver::count_solutions(&valences);
+            if total_solutions == 0 {
+                return Err(format!("Custom puzzle {} has no solutions", valences));
+            }
+            incoming.push((puzzle, total_solutions));
+        }
+
+        let mut added = 0;
+        for (puzzle, total_solutions) in incoming {
+            if self
+                .custom
+                .iter()
+                .any(|known| known.valences == puzzle.valences)
+            {
+                continue;
+            }
+            let valences = Valences::from_array(puzzle.valences);
+            let complexity = total_solutions * (valences.total() / 2);
+            let puzzles = self.puzzles_by_complexity.entry(complexity).or_default();
+            if !puzzles.iter().any(|known| known.valences == valences) {
+                puzzles.push(BasePuzzle {
+                    valences,
+                    difficulty: None,
+                });
+            }
+
+            let name = puzzle
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("Custom {}", self.custom.len() + 1));
+            self.custom.push(CustomEntry {
+                name,
+                valences: puzzle.valences,
+                total_solutions,
+            });
+            added += 1;
+        }
+
+        Ok(added)
+    }
+
+    /// Player-made puzzles, in import order
+    pub fn custom_puzzles(&self) -> &[CustomEntry] {
+        &self.custom
+    }
+
     /// Get a random puzzle of given complexity with random geometric transform
-    pub fn random_puzzle(&self, complexity: usize) -> Option<PuzzleConfig> {
+    pub fn random_puzzle(&self, complexity: usize, rng: &mut impl Rng) -> Option<PuzzleConfig> {
         let base_puzzles = self.puzzles_by_complexity.get(&complexity)?;
-        let base = base_puzzles.choose(&mut rng())?;
+        let base = base_puzzles.choose(rng)?;
 
         // Apply random symmetric transform
-        let transform = Symmetry::random();
+        let transform = Symmetry::random(rng);
         let valences = apply_symmetry(&base.valences, transform);
         let total_solutions = self.solution_count_for_puzzle(&valences, complexity);
 
@@ -104,6 +295,7 @@ impl PuzzleLibrary {
         &self,
         complexity: usize,
         tried_indices: &[usize],
+        rng: &mut impl Rng,
     ) -> Option<(PuzzleConfig, usize)> {
         let base_puzzles = self.puzzles_by_complexity.get(&complexity)?;
 
@@ -119,10 +311,10 @@ impl PuzzleLibrary {
         }
 
         // Pick a random untried puzzle
-        let (puzzle_idx, base) = untried.choose(&mut rng())?;
+        let (puzzle_idx, base) = untried.choose(rng)?;
 
         // Apply random transform
-        let transform = Symmetry::random();
+        let transform = Symmetry::random(rng);
         let valences = apply_symmetry(&base.valences, transform);
         let total_solutions = self.solution_count_for_puzzle(&valences, complexity);
 
@@ -135,6 +327,67 @@ impl PuzzleLibrary {
         Some((config, *puzzle_idx))
     }
 
+    /// Like `untried_puzzle`, but from the easier half of the untried puzzles by
+    /// difficulty score (puzzles without one count as the hardest)
+    pub fn gentle_puzzle(
+        &self,
+        complexity: usize,
+        tried_indices: &[usize],
+        rng: &mut impl Rng,
+    ) -> Option<(PuzzleConfig, usize)> {
+        let base_puzzles = self.puzzles_by_complexity.get(&complexity)?;
+
+        let mut untried: Vec<(usize, f32)> = base_puzzles
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !tried_indices.contains(idx))
+            .map(|(idx, base)| (idx, base.difficulty.unwrap_or(f32::INFINITY)))
+            .collect();
+        untried.sort_by(|a, b| a.1.total_cmp(&b.1));
+        untried.truncate(untried.len().div_ceil(2));
+
+        let &(index, _) = untried.choose(rng)?;
+        Some((self.puzzle_at(complexity, index, rng)?, index))
+    }
+
+    /// A specific base puzzle, with a random geometric transform
+    pub fn puzzle_at(
+        &self,
+        complexity: usize,
+        index: usize,
+        rng: &mut impl Rng,
+    ) -> Option<PuzzleConfig> {
+        let base = self.puzzles_by_complexity.get(&complexity)?.get(index)?;
+        let valences = apply_symmetry(&base.valences, Symmetry::random(rng));
+        let total_solutions = self.solution_count_for_puzzle(&valences, complexity);
+
+        Some(PuzzleConfig {
+            valences,
+            complexity,
+            total_solutions,
+        })
+    }
+
+    /// Index of the base puzzle that `valences` is a transform of
+    pub fn base_index(&self, complexity: usize, valences: &Valences) -> Option<usize> {
+        self.puzzles_by_complexity
+            .get(&complexity)?
+            .iter()
+            .position(|base| {
+                Symmetry::all()
+                    .into_iter()
+                    .any(|symmetry| apply_symmetry(&base.valences, symmetry) == *valences)
+            })
+    }
+
+    /// Difficulty score of a base puzzle, if the library has one for it
+    pub fn difficulty(&self, complexity: usize, index: usize) -> Option<f32> {
+        self.puzzles_by_complexity
+            .get(&complexity)?
+            .get(index)?
+            .difficulty
+    }
+
     /// Get the number of base puzzles for a given complexity
     pub fn puzzle_count(&self, complexity: usize) -> usize {
         self.puzzles_by_complexity
@@ -150,6 +403,11 @@ impl PuzzleLibrary {
         complexities
     }
 
+    /// Highest complexity with any puzzles (None for an empty library)
+    pub fn max_complexity(&self) -> Option<usize> {
+        self.puzzles_by_complexity.keys().copied().max()
+    }
+
     /// Get the total number of base puzzles across all complexities
     pub fn total_puzzle_count(&self) -> usize {
         self.puzzles_by_complexity.values().map(|v| v.len()).sum()
@@ -163,45 +421,56 @@ impl PuzzleLibrary {
 
 /// System to load and initialize the puzzle library
 /// This should run early in Startup schedule, before setup_puzzle
+///
+/// A library that fails to load is replaced by an empty one and reported as an
+/// `AppError`, so the rest of startup still runs.
 pub fn setup_puzzle_library(mut commands: Commands) {
     match PuzzleLibrary::load() {
         Ok(library) => {
-            let complexities = library.available_complexities();
-            let total_puzzles = library.total_puzzle_count();
-
-            info!("✓ Puzzle library loaded successfully:");
-            info!("  - {} unique complexity levels", complexities.len());
-            info!("  - {} total base puzzles", total_puzzles);
-            info!(
-                "  - Complexity range: {} to {}",
-                complexities.first().unwrap_or(&0),
-                complexities.last().unwrap_or(&0)
-            );
-
-            // Log some details about puzzle distribution
-            for &complexity in complexities.iter().take(5) {
-                let count = library.puzzle_count(complexity);
-                info!("  - Complexity {}: {} base puzzles", complexity, count);
-            }
-            if complexities.len() > 5 {
-                info!(
-                    "  - ... and {} more complexity levels",
-                    complexities.len() - 5
-                );
-            }
-
+            log_library(&library);
             commands.insert_resource(library);
         }
         Err(e) => {
-            error!("Failed to load puzzle library: {}", e);
-            panic!("Cannot continue without puzzle data");
+            commands.insert_resource(AppError::new(format!(
+                "Failed to load puzzle library: {}",
+                e
+            )));
+            commands.insert_resource(PuzzleLibrary::empty());
         }
     }
 }
 
+/// Log a summary of a freshly loaded library
+pub fn log_library(library: &PuzzleLibrary) {
+    let complexities = library.available_complexities();
+    let total_puzzles = library.total_puzzle_count();
+
+    info!("✓ Puzzle library loaded successfully:");
+    info!("  - {} unique complexity levels", complexities.len());
+    info!("  - {} total base puzzles", total_puzzles);
+    info!(
+        "  - Complexity range: {} to {}",
+        complexities.first().unwrap_or(&0),
+        complexities.last().unwrap_or(&0)
+    );
+
+    // Log some details about puzzle distribution
+    for &complexity in complexities.iter().take(5) {
+        let count = library.puzzle_count(complexity);
+        info!("  - Complexity {}: {} base puzzles", complexity, count);
+    }
+    if complexities.len() > 5 {
+        info!(
+            "  - ... and {} more complexity levels",
+            complexities.len() - 5
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::rng::GameRng;
 
     const TEST_CSV: &str = "\
 0,0,0,0,0,0,0,1,1,1
@@ -219,45 +488,122 @@ mod tests {
         assert_eq!(library.total_puzzle_count(), 5);
     }
 
+    #[test]
+    fn test_difficulty_scores_attach_by_valences() {
+        let mut library = PuzzleLibrary::from_csv(TEST_CSV).unwrap();
+        assert_eq!(library.difficulty(1, 0), None);
+
+        library
+            .add_difficulty("0,0,0,0,0,0,0,1,1,0.25\n9,9,9,9,9,9,9,9,8,1.00")
+            .unwrap();
+        assert_eq!(library.difficulty(1, 0), Some(0.25));
+        assert!(library.add_difficulty("0,0,0,0,0,0,0,1,1,hard").is_err());
+        assert!(library.add_difficulty("0,0,0,0,0,0,0,1,1,1,0.25").is_err());
+    }
+
+    #[test]
+    fn test_embedded_puzzles_all_have_difficulty() {
+        let library = PuzzleLibrary::load().unwrap();
+        for (&complexity, puzzles) in &library.puzzles_by_complexity {
+            for index in 0..puzzles.len() {
+                assert!(library.difficulty(complexity, index).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_gentle_puzzle_comes_from_the_easier_half() {
+        let mut library = PuzzleLibrary::from_csv(
+            "0,0,0,0,0,0,0,1,1,1\n0,0,0,0,0,1,0,1,0,1\n0,0,0,0,1,0,0,0,1,1",
+        )
+        .unwrap();
+        library
+            .add_difficulty("0,0,0,0,0,0,0,1,1,3.0\n0,0,0,0,0,1,0,1,0,0.5\n0,0,0,0,1,0,0,0,1,2.0")
+            .unwrap();
+        let mut rng = GameRng::from_seed(7);
+
+        for _ in 0..10 {
+            let (_, index) = library.gentle_puzzle(1, &[], &mut rng).unwrap();
+            assert!(index == 1 || index == 2, "index {} is the hardest", index);
+        }
+        // The last untried one, however hard
+        let (_, index) = library.gentle_puzzle(1, &[1, 2], &mut rng).unwrap();
+        assert_eq!(index, 0);
+        assert!(library.gentle_puzzle(1, &[0, 1, 2], &mut rng).is_none());
+    }
+
     #[test]
     fn test_available_complexities() {
         let library = PuzzleLibrary::from_csv(TEST_CSV).unwrap();
         let complexities = library.available_complexities();
 
         assert_eq!(complexities, vec![1, 2]);
+        assert_eq!(library.max_complexity(), Some(2));
+        assert_eq!(PuzzleLibrary::empty().max_complexity(), None);
     }
 
     #[test]
     fn test_random_puzzle() {
         let library = PuzzleLibrary::from_csv(TEST_CSV).unwrap();
+        let mut rng = GameRng::from_seed(7);
 
         // Should be able to get puzzles for complexity 1 and 2
-        assert!(library.random_puzzle(1).is_some());
-        assert!(library.random_puzzle(2).is_some());
+        assert!(library.random_puzzle(1, &mut rng).is_some());
+        assert!(library.random_puzzle(2, &mut rng).is_some());
 
         // Should return None for non-existent complexity
-        assert!(library.random_puzzle(999).is_none());
+        assert!(library.random_puzzle(999, &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_random_puzzle_is_reproducible() {
+        let library = PuzzleLibrary::from_csv(TEST_CSV).unwrap();
+        let mut a = GameRng::from_seed(1234);
+        let mut b = GameRng::from_seed(1234);
+
+        for _ in 0..10 {
+            let puzzle_a = library.random_puzzle(2, &mut a).unwrap();
+            let puzzle_b = library.random_puzzle(2, &mut b).unwrap();
+            assert_eq!(puzzle_a.valences, puzzle_b.valences);
+        }
+    }
+
+    #[test]
+    fn test_base_index_sees_through_transforms() {
+        let library = PuzzleLibrary::from_csv(TEST_CSV).unwrap();
+        let mut rng = GameRng::from_seed(7);
+
+        for index in 0..library.puzzle_count(1) {
+            let config = library.puzzle_at(1, index, &mut rng).unwrap();
+            assert_eq!(library.base_index(1, &config.valences), Some(index));
+        }
+        assert!(library.puzzle_at(1, 3, &mut rng).is_none());
     }
 
     #[test]
     fn test_untried_puzzle() {
         let library = PuzzleLibrary::from_csv(TEST_CSV).unwrap();
+        let mut rng = GameRng::from_seed(7);
 
         // First call should succeed
-        let (config1, idx1) = library.untried_puzzle(1, &[]).unwrap();
+        let (config1, idx1) = library.untried_puzzle(1, &[], &mut rng).unwrap();
         assert_eq!(config1.complexity, 1);
 
         // Can get another one
-        let (_, idx2) = library.untried_puzzle(1, &[idx1]).unwrap();
+        let (_, idx2) = library.untried_puzzle(1, &[idx1], &mut rng).unwrap();
         assert_ne!(idx1, idx2);
 
         // Can get a third
-        let (_, idx3) = library.untried_puzzle(1, &[idx1, idx2]).unwrap();
+        let (_, idx3) = library.untried_puzzle(1, &[idx1, idx2], &mut rng).unwrap();
         assert_ne!(idx3, idx1);
         assert_ne!(idx3, idx2);
 
         // After marking all 3 as tried, should return None
-        assert!(library.untried_puzzle(1, &[idx1, idx2, idx3]).is_none());
+        assert!(
+            library
+                .untried_puzzle(1, &[idx1, idx2, idx3], &mut rng)
+                .is_none()
+        );
     }
 
     #[test]
@@ -269,6 +615,69 @@ mod tests {
         // Non-numeric values
         let bad_csv2 = "0,0,0,x,0,0,0,1,1,1";
         assert!(PuzzleLibrary::from_csv(bad_csv2).is_err());
+
+        // Odd valence sum
+        let bad_csv3 = "0,0,0,0,0,0,0,1,2,1";
+        assert_eq!(
+            PuzzleLibrary::from_csv(bad_csv3).unwrap_err(),
+            "Puzzle on line 1 has an odd valence sum (3)"
+        );
+    }
+
+    #[test]
+    fn test_complexity_matches_solver() {
+        // complexity = solutions × edges; spot-check the embedded library with the solver
+        for line in PUZZLES_CSV.lines().step_by(151) {
+            let values: Vec<usize> = line.split(',').map(|s| s.trim().parse().unwrap()).collect();
+            let valences = Valences::new(values[0..9].to_vec());
+            let edges = valences.total() / 2;
+
+            assert_eq!(
+                crate::graph::solver::count_solutions(&valences) * edges,
+                values[9],
+                "solution count disagrees for {line}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_catalog_checks_solution_counts() {
+        let mut library = PuzzleLibrary::from_csv(TEST_CSV).unwrap();
+        let page = |valences, listed_solutions| CatalogPage {
+            complexity: 2,
+            puzzles: vec![CatalogPuzzle {
+                valences,
+                listed_solutions,
+            }],
+        };
+        let diagonal = [1, 0, 0, 0, 2, 0, 0, 0, 1];
+
+        // The solver finds one solution, not two
+        assert_eq!(library.merge_catalog(&page(diagonal, 2)), 0);
+        // An odd valence sum can't be drawn, whatever it lists
+        assert_eq!(
+            library.merge_catalog(&page([1, 0, 0, 0, 2, 0, 0, 0, 0], 1)),
+            0
+        );
+        assert_eq!(library.puzzle_count(2), 2);
+
+        assert_eq!(library.merge_catalog(&page(diagonal, 1)), 1);
+        assert_eq!(library.merge_catalog(&page(diagonal, 1)), 0);
+        assert_eq!(library.puzzle_count(2), 3);
+    }
+
+    #[test]
+    fn test_reload_keeps_custom_puzzles() {
+        let mut library = PuzzleLibrary::empty();
+        let triangle = CustomPuzzle {
+            name: Some("Triangle".to_string()),
+            valences: [2, 2, 0, 2, 0, 0, 0, 0, 0],
+        };
+        assert_eq!(library.merge_custom(&[triangle]), Ok(1));
+
+        let reloaded = library.reload().unwrap();
+        assert!(reloaded.total_puzzle_count() > library.total_puzzle_count());
+        assert_eq!(reloaded.custom_puzzles()[0].name, "Triangle");
     }
 
     #[test]
@@ -0,0 +1,169 @@
+// game/puzzle/remote.rs - puzzles fetched from the server catalog at runtime
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::PuzzleLibrary;
+use crate::game::progression::ProgressionTracker;
+
+/// One page of `GET /api/puzzles?complexity=N`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogPage {
+    pub complexity: usize,
+    pub puzzles: Vec<CatalogPuzzle>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogPuzzle {
+    pub valences: [usize; 9],
+    /// Solution count from the server's puzzle set (its complexity ÷ edges); checked
+    /// against the solver before the puzzle joins the library
+    pub listed_solutions: usize,
+}
+
+/// Resource: Fetches catalog pages from the game server and hands them to the library
+///
+/// The embedded CSV stays the baseline; remote pages only add puzzles to it, so play
+/// carries on unchanged when the server is unreachable.
+#[derive(Resource, Debug, Clone)]
+pub struct RemotePuzzleSource {
+    /// Prefix for `/api/puzzles` ("" for the page's own origin)
+    base_url: String,
+    requested: HashSet<usize>,
+    /// Finished fetches, filled from outside the ECS
+    inbox: Arc<Mutex<Vec<Result<CatalogPage, String>>>>,
+}
+
+impl RemotePuzzleSource {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            requested: HashSet::new(),
+            inbox: Arc::default(),
+        }
+    }
+
+    /// Fetch a complexity's catalog page, once per session
+    pub fn request(&mut self, complexity: usize) {
+        if !self.requested.insert(complexity) {
+            return;
+        }
+        let url = format!("{}/api/puzzles?complexity={}", self.base_url, complexity);
+        self.spawn_fetch(url);
+    }
+
+    /// Queue a response body for the next `receive_remote_puzzles`
+    pub fn deliver(&self, body: &str) {
+        let page = serde_json::from_str::<CatalogPage>(body).map_err(|e| e.to_string());
+        self.inbox.lock().unwrap().push(page);
+    }
+
+    fn take_delivered(&self) -> Vec<Result<CatalogPage, String>> {
+        std::mem::take(&mut *self.inbox.lock().unwrap())
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    fn spawn_fetch(&self, url: String) {
+        let source = self.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            match fetch_text(&url).await {
+                Ok(body) => source.deliver(&body),
+                Err(err) => source.inbox.lock().unwrap().push(Err(err)),
+            }
+        });
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    fn spawn_fetch(&self, url: String) {
+        debug!(
+            "Skipping {}: remote puzzles are only fetched in the wasm build",
+            url
+        );
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+async fn fetch_text(url: &str) -> Result<String, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no window")?;
+    let response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|err| format!("{url}: {err:?}"))?;
+    let response: web_sys::Response = response
+        .dyn_into()
+        .map_err(|_| format!("{url}: not a Response"))?;
+    if !response.ok() {
+        return Err(format!("{url}: HTTP {}", response.status()));
+    }
+
+    let text = response.text().map_err(|err| format!("{url}: {err:?}"))?;
+    JsFuture::from(text)
+        .await
+        .map_err(|err| format!("{url}: {err:?}"))?
+        .as_string()
+        .ok_or_else(|| format!("{url}: body isn't text"))
+}
+
+/// System: Ask for the current and next level's puzzles whenever the level changes
+pub fn request_remote_puzzles(
+    tracker: Res<ProgressionTracker>,
+    mut source: ResMut<RemotePuzzleSource>,
+) {
+    if !tracker.is_changed() {
+        return;
+    }
+
+    source.request(tracker.current_complexity());
+    let mut next = ProgressionTracker::at_level(tracker.current_level);
+    next.advance_level();
+    source.request(next.current_complexity());
+}
+
+/// System: Merge finished fetches into the puzzle library
+pub fn receive_remote_puzzles(source: Res<RemotePuzzleSource>, mut library: ResMut<PuzzleLibrary>) {
+    for page in source.take_delivered() {
+        match page.map(|page| library.merge_catalog(&page)) {
+            Ok(0) => {}
+            Ok(added) => info!("🌐 Added {} remote puzzles", added),
+            Err(err) => warn!("Remote puzzles unavailable: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivered_pages_reach_library() {
+        let mut library = PuzzleLibrary::from_csv("0,0,0,0,0,0,0,1,1,1").unwrap();
+        let source = RemotePuzzleSource::new("");
+
+        source.deliver(
+            r#"{"complexity":1,"puzzles":[
+                {"valences":[0,0,0,0,0,0,0,1,1],"listed_solutions":1},
+                {"valences":[1,1,0,0,0,0,0,0,0],"listed_solutions":1}
+            ]}"#,
+        );
+        source.deliver("not json");
+
+        let results: Vec<_> = source
+            .take_delivered()
+            .into_iter()
+            .map(|page| page.map(|page| library.merge_catalog(&page)))
+            .collect();
+
+        // The known puzzle is skipped, the new one added, the bad body reported
+        assert_eq!(results[0], Ok(1));
+        assert!(results[1].is_err());
+        assert_eq!(library.puzzle_count(1), 2);
+        assert!(source.take_delivered().is_empty());
+    }
+}
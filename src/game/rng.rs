@@ -0,0 +1,74 @@
+// game/rng.rs
+
+use bevy::prelude::*;
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+
+/// Environment variable that pins the session seed (for replays and bug repros)
+const SEED_ENV_VAR: &str = "VALENCE_SEED";
+
+/// Resource: The one source of gameplay randomness
+///
+/// Puzzle selection, symmetry and particle bursts all draw from this, so a session
+/// started from the same seed plays out the same way.
+#[derive(Resource, Debug, Clone)]
+pub struct GameRng {
+    rng: StdRng,
+    seed: u64,
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+        }
+    }
+
+    /// Seed the session was started with (log it to reproduce a run)
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Default for GameRng {
+    /// Seed from `VALENCE_SEED` if set, otherwise from entropy
+    fn default() -> Self {
+        let pinned = std::env::var(SEED_ENV_VAR)
+            .ok()
+            .and_then(|value| value.trim().parse().ok());
+        let seed = pinned.unwrap_or_else(|| rand::rng().random());
+
+        info!("🎲 Set {}={} to replay this session", SEED_ENV_VAR, seed);
+        Self::from_seed(seed)
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.rng.fill_bytes(dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = GameRng::from_seed(42);
+        let mut b = GameRng::from_seed(42);
+
+        let seq_a: Vec<u32> = (0..16).map(|_| a.random_range(0..100)).collect();
+        let seq_b: Vec<u32> = (0..16).map(|_| b.random_range(0..100)).collect();
+        assert_eq!(seq_a, seq_b);
+        assert_eq!(a.seed(), 42);
+    }
+}
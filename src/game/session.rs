@@ -1,8 +1,8 @@
 // game/session.rs
 
-use crate::graph::*;
-use bevy::prelude::Resource;
-use std::collections::HashSet;
+use crate::graph::{solver::enumerate_solutions, *};
+use bevy::prelude::{Local, Message, MessageWriter, Res, Resource};
+use std::{collections::HashSet, sync::OnceLock};
 
 /// A game session - manages one puzzle instance
 #[derive(Debug, Clone, Resource)]
@@ -13,6 +13,8 @@ pub struct PuzzleSession {
     found_solutions: HashSet<Solution>,
     /// Total number of solutions for this puzzle (if known)
     total_solutions: usize,
+    /// Every solution to this puzzle, from the solver the first time it's asked for
+    all_solutions: OnceLock<HashSet<Solution>>,
 }
 
 impl PuzzleSession {
@@ -22,6 +24,7 @@ impl PuzzleSession {
             state: GameState::new(puzzle_valences),
             found_solutions: HashSet::new(),
             total_solutions,
+            all_solutions: OnceLock::new(),
         }
     }
 
@@ -52,6 +55,16 @@ impl PuzzleSession {
         self.state.edges()
     }
 
+    /// Edges left to draw before the current attempt completes
+    pub fn remaining_edges(&self) -> usize {
+        self.state.total_remaining_valence() / 2
+    }
+
+    /// Is only one edge left to draw?
+    pub fn is_last_edge(&self) -> bool {
+        self.state.is_last_edge()
+    }
+
     /// Which nodes should flee from user input?
     pub fn nodes_to_flee(&self) -> Vec<NodeId> {
         self.state.nodes_that_should_flee()
@@ -91,11 +104,39 @@ impl PuzzleSession {
         &self.found_solutions
     }
 
+    /// Every solution the puzzle has, as the solver enumerates them (once, then kept)
+    pub fn all_solutions(&self) -> &HashSet<Solution> {
+        self.all_solutions
+            .get_or_init(|| enumerate_solutions(self.puzzle_valences()))
+    }
+
+    /// Are there undone moves that `redo` can make again?
+    pub fn can_redo(&self) -> bool {
+        self.state.can_redo()
+    }
+
+    /// Check the game state's bookkeeping (see `GameState::verify_invariants`)
+    pub fn verify_invariants(&self) -> Result<(), String> {
+        self.state.verify_invariants()
+    }
+
     // === Mutation Methods (for handling user input) ===
 
     /// Try to add a node to the current trail
     pub fn add_node(&mut self, node: NodeId) -> SessionResult {
-        match self.state.add_node(node) {
+        let result = self.state.add_node(node);
+        self.record(result)
+    }
+
+    /// Make the most recently undone move again (None when there's nothing to redo)
+    pub fn redo(&mut self) -> Option<SessionResult> {
+        let result = self.state.redo()?;
+        Some(self.record(result))
+    }
+
+    /// Turn a move's outcome into a session result, noting any solution it completed
+    fn record(&mut self, result: MoveResult) -> SessionResult {
+        match result {
             MoveResult::PuzzleComplete => {
                 let solution = Solution::from_edge_set(self.state.edges());
                 let is_new = !self.is_solution_known(&solution);
@@ -122,11 +163,60 @@ impl PuzzleSession {
         self.state.reset();
     }
 
+    /// Replace the current attempt with another player's trail (shared board)
+    ///
+    /// The trail is replayed under the same rules as local moves and rejected as a whole
+    /// if any step is invalid. A trail that completes the puzzle is recorded and reset,
+    /// as a local completion would be.
+    pub fn replace_trail(&mut self, trail: &[NodeId]) -> Result<(), ValidationError> {
+        let mut state = GameState::new(self.puzzle_valences().clone());
+        for &node in trail {
+            if let MoveResult::Invalid(err) = state.add_node(node) {
+                return Err(err);
+            }
+        }
+
+        if state.is_complete() {
+            self.found_solutions
+                .insert(Solution::from_edge_set(state.edges()));
+            state.reset();
+        }
+        self.state = state;
+        Ok(())
+    }
+
+    /// Record solutions found elsewhere (another player on a shared board)
+    ///
+    /// Only edge sets that really solve this puzzle are kept. Returns how many were new.
+    /// The puzzle's solutions are enumerated once and kept, so repeat merges of a shared
+    /// board only pay for the lookups.
+    pub fn merge_solutions(&mut self, solutions: impl IntoIterator<Item = Solution>) -> usize {
+        let unknown: Vec<Solution> = solutions
+            .into_iter()
+            .filter(|solution| !self.is_solution_known(solution))
+            .collect();
+        if unknown.is_empty() {
+            return 0;
+        }
+
+        let valid = self
+            .all_solutions
+            .get_or_init(|| enumerate_solutions(self.state.puzzle_valences()));
+        let before = self.found_solutions.len();
+        self.found_solutions.extend(
+            unknown
+                .into_iter()
+                .filter(|solution| valid.contains(solution)),
+        );
+        self.found_solutions.len() - before
+    }
+
     /// Start a completely new puzzle (clears found solutions)
     pub fn new_puzzle(&mut self, puzzle_valences: Valences, total_solutions: usize) {
         self.state = GameState::new(puzzle_valences);
         self.found_solutions.clear();
         self.total_solutions = total_solutions;
+        self.all_solutions = OnceLock::new();
     }
 }
 
@@ -167,6 +257,32 @@ impl ProgressInfo {
     }
 }
 
+/// Message: A different puzzle took over the board (the next level, a skip, a shared
+/// code, a restored game...), so the board's nodes should be rebuilt for it
+#[derive(Message, Debug, Clone)]
+pub struct NewPuzzle {
+    pub valences: Valences,
+}
+
+/// System: Announce a `NewPuzzle` whenever the session's puzzle changes, whichever system
+/// swapped it in (the first puzzle is the one the board was set up with)
+pub fn announce_new_puzzle(
+    session: Res<PuzzleSession>,
+    mut shown: Local<Option<Valences>>,
+    mut puzzles: MessageWriter<NewPuzzle>,
+) {
+    let valences = session.puzzle_valences();
+    if shown.as_ref() == Some(valences) {
+        return;
+    }
+    if shown.is_some() {
+        puzzles.write(NewPuzzle {
+            valences: valences.clone(),
+        });
+    }
+    *shown = Some(valences.clone());
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,6 +320,38 @@ mod tests {
         assert_eq!(session.found_solutions().len(), 1); // Still only 1 unique solution
     }
 
+    #[test]
+    fn test_remaining_edges_count_down() {
+        let valences = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        let mut session = PuzzleSession::new(valences, 1);
+        assert_eq!(session.remaining_edges(), 3);
+
+        session.add_node(NodeId(0));
+        assert_eq!(session.remaining_edges(), 3);
+        session.add_node(NodeId(1));
+        assert_eq!(session.remaining_edges(), 2);
+        session.add_node(NodeId(3));
+        session.add_node(NodeId(0));
+        assert_eq!(session.remaining_edges(), 0);
+    }
+
+    #[test]
+    fn test_redo_completes_like_a_move() {
+        let valences = Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]);
+        let mut session = PuzzleSession::new(valences, 1);
+
+        session.add_node(NodeId(0));
+        session.add_node(NodeId(1));
+        session.undo();
+        assert!(session.can_redo());
+
+        assert!(matches!(
+            session.redo(),
+            Some(SessionResult::Complete { is_new: false, .. })
+        ));
+        assert_eq!(session.found_solutions().len(), 1);
+    }
+
     #[test]
     fn test_progress_info() {
         let valences = Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]);
@@ -223,6 +371,37 @@ mod tests {
         assert!(progress.is_complete());
     }
 
+    #[test]
+    fn test_shared_board_merges() {
+        let valences = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        let mut session = PuzzleSession::new(valences.clone(), 1);
+
+        // A partial trail replaces the current attempt; an illegal one changes nothing
+        session.replace_trail(&[NodeId(0), NodeId(1)]).unwrap();
+        assert_eq!(session.current_trail(), &[NodeId(0), NodeId(1)]);
+        assert!(session.replace_trail(&[NodeId(0), NodeId(8)]).is_err());
+        assert_eq!(session.edges().len(), 1);
+
+        // Only a real solution is merged, and only once
+        let mut bogus = Solution::new();
+        bogus.add_edge(Edge::new(NodeId(0), NodeId(1)));
+        let mut other = PuzzleSession::new(valences, 1);
+        for node in [0, 1, 3, 0] {
+            other.add_node(NodeId(node));
+        }
+        let found = other.found_solutions().clone();
+
+        assert_eq!(session.all_solutions(), &found);
+        assert_eq!(session.merge_solutions([bogus]), 0);
+        assert_eq!(session.merge_solutions(found.clone()), 1);
+        assert_eq!(session.merge_solutions(found.clone()), 0);
+        assert!(session.progress().is_complete());
+
+        // The kept solutions go with the puzzle they were enumerated for
+        session.new_puzzle(Valences::new(vec![0, 2, 2, 0, 2, 0, 0, 0, 0]), 1);
+        assert_eq!(session.merge_solutions(found), 0);
+    }
+
     #[test]
     fn test_new_puzzle_clears_solutions() {
         let valences1 = Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]);
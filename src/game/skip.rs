@@ -0,0 +1,224 @@
+// game/skip.rs - skip or shuffle the level's puzzle; skipped puzzles come back once the
+// level's tour has served the rest
+
+use std::collections::VecDeque;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::Rng;
+
+use super::{
+    director::Director,
+    progression::ProgressionTracker,
+    puzzle::{PuzzleConfig, PuzzleLibrary},
+    rng::GameRng,
+    session::PuzzleSession,
+    tour::LevelTour,
+};
+
+/// Message asking for another puzzle on the current level
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleChange {
+    /// Set this puzzle aside for later and move to one the level's tour hasn't served
+    Skip,
+    /// Any puzzle from the level, in a random orientation
+    Shuffle,
+}
+
+/// Resource: The base puzzles skipped on the current level
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct LevelPuzzles {
+    /// Level the skips belong to
+    level: usize,
+    /// Skipped base puzzles (`PuzzleLibrary` indices), longest-skipped first
+    skipped: VecDeque<usize>,
+}
+
+impl LevelPuzzles {
+    /// Start over when the level changes
+    fn follow(&mut self, level: usize) {
+        if self.level != level {
+            *self = Self { level, ..default() };
+        }
+    }
+
+    /// Skipped puzzles waiting to come back on `level`
+    pub fn skipped_count(&self, level: usize) -> usize {
+        if self.level == level {
+            self.skipped.len()
+        } else {
+            0
+        }
+    }
+
+    /// Set `current` aside and pick a puzzle the level's tour hasn't served yet (one of
+    /// the easier ones when `gentle`)
+    ///
+    /// Once the tour has served them all, the longest-skipped puzzle comes back instead.
+    pub fn skip(
+        &mut self,
+        current: Option<usize>,
+        tried: &[usize],
+        library: &PuzzleLibrary,
+        complexity: usize,
+        gentle: bool,
+        rng: &mut impl Rng,
+    ) -> Option<PuzzleConfig> {
+        if let Some(current) = current
+            && !self.skipped.contains(&current)
+        {
+            self.skipped.push_back(current);
+        }
+
+        let served: Vec<_> = tried.iter().copied().chain(current).collect();
+        let untried = if gentle {
+            library.gentle_puzzle(complexity, &served, rng)
+        } else {
+            library.untried_puzzle(complexity, &served, rng)
+        };
+        if let Some((config, _)) = untried {
+            return Some(config);
+        }
+
+        let position = self
+            .skipped
+            .iter()
+            .position(|&index| Some(index) != current)?;
+        let index = self.skipped.remove(position)?;
+        library.puzzle_at(complexity, index, rng)
+    }
+
+    /// Any puzzle from the level (maybe the same one, turned); a skipped one it lands on
+    /// is back
+    pub fn shuffle(
+        &mut self,
+        library: &PuzzleLibrary,
+        complexity: usize,
+        rng: &mut impl Rng,
+    ) -> Option<PuzzleConfig> {
+        let config = library.random_puzzle(complexity, rng)?;
+        if let Some(index) = library.base_index(complexity, &config.valences) {
+            self.skipped.retain(|&skipped| skipped != index);
+        }
+        Some(config)
+    }
+}
+
+/// System: N skips the puzzle; Shift+N shuffles in another from the level
+pub fn skip_keys(keys: Res<ButtonInput<KeyCode>>, mut changes: MessageWriter<PuzzleChange>) {
+    if !keys.just_pressed(KeyCode::KeyN) {
+        return;
+    }
+    if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        changes.write(PuzzleChange::Shuffle);
+    } else {
+        changes.write(PuzzleChange::Skip);
+    }
+}
+
+/// What a skip steers by: the level's tour, and whether the player is stuck on the level
+#[derive(SystemParam)]
+pub struct SkipGuide<'w> {
+    tour: Res<'w, LevelTour>,
+    director: Res<'w, Director>,
+}
+
+/// System: Put up the puzzle a skip or shuffle picks
+///
+/// The level stays the same; solutions found on the old puzzle don't carry over.
+pub fn change_puzzle(
+    mut changes: MessageReader<PuzzleChange>,
+    library: Res<PuzzleLibrary>,
+    tracker: Res<ProgressionTracker>,
+    mut rng: ResMut<GameRng>,
+    mut session: ResMut<PuzzleSession>,
+    mut puzzles: ResMut<LevelPuzzles>,
+    guide: SkipGuide,
+) {
+    for change in changes.read() {
+        puzzles.follow(tracker.current_level);
+        let complexity = tracker.current_complexity();
+        let current = library.base_index(complexity, session.puzzle_valences());
+
+        let tried = guide.tour.tried(tracker.current_level);
+        let gentle = guide.director.stuck_on(tracker.current_level);
+
+        let next = match change {
+            PuzzleChange::Skip => {
+                puzzles.skip(current, tried, &library, complexity, gentle, &mut *rng)
+            }
+            PuzzleChange::Shuffle => puzzles.shuffle(&library, complexity, &mut *rng),
+        };
+        let Some(config) = next else {
+            info!("⏭️ No other puzzles on level {}", tracker.current_level);
+            continue;
+        };
+
+        info!(
+            "⏭️ {:?} to another level {} puzzle: {} solutions expected ({} skipped for later)",
+            change,
+            tracker.current_level,
+            config.total_solutions,
+            puzzles.skipped.len()
+        );
+        *session = PuzzleSession::new(config.valences, config.total_solutions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> PuzzleLibrary {
+        // Three complexity-1 puzzles, none a transform of another
+        PuzzleLibrary::from_csv("0,0,0,0,0,0,0,1,1,1\n0,0,0,0,0,1,0,1,0,1\n0,0,0,0,1,0,0,0,1,1")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_skipped_puzzles_come_back_after_the_rest() {
+        let library = library();
+        let mut rng = GameRng::from_seed(7);
+        let mut puzzles = LevelPuzzles::default();
+        puzzles.follow(1);
+
+        let mut current = Some(0);
+        let mut tried = vec![0];
+        for _ in 0..2 {
+            let config = puzzles
+                .skip(current, &tried, &library, 1, false, &mut rng)
+                .unwrap();
+            current = library.base_index(1, &config.valences);
+            tried.push(current.unwrap());
+        }
+        tried.sort_unstable();
+        assert_eq!(tried, vec![0, 1, 2]);
+        assert_eq!(puzzles.skipped_count(1), 2);
+
+        // Everything's been served: the first skipped puzzle returns
+        let config = puzzles
+            .skip(current, &tried, &library, 1, false, &mut rng)
+            .unwrap();
+        assert_eq!(library.base_index(1, &config.valences), Some(0));
+        assert_eq!(puzzles.skipped_count(1), 2);
+    }
+
+    #[test]
+    fn test_a_new_level_starts_over() {
+        let library = library();
+        let mut rng = GameRng::from_seed(7);
+        let mut puzzles = LevelPuzzles::default();
+        puzzles.follow(1);
+        puzzles.skip(Some(0), &[0], &library, 1, false, &mut rng);
+        assert_eq!(puzzles.skipped_count(1), 1);
+        assert_eq!(puzzles.skipped_count(2), 0);
+
+        puzzles.follow(2);
+        assert_eq!(
+            puzzles,
+            LevelPuzzles {
+                level: 2,
+                ..default()
+            }
+        );
+    }
+}
@@ -0,0 +1,141 @@
+// game/tour.rs - each level's base puzzles are served once before any comes back
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    progression::ProgressionTracker,
+    puzzle::{PuzzleConfig, PuzzleLibrary},
+    session::PuzzleSession,
+};
+use crate::graph::Valences;
+
+/// Resource: The base puzzles (`PuzzleLibrary` indices) served on each level so far
+///
+/// Kept with the player's profile, so the tour carries on across sessions and laps of
+/// the levels.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LevelTour {
+    tried: BTreeMap<usize, Vec<usize>>,
+}
+
+impl LevelTour {
+    /// Base puzzles served on `level` since its tour last started over
+    pub fn tried(&self, level: usize) -> &[usize] {
+        self.tried
+            .get(&level)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Note that the base puzzle at `index` was served on `level`
+    pub fn mark(&mut self, level: usize, index: usize) {
+        let tried = self.tried.entry(level).or_default();
+        if !tried.contains(&index) {
+            tried.push(index);
+        }
+    }
+
+    /// A puzzle for `level` that hasn't been served yet, in a random orientation (from the
+    /// easier half of them when `gentle`)
+    ///
+    /// Once all of the level's puzzles have been served, its tour starts over.
+    pub fn next_puzzle(
+        &mut self,
+        library: &PuzzleLibrary,
+        level: usize,
+        complexity: usize,
+        gentle: bool,
+        rng: &mut impl Rng,
+    ) -> Option<PuzzleConfig> {
+        let untried = if gentle {
+            library.gentle_puzzle(complexity, self.tried(level), rng)
+        } else {
+            library.untried_puzzle(complexity, self.tried(level), rng)
+        };
+        if let Some((config, _)) = untried {
+            return Some(config);
+        }
+
+        if self.tried.remove(&level).is_some() {
+            info!("🗺️ Seen every level {} puzzle; starting them over", level);
+        }
+        library.random_puzzle(complexity, rng)
+    }
+}
+
+/// System: Mark the puzzle on the board as served once it's put up
+///
+/// Boards are put up from many places (new levels, skips, profile switches, resumed
+/// saves); marking them here covers all of them. Puzzles from outside the library
+/// (custom ones, friends' codes) aren't part of the tour.
+pub fn track_served_puzzles(
+    session: Res<PuzzleSession>,
+    tracker: Res<ProgressionTracker>,
+    library: Res<PuzzleLibrary>,
+    mut tour: ResMut<LevelTour>,
+    mut marked: Local<Option<(usize, Valences)>>,
+) {
+    if !session.is_changed() && !tracker.is_changed() {
+        return;
+    }
+    let served = (tracker.current_level, session.puzzle_valences().clone());
+    if marked.as_ref() == Some(&served) {
+        return;
+    }
+
+    let level = tracker.current_level;
+    if let Some(index) = library.base_index(tracker.current_complexity(), &served.1)
+        && !tour.tried(level).contains(&index)
+    {
+        tour.mark(level, index);
+    }
+    *marked = Some(served);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::rng::GameRng;
+
+    fn library() -> PuzzleLibrary {
+        // Three complexity-1 puzzles, none a transform of another
+        PuzzleLibrary::from_csv("0,0,0,0,0,0,0,1,1,1\n0,0,0,0,0,1,0,1,0,1\n0,0,0,0,1,0,0,0,1,1")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_every_puzzle_is_served_before_a_repeat() {
+        let library = library();
+        let mut rng = GameRng::from_seed(7);
+        let mut tour = LevelTour::default();
+
+        for _ in 0..3 {
+            let config = tour.next_puzzle(&library, 1, 1, false, &mut rng).unwrap();
+            let index = library.base_index(1, &config.valences).unwrap();
+            assert!(!tour.tried(1).contains(&index));
+            tour.mark(1, index);
+        }
+        assert_eq!(tour.tried(1).len(), 3);
+
+        // The level's tour is done: it starts over
+        tour.next_puzzle(&library, 1, 1, false, &mut rng).unwrap();
+        assert!(tour.tried(1).is_empty());
+    }
+
+    #[test]
+    fn test_tour_round_trips_through_json() {
+        let mut tour = LevelTour::default();
+        tour.mark(1, 2);
+        tour.mark(12, 0);
+        tour.mark(12, 0);
+
+        let json = serde_json::to_string(&tour).unwrap();
+        assert_eq!(json, r#"{"1":[2],"12":[0]}"#);
+        assert_eq!(serde_json::from_str::<LevelTour>(&json).unwrap(), tour);
+    }
+}
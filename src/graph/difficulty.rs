@@ -0,0 +1,73 @@
+// difficulty.rs - how hard a puzzle plays, judged from the shape of its trail search
+// rather than how many solutions it has
+
+use super::{
+    solver::{self, SearchSurvey},
+    valences::Valences,
+};
+
+/// Search moves spent surveying a puzzle (dense boards get a sample of their trails)
+const DIFFICULTY_BUDGET: usize = 100_000;
+
+/// Heuristic difficulty of a puzzle, from a survey of its trails
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    /// Legal next nodes per mid-trail position, on average
+    pub branching: f32,
+    /// Edges a trail runs before it finishes or gets stuck, on average
+    pub average_trail: f32,
+    /// Share of mid-trail positions with only one legal next node
+    pub forced_share: f32,
+}
+
+impl Difficulty {
+    /// Survey the puzzle's trails (within `DIFFICULTY_BUDGET`) and sum them up
+    pub fn estimate(valences: &Valences) -> Self {
+        Self::from_survey(&solver::survey_trails(valences, DIFFICULTY_BUDGET))
+    }
+
+    pub fn from_survey(survey: &SearchSurvey) -> Self {
+        let ratio = |part: usize, whole: usize| {
+            if whole == 0 {
+                0.0
+            } else {
+                part as f32 / whole as f32
+            }
+        };
+        Self {
+            branching: ratio(survey.choices, survey.positions),
+            average_trail: ratio(survey.trail_edges, survey.trails),
+            forced_share: ratio(survey.forced, survey.positions),
+        }
+    }
+
+    /// One number to order puzzles by: the real decisions along an average trail, each
+    /// weighted by the bits of choice it offers
+    ///
+    /// 0 for a puzzle that plays itself; longer trails and more open positions score
+    /// higher, forced moves lower.
+    pub fn score(&self) -> f32 {
+        self.average_trail * (1.0 - self.forced_share) * (1.0 + self.branching).log2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_edge_plays_itself() {
+        let difficulty = Difficulty::estimate(&Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(difficulty.forced_share, 1.0);
+        assert_eq!(difficulty.average_trail, 1.0);
+        assert_eq!(difficulty.score(), 0.0);
+    }
+
+    #[test]
+    fn test_longer_open_trails_score_higher() {
+        // A triangle against a four-node loop, whose diagonals lead trails astray
+        let triangle = Difficulty::estimate(&Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]));
+        let square = Difficulty::estimate(&Valences::new(vec![2, 2, 0, 2, 2, 0, 0, 0, 0]));
+        assert!(square.score() > triangle.score());
+    }
+}
@@ -0,0 +1,145 @@
+// history.rs - the moves of the trail being drawn, with undone moves kept for redo
+
+use super::edge::Edge;
+use super::kings_graph::NodeId;
+
+/// One step of a trail: the node added and the edge it drew (none for the first node)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrailMove {
+    pub node: NodeId,
+    pub edge: Option<Edge>,
+}
+
+/// The authoritative record of a trail: moves in draw order, plus the moves undone
+/// since the player last diverged (most recently undone last)
+///
+/// `GameState` keeps its trail here and undoes and redoes through it, so the trail can't
+/// drift from its history. Shared boards and replays send plain trails and rebuild the
+/// log by replaying them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveLog {
+    moves: Vec<TrailMove>,
+    /// The trail's nodes, kept alongside `moves` so it can be lent out as a slice
+    trail: Vec<NodeId>,
+    undone: Vec<TrailMove>,
+}
+
+impl MoveLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves in draw order
+    pub fn moves(&self) -> &[TrailMove] {
+        &self.moves
+    }
+
+    /// Nodes of the trail in draw order
+    pub fn trail(&self) -> &[NodeId] {
+        &self.trail
+    }
+
+    pub fn last_node(&self) -> Option<NodeId> {
+        self.trail.last().copied()
+    }
+
+    /// Record a new move
+    ///
+    /// Making the move that would have been redone keeps the rest of the undone branch;
+    /// any other move diverges from it, so it's dropped.
+    pub fn record(&mut self, step: TrailMove) {
+        if self.undone.last() == Some(&step) {
+            self.undone.pop();
+        } else {
+            self.undone.clear();
+        }
+        self.moves.push(step);
+        self.trail.push(step.node);
+    }
+
+    /// Take back the last move, keeping it for redo
+    pub fn undo(&mut self) -> Option<TrailMove> {
+        let step = self.moves.pop()?;
+        self.trail.pop();
+        self.undone.push(step);
+        Some(step)
+    }
+
+    /// The move `redo` would make again
+    pub fn next_redo(&self) -> Option<TrailMove> {
+        self.undone.last().copied()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Forget the undone moves (e.g. when redoing one turns out to be impossible)
+    pub fn clear_redo(&mut self) {
+        self.undone.clear();
+    }
+
+    /// Forget everything (a fresh attempt)
+    pub fn clear(&mut self) {
+        self.moves.clear();
+        self.trail.clear();
+        self.undone.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(from: usize, to: usize) -> TrailMove {
+        TrailMove {
+            node: NodeId(to),
+            edge: Some(Edge::new(NodeId(from), NodeId(to))),
+        }
+    }
+
+    fn start(node: usize) -> TrailMove {
+        TrailMove {
+            node: NodeId(node),
+            edge: None,
+        }
+    }
+
+    #[test]
+    fn test_undo_keeps_moves_for_redo() {
+        let mut log = MoveLog::new();
+        log.record(start(0));
+        log.record(step(0, 1));
+        log.record(step(1, 4));
+
+        assert_eq!(log.undo(), Some(step(1, 4)));
+        assert_eq!(log.undo(), Some(step(0, 1)));
+        assert_eq!(log.trail(), &[NodeId(0)]);
+        assert_eq!(log.next_redo(), Some(step(0, 1)));
+    }
+
+    #[test]
+    fn test_repeating_the_undone_move_keeps_the_branch() {
+        let mut log = MoveLog::new();
+        log.record(start(0));
+        log.record(step(0, 1));
+        log.record(step(1, 4));
+        log.undo();
+        log.undo();
+
+        log.record(step(0, 1));
+        assert_eq!(log.next_redo(), Some(step(1, 4)));
+    }
+
+    #[test]
+    fn test_diverging_drops_the_branch() {
+        let mut log = MoveLog::new();
+        log.record(start(0));
+        log.record(step(0, 1));
+        log.undo();
+
+        log.record(step(0, 3));
+        assert!(!log.can_redo());
+        assert_eq!(log.trail(), &[NodeId(0), NodeId(3)]);
+    }
+}
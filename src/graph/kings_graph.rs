@@ -82,7 +82,7 @@ impl KingsGraph {
     pub fn new_3x3() -> Self {
         let mut adjacency = vec![Vec::new(); 9];
 
-        for i in 0..9 {
+        for (i, neighbors) in adjacency.iter_mut().enumerate() {
             let node = NodeId(i);
             let pos = GridPos::from_node_id(node);
 
@@ -95,7 +95,7 @@ impl KingsGraph {
                 let other_pos = GridPos::from_node_id(other);
 
                 if pos.is_adjacent(&other_pos) {
-                    adjacency[i].push(other);
+                    neighbors.push(other);
                 }
             }
         }
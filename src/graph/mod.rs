@@ -1,11 +1,19 @@
+mod difficulty;
 mod edge;
+mod history;
 mod kings_graph;
+mod permalink;
 mod solution;
+pub mod solver;
 mod state;
+mod symmetry;
 mod valences;
 
+pub use difficulty::Difficulty;
 pub use edge::{Edge, EdgeSet};
 pub use kings_graph::{GridPos, KingsGraph, NodeId};
+pub use permalink::{PermalinkError, PuzzleCode};
 pub use solution::Solution;
 pub use state::{GameState, MoveResult, ValidationError};
-pub use valences::Valences;
+pub use symmetry::{Symmetry, apply_symmetry};
+pub use valences::{ValenceError, Valences};
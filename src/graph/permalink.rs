@@ -0,0 +1,268 @@
+// permalink.rs - compact share codes for a board and, optionally, one of its solutions
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use std::fmt;
+
+use super::{
+    edge::Edge,
+    kings_graph::{KingsGraph, NodeId},
+    solution::Solution,
+    symmetry::{Symmetry, apply_symmetry},
+    valences::Valences,
+};
+
+/// Bumped whenever the byte layout changes, so old links fail loudly instead of misreading
+const FORMAT_VERSION: u8 = 1;
+
+/// Version byte, then 9 valence nibbles and a symmetry nibble
+const BOARD_BYTES: usize = 6;
+
+/// One bit per king's graph edge (20 of them)
+const SOLUTION_BYTES: usize = 3;
+
+/// Why a code couldn't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermalinkError {
+    NotBase64,
+    WrongLength(usize),
+    UnknownVersion(u8),
+    ValenceTooHigh {
+        node: NodeId,
+        valence: usize,
+    },
+    UnknownSymmetry(u8),
+    /// The solution's edges don't use up the board's valences
+    SolutionMismatch,
+}
+
+impl fmt::Display for PermalinkError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotBase64 => write!(f, "Not a puzzle code"),
+            Self::WrongLength(len) => write!(f, "Puzzle code has {} bytes", len),
+            Self::UnknownVersion(version) => write!(f, "Unknown puzzle code version {}", version),
+            Self::ValenceTooHigh { node, valence } => {
+                write!(f, "Node {} has valence {} (max 8)", node.0, valence)
+            }
+            Self::UnknownSymmetry(index) => write!(f, "Unknown symmetry {}", index),
+            Self::SolutionMismatch => write!(f, "Solution doesn't fit the puzzle"),
+        }
+    }
+}
+
+/// A board as the base orientation of its puzzle plus the symmetry applied to it, so
+/// rotated and flipped copies of one puzzle share the same base
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleCode {
+    pub valences: Valences,
+    pub symmetry: Symmetry,
+    /// A solution to the board (not the base), to show a friend how it's done
+    pub solution: Option<Solution>,
+}
+
+/// The king's graph edges in a fixed order, one bit each in the solution mask
+fn board_edges() -> Vec<Edge> {
+    let graph = KingsGraph::new_3x3();
+    (0..9)
+        .flat_map(|a| (a + 1..9).map(move |b| (NodeId(a), NodeId(b))))
+        .filter(|&(a, b)| graph.are_adjacent(a, b))
+        .map(|(a, b)| Edge::new(a, b))
+        .collect()
+}
+
+fn to_array(valences: &Valences) -> [usize; 9] {
+    std::array::from_fn(|i| valences.get(NodeId(i)))
+}
+
+impl PuzzleCode {
+    /// Code for a board as it's shown
+    pub fn for_board(board: &Valences, solution: Option<Solution>) -> Self {
+        let valences = Symmetry::all()
+            .into_iter()
+            .map(|symmetry| apply_symmetry(board, symmetry))
+            .min_by_key(to_array)
+            .unwrap_or_else(|| board.clone());
+        let symmetry = Symmetry::all()
+            .into_iter()
+            .find(|&symmetry| apply_symmetry(&valences, symmetry) == *board)
+            .unwrap_or(Symmetry::Identity);
+
+        Self {
+            valences,
+            symmetry,
+            solution,
+        }
+    }
+
+    /// The board to play: the base valences in the shared orientation
+    pub fn board(&self) -> Valences {
+        apply_symmetry(&self.valences, self.symmetry)
+    }
+
+    pub fn encode(&self) -> String {
+        let mut nibbles = to_array(&self.valences).map(|v| v as u8).to_vec();
+        nibbles.push(self.symmetry.index() as u8);
+
+        let mut bytes = vec![FORMAT_VERSION];
+        bytes.extend(nibbles.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+
+        if let Some(solution) = &self.solution {
+            let mask = board_edges()
+                .iter()
+                .enumerate()
+                .filter(|(_, edge)| solution.contains(edge))
+                .fold(0u32, |mask, (bit, _)| mask | (1 << bit));
+            bytes.extend(&mask.to_le_bytes()[..SOLUTION_BYTES]);
+        }
+
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Read a code, checking that it describes a playable board (whitespace is ignored,
+    /// so codes survive being pasted from chat)
+    pub fn decode(code: &str) -> Result<Self, PermalinkError> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        let bytes = URL_SAFE_NO_PAD
+            .decode(code)
+            .map_err(|_| PermalinkError::NotBase64)?;
+        if bytes.len() != BOARD_BYTES && bytes.len() != BOARD_BYTES + SOLUTION_BYTES {
+            return Err(PermalinkError::WrongLength(bytes.len()));
+        }
+        if bytes[0] != FORMAT_VERSION {
+            return Err(PermalinkError::UnknownVersion(bytes[0]));
+        }
+
+        let nibbles: Vec<u8> = bytes[1..BOARD_BYTES]
+            .iter()
+            .flat_map(|&byte| [byte >> 4, byte & 0x0f])
+            .collect();
+        let mut valences = [0; 9];
+        for (i, &valence) in nibbles[..9].iter().enumerate() {
+            if valence > 8 {
+                return Err(PermalinkError::ValenceTooHigh {
+                    node: NodeId(i),
+                    valence: valence as usize,
+                });
+            }
+            valences[i] = valence as usize;
+        }
+        let symmetry = *Symmetry::all()
+            .get(nibbles[9] as usize)
+            .ok_or(PermalinkError::UnknownSymmetry(nibbles[9]))?;
+
+        let mut code = Self {
+            valences: Valences::from_array(valences),
+            symmetry,
+            solution: None,
+        };
+
+        if bytes.len() > BOARD_BYTES {
+            let mask = bytes[BOARD_BYTES..]
+                .iter()
+                .rev()
+                .fold(0u32, |mask, &byte| (mask << 8) | byte as u32);
+            let mut solution = Solution::new();
+            for (bit, edge) in board_edges().into_iter().enumerate() {
+                if mask & (1 << bit) != 0 {
+                    solution.add_edge(edge);
+                }
+            }
+
+            let board = code.board();
+            let fits = !solution.edges().is_empty()
+                && (0..9).map(NodeId).all(|node| {
+                    let degree = solution
+                        .edges()
+                        .iter()
+                        .filter(|edge| edge.contains_node(node))
+                        .count();
+                    degree == board.get(node)
+                });
+            if mask >> board_edges().len() != 0 || !fits {
+                return Err(PermalinkError::SolutionMismatch);
+            }
+            code.solution = Some(solution);
+        }
+
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangle() -> Solution {
+        let mut solution = Solution::new();
+        solution.add_edge(Edge::new(NodeId(0), NodeId(1)));
+        solution.add_edge(Edge::new(NodeId(1), NodeId(4)));
+        solution.add_edge(Edge::new(NodeId(0), NodeId(4)));
+        solution
+    }
+
+    #[test]
+    fn test_board_round_trips() {
+        let board = Valences::new(vec![2, 1, 3, 4, 2, 0, 1, 5, 2]);
+        let code = PuzzleCode::for_board(&board, None);
+        let decoded = PuzzleCode::decode(&code.encode()).unwrap();
+
+        assert_eq!(decoded, code);
+        assert_eq!(decoded.board(), board);
+    }
+
+    #[test]
+    fn test_solution_round_trips() {
+        let board = Valences::new(vec![2, 2, 0, 0, 2, 0, 0, 0, 0]);
+        let code = PuzzleCode::for_board(&board, Some(triangle()));
+        let encoded = code.encode();
+
+        assert_eq!(PuzzleCode::decode(&encoded).unwrap(), code);
+        assert_eq!(encoded.len(), 12);
+    }
+
+    #[test]
+    fn test_orientations_share_a_base() {
+        let board = Valences::new(vec![2, 1, 3, 4, 2, 0, 1, 5, 2]);
+        let base = PuzzleCode::for_board(&board, None).valences;
+
+        for symmetry in Symmetry::all() {
+            let turned = apply_symmetry(&board, symmetry);
+            let code = PuzzleCode::for_board(&turned, None);
+            assert_eq!(code.valences, base);
+            assert_eq!(code.board(), turned);
+        }
+    }
+
+    #[test]
+    fn test_bad_codes_are_rejected() {
+        assert_eq!(
+            PuzzleCode::decode("not a code!"),
+            Err(PermalinkError::NotBase64)
+        );
+        assert_eq!(
+            PuzzleCode::decode("AQ"),
+            Err(PermalinkError::WrongLength(1))
+        );
+
+        let encode = |bytes: &[u8]| URL_SAFE_NO_PAD.encode(bytes);
+        assert_eq!(
+            PuzzleCode::decode(&encode(&[9, 0, 0, 0, 0, 0])),
+            Err(PermalinkError::UnknownVersion(9))
+        );
+        assert!(matches!(
+            PuzzleCode::decode(&encode(&[1, 0x90, 0, 0, 0, 0])),
+            Err(PermalinkError::ValenceTooHigh { .. })
+        ));
+        assert_eq!(
+            PuzzleCode::decode(&encode(&[1, 0, 0, 0, 0, 0x08])),
+            Err(PermalinkError::UnknownSymmetry(8))
+        );
+
+        // A triangle's edges don't fit an empty board
+        let empty = PuzzleCode::for_board(&Valences::zeros(), Some(triangle()));
+        assert_eq!(
+            PuzzleCode::decode(&empty.encode()),
+            Err(PermalinkError::SolutionMismatch)
+        );
+    }
+}
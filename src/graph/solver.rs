@@ -0,0 +1,287 @@
+// solver.rs - exhaustive trail search over a puzzle, using the same move rules as play
+
+use std::collections::HashSet;
+
+use super::{GameState, MoveResult, NodeId, Solution, Valences};
+
+/// Find every distinct solution (edge set) reachable by a legal trail
+pub fn enumerate_solutions(valences: &Valences) -> HashSet<Solution> {
+    let mut solutions = HashSet::new();
+    let mut state = GameState::new(valences.clone());
+    let mut budget = usize::MAX;
+
+    if state.is_complete() {
+        return solutions;
+    }
+
+    search(&mut state, &mut budget, &mut |state| {
+        solutions.insert(Solution::from_edge_set(state.edges()));
+        false
+    });
+
+    solutions
+}
+
+/// Count distinct solutions, giving up (None) after `budget` moves of search
+///
+/// Dense boards have astronomically many trails; this keeps interactive callers (the
+/// editor) responsive.
+pub fn count_solutions_within(valences: &Valences, budget: usize) -> Option<usize> {
+    let mut solutions = HashSet::new();
+    let mut state = GameState::new(valences.clone());
+    let mut budget = budget;
+
+    if state.is_complete() {
+        return Some(0);
+    }
+
+    search(&mut state, &mut budget, &mut |state| {
+        solutions.insert(Solution::from_edge_set(state.edges()));
+        false
+    });
+
+    (budget > 0).then_some(solutions.len())
+}
+
+/// Count distinct solutions (what the puzzle library's complexity is built from)
+pub fn count_solutions(valences: &Valences) -> usize {
+    enumerate_solutions(valences).len()
+}
+
+/// Find one trail that completes the puzzle, in draw order
+pub fn find_solution_trail(valences: &Valences) -> Option<Vec<NodeId>> {
+    let mut state = GameState::new(valences.clone());
+    let mut trail = None;
+    let mut budget = usize::MAX;
+
+    search(&mut state, &mut budget, &mut |state| {
+        trail = Some(state.current_trail().to_vec());
+        true
+    });
+
+    trail
+}
+
+/// The node to play next so that `trail` goes on to a solution not in `known`
+///
+/// With an empty trail this is where to start. None if `trail` isn't legal, can't reach a
+/// new solution, or `budget` moves of search weren't enough to find one.
+pub fn hint_next_node(
+    valences: &Valences,
+    trail: &[NodeId],
+    known: &HashSet<Solution>,
+    budget: usize,
+) -> Option<NodeId> {
+    let mut state = GameState::new(valences.clone());
+    for &node in trail {
+        if !matches!(
+            state.add_node(node),
+            MoveResult::FirstNode(_) | MoveResult::EdgeAdded(_)
+        ) {
+            return None;
+        }
+    }
+
+    let mut next = None;
+    let mut budget = budget;
+    search(&mut state, &mut budget, &mut |state| {
+        if known.contains(&Solution::from_edge_set(state.edges())) {
+            return false;
+        }
+        next = state.current_trail().get(trail.len()).copied();
+        true
+    });
+
+    next
+}
+
+/// Whether the puzzle has any solution, or None if `budget` moves weren't enough to tell
+pub fn probe_solvable(valences: &Valences, budget: usize) -> Option<bool> {
+    let mut state = GameState::new(valences.clone());
+    let mut budget = budget;
+    let mut found = false;
+
+    if state.is_complete() {
+        return Some(false);
+    }
+
+    search(&mut state, &mut budget, &mut |_| {
+        found = true;
+        true
+    });
+
+    (found || budget > 0).then_some(found)
+}
+
+/// What a search saw of a puzzle's trails: how they branch and how long they run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchSurvey {
+    /// Mid-trail positions the search stood at (a starting node placed, not finished)
+    pub positions: usize,
+    /// Legal next nodes summed over those positions
+    pub choices: usize,
+    /// Positions with exactly one legal next node
+    pub forced: usize,
+    /// Trails the search ended, finished or stuck
+    pub trails: usize,
+    /// Edges summed over those trails
+    pub trail_edges: usize,
+    /// Whether the budget ran out before every trail was seen
+    pub truncated: bool,
+}
+
+/// Walk the puzzle's trails like `search`, tallying a `SearchSurvey` for up to `budget`
+/// moves
+pub fn survey_trails(valences: &Valences, budget: usize) -> SearchSurvey {
+    let mut survey = SearchSurvey::default();
+    let mut state = GameState::new(valences.clone());
+    let mut budget = budget;
+
+    if !state.is_complete() {
+        survey_from(&mut state, &mut budget, &mut survey);
+    }
+    survey.truncated = budget == 0;
+    survey
+}
+
+fn survey_from(state: &mut GameState, budget: &mut usize, survey: &mut SearchSurvey) {
+    let next = state.valid_next_nodes();
+    if !state.current_trail().is_empty() {
+        survey.positions += 1;
+        survey.choices += next.len();
+        survey.forced += usize::from(next.len() == 1);
+    }
+    if next.is_empty() || state.is_degenerate() {
+        // Stuck, whether or not a player would see it yet
+        survey.trails += 1;
+        survey.trail_edges += state.edge_count();
+        return;
+    }
+
+    for node in next {
+        if *budget == 0 {
+            return;
+        }
+        *budget -= 1;
+
+        match state.add_node(node) {
+            MoveResult::PuzzleComplete => {
+                survey.trails += 1;
+                survey.trail_edges += state.edge_count();
+            }
+            MoveResult::FirstNode(_) | MoveResult::EdgeAdded(_) => {
+                survey_from(state, budget, survey);
+            }
+            MoveResult::Invalid(_) => continue,
+        }
+        state.pop_node();
+    }
+}
+
+/// Depth-first over every legal next node, calling `on_complete` at each finished trail
+///
+/// Each move spends one unit of `budget`. Returns true as soon as `on_complete` does or
+/// the budget runs out (stops the search).
+fn search(
+    state: &mut GameState,
+    budget: &mut usize,
+    on_complete: &mut impl FnMut(&GameState) -> bool,
+) -> bool {
+    for node in state.valid_next_nodes() {
+        if *budget == 0 {
+            return true;
+        }
+        *budget -= 1;
+
+        let stop = match state.add_node(node) {
+            MoveResult::PuzzleComplete => on_complete(state),
+            MoveResult::FirstNode(_) | MoveResult::EdgeAdded(_) => {
+                !state.is_degenerate() && search(state, budget, on_complete)
+            }
+            MoveResult::Invalid(_) => continue,
+        };
+
+        if stop {
+            return true;
+        }
+        state.pop_node();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_edge_has_one_solution() {
+        let valences = Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(count_solutions(&valences), 1);
+    }
+
+    #[test]
+    fn test_triangle_has_one_solution() {
+        // Nodes 0, 1, 3 are mutually adjacent
+        let valences = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        assert_eq!(count_solutions(&valences), 1);
+    }
+
+    #[test]
+    fn test_found_trail_completes_puzzle() {
+        let valences = Valences::new(vec![0, 0, 0, 0, 0, 1, 0, 1, 2]);
+        let trail = find_solution_trail(&valences).expect("puzzle is solvable");
+
+        let mut state = GameState::new(valences);
+        for node in trail {
+            state.add_node(node);
+        }
+        assert!(state.is_complete());
+    }
+
+    #[test]
+    fn test_budget_gives_up_on_dense_boards() {
+        let triangle = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        assert_eq!(count_solutions_within(&triangle, 1_000), Some(1));
+
+        // Every edge of the king's graph
+        let dense = Valences::new(vec![3, 5, 3, 5, 8, 5, 3, 5, 3]);
+        assert_eq!(count_solutions_within(&dense, 1_000), None);
+    }
+
+    #[test]
+    fn test_unsolvable_puzzle_has_no_solutions() {
+        // Corners 0 and 8 aren't adjacent
+        let valences = Valences::new(vec![1, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(count_solutions(&valences), 0);
+        assert!(find_solution_trail(&valences).is_none());
+        assert_eq!(probe_solvable(&valences, 1_000), Some(false));
+    }
+
+    #[test]
+    fn test_hint_leads_to_a_new_solution() {
+        let valences = Valences::new(vec![0, 0, 0, 0, 0, 1, 0, 1, 2]);
+        let trail = find_solution_trail(&valences).unwrap();
+        let none_known = HashSet::new();
+
+        assert_eq!(
+            hint_next_node(&valences, &[], &none_known, 1_000),
+            Some(trail[0])
+        );
+        assert_eq!(
+            hint_next_node(&valences, &trail[..1], &none_known, 1_000),
+            Some(trail[1])
+        );
+
+        // Its only solution is already found
+        let found = enumerate_solutions(&valences);
+        assert_eq!(hint_next_node(&valences, &[], &found, 1_000), None);
+    }
+
+    #[test]
+    fn test_probe_finds_a_solution() {
+        let valences = Valences::new(vec![0, 0, 0, 0, 0, 1, 0, 1, 2]);
+        assert_eq!(probe_solvable(&valences, 1_000), Some(true));
+        assert_eq!(probe_solvable(&valences, 0), None);
+    }
+}
@@ -1,4 +1,5 @@
 use super::edge::{Edge, EdgeSet};
+use super::history::{MoveLog, TrailMove};
 use super::kings_graph::{KingsGraph, NodeId};
 use super::valences::Valences;
 use std::fmt;
@@ -57,10 +58,10 @@ pub struct GameState {
     /// Set of edges that have been drawn
     edges: EdgeSet,
 
-    /// Current trail being drawn (nodes in order)
-    /// Note: This is NOT the same as edges! The trail includes the starting node
+    /// Moves of the current trail (and undone ones, for redo)
+    /// Note: The trail includes the starting node, which draws no edge
     /// Example: trail [0, 1, 4] represents edges (0-1) and (1-4)
-    current_trail: Vec<NodeId>,
+    history: MoveLog,
 }
 
 impl GameState {
@@ -71,7 +72,7 @@ impl GameState {
             puzzle_valences: puzzle_valences.clone(),
             current_valences: puzzle_valences,
             edges: EdgeSet::new(),
-            current_trail: Vec::new(),
+            history: MoveLog::new(),
         }
     }
 
@@ -92,7 +93,12 @@ impl GameState {
 
     /// Get the current trail of nodes
     pub fn current_trail(&self) -> &[NodeId] {
-        &self.current_trail
+        self.history.trail()
+    }
+
+    /// Are there undone moves that `redo` can make again?
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
     }
 
     /// Get all edges that have been drawn
@@ -105,6 +111,21 @@ impl GameState {
         self.current_valences.total()
     }
 
+    /// Get total valence of the puzzle before any edges were drawn
+    pub fn puzzle_total_valence(&self) -> usize {
+        self.puzzle_valences.total()
+    }
+
+    /// Get the number of edges drawn so far
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Get how many drawn edges touch a node
+    pub fn edge_degree(&self, node: NodeId) -> usize {
+        self.edges.degree(node)
+    }
+
     /// Check if we're at the last edge (total valence == 2)
     pub fn is_last_edge(&self) -> bool {
         self.total_remaining_valence() == 2
@@ -123,11 +144,9 @@ impl GameState {
         }
 
         // If this is the first node, it's always valid
-        if self.current_trail.is_empty() {
+        let Some(last_node) = self.history.last_node() else {
             return Ok(());
-        }
-
-        let last_node = *self.current_trail.last().unwrap();
+        };
 
         // Can't add the same node twice in a row
         if node == last_node {
@@ -161,20 +180,28 @@ impl GameState {
         }
 
         // If this is the first node, just add it to the trail
-        if self.current_trail.is_empty() {
-            self.current_trail.push(node);
+        let Some(last_node) = self.history.last_node() else {
+            self.history.record(TrailMove { node, edge: None });
             return MoveResult::FirstNode(node);
+        };
+
+        // Spend a unit of valence at both ends (checked before anything is touched)
+        let mut valences = self.current_valences.clone();
+        for end in [node, last_node] {
+            if valences.checked_decrement(end).is_err() {
+                return MoveResult::Invalid(ValidationError::NodeHasNoValence(end));
+            }
         }
 
         // Add the edge and update valences
-        let last_node = *self.current_trail.last().unwrap();
         let edge = Edge::new(node, last_node);
         self.edges.add(edge);
-
-        self.current_valences.decrement(node);
-        self.current_valences.decrement(last_node);
-
-        self.current_trail.push(node);
+        self.current_valences = valences;
+        self.history.record(TrailMove {
+            node,
+            edge: Some(edge),
+        });
+        self.debug_check_nodes([node, last_node]);
 
         // Check if puzzle is complete
         if self.is_complete() {
@@ -184,31 +211,44 @@ impl GameState {
         }
     }
 
-    /// Remove the last node from the trail (undo)
+    /// Remove the last node from the trail (undo), keeping it for `redo`
+    ///
+    /// Returns the node whose edge was removed, or None when only the starting node (or
+    /// nothing) was left.
     pub fn pop_node(&mut self) -> Option<NodeId> {
-        if self.current_trail.len() <= 1 {
-            // If there's only one node or none, just clear the trail
-            self.current_trail.clear();
-            return None;
+        let step = self.history.undo()?;
+        let edge = step.edge?;
+
+        // Remove the edge and restore valences (never above the puzzle's)
+        self.edges.pop();
+        for end in [edge.from, edge.to] {
+            let limit = self.puzzle_valences.get(end);
+            let restored = self.current_valences.checked_increment(end, limit);
+            debug_assert!(restored.is_ok(), "Undo corrupted valences: {:?}", restored);
         }
+        self.debug_check_nodes([edge.from, edge.to]);
 
-        let node = self.current_trail.pop()?;
-        let prev_node = *self.current_trail.last().unwrap();
+        Some(step.node)
+    }
 
-        // Remove the edge and restore valences
-        if let Some(_edge) = self.edges.pop() {
-            self.current_valences.increment(node);
-            self.current_valences.increment(prev_node);
+    /// Make the most recently undone move again
+    ///
+    /// None when there's nothing to redo. A move that no longer applies (it can't after a
+    /// plain undo) comes back Invalid and drops the rest of the undone moves.
+    pub fn redo(&mut self) -> Option<MoveResult> {
+        let step = self.history.next_redo()?;
+        let result = self.add_node(step.node);
+        if matches!(result, MoveResult::Invalid(_)) {
+            self.history.clear_redo();
         }
-
-        Some(node)
+        Some(result)
     }
 
     /// Reset to the initial puzzle state
     pub fn reset(&mut self) {
         self.current_valences = self.puzzle_valences.clone();
         self.edges.clear();
-        self.current_trail.clear();
+        self.history.clear();
     }
 
     /// Get all nodes that are currently valid to add
@@ -221,11 +261,9 @@ impl GameState {
 
     /// Get all nodes that should "flee" (cannot be added)
     pub fn nodes_that_should_flee(&self) -> Vec<NodeId> {
-        if self.current_trail.is_empty() {
+        let Some(last_node) = self.history.last_node() else {
             return Vec::new();
-        }
-
-        let last_node = *self.current_trail.last().unwrap();
+        };
 
         (0..9)
             .map(NodeId)
@@ -233,6 +271,74 @@ impl GameState {
             .collect()
     }
 
+    /// Check the bookkeeping: every node's spent valence matches its drawn edges, and the
+    /// drawn edges are exactly the move log's, in order
+    pub fn verify_invariants(&self) -> Result<(), String> {
+        for node in (0..9).map(NodeId) {
+            let initial = self.puzzle_valences.get(node);
+            let remaining = self.valence(node);
+            let Some(spent) = initial.checked_sub(remaining) else {
+                return Err(format!(
+                    "Node {} has {} valence left, above its puzzle valence of {}",
+                    node, remaining, initial
+                ));
+            };
+
+            let degree = self.edges.degree(node);
+            if spent != degree {
+                return Err(format!(
+                    "Node {} has spent {} valence but has {} edges",
+                    node, spent, degree
+                ));
+            }
+        }
+
+        // Only the first move starts without an edge, and every other one joins its node
+        // to the one before
+        let trail = self.history.trail();
+        for (index, step) in self.history.moves().iter().enumerate() {
+            let expected = index
+                .checked_sub(1)
+                .map(|prev| Edge::new(trail[prev], step.node));
+            if step.edge != expected {
+                return Err(format!(
+                    "Move {} to node {} recorded edge {:?}, expected {:?}",
+                    index, step.node, step.edge, expected
+                ));
+            }
+        }
+
+        let drawn: Vec<Edge> = self
+            .history
+            .moves()
+            .iter()
+            .filter_map(|step| step.edge)
+            .collect();
+        if self.edges.edges_in_order() != drawn.as_slice() {
+            return Err(format!(
+                "{} edges drawn, but the move log has {}",
+                self.edges.len(),
+                drawn.len()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Debug builds: the nodes a move touched must still balance valence against edges
+    fn debug_check_nodes(&self, nodes: [NodeId; 2]) {
+        for node in nodes {
+            debug_assert_eq!(
+                self.puzzle_valences
+                    .get(node)
+                    .checked_sub(self.valence(node)),
+                Some(self.edges.degree(node)),
+                "Valence/edge bookkeeping diverged at node {}",
+                node
+            );
+        }
+    }
+
     /// Count available edges for a node (for degenerate detection)
     fn count_available_edges(&self, node: NodeId) -> usize {
         self.graph
@@ -289,6 +395,54 @@ mod tests {
         assert_eq!(state.add_node(NodeId(0)), MoveResult::PuzzleComplete);
 
         assert!(state.is_complete());
+        assert_eq!(state.verify_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_invariants_catches_corruption() {
+        let valences = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        let mut state = GameState::new(valences);
+        state.add_node(NodeId(0));
+        state.add_node(NodeId(1));
+        assert_eq!(state.verify_invariants(), Ok(()));
+
+        // A valence restored without removing its edge
+        state.current_valences.increment(NodeId(1));
+        assert_eq!(
+            state.verify_invariants(),
+            Err("Node 1 has spent 0 valence but has 1 edges".to_string())
+        );
+
+        // An edge missing from the move log
+        let mut state = GameState::new(Valences::new(vec![1, 1, 0, 0, 0, 0, 0, 0, 0]));
+        state.edges.add(Edge::new(NodeId(0), NodeId(1)));
+        state.current_valences = Valences::zeros();
+        assert!(state.verify_invariants().is_err());
+    }
+
+    #[test]
+    fn test_redo_replays_undone_moves() {
+        let valences = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        let mut state = GameState::new(valences);
+        assert_eq!(state.redo(), None);
+
+        state.add_node(NodeId(0));
+        state.add_node(NodeId(1));
+        state.add_node(NodeId(3));
+        assert_eq!(state.pop_node(), Some(NodeId(3)));
+        assert_eq!(state.pop_node(), Some(NodeId(1)));
+
+        assert!(matches!(state.redo(), Some(MoveResult::EdgeAdded(_))));
+        assert!(matches!(state.redo(), Some(MoveResult::EdgeAdded(_))));
+        assert_eq!(state.redo(), None);
+        assert_eq!(state.current_trail(), &[NodeId(0), NodeId(1), NodeId(3)]);
+        assert_eq!(state.verify_invariants(), Ok(()));
+
+        // A new move diverges from what was undone
+        state.pop_node();
+        state.pop_node();
+        state.add_node(NodeId(3));
+        assert_eq!(state.redo(), None);
     }
 
     #[test]
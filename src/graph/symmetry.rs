@@ -1,8 +1,8 @@
-use crate::graph::{NodeId, Valences};
+use super::{kings_graph::NodeId, valences::Valences};
 use rand::Rng;
 
 /// The 8 symmetries of the square (dihedral group D₄)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Symmetry {
     Identity,
     Rot90,
@@ -15,9 +15,30 @@ pub enum Symmetry {
 }
 
 impl Symmetry {
+    /// All 8 symmetries in order
+    pub fn all() -> [Symmetry; 8] {
+        [
+            Symmetry::Identity,
+            Symmetry::Rot90,
+            Symmetry::Rot180,
+            Symmetry::Rot270,
+            Symmetry::FlipHorizontal,
+            Symmetry::FlipVertical,
+            Symmetry::FlipMainDiag,
+            Symmetry::FlipAntiDiag,
+        ]
+    }
+
+    /// Position in `all()`
+    pub fn index(self) -> usize {
+        Self::all()
+            .iter()
+            .position(|&symmetry| symmetry == self)
+            .unwrap_or_default()
+    }
+
     /// Get a random symmetry with uniform distribution
-    pub fn random() -> Self {
-        let mut rng = rand::rng();
+    pub fn random(rng: &mut impl Rng) -> Self {
         match rng.random_range(0..8) {
             0 => Symmetry::Identity,
             1 => Symmetry::Rot90,
@@ -112,22 +133,6 @@ pub fn apply_symmetry(valences: &Valences, symmetry: Symmetry) -> Valences {
 mod tests {
     use super::*;
 
-    impl Symmetry {
-        /// All 8 symmetries in order
-        pub fn all() -> [Symmetry; 8] {
-            [
-                Symmetry::Identity,
-                Symmetry::Rot90,
-                Symmetry::Rot180,
-                Symmetry::Rot270,
-                Symmetry::FlipHorizontal,
-                Symmetry::FlipVertical,
-                Symmetry::FlipMainDiag,
-                Symmetry::FlipAntiDiag,
-            ]
-        }
-    }
-
     #[test]
     fn test_all_symmetries_are_unique() {
         // Apply all 8 symmetries to a non-symmetric puzzle
@@ -1,8 +1,33 @@
 // valences.rs - NEW FILE
 
-use super::kings_graph::NodeId;
+use super::kings_graph::{KingsGraph, NodeId};
+use super::solver;
 use std::fmt;
 
+/// Search moves the feasibility probe may spend before giving the board the benefit of
+/// the doubt (dense boards can't be ruled out cheaply)
+const PROBE_BUDGET: usize = 20_000;
+
+/// A valence change that would leave a node outside its allowed range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValenceError {
+    /// Decrementing a node that has no valence left
+    Exhausted(NodeId),
+    /// Incrementing a node past its limit (its puzzle valence)
+    AboveLimit(NodeId, usize),
+}
+
+impl fmt::Display for ValenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValenceError::Exhausted(n) => write!(f, "Node {} has no valence left", n),
+            ValenceError::AboveLimit(n, limit) => {
+                write!(f, "Node {} would go above its valence of {}", n, limit)
+            }
+        }
+    }
+}
+
 /// Valence values for all 9 nodes in the grid
 /// Always exactly 9 values, indexed by NodeId
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,7 +62,7 @@ impl Valences {
         self.0[node.index()] = value;
     }
 
-    /// Decrement valence for a node
+    /// Decrement valence for a node (panics at zero; see `checked_decrement`)
     pub fn decrement(&mut self, node: NodeId) {
         self.0[node.index()] -= 1;
     }
@@ -47,6 +72,25 @@ impl Valences {
         self.0[node.index()] += 1;
     }
 
+    /// Decrement valence for a node, refusing to go below zero
+    pub fn checked_decrement(&mut self, node: NodeId) -> Result<(), ValenceError> {
+        let valence = &mut self.0[node.index()];
+        *valence = valence
+            .checked_sub(1)
+            .ok_or(ValenceError::Exhausted(node))?;
+        Ok(())
+    }
+
+    /// Increment valence for a node, refusing to go above `limit`
+    pub fn checked_increment(&mut self, node: NodeId, limit: usize) -> Result<(), ValenceError> {
+        let valence = &mut self.0[node.index()];
+        if *valence >= limit {
+            return Err(ValenceError::AboveLimit(node, limit));
+        }
+        *valence += 1;
+        Ok(())
+    }
+
     /// Check if all valences are zero
     pub fn all_zero(&self) -> bool {
         self.0.iter().all(|&v| v == 0)
@@ -63,6 +107,39 @@ impl Valences {
     pub fn total(&self) -> usize {
         self.0.iter().sum()
     }
+
+    /// Check the board can be a puzzle, explaining why not
+    ///
+    /// The valence sum must be even (each edge counts twice), no node can need more edges
+    /// than it has neighbors, and a bounded solver probe must not rule it out.
+    pub fn is_feasible(&self) -> Result<(), String> {
+        if self.all_zero() {
+            return Err("has no edges to draw".to_string());
+        }
+
+        let total = self.total();
+        if total % 2 == 1 {
+            return Err(format!("has an odd valence sum ({})", total));
+        }
+
+        let graph = KingsGraph::new_3x3();
+        for node in (0..9).map(NodeId) {
+            let neighbors = graph.neighbors(node).len();
+            if self.get(node) > neighbors {
+                return Err(format!(
+                    "needs {} edges at node {}, which has only {} neighbors",
+                    self.get(node),
+                    node,
+                    neighbors
+                ));
+            }
+        }
+
+        match solver::probe_solvable(self, PROBE_BUDGET) {
+            Some(false) => Err("has no solutions".to_string()),
+            Some(true) | None => Ok(()),
+        }
+    }
 }
 
 impl fmt::Display for Valences {
@@ -106,6 +183,25 @@ mod tests {
         assert_eq!(v.get(NodeId(4)), 4);
     }
 
+    #[test]
+    fn test_checked_modification() {
+        let mut v = Valences::zeros();
+        assert_eq!(
+            v.checked_decrement(NodeId(2)),
+            Err(ValenceError::Exhausted(NodeId(2)))
+        );
+
+        assert_eq!(v.checked_increment(NodeId(2), 1), Ok(()));
+        assert_eq!(
+            v.checked_increment(NodeId(2), 1),
+            Err(ValenceError::AboveLimit(NodeId(2), 1))
+        );
+        assert_eq!(v.get(NodeId(2)), 1);
+
+        assert_eq!(v.checked_decrement(NodeId(2)), Ok(()));
+        assert!(v.all_zero());
+    }
+
     #[test]
     fn test_all_zero() {
         let v = Valences::zeros();
@@ -124,4 +220,25 @@ mod tests {
         assert!(odd.contains(&NodeId(0)));
         assert!(odd.contains(&NodeId(2)));
     }
+
+    #[test]
+    fn test_feasibility() {
+        let triangle = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        assert_eq!(triangle.is_feasible(), Ok(()));
+        assert!(Valences::zeros().is_feasible().is_err());
+
+        let odd = Valences::new(vec![1, 2, 0, 0, 0, 0, 0, 0, 0]).is_feasible();
+        assert_eq!(odd, Err("has an odd valence sum (3)".to_string()));
+
+        // Corners only have 3 neighbors
+        let crowded = Valences::new(vec![4, 2, 0, 2, 0, 0, 0, 0, 0]).is_feasible();
+        assert_eq!(
+            crowded,
+            Err("needs 4 edges at node 0, which has only 3 neighbors".to_string())
+        );
+
+        // Corners 0 and 8 aren't adjacent
+        let apart = Valences::new(vec![1, 0, 0, 0, 0, 0, 0, 0, 1]).is_feasible();
+        assert_eq!(apart, Err("has no solutions".to_string()));
+    }
 }
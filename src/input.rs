@@ -1,13 +1,28 @@
+use std::collections::HashSet;
+
 use bevy::input::touch::{TouchInput, TouchPhase};
 use bevy::prelude::*;
 use bevy::window::CursorMoved;
 
+use crate::camera::MainCamera;
+
 pub struct InputPlugin;
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CursorPos>()
+            .init_resource::<SmoothedCursor>()
             .add_message::<PointerEvent>()
-            .add_systems(Update, (track_cursor_pos, collect_pointer_events));
+            .add_message::<WorldPointerEvent>()
+            .add_systems(
+                Update,
+                (
+                    track_cursor_pos,
+                    collect_pointer_events,
+                    project_pointer_events,
+                    smooth_cursor,
+                )
+                    .chain(),
+            );
     }
 }
 
@@ -20,6 +35,17 @@ pub struct PointerEvent {
     pub id: u64,
 }
 
+/// A pointer event projected onto the board plane (z = 0)
+///
+/// Gameplay reads these instead of `PointerEvent`, so it never needs a camera
+/// (integration tests write them directly).
+#[derive(Message, Debug, Clone)]
+pub struct WorldPointerEvent {
+    pub world_pos: Vec3,
+    pub event_type: PointerEventType,
+    pub id: u64,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PointerEventType {
     Down,
@@ -28,20 +54,87 @@ pub enum PointerEventType {
 }
 
 impl PointerEvent {
-    /// Convert window coords to world space using a camera
+    /// Convert window coords to a point on the board plane, seen through any camera
     pub fn to_world_position(
         &self,
         camera: &Camera,
         camera_transform: &GlobalTransform,
     ) -> Option<Vec3> {
-        camera
-            .viewport_to_world(camera_transform, self.position)
-            .ok()
-            .map(|ray| {
-                // Example: intersect z=0 plane (top-down ortho)
-                let t = -ray.origin.z / ray.direction.z;
-                ray.origin + ray.direction * t
-            })
+        window_to_world(self.position, camera, camera_transform)
+    }
+}
+
+/// Convert window coords to a point on the z=0 plane
+///
+/// The pointer's ray is intersected with the plane, so this holds for the top-down
+/// orthographic camera and the tilted perspective one alike. None if the ray misses.
+pub fn window_to_world(
+    position: Vec2,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Vec3> {
+    let ray = camera.viewport_to_world(camera_transform, position).ok()?;
+    board_point(ray)
+}
+
+/// Where a ray meets the board plane (None if it runs alongside or points away)
+pub fn board_point(ray: Ray3d) -> Option<Vec3> {
+    ray.intersect_plane(Vec3::ZERO, InfinitePlane3d::new(Vec3::Z))
+        .map(|distance| ray.get_point(distance))
+}
+
+/// Spring stiffness of the cursor filter (rad/s); higher = tighter, less smoothing
+const CURSOR_SMOOTHING: f32 = 35.0;
+
+/// Distance (pixels) at which the filtered cursor snaps onto the raw one
+const CURSOR_SETTLE_DISTANCE: f32 = 0.05;
+
+/// Pointer position with high-DPI/touch jitter filtered out by a critically damped spring
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct SmoothedCursor {
+    /// Filtered window coords (`None` until the first pointer event)
+    pub position: Option<Vec2>,
+    /// Filtered velocity in pixels per second
+    pub velocity: Vec2,
+    /// Latest raw pointer position the filter is chasing
+    target: Option<Vec2>,
+}
+
+impl SmoothedCursor {
+    /// Extrapolate ahead to hide the filter's lag
+    pub fn predicted(&self, lookahead: f32) -> Option<Vec2> {
+        self.position.map(|p| p + self.velocity * lookahead)
+    }
+
+    /// Jump straight to a position (e.g. on press, so clicks aren't smeared)
+    fn snap(&mut self, position: Vec2) {
+        self.position = Some(position);
+        self.target = Some(position);
+        self.velocity = Vec2::ZERO;
+    }
+
+    /// Whether the filter still has somewhere to go
+    fn is_settled(&self) -> bool {
+        self.position == self.target && self.velocity == Vec2::ZERO
+    }
+
+    /// Advance the critically damped spring (exact solution, stable at any dt)
+    fn step(&mut self, dt: f32) {
+        let (Some(position), Some(target)) = (self.position, self.target) else {
+            return;
+        };
+
+        let offset = position - target;
+        let decay = (-CURSOR_SMOOTHING * dt).exp();
+        let temp = (self.velocity + CURSOR_SMOOTHING * offset) * dt;
+        let offset = (offset + temp) * decay;
+        self.velocity = (self.velocity - CURSOR_SMOOTHING * temp) * decay;
+
+        if offset.length() < CURSOR_SETTLE_DISTANCE && self.velocity.length() < 1.0 {
+            self.snap(target);
+        } else {
+            self.position = Some(target + offset);
+        }
     }
 }
 
@@ -60,24 +153,27 @@ fn collect_pointer_events(
     cursor: Res<CursorPos>,
     mut touch_events: MessageReader<TouchInput>,
     mut out: MessageWriter<PointerEvent>,
+    mut pointers: Local<PointerFilter>,
 ) {
+    let mut events = Vec::new();
+
     if let Some(p) = cursor.0 {
         if mouse_buttons.just_pressed(MouseButton::Left) {
-            out.write(PointerEvent {
+            events.push(PointerEvent {
                 position: p,
                 event_type: PointerEventType::Down,
                 id: 0,
             });
         }
         if mouse_buttons.pressed(MouseButton::Left) {
-            out.write(PointerEvent {
+            events.push(PointerEvent {
                 position: p,
                 event_type: PointerEventType::Move,
                 id: 0,
             });
         }
         if mouse_buttons.just_released(MouseButton::Left) {
-            out.write(PointerEvent {
+            events.push(PointerEvent {
                 position: p,
                 event_type: PointerEventType::Up,
                 id: 0,
@@ -91,10 +187,309 @@ fn collect_pointer_events(
             TouchPhase::Moved => PointerEventType::Move,
             TouchPhase::Ended | TouchPhase::Canceled => PointerEventType::Up,
         };
-        out.write(PointerEvent {
+        events.push(PointerEvent {
             position: ev.position,
             event_type,
-            id: ev.id, // keep the id; you can ignore > 1 if you want single-touch only
+            id: ev.id,
         });
     }
+
+    // Downstream systems scan every node per event, so only the latest move matters
+    out.write_batch(coalesce_moves(pointers.filter(events)));
+}
+
+/// Which pointer draws, kept across frames: the first one pressed
+///
+/// A second pointer coming down starts a pinch or two-finger pan. It ends the first
+/// one's press with an Up, and every pointer down during the pinch is retired: its events
+/// are dropped until its own Up, so no finger reaches the game without a Down.
+#[derive(Debug, Default)]
+struct PointerFilter {
+    /// The drawing pointer and its last position
+    primary: Option<(u64, Vec2)>,
+    /// Pointers still held from a pinch
+    retired: HashSet<u64>,
+}
+
+impl PointerFilter {
+    fn filter(&mut self, events: Vec<PointerEvent>) -> Vec<PointerEvent> {
+        let mut out = Vec::with_capacity(events.len());
+
+        for event in events {
+            if self.retired.contains(&event.id) {
+                if event.event_type == PointerEventType::Up {
+                    self.retired.remove(&event.id);
+                }
+                continue;
+            }
+
+            let is_primary = self.primary.is_some_and(|(id, _)| id == event.id);
+            match event.event_type {
+                PointerEventType::Down if is_primary => {}
+                PointerEventType::Down => {
+                    if let Some((id, position)) = self.primary.take() {
+                        out.push(PointerEvent {
+                            position,
+                            event_type: PointerEventType::Up,
+                            id,
+                        });
+                        self.retired.insert(id);
+                    } else if self.retired.is_empty() {
+                        self.primary = Some((event.id, event.position));
+                        out.push(event);
+                        continue;
+                    }
+                    self.retired.insert(event.id);
+                }
+                PointerEventType::Move if is_primary => {
+                    self.primary = Some((event.id, event.position));
+                    out.push(event);
+                }
+                PointerEventType::Up if is_primary => {
+                    self.primary = None;
+                    out.push(event);
+                }
+                // A pointer that never got its Down through
+                PointerEventType::Move | PointerEventType::Up => {}
+            }
+        }
+
+        out
+    }
+}
+
+/// Collapse runs of Move events per pointer down to the latest one
+///
+/// Down/Up events are kept in order, and a Move never jumps across a Down/Up of the
+/// same pointer (so a press-drag-release within one frame still reads correctly).
+fn coalesce_moves(events: Vec<PointerEvent>) -> Vec<PointerEvent> {
+    let mut out: Vec<PointerEvent> = Vec::with_capacity(events.len());
+
+    for event in events {
+        if event.event_type == PointerEventType::Move {
+            // Latest event for this pointer so far - if it's a Move, this one supersedes it
+            let previous = out.iter_mut().rev().find(|e| e.id == event.id);
+            if let Some(previous) = previous
+                && previous.event_type == PointerEventType::Move
+            {
+                *previous = event;
+                continue;
+            }
+        }
+        out.push(event);
+    }
+
+    out
+}
+
+fn project_pointer_events(
+    mut pointer_events: MessageReader<PointerEvent>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut out: MessageWriter<WorldPointerEvent>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    for event in pointer_events.read() {
+        if let Some(world_pos) = event.to_world_position(camera, camera_transform) {
+            out.write(WorldPointerEvent {
+                world_pos,
+                event_type: event.event_type,
+                id: event.id,
+            });
+        }
+    }
+}
+
+fn smooth_cursor(
+    time: Res<Time<Real>>, // Cursor feel shouldn't change with slow-mo
+    mut pointer_events: MessageReader<PointerEvent>,
+    mut cursor: ResMut<SmoothedCursor>,
+) {
+    for event in pointer_events.read() {
+        match event.event_type {
+            PointerEventType::Down => cursor.snap(event.position),
+            PointerEventType::Move | PointerEventType::Up if cursor.position.is_none() => {
+                cursor.snap(event.position)
+            }
+            PointerEventType::Move | PointerEventType::Up => cursor.target = Some(event.position),
+        }
+    }
+
+    // Don't trip change detection once the filter has caught up
+    if !cursor.is_settled() {
+        cursor.step(time.delta_secs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chasing(target: Vec2) -> SmoothedCursor {
+        let mut cursor = SmoothedCursor::default();
+        cursor.snap(Vec2::ZERO);
+        cursor.target = Some(target);
+        cursor
+    }
+
+    fn event(event_type: PointerEventType, x: f32, id: u64) -> PointerEvent {
+        PointerEvent {
+            position: Vec2::new(x, 0.0),
+            event_type,
+            id,
+        }
+    }
+
+    fn summary(events: &[PointerEvent]) -> Vec<(PointerEventType, f32, u64)> {
+        events
+            .iter()
+            .map(|e| (e.event_type, e.position.x, e.id))
+            .collect()
+    }
+
+    #[test]
+    fn test_rays_meet_the_board_plane_from_any_angle() {
+        let straight = Ray3d::new(Vec3::new(1.0, 2.0, 10.0), Dir3::NEG_Z);
+        assert_eq!(board_point(straight), Some(Vec3::new(1.0, 2.0, 0.0)));
+
+        let slanted = Ray3d::new(
+            Vec3::new(0.0, 0.0, 4.0),
+            Dir3::new(Vec3::new(1.0, 0.0, -2.0)).unwrap(),
+        );
+        let hit = board_point(slanted).unwrap();
+        assert!((hit - Vec3::new(2.0, 0.0, 0.0)).length() < 1e-5);
+
+        let alongside = Ray3d::new(Vec3::new(0.0, 0.0, 1.0), Dir3::X);
+        assert_eq!(board_point(alongside), None);
+        let away = Ray3d::new(Vec3::new(0.0, 0.0, 1.0), Dir3::Z);
+        assert_eq!(board_point(away), None);
+    }
+
+    #[test]
+    fn test_coalesce_keeps_latest_move() {
+        use PointerEventType::*;
+        let events = vec![
+            event(Move, 1.0, 0),
+            event(Move, 2.0, 0),
+            event(Move, 3.0, 0),
+        ];
+
+        assert_eq!(summary(&coalesce_moves(events)), vec![(Move, 3.0, 0)]);
+    }
+
+    #[test]
+    fn test_coalesce_preserves_down_up_order() {
+        use PointerEventType::*;
+        let events = vec![
+            event(Down, 0.0, 1),
+            event(Move, 1.0, 1),
+            event(Move, 2.0, 1),
+            event(Up, 2.0, 1),
+            event(Move, 5.0, 1),
+        ];
+
+        assert_eq!(
+            summary(&coalesce_moves(events)),
+            vec![(Down, 0.0, 1), (Move, 2.0, 1), (Up, 2.0, 1), (Move, 5.0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_coalesce_tracks_pointers_separately() {
+        use PointerEventType::*;
+        let events = vec![
+            event(Move, 1.0, 1),
+            event(Move, 10.0, 2),
+            event(Move, 2.0, 1),
+            event(Down, 11.0, 2),
+        ];
+
+        assert_eq!(
+            summary(&coalesce_moves(events)),
+            vec![(Move, 2.0, 1), (Move, 10.0, 2), (Down, 11.0, 2)]
+        );
+    }
+
+    #[test]
+    fn test_pinch_ends_the_first_press_when_it_lifts_first() {
+        use PointerEventType::*;
+        let mut pointers = PointerFilter::default();
+        let events = vec![
+            event(Down, 0.0, 1),
+            event(Move, 1.0, 1),
+            event(Down, 10.0, 2),
+            event(Move, 2.0, 1),
+            event(Up, 2.0, 1),
+            // The second finger carries on alone, but it never drew
+            event(Move, 11.0, 2),
+            event(Up, 11.0, 2),
+            event(Down, 5.0, 3),
+        ];
+
+        assert_eq!(
+            summary(&pointers.filter(events)),
+            vec![(Down, 0.0, 1), (Move, 1.0, 1), (Up, 1.0, 1), (Down, 5.0, 3)]
+        );
+        assert_eq!(pointers.primary, Some((3, Vec2::new(5.0, 0.0))));
+    }
+
+    #[test]
+    fn test_pinch_ends_the_first_press_when_it_lifts_last() {
+        use PointerEventType::*;
+        let mut pointers = PointerFilter::default();
+        let events = vec![
+            event(Down, 0.0, 1),
+            event(Down, 10.0, 2),
+            // A third finger joining the pinch doesn't draw either
+            event(Down, 20.0, 3),
+            event(Up, 10.0, 2),
+            event(Move, 1.0, 1),
+            event(Up, 1.0, 1),
+            event(Move, 21.0, 3),
+            event(Up, 21.0, 3),
+            event(Down, 5.0, 4),
+        ];
+
+        assert_eq!(
+            summary(&pointers.filter(events)),
+            vec![(Down, 0.0, 1), (Up, 0.0, 1), (Down, 5.0, 4)]
+        );
+        assert!(pointers.retired.is_empty());
+    }
+
+    #[test]
+    fn test_smoothed_cursor_settles_on_target() {
+        let target = Vec2::new(100.0, -40.0);
+        let mut cursor = chasing(target);
+
+        for _ in 0..120 {
+            cursor.step(1.0 / 60.0);
+        }
+
+        assert_eq!(cursor.position, Some(target));
+        assert!(cursor.is_settled());
+    }
+
+    #[test]
+    fn test_smoothed_cursor_does_not_overshoot() {
+        let target = Vec2::new(100.0, 0.0);
+        let mut cursor = chasing(target);
+
+        for _ in 0..120 {
+            cursor.step(1.0 / 60.0);
+            assert!(cursor.position.unwrap().x <= target.x + 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_smoothed_cursor_stable_with_long_frames() {
+        let target = Vec2::new(50.0, 50.0);
+        let mut cursor = chasing(target);
+
+        cursor.step(0.5); // A big hitch shouldn't fling the cursor past the target
+        let position = cursor.position.unwrap();
+        assert!(position.distance(target) < target.length());
+    }
 }
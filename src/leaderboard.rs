@@ -0,0 +1,419 @@
+// leaderboard.rs - the daily leaderboard on the HUD: pages of the server's ranking, or
+// the page around this device's own entry
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    input::{PointerEventType, WorldPointerEvent},
+    profile::unix_millis,
+    sync::transport,
+    visual::{
+        interactions::handle_pointer_input,
+        ui::number_group::{
+            HudGroup, LeaderboardRow, leaderboard_heading_group, leaderboard_row_groups,
+        },
+    },
+};
+
+/// Rows on a leaderboard page
+const PAGE_SIZE: usize = 5;
+
+/// Characters of a player id shown as their tag
+const TAG_LEN: usize = 4;
+
+/// Longest time the seconds column shows (four digits)
+const MAX_SECONDS: u64 = 9999;
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        // Without a server to ask, there's no leaderboard to show
+        let Some(base_url) = transport::server_url() else {
+            return;
+        };
+
+        app.insert_resource(LeaderboardClient::new(base_url, player_token()))
+            .add_systems(
+                Update,
+                (
+                    toggle_leaderboard,
+                    browse_leaderboard.run_if(resource_exists::<LeaderboardView>),
+                    receive_leaderboard,
+                )
+                    .chain()
+                    .before(handle_pointer_input),
+            );
+    }
+}
+
+/// One page of `GET /api/daily/{date}/leaderboard`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScorePage {
+    /// Entries ranked for the day
+    pub total: usize,
+    /// Rank of the first entry, from 0
+    pub offset: usize,
+    pub entries: Vec<ScoreRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ScoreRow {
+    pub player: String,
+    pub time_ms: u64,
+    pub solutions_found: u32,
+}
+
+/// Which part of the ranking is on show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardView {
+    /// The ranking from the top, a page at a time (from 0)
+    Top { page: usize },
+    /// The page around this device's own entry
+    AroundMe,
+}
+
+/// Resource: Present while the leaderboard is open
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardView {
+    /// UTC day the board is for, "YYYY-MM-DD" (kept while paging, even past midnight)
+    pub date: String,
+    pub view: BoardView,
+    /// The page on show (None until the server replies)
+    pub page: Option<ScorePage>,
+    /// This device's player id, to mark its own entry
+    pub player: Option<String>,
+}
+
+impl LeaderboardView {
+    pub fn new(date: impl Into<String>, player: Option<String>) -> Self {
+        Self {
+            date: date.into(),
+            view: BoardView::Top { page: 0 },
+            page: None,
+            player,
+        }
+    }
+
+    /// Page through the ranking from the top, stopping at either end; false if it
+    /// didn't move
+    ///
+    /// From the page around the player, the pages go on from the one on show.
+    pub fn step(&mut self, step: isize) -> bool {
+        let current = match self.view {
+            BoardView::Top { page } => page,
+            BoardView::AroundMe => self.page.as_ref().map_or(0, |page| page.offset / PAGE_SIZE),
+        };
+        let last = self
+            .page
+            .as_ref()
+            .map_or(usize::MAX, |page| page.total.div_ceil(PAGE_SIZE).max(1) - 1);
+        let page = current.saturating_add_signed(step).min(last);
+
+        let view = BoardView::Top { page };
+        if view == self.view {
+            return false;
+        }
+        self.view = view;
+        true
+    }
+
+    /// The HUD rows: a heading, then rank, tag, solutions and seconds per entry
+    pub fn groups(&self) -> Vec<HudGroup> {
+        let mut groups = vec![match self.view {
+            BoardView::Top { page } => leaderboard_heading_group("top", Some(page + 1)),
+            BoardView::AroundMe => leaderboard_heading_group("you", None),
+        }];
+        for (row, entry) in self.rows().iter().enumerate() {
+            groups.extend(leaderboard_row_groups(entry, row));
+        }
+        groups
+    }
+
+    fn rows(&self) -> Vec<LeaderboardRow> {
+        let Some(page) = &self.page else {
+            return Vec::new();
+        };
+        page.entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| LeaderboardRow {
+                rank: page.offset + i + 1,
+                mine: self.player.as_deref() == Some(entry.player.as_str()),
+                tag: player_tag(&entry.player),
+                solutions: entry.solutions_found as usize,
+                seconds: (entry.time_ms / 1000).min(MAX_SECONDS) as usize,
+            })
+            .collect()
+    }
+}
+
+/// The last few characters of a player id (the web build's ids are hex, which seven
+/// segments show well)
+fn player_tag(player: &str) -> String {
+    let chars: Vec<char> = player.chars().collect();
+    chars[chars.len().saturating_sub(TAG_LEN)..]
+        .iter()
+        .collect()
+}
+
+/// A finished request: the view it was for, and the response body
+type Reply = (BoardView, Result<Option<String>, String>);
+
+/// Resource: Asks the server for leaderboard pages
+#[derive(Resource, Debug, Clone)]
+pub struct LeaderboardClient {
+    /// Prefix for `/api/daily` ("" for the page's own origin)
+    base_url: String,
+    /// This device's anonymous player id (None where it doesn't post daily scores)
+    player: Option<String>,
+    /// Finished requests, filled from outside the ECS
+    inbox: Arc<Mutex<Vec<Reply>>>,
+}
+
+impl LeaderboardClient {
+    pub fn new(base_url: impl Into<String>, player: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            player,
+            inbox: Arc::default(),
+        }
+    }
+
+    fn url(&self, date: &str, view: BoardView) -> String {
+        let url = format!(
+            "{}/api/daily/{}/leaderboard?limit={}",
+            self.base_url, date, PAGE_SIZE
+        );
+        match (view, &self.player) {
+            (BoardView::Top { page }, _) => format!("{url}&offset={}", page * PAGE_SIZE),
+            (BoardView::AroundMe, Some(player)) => format!("{url}&around={player}"),
+            (BoardView::AroundMe, None) => url,
+        }
+    }
+
+    /// Ask for the page a view shows
+    fn request(&self, date: &str, view: BoardView) {
+        let inbox = self.inbox.clone();
+        transport::request(self.url(date, view), None, None, move |result| {
+            inbox.lock().unwrap().push((view, result));
+        });
+    }
+
+    fn take_delivered(&self) -> Vec<Reply> {
+        std::mem::take(&mut *self.inbox.lock().unwrap())
+    }
+}
+
+/// This device's anonymous leaderboard id, the one its daily scores are posted under
+///
+/// Only the web build posts daily scores, so elsewhere there's no entry to look around.
+fn player_token() -> Option<String> {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        Some(crate::web::player_id())
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    {
+        None
+    }
+}
+
+/// The UTC day of a Unix time, "YYYY-MM-DD" (how the server names its days)
+fn utc_date(unix_secs: u64) -> String {
+    // Days to a civil date, after Howard Hinnant's `civil_from_days`
+    let z = (unix_secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// System: Tab opens today's leaderboard, or closes it
+pub fn toggle_leaderboard(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    client: Res<LeaderboardClient>,
+    view: Option<Res<LeaderboardView>>,
+) {
+    if !keys.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    if view.is_some() {
+        commands.remove_resource::<LeaderboardView>();
+        return;
+    }
+
+    let view = LeaderboardView::new(utc_date(unix_millis() / 1000), client.player.clone());
+    info!(
+        "🏆 Leaderboard for {}: ←/→ to page, M for your own entry, Tab to close",
+        view.date
+    );
+    client.request(&view.date, view.view);
+    commands.insert_resource(view);
+}
+
+/// System: ←/→ page through the leaderboard and M flips to the page around the player;
+/// a tap or Escape closes it
+///
+/// Presses over the leaderboard are drained, so gameplay doesn't act on them.
+pub fn browse_leaderboard(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut pointer_events: ResMut<Messages<WorldPointerEvent>>,
+    client: Res<LeaderboardClient>,
+    mut view: ResMut<LeaderboardView>,
+) {
+    let tapped = pointer_events
+        .drain()
+        .any(|event| event.event_type == PointerEventType::Down);
+    if tapped || keys.just_pressed(KeyCode::Escape) {
+        commands.remove_resource::<LeaderboardView>();
+        return;
+    }
+
+    let mut moved = false;
+    if keys.just_pressed(KeyCode::ArrowLeft) {
+        moved |= view.step(-1);
+    }
+    if keys.just_pressed(KeyCode::ArrowRight) {
+        moved |= view.step(1);
+    }
+    if keys.just_pressed(KeyCode::KeyM) {
+        if client.player.is_none() {
+            info!("🏆 No daily scores from this device to look around");
+        } else if view.view == BoardView::AroundMe {
+            moved |= view.step(0);
+        } else {
+            view.view = BoardView::AroundMe;
+            moved = true;
+        }
+    }
+
+    if moved {
+        client.request(&view.date, view.view);
+    }
+}
+
+/// System: Put the pages the server sends on show (replies for a view left since are
+/// dropped)
+pub fn receive_leaderboard(client: Res<LeaderboardClient>, view: Option<ResMut<LeaderboardView>>) {
+    let delivered = client.take_delivered();
+    let Some(mut view) = view else {
+        return;
+    };
+
+    for (asked, result) in delivered {
+        if asked != view.view {
+            continue;
+        }
+        let page = match result {
+            Ok(Some(body)) => serde_json::from_str::<ScorePage>(&body).map_err(|e| e.to_string()),
+            Ok(None) => Err("not found".to_string()),
+            Err(err) => Err(err),
+        };
+        match page {
+            Ok(page) => {
+                info!("🏆 {} on the board for {}", page.total, view.date);
+                view.page = Some(page);
+                for row in view.rows() {
+                    info!(
+                        "  {}{}: {} ({} solutions, {}s)",
+                        if row.mine { "▶ " } else { "" },
+                        row.rank,
+                        row.tag,
+                        row.solutions,
+                        row.seconds
+                    );
+                }
+            }
+            Err(err) => warn!("🏆 Leaderboard unavailable: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(total: usize, offset: usize, players: &[&str]) -> ScorePage {
+        ScorePage {
+            total,
+            offset,
+            entries: players
+                .iter()
+                .map(|player| ScoreRow {
+                    player: player.to_string(),
+                    time_ms: 61_500,
+                    solutions_found: 2,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_utc_date() {
+        assert_eq!(utc_date(0), "1970-01-01");
+        assert_eq!(utc_date(1_709_251_199), "2024-02-29");
+        assert_eq!(utc_date(1_792_195_200), "2026-10-17");
+    }
+
+    #[test]
+    fn test_urls_ask_for_a_page_or_the_players_entry() {
+        let client = LeaderboardClient::new("https://example.com", Some("p00ff".to_string()));
+        assert_eq!(
+            client.url("2026-10-17", BoardView::Top { page: 2 }),
+            "https://example.com/api/daily/2026-10-17/leaderboard?limit=5&offset=10"
+        );
+        assert_eq!(
+            client.url("2026-10-17", BoardView::AroundMe),
+            "https://example.com/api/daily/2026-10-17/leaderboard?limit=5&around=p00ff"
+        );
+    }
+
+    #[test]
+    fn test_paging_stops_at_either_end() {
+        let mut view = LeaderboardView::new("2026-10-17", None);
+        assert!(!view.step(-1));
+
+        view.page = Some(page(12, 0, &["a"]));
+        assert!(view.step(1));
+        assert!(view.step(1));
+        assert!(!view.step(1));
+        assert_eq!(view.view, BoardView::Top { page: 2 });
+
+        // From around the player, paging carries on from their page
+        view.view = BoardView::AroundMe;
+        view.page = Some(page(12, 6, &["a"]));
+        assert!(view.step(-1));
+        assert_eq!(view.view, BoardView::Top { page: 0 });
+    }
+
+    #[test]
+    fn test_rows_rank_tag_and_mark_the_player() {
+        let mut view = LeaderboardView::new("2026-10-17", Some("p12345678".to_string()));
+        view.page = Some(page(9, 5, &["p00ab12cd", "p12345678"]));
+
+        let rows = view.rows();
+        assert_eq!((rows[0].rank, rows[0].tag.as_str()), (6, "12cd"));
+        assert_eq!((rows[0].solutions, rows[0].seconds), (2, 61));
+        assert!(!rows[0].mine);
+        assert!(rows[1].mine);
+        // The heading, then four columns a row
+        assert_eq!(view.groups().len(), 1 + 4 * 2);
+    }
+}
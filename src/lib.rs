@@ -0,0 +1,25 @@
+// lib.rs - the game as a library, so integration tests can build it headless
+
+pub mod autosave;
+pub mod camera;
+pub mod challenge;
+#[cfg(feature = "dev")]
+pub mod dev;
+pub mod editor;
+pub mod event_log;
+pub mod export;
+pub mod game;
+pub mod graph;
+pub mod input;
+pub mod leaderboard;
+pub mod net;
+pub mod photo;
+pub mod profile;
+pub mod score;
+pub mod settings;
+pub mod sync;
+pub mod sync_rules;
+pub mod telemetry;
+pub mod visual;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub mod web;
@@ -1,36 +1,73 @@
 use bevy::prelude::*;
 
-mod camera;
-mod game;
-mod graph;
-mod input;
-mod visual;
-
 use bevy::window::WindowResolution;
-use camera::CameraPlugin;
-use input::InputPlugin;
-use visual::sdf::material::SdfMaterialPlugin;
-use visual::sdf::seven_segment::SevenSegmentMaterialPlugin;
-
-use crate::visual::plugin::GraphPlugin;
+use valence_sdf::camera::CameraPlugin;
+use valence_sdf::challenge::ChallengePlugin;
+use valence_sdf::editor::EditorPlugin;
+use valence_sdf::event_log::EventLogPlugin;
+use valence_sdf::export::ExportPlugin;
+use valence_sdf::game::clock::ClockPlugin;
+use valence_sdf::input::InputPlugin;
+use valence_sdf::leaderboard::LeaderboardPlugin;
+use valence_sdf::net::NetPlugin;
+use valence_sdf::photo::PhotoPlugin;
+use valence_sdf::profile::ProfilePlugin;
+use valence_sdf::score::ScorePlugin;
+use valence_sdf::settings::SettingsPlugin;
+use valence_sdf::sync::SyncPlugin;
+use valence_sdf::telemetry::TelemetryPlugin;
+use valence_sdf::visual::plugin::GraphPlugin;
+use valence_sdf::visual::sdf::material::SdfMaterialPlugin;
+use valence_sdf::visual::sdf::seven_segment::SevenSegmentMaterialPlugin;
 
 fn main() {
     let mut app = App::new();
 
-    app.add_plugins(DefaultPlugins.set(WindowPlugin {
-        primary_window: Some(Window {
-            title: "Valence SDF".into(),
-            resolution: WindowResolution::new(1080, 1920),
-            resizable: true,
-            ..default()
-        }),
+    let window = Window {
+        title: "Valence SDF".into(),
+        resolution: WindowResolution::new(1080, 1920),
+        resizable: true,
         ..default()
-    }))
+    };
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    let window = valence_sdf::web::configure_window(window);
+
+    app.add_plugins(
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(window),
+                ..default()
+            })
+            .set(AssetPlugin {
+                // Shader hot-reload is a dev-only feature (`cargo run --features dev`)
+                watch_for_changes_override: Some(cfg!(feature = "dev")),
+                ..default()
+            }),
+    )
+    .add_plugins(SettingsPlugin)
+    .add_plugins(ClockPlugin)
     .add_plugins(CameraPlugin)
     .add_plugins(InputPlugin)
+    .add_plugins(EventLogPlugin)
+    .add_plugins(ExportPlugin)
+    .add_plugins(PhotoPlugin)
+    .add_plugins(ChallengePlugin)
+    .add_plugins(EditorPlugin)
     .add_plugins(SdfMaterialPlugin)
     .add_plugins(SevenSegmentMaterialPlugin)
-    .add_plugins(GraphPlugin);
+    .add_plugins(GraphPlugin)
+    .add_plugins(TelemetryPlugin)
+    .add_plugins(NetPlugin)
+    .add_plugins(ProfilePlugin)
+    .add_plugins(ScorePlugin)
+    .add_plugins(SyncPlugin)
+    .add_plugins(LeaderboardPlugin);
+
+    #[cfg(feature = "dev")]
+    app.add_plugins(valence_sdf::dev::DevPlugin);
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    app.add_plugins(valence_sdf::web::WebPlugin);
 
     app.run();
 }
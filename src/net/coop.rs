@@ -0,0 +1,215 @@
+// net/coop.rs - shared-board co-op: everyone in a room draws on the same puzzle
+
+use std::cmp::Reverse;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{RoomLink, RoomMessage};
+use crate::{
+    game::{progression::ProgressionTracker, session::PuzzleSession},
+    graph::{Edge, NodeId, Solution, Valences},
+};
+
+/// The whole shared board, sent after every local change
+///
+/// The trail is last-writer-wins: the server relays states in one order and each
+/// client adopts the newest trail that replays legally. Found solutions only grow, so
+/// they're merged as a union and nothing is lost when trails collide.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoardState {
+    pub level: usize,
+    pub valences: [usize; 9],
+    pub total_solutions: usize,
+    pub trail: Vec<usize>,
+    /// Each found solution as its edges, sorted
+    pub found: Vec<Vec<[usize; 2]>>,
+}
+
+impl BoardState {
+    pub fn capture(session: &PuzzleSession, tracker: &ProgressionTracker) -> Self {
+        let valences = session.puzzle_valences();
+        let mut found: Vec<Vec<[usize; 2]>> = session
+            .found_solutions()
+            .iter()
+            .map(|solution| {
+                let mut edges: Vec<[usize; 2]> = solution
+                    .edges()
+                    .iter()
+                    .map(|edge| [edge.from.0, edge.to.0])
+                    .collect();
+                edges.sort_unstable();
+                edges
+            })
+            .collect();
+        found.sort_unstable();
+
+        Self {
+            level: tracker.current_level,
+            valences: std::array::from_fn(|i| valences.get(NodeId(i))),
+            total_solutions: session.progress().total_solutions.unwrap_or_default(),
+            trail: session.current_trail().iter().map(|node| node.0).collect(),
+            found,
+        }
+    }
+
+    /// Whether this board's puzzle replaces `other`'s when two players moved on at once
+    ///
+    /// Both clients pick a puzzle when a level completes; the higher level wins, then
+    /// the smaller valences, so every client settles on the same one.
+    fn supersedes(&self, other: &BoardState) -> bool {
+        (self.level, Reverse(self.valences)) > (other.level, Reverse(other.valences))
+    }
+
+    /// Nodes on a 3x3 board have at most 8 neighbours
+    pub(crate) fn is_well_formed(&self) -> bool {
+        self.valences.iter().all(|&v| v <= 8)
+            && self.trail.iter().all(|&node| node < 9)
+            && self
+                .found
+                .iter()
+                .flatten()
+                .all(|edge| edge.iter().all(|&node| node < 9))
+    }
+
+    pub(crate) fn trail_nodes(&self) -> Vec<NodeId> {
+        self.trail.iter().copied().map(NodeId).collect()
+    }
+
+    pub(crate) fn solutions(&self) -> impl Iterator<Item = Solution> + '_ {
+        self.found.iter().map(|edges| {
+            let mut solution = Solution::new();
+            for &[a, b] in edges {
+                solution.add_edge(Edge::new(NodeId(a), NodeId(b)));
+            }
+            solution
+        })
+    }
+}
+
+/// Resource: The board as the room last saw it, so only real changes are sent
+#[derive(Resource, Debug, Default)]
+pub struct SharedBoard {
+    last_shared: Option<BoardState>,
+}
+
+/// System: Merge boards from the other players into the local session
+pub fn apply_remote_boards(
+    mut messages: MessageReader<RoomMessage>,
+    mut session: ResMut<PuzzleSession>,
+    mut tracker: ResMut<ProgressionTracker>,
+    mut shared: ResMut<SharedBoard>,
+) {
+    for message in messages.read() {
+        let board = match message {
+            RoomMessage::Board(board) => board,
+            // The room's last board, if it has one; otherwise ours becomes the room's
+            RoomMessage::Welcome { latest, .. } => {
+                let Some(board) = latest.iter().find_map(|m| match m {
+                    RoomMessage::Board(board) => Some(board),
+                    _ => None,
+                }) else {
+                    continue;
+                };
+                board
+            }
+            _ => continue,
+        };
+        if !board.is_well_formed() {
+            warn!("Ignoring malformed shared board");
+            continue;
+        }
+
+        let local = BoardState::capture(&session, &tracker);
+        if board.valences != local.valences {
+            if !board.supersedes(&local) {
+                // Ours wins; the other players switch when they see our board
+                continue;
+            }
+            info!("🔗 Switching to the shared puzzle (level {})", board.level);
+            *session =
+                PuzzleSession::new(Valences::from_array(board.valences), board.total_solutions);
+            *tracker = ProgressionTracker::at_level(board.level);
+        }
+
+        let added = session.merge_solutions(board.solutions());
+        if added > 0 {
+            info!("🔗 Partner found {} new solutions", added);
+        }
+        let trail = board.trail_nodes();
+        if trail != session.current_trail()
+            && let Err(err) = session.replace_trail(&trail)
+        {
+            // Our own trail stays and overwrites theirs on the next publish
+            warn!("Rejected shared trail: {}", err);
+        }
+
+        shared.last_shared = Some(board.clone());
+    }
+}
+
+/// System: Send the local board whenever it differs from what the room last saw
+pub fn publish_local_board(
+    session: Res<PuzzleSession>,
+    tracker: Res<ProgressionTracker>,
+    mut shared: ResMut<SharedBoard>,
+    mut link: ResMut<RoomLink>,
+) {
+    let changed = session.is_changed() || tracker.is_changed() || link.is_changed();
+    if !link.welcomed || !changed {
+        return;
+    }
+
+    let board = BoardState::capture(&session, &tracker);
+    if shared.last_shared.as_ref() == Some(&board) {
+        return;
+    }
+
+    shared.last_shared = Some(board.clone());
+    link.send(RoomMessage::Board(board));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_round_trips_into_another_session() {
+        let valences = Valences::new(vec![2, 2, 0, 2, 0, 0, 0, 0, 0]);
+        let tracker = ProgressionTracker::at_level(3);
+        let mut ours = PuzzleSession::new(valences.clone(), 1);
+        for node in [0, 1, 3, 0] {
+            ours.add_node(NodeId(node));
+        }
+        ours.reset();
+        ours.add_node(NodeId(1));
+
+        let board = BoardState::capture(&ours, &tracker);
+        assert!(board.is_well_formed());
+        let json = serde_json::to_string(&RoomMessage::Board(board.clone())).unwrap();
+        let RoomMessage::Board(received) = serde_json::from_str(&json).unwrap() else {
+            panic!("not a board");
+        };
+
+        let mut theirs = PuzzleSession::new(valences, 1);
+        assert_eq!(theirs.merge_solutions(received.solutions()), 1);
+        theirs.replace_trail(&received.trail_nodes()).unwrap();
+        assert_eq!(BoardState::capture(&theirs, &tracker), board);
+    }
+
+    #[test]
+    fn test_puzzle_tie_break_is_symmetric() {
+        let board = |level, first| BoardState {
+            level,
+            valences: [first, 1, 0, 0, 0, 0, 0, 0, 0],
+            total_solutions: 1,
+            trail: Vec::new(),
+            found: Vec::new(),
+        };
+
+        assert!(board(4, 1).supersedes(&board(3, 0)));
+        assert!(board(3, 0).supersedes(&board(3, 1)));
+        assert!(!board(3, 1).supersedes(&board(3, 0)));
+        assert!(!board(3, 1).supersedes(&board(3, 1)));
+    }
+}
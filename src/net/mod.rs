@@ -0,0 +1,333 @@
+// net/mod.rs - a WebSocket link to a server room (`/ws/room/{id}`) shared with other players
+
+pub mod coop;
+pub mod race;
+pub mod replay;
+pub mod spectate;
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::visual::{interactions::handle_pointer_input, setup::check_level_progression};
+pub use coop::{BoardState, SharedBoard};
+pub use race::{RaceProgress, RaceState};
+pub use replay::{Recorder, Replay, ReplayFrame};
+pub use spectate::{Spectator, not_spectating};
+
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<RoomMessage>()
+            .init_resource::<SharedBoard>()
+            .init_resource::<RaceState>()
+            .add_systems(
+                Update,
+                (
+                    receive_room_messages,
+                    coop::apply_remote_boards.run_if(in_room_mode(RoomMode::Coop)),
+                    race::apply_race_progress.run_if(in_room_mode(RoomMode::Race)),
+                )
+                    .chain()
+                    .after(handle_pointer_input)
+                    .before(check_level_progression)
+                    .run_if(resource_exists::<RoomLink>),
+            )
+            .add_systems(
+                Update,
+                (
+                    replay::play_replay.run_if(resource_exists::<Replay>),
+                    spectate::follow_spectated_boards,
+                    spectate::boost_spectator_bursts,
+                )
+                    .chain()
+                    .after(receive_room_messages)
+                    .after(handle_pointer_input)
+                    .before(check_level_progression)
+                    .run_if(resource_exists::<Spectator>),
+            )
+            .add_systems(
+                Update,
+                spectate::boost_spectator_shake
+                    .run_if(resource_added::<Spectator>.or(resource_removed::<Spectator>)),
+            );
+        app.add_systems(
+            Update,
+            (
+                coop::publish_local_board.run_if(in_room_mode(RoomMode::Coop)),
+                (race::align_race_puzzle, race::publish_race_progress)
+                    .chain()
+                    .run_if(in_room_mode(RoomMode::Race)),
+                flush_room_link,
+            )
+                .chain()
+                .after(check_level_progression)
+                .run_if(resource_exists::<RoomLink>),
+        );
+        app.add_systems(
+            Update,
+            replay::record_boards
+                .after(check_level_progression)
+                .run_if(resource_exists::<Recorder>),
+        );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(PreStartup, replay::start_replay_from_env);
+    }
+}
+
+/// What the players in a room do together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomMode {
+    /// One shared board everyone draws on
+    Coop,
+    /// Same puzzles, separate boards; the HUD shows the opponent's count
+    Race,
+    /// Watch the shared board without playing (streams and kiosks)
+    Spectate,
+}
+
+/// Run condition: Connected to a room in this mode
+pub fn in_room_mode(mode: RoomMode) -> impl Fn(Option<Res<RoomLink>>) -> bool + Clone {
+    move |link: Option<Res<RoomLink>>| link.is_some_and(|link| link.mode == mode)
+}
+
+/// Everything sent through a room; the server relays it to the other peers
+#[derive(Message, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RoomMessage {
+    /// From the server on joining: peers already there and the room's latest messages
+    Welcome {
+        peers: usize,
+        latest: Vec<RoomMessage>,
+    },
+    /// From the server when someone joins or leaves (count excludes us)
+    Peers { count: usize },
+    /// Shared-board state (co-op)
+    Board(BoardState),
+    /// A racer's progress (race mode)
+    Race(RaceProgress),
+    /// A message type this build doesn't know
+    #[serde(other)]
+    Unknown,
+}
+
+/// Resource: Present while connected to a room
+///
+/// Messages cross the socket outside the ECS: incoming ones collect in `inbox` until
+/// `receive_room_messages` turns them into `RoomMessage`s, outgoing ones wait in
+/// `outbox` for `flush_room_link`.
+#[derive(Resource, Debug)]
+pub struct RoomLink {
+    room: String,
+    pub mode: RoomMode,
+    inbox: Arc<Mutex<Vec<Result<RoomMessage, String>>>>,
+    outbox: Vec<RoomMessage>,
+    /// Other peers in the room
+    pub peers: usize,
+    /// The server's welcome has arrived (sends before it would race the room's state)
+    pub welcomed: bool,
+}
+
+impl RoomLink {
+    /// A link that isn't connected to anything (tests feed it with `deliver`)
+    pub fn offline(room: impl Into<String>, mode: RoomMode) -> Self {
+        Self {
+            room: room.into(),
+            mode,
+            inbox: Arc::default(),
+            outbox: Vec::new(),
+            peers: 0,
+            welcomed: false,
+        }
+    }
+
+    /// Open the socket to `/ws/room/{room}` on the page's own server
+    pub fn connect(room: impl Into<String>, mode: RoomMode) -> Self {
+        let link = Self::offline(room, mode);
+        transport::connect(&link.room, link.inbox.clone());
+        link
+    }
+
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    /// Queue a message for the other peers
+    pub fn send(&mut self, message: RoomMessage) {
+        self.outbox.push(message);
+    }
+
+    /// Queue a received frame for the next `receive_room_messages`
+    pub fn deliver(&self, text: &str) {
+        let message = serde_json::from_str::<RoomMessage>(text).map_err(|e| e.to_string());
+        self.inbox.lock().unwrap().push(message);
+    }
+
+    fn take_received(&self) -> Vec<Result<RoomMessage, String>> {
+        std::mem::take(&mut *self.inbox.lock().unwrap())
+    }
+
+    /// Messages queued by `send`, oldest first
+    pub fn take_outgoing(&mut self) -> Vec<RoomMessage> {
+        std::mem::take(&mut self.outbox)
+    }
+}
+
+/// System: Turn frames from the socket into `RoomMessage`s
+pub fn receive_room_messages(mut link: ResMut<RoomLink>, mut out: MessageWriter<RoomMessage>) {
+    for message in link.take_received() {
+        match message {
+            Ok(RoomMessage::Welcome { peers, latest }) => {
+                info!("🔗 Joined room {} ({} other players)", link.room, peers);
+                link.peers = peers;
+                link.welcomed = true;
+                out.write(RoomMessage::Welcome { peers, latest });
+            }
+            Ok(RoomMessage::Peers { count }) => {
+                info!("🔗 Room {}: {} other players", link.room, count);
+                link.peers = count;
+                out.write(RoomMessage::Peers { count });
+            }
+            Ok(message) => {
+                out.write(message);
+            }
+            Err(err) => warn!("Room {}: {}", link.room, err),
+        }
+    }
+}
+
+/// System: Send queued messages once the server has welcomed us
+fn flush_room_link(mut link: ResMut<RoomLink>) {
+    if !link.welcomed || link.outbox.is_empty() {
+        return;
+    }
+
+    for message in link.take_outgoing() {
+        match serde_json::to_string(&message) {
+            Ok(text) => transport::send(&text),
+            Err(err) => warn!("Room {}: unsendable message: {}", link.room, err),
+        }
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod transport {
+    use std::{
+        cell::RefCell,
+        sync::{Arc, Mutex},
+    };
+
+    use bevy::prelude::*;
+    use wasm_bindgen::{JsCast, closure::Closure};
+    use web_sys::{MessageEvent, WebSocket};
+
+    use super::RoomMessage;
+
+    type Inbox = Arc<Mutex<Vec<Result<RoomMessage, String>>>>;
+
+    thread_local! {
+        // The browser is single-threaded and the game joins one room at a time
+        static SOCKET: RefCell<Option<WebSocket>> = const { RefCell::new(None) };
+    }
+
+    pub fn connect(room: &str, inbox: Inbox) {
+        let Some(location) = web_sys::window().map(|window| window.location()) else {
+            return;
+        };
+        let scheme = match location.protocol().as_deref() {
+            Ok("https:") => "wss",
+            _ => "ws",
+        };
+        let host = location.host().unwrap_or_default();
+        let url = format!("{scheme}://{host}/ws/room/{room}");
+
+        let socket = match WebSocket::new(&url) {
+            Ok(socket) => socket,
+            Err(err) => {
+                warn!("Failed to open {}: {:?}", url, err);
+                return;
+            }
+        };
+
+        let messages = inbox.clone();
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            if let Some(text) = event.data().as_string() {
+                let message = serde_json::from_str(&text).map_err(|e| e.to_string());
+                messages.lock().unwrap().push(message);
+            }
+        });
+        let on_close = Closure::<dyn FnMut()>::new(move || {
+            inbox
+                .lock()
+                .unwrap()
+                .push(Err("connection closed".to_string()));
+        });
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        // The handlers live as long as the page
+        on_message.forget();
+        on_close.forget();
+        SOCKET.with(|cell| *cell.borrow_mut() = Some(socket));
+    }
+
+    pub fn send(text: &str) {
+        SOCKET.with(|cell| {
+            let sent = cell
+                .borrow()
+                .as_ref()
+                .map(|socket| socket.send_with_str(text));
+            if !matches!(sent, Some(Ok(()))) {
+                debug!("Room message dropped: socket not open");
+            }
+        });
+    }
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+mod transport {
+    use std::sync::{Arc, Mutex};
+
+    use bevy::prelude::*;
+
+    use super::RoomMessage;
+
+    pub fn connect(room: &str, _inbox: Arc<Mutex<Vec<Result<RoomMessage, String>>>>) {
+        debug!(
+            "Not joining room {}: rooms are only joined in the wasm build",
+            room
+        );
+    }
+
+    pub fn send(_text: &str) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_round_trip() {
+        let link = RoomLink::offline("lobby", RoomMode::Coop);
+        link.deliver(r#"{"type":"welcome","peers":1,"latest":[{"type":"emote","x":1}]}"#);
+        link.deliver(r#"{"type":"peers","count":0}"#);
+        link.deliver("{}");
+
+        let received = link.take_received();
+        assert_eq!(
+            received[0],
+            Ok(RoomMessage::Welcome {
+                peers: 1,
+                latest: vec![RoomMessage::Unknown],
+            })
+        );
+        assert_eq!(received[1], Ok(RoomMessage::Peers { count: 0 }));
+        assert!(received[2].is_err());
+
+        let text = serde_json::to_string(&RoomMessage::Peers { count: 2 }).unwrap();
+        assert_eq!(text, r#"{"type":"peers","count":2}"#);
+    }
+}
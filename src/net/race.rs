@@ -0,0 +1,217 @@
+// net/race.rs - race mode: everyone in the room plays the same puzzles, each on their own board
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{RoomLink, RoomMessage};
+use crate::{
+    game::{progression::ProgressionTracker, session::PuzzleSession},
+    graph::{NodeId, Valences},
+};
+
+/// Real seconds the HUD pip takes to settle after the opponent scores
+pub const PULSE_SECONDS: f32 = 0.6;
+
+/// A racer's position, sent whenever their level, puzzle or solution count changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RaceProgress {
+    pub level: usize,
+    pub valences: [usize; 9],
+    pub total_solutions: usize,
+    pub solutions_found: usize,
+}
+
+impl RaceProgress {
+    pub fn capture(session: &PuzzleSession, tracker: &ProgressionTracker) -> Self {
+        let puzzle = RacePuzzle::of(session);
+        Self {
+            level: tracker.current_level,
+            valences: puzzle.valences,
+            total_solutions: puzzle.total_solutions,
+            solutions_found: session.progress().solutions_found,
+        }
+    }
+
+    fn puzzle(&self) -> RacePuzzle {
+        RacePuzzle {
+            valences: self.valences,
+            total_solutions: self.total_solutions,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RacePuzzle {
+    valences: [usize; 9],
+    total_solutions: usize,
+}
+
+impl RacePuzzle {
+    fn of(session: &PuzzleSession) -> Self {
+        let valences = session.puzzle_valences();
+        Self {
+            valences: std::array::from_fn(|i| valences.get(NodeId(i))),
+            total_solutions: session.progress().total_solutions.unwrap_or_default(),
+        }
+    }
+}
+
+/// Resource: What the room agreed on and how the opponent is doing
+///
+/// The first puzzle announced for a level is that level's race puzzle; players who get
+/// there later switch to it. When two players reach a level at the same moment, both
+/// settle on the one with the smaller valences.
+#[derive(Resource, Debug, Default)]
+pub struct RaceState {
+    puzzles: HashMap<usize, RacePuzzle>,
+    /// The other racer's latest progress (the most recent sender, if there are several)
+    pub opponent: Option<RaceProgress>,
+    /// Real seconds when the opponent last found a solution
+    pub opponent_scored_at: Option<f32>,
+    last_sent: Option<RaceProgress>,
+}
+
+impl RaceState {
+    /// The opponent's solution count, while they're on our level
+    pub fn opponent_found(&self, level: usize) -> Option<usize> {
+        self.opponent
+            .filter(|opponent| opponent.level == level)
+            .map(|opponent| opponent.solutions_found)
+    }
+
+    /// HUD pip phase: 0 the moment the opponent scores, 1 once settled
+    pub fn pulse(&self, now: f32) -> f32 {
+        self.opponent_scored_at
+            .map_or(1.0, |at| ((now - at) / PULSE_SECONDS).clamp(0.0, 1.0))
+    }
+
+    /// Take in another racer's progress
+    fn receive(&mut self, progress: RaceProgress, now: f32) {
+        let theirs = progress.puzzle();
+        let settled = self.puzzles.entry(progress.level).or_insert(theirs);
+        if theirs.valences < settled.valences {
+            *settled = theirs;
+        }
+
+        let scored = self.opponent.is_some_and(|previous| {
+            (progress.level, progress.solutions_found) > (previous.level, previous.solutions_found)
+        });
+        if scored {
+            self.opponent_scored_at = Some(now);
+        }
+        self.opponent = Some(progress);
+    }
+}
+
+/// System: Track the other racer from their progress messages
+pub fn apply_race_progress(
+    mut messages: MessageReader<RoomMessage>,
+    mut race: ResMut<RaceState>,
+    time: Res<Time<Real>>,
+) {
+    for message in messages.read() {
+        let progress = match message {
+            RoomMessage::Race(progress) => Some(*progress),
+            RoomMessage::Welcome { latest, .. } => latest.iter().find_map(|m| match m {
+                RoomMessage::Race(progress) => Some(*progress),
+                _ => None,
+            }),
+            _ => None,
+        };
+        let Some(progress) = progress else {
+            continue;
+        };
+        if progress.valences.iter().any(|&v| v > 8) {
+            warn!("Ignoring malformed race progress");
+            continue;
+        }
+
+        race.receive(progress, time.elapsed_secs());
+    }
+}
+
+/// System: Play the room's puzzle for the current level, or make ours the room's
+pub fn align_race_puzzle(
+    mut session: ResMut<PuzzleSession>,
+    tracker: Res<ProgressionTracker>,
+    mut race: ResMut<RaceState>,
+) {
+    if !(session.is_changed() || tracker.is_changed() || race.is_changed()) {
+        return;
+    }
+
+    let ours = RacePuzzle::of(&session);
+    let settled = *race.puzzles.entry(tracker.current_level).or_insert(ours);
+    if settled != ours {
+        info!(
+            "🏁 Switching to the race puzzle for level {}",
+            tracker.current_level
+        );
+        *session = PuzzleSession::new(
+            Valences::from_array(settled.valences),
+            settled.total_solutions,
+        );
+    }
+}
+
+/// System: Send our progress whenever it changes
+pub fn publish_race_progress(
+    session: Res<PuzzleSession>,
+    tracker: Res<ProgressionTracker>,
+    mut race: ResMut<RaceState>,
+    mut link: ResMut<RoomLink>,
+) {
+    if !link.welcomed {
+        return;
+    }
+
+    let progress = RaceProgress::capture(&session, &tracker);
+    if race.last_sent == Some(progress) {
+        return;
+    }
+    race.last_sent = Some(progress);
+    link.send(RoomMessage::Race(progress));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(level: usize, first: usize, solutions_found: usize) -> RaceProgress {
+        RaceProgress {
+            level,
+            valences: [first, 1, 0, 0, 0, 0, 0, 0, 0],
+            total_solutions: 1,
+            solutions_found,
+        }
+    }
+
+    #[test]
+    fn test_level_settles_on_smallest_announced_puzzle() {
+        let mut race = RaceState::default();
+        race.receive(progress(5, 3, 0), 0.0);
+        race.receive(progress(5, 1, 0), 0.0);
+        race.receive(progress(5, 2, 0), 0.0);
+
+        assert_eq!(race.puzzles[&5].valences[0], 1);
+    }
+
+    #[test]
+    fn test_pulse_when_opponent_scores() {
+        let mut race = RaceState::default();
+        race.receive(progress(5, 1, 0), 1.0);
+        assert_eq!(race.pulse(1.0), 1.0);
+
+        race.receive(progress(5, 1, 1), 2.0);
+        assert_eq!(race.pulse(2.0), 0.0);
+        assert_eq!(race.pulse(2.0 + 2.0 * PULSE_SECONDS), 1.0);
+        assert_eq!(race.opponent_found(5), Some(1));
+        assert_eq!(race.opponent_found(6), None);
+
+        // Finishing the level counts as scoring too
+        race.receive(progress(6, 1, 0), 10.0);
+        assert_eq!(race.pulse(10.0), 0.0);
+    }
+}
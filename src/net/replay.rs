@@ -0,0 +1,205 @@
+// net/replay.rs - record a session's boards to a file and play them back for spectators
+
+use std::io::Write;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{BoardState, RoomMessage};
+use crate::game::{progression::ProgressionTracker, session::PuzzleSession};
+
+/// Environment variable naming a file to record the session's boards into (native only)
+pub const RECORD_ENV_VAR: &str = "VALENCE_RECORD";
+
+/// Environment variable naming a replay file to spectate instead of playing (native only)
+pub const SPECTATE_ENV_VAR: &str = "VALENCE_SPECTATE";
+
+/// Real seconds a replay holds its last board before looping (kiosks play it forever)
+const LOOP_PAUSE: f32 = 4.0;
+
+/// One line of a replay file: the board `at` real seconds into the recording
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub at: f32,
+    pub board: BoardState,
+}
+
+/// Resource: A recording being played back as `RoomMessage::Board`s
+#[derive(Resource, Debug)]
+pub struct Replay {
+    frames: Vec<ReplayFrame>,
+    next: usize,
+    /// Real time the current loop started
+    started_at: Option<f32>,
+}
+
+impl Replay {
+    /// Parse a replay file (JSON lines; blank lines are skipped)
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut frames = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str::<ReplayFrame>(line)
+                    .map_err(|e| format!("line {}: {e}", i + 1))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if frames.is_empty() {
+            return Err("no frames".to_string());
+        }
+        frames.sort_by(|a, b| a.at.total_cmp(&b.at));
+
+        Ok(Self {
+            frames,
+            next: 0,
+            started_at: None,
+        })
+    }
+
+    /// Frames due by real time `now`, starting over after the last one has been held
+    fn due(&mut self, now: f32) -> &[ReplayFrame] {
+        let started_at = *self.started_at.get_or_insert(now);
+        let elapsed = now - started_at;
+
+        let last = self.frames[self.frames.len() - 1].at;
+        if self.next == self.frames.len() && elapsed >= last + LOOP_PAUSE {
+            self.next = 0;
+            self.started_at = Some(now);
+            return self.due(now);
+        }
+
+        let from = self.next;
+        while self.next < self.frames.len() && self.frames[self.next].at <= elapsed {
+            self.next += 1;
+        }
+        &self.frames[from..self.next]
+    }
+}
+
+/// Resource: Present while recording; boards are appended to the file as they change
+#[derive(Resource)]
+pub struct Recorder {
+    file: std::io::BufWriter<std::fs::File>,
+    last: Option<BoardState>,
+    started_at: Option<f32>,
+}
+
+impl Recorder {
+    pub fn create(path: &str) -> std::io::Result<Self> {
+        Ok(Self {
+            file: std::io::BufWriter::new(std::fs::File::create(path)?),
+            last: None,
+            started_at: None,
+        })
+    }
+}
+
+/// System: Start recording or spectating from the environment (native only)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn start_replay_from_env(mut commands: Commands) {
+    if let Ok(path) = std::env::var(SPECTATE_ENV_VAR) {
+        match std::fs::read_to_string(&path).map_err(|e| e.to_string()) {
+            Ok(text) => match Replay::parse(&text) {
+                Ok(replay) => {
+                    info!("📺 Spectating {} ({} boards)", path, replay.frames.len());
+                    commands.insert_resource(replay);
+                    commands.insert_resource(super::Spectator);
+                }
+                Err(err) => warn!("Failed to parse replay {}: {}", path, err),
+            },
+            Err(err) => warn!("Failed to read replay {}: {}", path, err),
+        }
+    } else if let Ok(path) = std::env::var(RECORD_ENV_VAR) {
+        match Recorder::create(&path) {
+            Ok(recorder) => {
+                info!("⏺ Recording boards to {}", path);
+                commands.insert_resource(recorder);
+            }
+            Err(err) => warn!("Failed to record to {}: {}", path, err),
+        }
+    }
+}
+
+/// System: Feed due frames to the spectator
+pub fn play_replay(
+    time: Res<Time<Real>>,
+    mut replay: ResMut<Replay>,
+    mut out: MessageWriter<RoomMessage>,
+) {
+    for frame in replay.due(time.elapsed_secs()) {
+        out.write(RoomMessage::Board(frame.board.clone()));
+    }
+}
+
+/// System: Append the board whenever it changes
+pub fn record_boards(
+    time: Res<Time<Real>>,
+    session: Res<PuzzleSession>,
+    tracker: Res<ProgressionTracker>,
+    mut recorder: ResMut<Recorder>,
+) {
+    if !(session.is_changed() || tracker.is_changed()) {
+        return;
+    }
+    let board = BoardState::capture(&session, &tracker);
+    if recorder.last.as_ref() == Some(&board) {
+        return;
+    }
+
+    let now = time.elapsed_secs();
+    let frame = ReplayFrame {
+        at: now - *recorder.started_at.get_or_insert(now),
+        board: board.clone(),
+    };
+    let written = serde_json::to_string(&frame)
+        .map_err(std::io::Error::other)
+        .and_then(|line| {
+            writeln!(recorder.file, "{line}")?;
+            recorder.file.flush()
+        });
+    if let Err(err) = written {
+        warn!("Failed to record board: {}", err);
+    }
+    recorder.last = Some(board);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(at: f32, level: usize) -> String {
+        let board = BoardState {
+            level,
+            valences: [1, 1, 0, 0, 0, 0, 0, 0, 0],
+            total_solutions: 1,
+            trail: Vec::new(),
+            found: Vec::new(),
+        };
+        serde_json::to_string(&ReplayFrame { at, board }).unwrap()
+    }
+
+    #[test]
+    fn test_replay_plays_in_order_then_loops() {
+        let text = format!(
+            "{}\n\n{}\n{}\n",
+            frame(2.0, 2),
+            frame(0.0, 1),
+            frame(3.0, 3)
+        );
+        let mut replay = Replay::parse(&text).unwrap();
+        let levels =
+            |frames: &[ReplayFrame]| frames.iter().map(|f| f.board.level).collect::<Vec<_>>();
+
+        assert_eq!(levels(replay.due(10.0)), vec![1]);
+        assert_eq!(levels(replay.due(12.5)), vec![2]);
+        assert_eq!(levels(replay.due(20.0)), vec![3]);
+        assert_eq!(levels(replay.due(20.0 + LOOP_PAUSE)), vec![1]);
+    }
+
+    #[test]
+    fn test_bad_replays_are_rejected() {
+        assert!(Replay::parse("").is_err());
+        assert!(Replay::parse(&format!("{}\nnot json", frame(0.0, 1))).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+// net/spectate.rs - read-only spectating of a room's shared board, for streams and kiosks
+
+use bevy::prelude::*;
+
+use super::{BoardState, RoomMessage};
+use crate::{
+    camera::{CameraShake, MainCamera},
+    game::{progression::ProgressionTracker, rng::GameRng, session::PuzzleSession},
+    graph::Valences,
+    visual::{
+        interactions::SolutionFound,
+        nodes::{GraphNode, NodeVisual},
+        particles::ParticleSystem,
+        physics::NodePhysics,
+    },
+};
+
+/// Extra particle bursts per node on top of the usual one, so solutions read on a stream
+const EXTRA_BURSTS: usize = 2;
+
+/// Screen shake multiplier while spectating (still off with reduced motion)
+const SPECTATOR_SHAKE: f32 = 1.75;
+
+/// Resource: Present while spectating; the board follows a feed and pointer input is ignored
+///
+/// The feed is either a room (`RoomMode::Spectate`) or a replay file; both arrive as
+/// `RoomMessage::Board`s.
+#[derive(Resource, Debug, Default)]
+pub struct Spectator;
+
+/// Run condition: Pointer input drives the board (not spectating)
+pub fn not_spectating(spectator: Option<Res<Spectator>>) -> bool {
+    spectator.is_none()
+}
+
+/// System: Turn up screen shake while spectating, leaving the saved setting alone
+pub fn boost_spectator_shake(
+    spectator: Option<Res<Spectator>>,
+    mut cameras: Query<&mut CameraShake, With<MainCamera>>,
+) {
+    let boost = if spectator.is_some() {
+        SPECTATOR_SHAKE
+    } else {
+        1.0
+    };
+    for mut shake in &mut cameras {
+        shake.boost = boost;
+    }
+}
+
+/// System: Show each board from the feed, celebrating the solutions it adds
+///
+/// Unlike co-op, the feed always wins: a spectator has no board of its own to keep.
+pub fn follow_spectated_boards(
+    mut messages: MessageReader<RoomMessage>,
+    mut session: ResMut<PuzzleSession>,
+    mut tracker: ResMut<ProgressionTracker>,
+    nodes: Query<(&GraphNode, &NodePhysics)>,
+    mut solutions: MessageWriter<SolutionFound>,
+) {
+    for message in messages.read() {
+        let board = match message {
+            RoomMessage::Board(board) => board,
+            RoomMessage::Welcome { latest, .. } => {
+                let Some(board) = latest.iter().find_map(|m| match m {
+                    RoomMessage::Board(board) => Some(board),
+                    _ => None,
+                }) else {
+                    continue;
+                };
+                board
+            }
+            _ => continue,
+        };
+        if !board.is_well_formed() {
+            warn!("Ignoring malformed spectated board");
+            continue;
+        }
+
+        let local = BoardState::capture(&session, &tracker);
+        if board.valences != local.valences || board.level != local.level {
+            info!("📺 Spectating level {}", board.level);
+            *session =
+                PuzzleSession::new(Valences::from_array(board.valences), board.total_solutions);
+            *tracker = ProgressionTracker::at_level(board.level);
+        }
+
+        // A solution ends where the player's trail last was
+        let last_position = session
+            .current_trail()
+            .last()
+            .and_then(|&last| nodes.iter().find(|(node, _)| node.node_id == last))
+            .map_or(Vec3::ZERO, |(_, physics)| physics.position);
+        for _ in 0..session.merge_solutions(board.solutions()) {
+            solutions.write(SolutionFound {
+                last_position,
+                is_new: true,
+            });
+        }
+
+        let trail = board.trail_nodes();
+        if trail != session.current_trail()
+            && let Err(err) = session.replace_trail(&trail)
+        {
+            warn!("Spectated trail doesn't replay: {}", err);
+        }
+    }
+}
+
+/// System: Throw extra particles for each solution while spectating
+pub fn boost_spectator_bursts(
+    mut solutions: MessageReader<SolutionFound>,
+    nodes: Query<(&NodePhysics, &NodeVisual), With<GraphNode>>,
+    mut particles: ResMut<ParticleSystem>,
+    mut rng: ResMut<GameRng>,
+) {
+    for solution in solutions.read() {
+        if !solution.is_new {
+            continue;
+        }
+        for _ in 0..EXTRA_BURSTS {
+            for (physics, visual) in &nodes {
+                particles.emit_burst(physics.position, visual.current_color, &mut *rng);
+            }
+        }
+    }
+}
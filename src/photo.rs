@@ -0,0 +1,179 @@
+// photo.rs - photo mode: the HUD hides, the camera zooms and pans freely, the theme's
+// glow and light can be nudged, and X saves the frame as a PNG
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, save_to_disk};
+
+use crate::{
+    camera::{GameCamera, apply_camera_view},
+    visual::{
+        theme::{Theme, apply_theme},
+        ui::{LevelSummary, ProfileSelect, hud::HudPlane},
+    },
+};
+
+/// Zoom factor per +/- press
+const ZOOM_STEP: f32 = 1.15;
+
+/// Pan speed with the arrow keys, in screen heights per second
+const PAN_SPEED: f32 = 0.5;
+
+/// How fast the sliders move per second held: glow, ambient and light angle (radians)
+const GLOW_RATE: f32 = 1.0;
+const AMBIENT_RATE: f32 = 0.4;
+const LIGHT_TURN_RATE: f32 = 1.2;
+
+/// Slider ranges
+const MAX_GLOW: f32 = 4.0;
+const MAX_AMBIENT: f32 = 1.0;
+
+pub struct PhotoPlugin;
+
+impl Plugin for PhotoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                toggle_photo_mode,
+                (frame_photo, tweak_photo_theme, capture_photo)
+                    .run_if(resource_exists::<PhotoMode>),
+            )
+                .chain()
+                .before(apply_theme)
+                .before(apply_camera_view),
+        );
+    }
+}
+
+/// Resource: Present while in photo mode, with what to put back after
+#[derive(Resource, Debug, Clone)]
+pub struct PhotoMode {
+    /// The camera's zoom and pan before photo mode
+    resting_view: (f32, Vec2),
+    /// The theme before any slider moved
+    original_theme: Theme,
+}
+
+/// System: F enters photo mode, and F or Escape leaves it (putting the camera, theme and
+/// HUD back)
+///
+/// Not while the level summary or profile select is up: they'd sit hidden under photo
+/// mode, still taking its keys.
+#[allow(clippy::too_many_arguments)]
+pub fn toggle_photo_mode(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    photo: Option<Res<PhotoMode>>,
+    summary: Option<Res<LevelSummary>>,
+    select: Option<Res<ProfileSelect>>,
+    mut theme: ResMut<Theme>,
+    mut game_camera: ResMut<GameCamera>,
+    mut hud: Query<&mut Visibility, With<HudPlane>>,
+) {
+    let leaving = photo.is_some() && keys.any_just_pressed([KeyCode::KeyF, KeyCode::Escape]);
+    let entering = photo.is_none()
+        && summary.is_none()
+        && select.is_none()
+        && keys.just_pressed(KeyCode::KeyF);
+    if !leaving && !entering {
+        return;
+    }
+
+    if let Some(photo) = photo {
+        (game_camera.scale, game_camera.offset) = photo.resting_view;
+        *theme = photo.original_theme.clone();
+        for mut visibility in &mut hud {
+            *visibility = Visibility::Inherited;
+        }
+        commands.remove_resource::<PhotoMode>();
+        info!("📸 Photo mode off");
+        return;
+    }
+
+    for mut visibility in &mut hud {
+        *visibility = Visibility::Hidden;
+    }
+    commands.insert_resource(PhotoMode {
+        resting_view: (game_camera.scale, game_camera.offset),
+        original_theme: theme.clone(),
+    });
+    info!(
+        "📸 Photo mode: wheel or +/- zoom, arrows pan, [ ] glow, ; ' ambient, , . light, T theme, X saves, F leaves"
+    );
+}
+
+/// System: Zoom with +/- and pan with the arrow keys (the wheel and drags work as in play)
+pub fn frame_photo(
+    time: Res<Time<Real>>, // Framing works while gameplay is paused
+    keys: Res<ButtonInput<KeyCode>>,
+    mut game_camera: ResMut<GameCamera>,
+) {
+    let mut steps = 0.0;
+    if keys.just_pressed(KeyCode::Equal) {
+        steps += 1.0;
+    }
+    if keys.just_pressed(KeyCode::Minus) {
+        steps -= 1.0;
+    }
+    if steps != 0.0 {
+        let center = game_camera.view().center();
+        game_camera.zoom_about(ZOOM_STEP.powf(steps), center);
+    }
+
+    let direction = Vec2::new(
+        axis(&keys, KeyCode::ArrowLeft, KeyCode::ArrowRight),
+        axis(&keys, KeyCode::ArrowDown, KeyCode::ArrowUp),
+    );
+    if direction != Vec2::ZERO {
+        // The same screen speed at any zoom
+        let step = PAN_SPEED * game_camera.view().height() * time.delta_secs();
+        game_camera.pan_by(direction * step);
+    }
+}
+
+/// System: Held slider keys nudge the glow, ambient light and light direction
+pub fn tweak_photo_theme(
+    time: Res<Time<Real>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut theme: ResMut<Theme>,
+) {
+    let dt = time.delta_secs();
+    let glow = axis(&keys, KeyCode::BracketLeft, KeyCode::BracketRight);
+    let ambient = axis(&keys, KeyCode::Semicolon, KeyCode::Quote);
+    let turn = axis(&keys, KeyCode::Comma, KeyCode::Period);
+    if glow == 0.0 && ambient == 0.0 && turn == 0.0 {
+        return;
+    }
+
+    theme.glow_intensity = (theme.glow_intensity + glow * GLOW_RATE * dt).clamp(0.0, MAX_GLOW);
+    theme.ambient = (theme.ambient + ambient * AMBIENT_RATE * dt).clamp(0.0, MAX_AMBIENT);
+    theme.light_direction =
+        Quat::from_rotation_z(turn * LIGHT_TURN_RATE * dt) * theme.light_direction;
+}
+
+/// System: X saves the frame as a PNG (next to the game natively, downloaded on the web)
+///
+/// Its own key: Space and Enter belong to the level summary and profile select.
+pub fn capture_photo(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>, theme: Res<Theme>) {
+    if !keys.just_pressed(KeyCode::KeyX) {
+        return;
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let path = format!("valence_photo_{}.png", crate::profile::unix_millis() / 1000);
+    #[cfg(target_arch = "wasm32")]
+    let path = "valence_photo.png".to_string();
+
+    info!(
+        "📸 Capturing (glow {:.2}, ambient {:.2})",
+        theme.glow_intensity, theme.ambient
+    );
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path));
+}
+
+/// -1, 0 or 1 from a pair of held keys
+fn axis(keys: &ButtonInput<KeyCode>, negative: KeyCode, positive: KeyCode) -> f32 {
+    keys.pressed(positive) as i32 as f32 - keys.pressed(negative) as i32 as f32
+}
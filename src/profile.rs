@@ -0,0 +1,670 @@
+// profile.rs - player profiles, so people sharing a device each keep their own name,
+// settings, level, board in progress and stats
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    autosave::{autosaving, resume, resume_saved_board, save_board},
+    game::{
+        hints::HintWallet,
+        progression::{CompletionPolicy, ProgressionTracker},
+        puzzle::PuzzleLibrary,
+        session::PuzzleSession,
+        tour::{LevelTour, track_served_puzzles},
+    },
+    net::BoardState,
+    score::{HighScore, HighScores},
+    settings::{RenderQuality, ScenePresentation, Settings, TransitionStyle},
+    visual::{
+        interactions::{SolutionFound, handle_pointer_input},
+        setup::{PuzzleDraw, check_level_progression, setup_puzzle, setup_scene, start_level},
+        theme::{Theme, ThemePreset},
+        ui::{LevelSummary, ProfileSelect, board_uncovered, choose_profile, offer_profile_select},
+    },
+};
+
+/// Where the profiles are kept: a file next to the game (native) or a localStorage key (web)
+#[cfg(not(target_arch = "wasm32"))]
+pub const PROFILES_SLOT: &str = "profiles.json";
+#[cfg(target_arch = "wasm32")]
+pub const PROFILES_SLOT: &str = "valence_sdf.profiles";
+
+/// Most profiles a device keeps (one per number key on the profile select)
+pub const MAX_PROFILES: usize = 9;
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Profiles>()
+            .add_message::<SwitchProfile>()
+            .add_systems(PreStartup, load_profiles)
+            .add_systems(
+                Startup,
+                (resume_saved_board, offer_profile_select)
+                    .after(setup_puzzle)
+                    .before(setup_scene)
+                    .run_if(autosaving),
+            )
+            // U switches to the next profile (Shift+U adds one); the select comes first
+            .add_systems(
+                Update,
+                (
+                    choose_profile.run_if(resource_exists::<ProfileSelect>),
+                    profile_keys.run_if(board_uncovered).run_if(autosaving),
+                    switch_profiles,
+                )
+                    .chain()
+                    .before(handle_pointer_input),
+            )
+            .add_systems(
+                Update,
+                (
+                    (
+                        save_board,
+                        record_profile_stats,
+                        remember_profile_settings,
+                        remember_level_tour,
+                        remember_hint_wallet,
+                    )
+                        .run_if(autosaving),
+                    persist_profiles,
+                )
+                    .chain()
+                    .after(check_level_progression)
+                    .after(track_served_puzzles),
+            );
+    }
+}
+
+/// One player's saved game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// Random id the server keeps the profile under (see `sync`)
+    pub id: String,
+    /// Random secret the server's copy is claimed with; devices sharing the profile share it
+    /// too (saves from before it get one on loading)
+    #[serde(default)]
+    pub token: String,
+    pub name: String,
+    /// Level reached
+    pub level: usize,
+    pub settings: ProfileSettings,
+    pub stats: ProfileStats,
+    /// The board in progress (kept by `autosave`)
+    pub board: Option<BoardState>,
+    /// Puzzles served on each level, so the next ones are new
+    pub tour: LevelTour,
+    /// Hint tokens to spend
+    pub hints: HintWallet,
+    /// Unix milliseconds of the last change, to settle sync conflicts
+    pub updated_at: u64,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::new("Player 1")
+    }
+}
+
+impl Profile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: format!("{:016x}", rand::random::<u64>()),
+            token: new_token(),
+            name: name.into(),
+            level: 1,
+            settings: ProfileSettings::default(),
+            stats: ProfileStats::default(),
+            board: None,
+            tour: LevelTour::default(),
+            hints: HintWallet::default(),
+            updated_at: unix_millis(),
+        }
+    }
+}
+
+/// The settings each profile keeps for itself
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSettings {
+    pub completion: CompletionPolicy,
+    pub theme: ThemePreset,
+    pub shake_intensity: f32,
+    pub reduced_motion: bool,
+    pub adaptive: bool,
+    pub perspective: bool,
+    pub presentation: ScenePresentation,
+    pub quality: RenderQuality,
+    pub auto_quality: bool,
+    pub transition_style: TransitionStyle,
+}
+
+impl Default for ProfileSettings {
+    fn default() -> Self {
+        let settings = Settings::default();
+        Self {
+            completion: CompletionPolicy::default(),
+            theme: ThemePreset::default(),
+            shake_intensity: settings.shake_intensity,
+            reduced_motion: settings.reduced_motion,
+            adaptive: settings.adaptive,
+            perspective: settings.perspective,
+            presentation: settings.presentation,
+            quality: settings.quality,
+            auto_quality: settings.auto_quality,
+            transition_style: settings.transition_style,
+        }
+    }
+}
+
+/// Lifetime totals for a profile
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStats {
+    pub levels_completed: u32,
+    /// New solutions (repeats don't count)
+    pub solutions_found: u32,
+    /// Gameplay seconds (pauses don't count)
+    pub seconds_played: f32,
+    /// Attempts on finished levels, and those levels' par summed, for a score against par
+    pub attempts: u32,
+    pub par: u32,
+}
+
+/// Everything kept on the device: the profiles, and which one is playing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileBook {
+    pub active: usize,
+    pub profiles: Vec<Profile>,
+    /// Best level scores on this device, per mode (not synced)
+    #[serde(default)]
+    pub high_scores: HighScores,
+}
+
+impl Default for ProfileBook {
+    fn default() -> Self {
+        Self {
+            active: 0,
+            profiles: vec![Profile::default()],
+            high_scores: HighScores::default(),
+        }
+    }
+}
+
+impl ProfileBook {
+    /// Read a saved book, rejecting one without profiles
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let mut book: Self = serde_json::from_str(json).map_err(|err| err.to_string())?;
+        if book.profiles.is_empty() {
+            return Err("no profiles".to_string());
+        }
+        book.profiles.truncate(MAX_PROFILES);
+        book.active = book.active.min(book.profiles.len() - 1);
+        Ok(book)
+    }
+
+    pub fn active(&self) -> &Profile {
+        &self.profiles[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Profile {
+        &mut self.profiles[self.active]
+    }
+
+    /// Add a profile, returning its index (None once the book is full)
+    pub fn add(&mut self, name: impl Into<String>) -> Option<usize> {
+        if self.profiles.len() >= MAX_PROFILES {
+            return None;
+        }
+        self.profiles.push(Profile::new(name));
+        Some(self.profiles.len() - 1)
+    }
+}
+
+/// Resource: The profile book, where it's kept and whether it needs writing out
+#[derive(Resource, Debug)]
+pub struct Profiles {
+    slot: String,
+    pub book: ProfileBook,
+    dirty: bool,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self::at(PROFILES_SLOT)
+    }
+}
+
+impl Profiles {
+    /// Keep the profiles in another file (or localStorage key on the web)
+    pub fn at(slot: impl Into<String>) -> Self {
+        Self {
+            slot: slot.into(),
+            book: ProfileBook::default(),
+            dirty: false,
+        }
+    }
+
+    pub fn active(&self) -> &Profile {
+        self.book.active()
+    }
+
+    /// The active profile, marked as changed now and to be written out
+    pub fn active_mut(&mut self) -> &mut Profile {
+        self.dirty = true;
+        let profile = self.book.active_mut();
+        profile.updated_at = unix_millis();
+        profile
+    }
+
+    /// Point the active profile at another device's sync id and token
+    ///
+    /// Its timestamp is cleared, so the server's copy wins the first sync.
+    pub fn link(&mut self, id: &str, token: &str) {
+        let profile = self.book.active_mut();
+        profile.id = id.to_string();
+        profile.token = token.to_string();
+        profile.updated_at = 0;
+        self.dirty = true;
+    }
+
+    /// Put a synced copy of a profile in place of ours, keeping its timestamp
+    ///
+    /// Returns the profile's index (None if it's no longer on this device).
+    pub fn store_synced(&mut self, profile: Profile) -> Option<usize> {
+        let index = self
+            .book
+            .profiles
+            .iter()
+            .position(|ours| ours.id == profile.id)?;
+        self.book.profiles[index] = profile;
+        self.dirty = true;
+        Some(index)
+    }
+
+    /// Put a score in the device's high-score table, returning its rank if it made it
+    pub fn enter_high_score(&mut self, score: HighScore) -> Option<usize> {
+        let rank = self.book.high_scores.enter(score);
+        self.dirty |= rank.is_some();
+        rank
+    }
+
+    fn read(&self) -> Option<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            std::fs::read_to_string(&self.slot).ok()
+        }
+
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        {
+            crate::web::read_storage(&self.slot)
+        }
+
+        #[cfg(all(not(feature = "wasm"), target_arch = "wasm32"))]
+        {
+            None
+        }
+    }
+
+    fn write(&self, json: &str) -> Result<(), String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            std::fs::write(&self.slot, json).map_err(|err| err.to_string())
+        }
+
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        {
+            crate::web::write_storage(&self.slot, json)
+        }
+
+        #[cfg(all(not(feature = "wasm"), target_arch = "wasm32"))]
+        {
+            let _ = json;
+            Ok(())
+        }
+    }
+}
+
+/// Message asking to play as another profile
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchProfile {
+    /// The profile at this index
+    To(usize),
+    /// A new profile
+    New,
+}
+
+/// The settings a profile carries, as live resources
+#[derive(SystemParam)]
+pub struct ProfilePrefs<'w> {
+    policy: ResMut<'w, CompletionPolicy>,
+    theme: ResMut<'w, Theme>,
+    settings: ResMut<'w, Settings>,
+}
+
+impl ProfilePrefs<'_> {
+    fn apply(&mut self, prefs: &ProfileSettings) {
+        *self.policy = prefs.completion;
+        if self.theme.preset != prefs.theme {
+            *self.theme = Theme::from_preset(prefs.theme);
+        }
+        self.settings.shake_intensity = prefs.shake_intensity;
+        self.settings.reduced_motion = prefs.reduced_motion;
+        self.settings.adaptive = prefs.adaptive;
+        self.settings.perspective = prefs.perspective;
+        self.settings.presentation = prefs.presentation;
+        self.settings.quality = prefs.quality;
+        self.settings.auto_quality = prefs.auto_quality;
+        self.settings.transition_style = prefs.transition_style;
+    }
+
+    fn capture(&self) -> ProfileSettings {
+        ProfileSettings {
+            completion: *self.policy,
+            theme: self.theme.preset,
+            shake_intensity: self.settings.shake_intensity,
+            reduced_motion: self.settings.reduced_motion,
+            adaptive: self.settings.adaptive,
+            perspective: self.settings.perspective,
+            presentation: self.settings.presentation,
+            quality: self.settings.quality,
+            auto_quality: self.settings.auto_quality,
+            transition_style: self.settings.transition_style,
+        }
+    }
+
+    fn changed(&self) -> bool {
+        self.policy.is_changed() || self.theme.is_changed() || self.settings.is_changed()
+    }
+}
+
+/// The board a profile switch swaps out
+#[derive(SystemParam)]
+pub struct ProfileBoard<'w> {
+    session: ResMut<'w, PuzzleSession>,
+    tracker: ResMut<'w, ProgressionTracker>,
+    library: Res<'w, PuzzleLibrary>,
+    draw: PuzzleDraw<'w>,
+    wallet: ResMut<'w, HintWallet>,
+}
+
+impl ProfileBoard<'_> {
+    /// Pick up where the profile left off: its saved board, or a fresh puzzle at its level
+    fn load(&mut self, profile: &Profile) {
+        *self.draw.tour = profile.tour.clone();
+        *self.wallet = profile.hints;
+        let saved = profile
+            .board
+            .as_ref()
+            .filter(|board| board.level == profile.level);
+        match saved.map(resume) {
+            Some(Ok(session)) => {
+                *self.session = session;
+                *self.tracker = ProgressionTracker::at_level(profile.level);
+                return;
+            }
+            Some(Err(err)) => warn!("Ignoring {}'s saved board: {}", profile.name, err),
+            None => {}
+        }
+
+        let tracker = ProgressionTracker::at_level(profile.level);
+        match start_level(
+            &self.library,
+            &tracker,
+            &mut self.draw.rng,
+            &mut self.draw.tour,
+        ) {
+            Ok(session) => {
+                *self.session = session;
+                *self.tracker = tracker;
+            }
+            Err(reason) => warn!("Can't start {}'s level: {}", profile.name, reason),
+        }
+    }
+}
+
+/// A fresh sync token: 32 random hex digits
+fn new_token() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// Milliseconds since the Unix epoch (the browser's clock on the web)
+pub fn unix_millis() -> u64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|since| since.as_millis() as u64)
+            .unwrap_or_default()
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        js_sys::Date::now() as u64
+    }
+
+    #[cfg(all(not(feature = "wasm"), target_arch = "wasm32"))]
+    {
+        0
+    }
+}
+
+/// System: Read the profiles and set up the active one's settings and level
+///
+/// Runs before the web build's links (daily puzzle, rooms), so their levels win.
+pub fn load_profiles(
+    mut commands: Commands,
+    mut profiles: ResMut<Profiles>,
+    mut prefs: ProfilePrefs,
+) {
+    match profiles.read().map(|json| ProfileBook::parse(&json)) {
+        Some(Ok(mut book)) => {
+            for profile in book.profiles.iter_mut().filter(|p| p.token.is_empty()) {
+                profile.token = new_token();
+                // Written out straight away, so the server never sees two tokens for it
+                profiles.dirty = true;
+            }
+            profiles.book = book;
+        }
+        Some(Err(err)) => warn!("Ignoring the profiles in {}: {}", profiles.slot, err),
+        None => {
+            // The web build kept just the level before profiles
+            #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+            if let Some(level) = crate::web::take_legacy_level() {
+                profiles.active_mut().level = level;
+            }
+        }
+    }
+
+    let profile = profiles.active().clone();
+    info!("👤 Playing as {} (level {})", profile.name, profile.level);
+    prefs.apply(&profile.settings);
+    commands.insert_resource(ProgressionTracker::at_level(profile.level));
+    commands.insert_resource(profile.tour);
+    commands.insert_resource(profile.hints);
+}
+
+/// System: U switches to the next profile; Shift+U adds a profile and switches to it
+fn profile_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    profiles: Res<Profiles>,
+    mut switches: MessageWriter<SwitchProfile>,
+) {
+    if !keys.just_pressed(KeyCode::KeyU) {
+        return;
+    }
+
+    if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        switches.write(SwitchProfile::New);
+    } else if profiles.book.profiles.len() > 1 {
+        let next = (profiles.book.active + 1) % profiles.book.profiles.len();
+        switches.write(SwitchProfile::To(next));
+    } else {
+        info!("👤 Only one profile; Shift+U adds another");
+    }
+}
+
+/// System: Play as another profile: its settings, then its board
+///
+/// The outgoing profile's board is already saved, as `save_board` keeps it current.
+pub fn switch_profiles(
+    mut switches: MessageReader<SwitchProfile>,
+    mut profiles: ResMut<Profiles>,
+    mut prefs: ProfilePrefs,
+    mut board: ProfileBoard,
+) {
+    for switch in switches.read() {
+        let index = match *switch {
+            SwitchProfile::To(index) if index < profiles.book.profiles.len() => index,
+            SwitchProfile::To(index) => {
+                warn!("No profile {}", index + 1);
+                continue;
+            }
+            SwitchProfile::New => {
+                let name = new_profile_name(profiles.book.profiles.len() + 1);
+                match profiles.book.add(name) {
+                    Some(index) => index,
+                    None => {
+                        warn!("Already {} profiles; can't add another", MAX_PROFILES);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        profiles.book.active = index;
+        profiles.dirty = true;
+        let profile = profiles.active().clone();
+        info!(
+            "👤 Playing as {} (profile {}, level {})",
+            profile.name,
+            index + 1,
+            profile.level
+        );
+        prefs.apply(&profile.settings);
+        board.load(&profile);
+    }
+}
+
+/// A name for the `number`th profile (the web build asks for one)
+fn new_profile_name(number: usize) -> String {
+    let name = format!("Player {}", number);
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        crate::web::prompt("Name for the new profile", &name)
+            .map(|typed| typed.trim().to_string())
+            .filter(|typed| !typed.is_empty())
+            .unwrap_or(name)
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    {
+        name
+    }
+}
+
+/// System: Add new solutions, finished levels (with their attempts and par) and playing
+/// time to the active profile
+fn record_profile_stats(
+    time: Res<Time>,
+    mut solutions: MessageReader<SolutionFound>,
+    summary: Option<Res<LevelSummary>>,
+    mut profiles: ResMut<Profiles>,
+) {
+    // Playing time alone doesn't call for a write; it goes out with the next one
+    profiles.book.active_mut().stats.seconds_played += time.delta_secs();
+
+    let found = solutions.read().filter(|solution| solution.is_new).count() as u32;
+    let finished = summary.filter(|summary| summary.is_added());
+    if found > 0 || finished.is_some() {
+        let stats = &mut profiles.active_mut().stats;
+        stats.solutions_found += found;
+        if let Some(summary) = finished {
+            stats.levels_completed += 1;
+            stats.attempts += summary.attempts;
+            stats.par += summary.par;
+        }
+    }
+}
+
+/// System: Keep the active profile's settings in step with the live ones
+fn remember_profile_settings(prefs: ProfilePrefs, mut profiles: ResMut<Profiles>) {
+    if !prefs.changed() {
+        return;
+    }
+    let settings = prefs.capture();
+    if profiles.active().settings != settings {
+        profiles.active_mut().settings = settings;
+    }
+}
+
+/// System: Keep the active profile's level tour in step with the live one
+fn remember_level_tour(tour: Res<LevelTour>, mut profiles: ResMut<Profiles>) {
+    if tour.is_changed() && profiles.active().tour != *tour {
+        profiles.active_mut().tour = tour.clone();
+    }
+}
+
+/// System: Keep the active profile's hint tokens in step with the live ones
+fn remember_hint_wallet(wallet: Res<HintWallet>, mut profiles: ResMut<Profiles>) {
+    if wallet.is_changed() && profiles.active().hints != *wallet {
+        profiles.active_mut().hints = *wallet;
+    }
+}
+
+/// System: Write the profiles out when something in them changed
+pub fn persist_profiles(mut profiles: ResMut<Profiles>) {
+    if !profiles.dirty {
+        return;
+    }
+    profiles.dirty = false;
+
+    let written = serde_json::to_string(&profiles.book)
+        .map_err(|err| err.to_string())
+        .and_then(|json| profiles.write(&json));
+    if let Err(err) = written {
+        warn!("Failed to save profiles to {}: {}", profiles.slot, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keeps_the_active_profile_in_range() {
+        let mut book = ProfileBook::default();
+        book.add("Player 2");
+        book.active = 7;
+        let json = serde_json::to_string(&book).unwrap();
+
+        let parsed = ProfileBook::parse(&json).unwrap();
+        assert_eq!(parsed.active, 1);
+        assert_eq!(parsed.active().name, "Player 2");
+
+        assert!(ProfileBook::parse(r#"{"active": 0, "profiles": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_book_stops_at_max_profiles() {
+        let mut book = ProfileBook::default();
+        for number in 2..=MAX_PROFILES {
+            assert_eq!(book.add(format!("Player {}", number)), Some(number - 1));
+        }
+        assert_eq!(book.add("One too many"), None);
+    }
+
+    #[test]
+    fn test_missing_fields_take_defaults() {
+        let profile: Profile = serde_json::from_str(r#"{"name": "Sam"}"#).unwrap();
+        assert_eq!(profile.level, 1);
+        // Left for `load_profiles` to fill in and write out
+        assert!(profile.token.is_empty());
+        assert_eq!(profile.settings, ProfileSettings::default());
+        assert!(profile.board.is_none());
+    }
+}
@@ -0,0 +1,282 @@
+// score.rs - arcade scoring: points for edges and solutions (faster ones earn more), all
+// scaled by the combo multiplier, with a high-score table per completion mode
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        progression::{CompletionPolicy, ProgressionTracker},
+        session::PuzzleSession,
+    },
+    profile::{Profiles, persist_profiles},
+    visual::{
+        interactions::{Combo, EdgeDrawn, SolutionFound, track_combo},
+        setup::check_level_progression,
+        ui::{
+            LevelSummary, ToastQueue,
+            odometer::{Odometer, RollSpeed},
+        },
+    },
+};
+
+/// Points per valid edge
+const EDGE_POINTS: u32 = 10;
+
+/// Points for finding a new solution
+const SOLUTION_BONUS: u32 = 100;
+
+/// Solutions drawn in less than this many seconds earn a speed bonus...
+const SPEED_BONUS_SECS: f32 = 20.0;
+
+/// ...of this many points per second to spare
+const SPEED_POINTS_PER_SEC: f32 = 5.0;
+
+/// How fast the HUD's score rolls to the real one, odometer-style
+const SCORE_ROLL: RollSpeed = RollSpeed {
+    per_sec: 30.0,
+    max_secs: 0.8,
+};
+
+/// Scores kept in each mode's high-score table
+pub const HIGH_SCORES_KEPT: usize = 10;
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .add_systems(
+                Update,
+                score_play
+                    .after(track_combo)
+                    .before(check_level_progression),
+            )
+            .add_systems(
+                Update,
+                record_high_score
+                    .run_if(resource_exists::<LevelSummary>)
+                    .run_if(resource_exists::<Profiles>)
+                    .after(check_level_progression)
+                    .before(persist_profiles),
+            );
+    }
+}
+
+/// Resource: Points scored on the current level
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+pub struct Score {
+    /// Level the points belong to
+    pub level: usize,
+    pub points: u32,
+    /// What the HUD reads, rolling up toward `points`
+    display: Odometer,
+    /// Gameplay seconds into the trail being drawn
+    attempt_secs: f32,
+}
+
+impl Score {
+    /// The score as the HUD shows it
+    pub fn shown(&self) -> u32 {
+        self.display.shown()
+    }
+
+    /// Bonus for a new solution drawn in `attempt_secs`, before the multiplier
+    pub fn solution_points(attempt_secs: f32) -> u32 {
+        let spare = (SPEED_BONUS_SECS - attempt_secs).max(0.0);
+        SOLUTION_BONUS + (spare * SPEED_POINTS_PER_SEC).round() as u32
+    }
+}
+
+/// One entry in the high-score table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub mode: CompletionPolicy,
+    /// Profile that scored it
+    pub name: String,
+    pub level: usize,
+    pub points: u32,
+}
+
+/// The device's best level scores, per completion mode (kept with the profiles)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HighScores {
+    /// Every mode's entries, best first
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// A mode's table, best first
+    pub fn table(&self, mode: CompletionPolicy) -> impl Iterator<Item = &HighScore> {
+        self.entries.iter().filter(move |entry| entry.mode == mode)
+    }
+
+    /// Put a score in its mode's table; its rank there (0 = best), or None if it didn't
+    /// make the table
+    ///
+    /// Ties go below the scores already in the table.
+    pub fn enter(&mut self, score: HighScore) -> Option<usize> {
+        let mode = score.mode;
+        let rank = self
+            .table(mode)
+            .take_while(|kept| kept.points >= score.points)
+            .count();
+        if rank >= HIGH_SCORES_KEPT {
+            return None;
+        }
+
+        let at = self
+            .entries
+            .iter()
+            .position(|kept| kept.points < score.points)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(at, score);
+
+        let mut kept = 0;
+        self.entries.retain(|entry| {
+            if entry.mode != mode {
+                return true;
+            }
+            kept += 1;
+            kept <= HIGH_SCORES_KEPT
+        });
+        Some(rank)
+    }
+}
+
+/// System: Score the edges the player draws and new solutions, and roll the HUD's score
+/// toward them
+///
+/// Edges come from `EdgeDrawn`, as in `track_combo`, so redo and a partner's trail score
+/// nothing.
+pub fn score_play(
+    time: Res<Time>,
+    tracker: Res<ProgressionTracker>,
+    session: Res<PuzzleSession>,
+    combo: Res<Combo>,
+    mut edges: MessageReader<EdgeDrawn>,
+    mut solutions: MessageReader<SolutionFound>,
+    mut score: ResMut<Score>,
+) {
+    if score.level != tracker.current_level {
+        *score = Score {
+            level: tracker.current_level,
+            ..default()
+        };
+    }
+
+    let mut points = edges.read().count() as u32 * EDGE_POINTS;
+    for solution in solutions.read() {
+        if solution.is_new {
+            points += Score::solution_points(score.attempt_secs);
+        }
+    }
+    if points > 0 {
+        score.points += combo.scored(points);
+    }
+
+    if session.current_trail().is_empty() {
+        score.attempt_secs = 0.0;
+    } else {
+        score.attempt_secs += time.delta_secs();
+    }
+
+    let points = score.points;
+    if score.display.shown() != points {
+        score.display.roll_to(points, SCORE_ROLL);
+        score.display.tick(time.delta_secs());
+    }
+}
+
+/// System: Enter a finished level's score in the high-score table for its mode
+fn record_high_score(
+    summary: Res<LevelSummary>,
+    score: Res<Score>,
+    policy: Res<CompletionPolicy>,
+    mut profiles: ResMut<Profiles>,
+    mut toasts: ResMut<ToastQueue>,
+) {
+    if !summary.is_added() || score.points == 0 {
+        return;
+    }
+
+    let entry = HighScore {
+        mode: *policy,
+        name: profiles.active().name.clone(),
+        level: score.level,
+        points: score.points,
+    };
+    if let Some(rank) = profiles.enter_high_score(entry) {
+        info!(
+            "🏅 Level {} scored {}: #{} on the {:?} table",
+            score.level,
+            score.points,
+            rank + 1,
+            *policy
+        );
+        if rank == 0 {
+            toasts.push("pb");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(mode: CompletionPolicy, points: u32) -> HighScore {
+        HighScore {
+            mode,
+            name: "Player 1".to_string(),
+            level: 1,
+            points,
+        }
+    }
+
+    #[test]
+    fn test_faster_solutions_score_more() {
+        assert!(Score::solution_points(2.0) > Score::solution_points(10.0));
+        assert_eq!(Score::solution_points(SPEED_BONUS_SECS), SOLUTION_BONUS);
+        assert_eq!(Score::solution_points(600.0), SOLUTION_BONUS);
+    }
+
+    #[test]
+    fn test_tables_rank_and_cap_each_mode() {
+        let mut scores = HighScores::default();
+        assert_eq!(
+            scores.enter(entry(CompletionPolicy::AllSolutions, 50)),
+            Some(0)
+        );
+        assert_eq!(
+            scores.enter(entry(CompletionPolicy::AllSolutions, 80)),
+            Some(0)
+        );
+        assert_eq!(
+            scores.enter(entry(CompletionPolicy::AllSolutions, 50)),
+            Some(2)
+        );
+        // Other modes keep their own tables
+        assert_eq!(
+            scores.enter(entry(CompletionPolicy::FirstSolution, 10)),
+            Some(0)
+        );
+
+        for points in 100..100 + HIGH_SCORES_KEPT as u32 {
+            scores.enter(entry(CompletionPolicy::AllSolutions, points));
+        }
+        assert_eq!(
+            scores.table(CompletionPolicy::AllSolutions).count(),
+            HIGH_SCORES_KEPT
+        );
+        assert_eq!(scores.enter(entry(CompletionPolicy::AllSolutions, 1)), None);
+        assert_eq!(scores.table(CompletionPolicy::FirstSolution).count(), 1);
+
+        let best: Vec<u32> = scores
+            .table(CompletionPolicy::AllSolutions)
+            .map(|entry| entry.points)
+            .take(2)
+            .collect();
+        assert_eq!(best, vec![109, 108]);
+    }
+}
@@ -0,0 +1,204 @@
+// settings.rs
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Settings>();
+    }
+}
+
+/// Player-facing preferences
+#[derive(Resource, Debug, Clone)]
+pub struct Settings {
+    /// Multiplier on screen-shake amplitude (0.0 = off, 1.0 = default)
+    pub shake_intensity: f32,
+    /// Disable camera motion effects entirely (accessibility)
+    pub reduced_motion: bool,
+    /// How node weight follows remaining valence
+    pub mass_curve: MassCurve,
+    /// Send anonymous gameplay events to the server (off unless the player opts in)
+    pub telemetry_opt_in: bool,
+    /// Ease off (hints, easier puzzles) when the player is clearly stuck
+    pub adaptive: bool,
+    /// View the board in perspective, leaning toward the pointer or the phone's tilt
+    /// (level under `reduced_motion`)
+    pub perspective: bool,
+    /// How the SDF scene is drawn
+    pub presentation: ScenePresentation,
+    /// How much work the scene shader puts into each frame
+    pub quality: RenderQuality,
+    /// Step the quality down when frames keep running over budget
+    pub auto_quality: bool,
+    /// How HUD digits morph from one value to the next
+    pub transition_style: TransitionStyle,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            shake_intensity: 1.0,
+            reduced_motion: false,
+            mass_curve: MassCurve::default(),
+            telemetry_opt_in: false,
+            adaptive: false,
+            perspective: false,
+            presentation: ScenePresentation::default(),
+            quality: RenderQuality::default(),
+            auto_quality: true,
+            transition_style: TransitionStyle::default(),
+        }
+    }
+}
+
+/// How the SDF scene is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScenePresentation {
+    /// Raymarched through the board plane from the game camera (the board you play on)
+    #[default]
+    Flat,
+    /// Raymarched full-screen as 3D blobs from a camera circling the board (to look at,
+    /// not play on)
+    Orbit,
+}
+
+/// Render quality preset for the scene shader (see `SdfQualityUniform::for_preset`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RenderQuality {
+    Low,
+    Medium,
+    #[default]
+    High,
+}
+
+impl RenderQuality {
+    /// The next preset down (None at the bottom)
+    pub fn lower(self) -> Option<Self> {
+        match self {
+            Self::High => Some(Self::Medium),
+            Self::Medium => Some(Self::Low),
+            Self::Low => None,
+        }
+    }
+
+    /// The next preset in the cycle, wrapping from High back to Low
+    pub fn next(self) -> Self {
+        match self {
+            Self::Low => Self::Medium,
+            Self::Medium => Self::High,
+            Self::High => Self::Low,
+        }
+    }
+}
+
+impl ScenePresentation {
+    /// The shader's `presentation` value
+    pub fn shader_value(self) -> u32 {
+        match self {
+            Self::Flat => 0,
+            Self::Orbit => 1,
+        }
+    }
+}
+
+/// How a seven-segment digit's blobs flow between values (see `seven_segment::flows`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransitionStyle {
+    /// Vanishing segments split evenly between their nearest targets, nothing more
+    Deterministic,
+    /// Deterministic, plus a spark from the nearest steady segment into each new one
+    #[default]
+    Excitement,
+    /// Each vanishing segment picks one nearest target at random, sparks come from any
+    /// steady segment, and the blobs wander differently every time
+    Organic,
+}
+
+impl TransitionStyle {
+    /// The shader's `transition_style` value
+    pub fn shader_value(self) -> u32 {
+        match self {
+            Self::Deterministic => 0,
+            Self::Excitement => 1,
+            Self::Organic => 2,
+        }
+    }
+
+    /// The next style in the cycle, wrapping from Organic back to Deterministic
+    pub fn next(self) -> Self {
+        match self {
+            Self::Deterministic => Self::Excitement,
+            Self::Excitement => Self::Organic,
+            Self::Organic => Self::Deterministic,
+        }
+    }
+}
+
+/// Node mass as a function of remaining valence: `min_mass + per_valence * valence^exponent`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassCurve {
+    /// Mass of a finished node (valence 0) - keep low so it feels light and bouncy
+    pub min_mass: f32,
+    /// Extra mass per unit of (curved) remaining valence
+    pub per_valence: f32,
+    /// > 1.0 makes high valences disproportionately heavy
+    pub exponent: f32,
+}
+
+impl Default for MassCurve {
+    fn default() -> Self {
+        Self {
+            min_mass: 0.6,
+            per_valence: 0.25,
+            exponent: 1.0,
+        }
+    }
+}
+
+impl MassCurve {
+    pub fn mass(&self, valence: usize) -> f32 {
+        self.min_mass + self.per_valence * (valence as f32).powf(self.exponent)
+    }
+}
+
+impl Settings {
+    /// Effective screen-shake multiplier after accessibility settings
+    pub fn shake_scale(&self) -> f32 {
+        if self.reduced_motion {
+            0.0
+        } else {
+            self.shake_intensity.max(0.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mass_grows_with_valence() {
+        let curve = MassCurve::default();
+        let masses: Vec<f32> = (0..=8).map(|valence| curve.mass(valence)).collect();
+
+        assert!(masses.windows(2).all(|pair| pair[1] > pair[0]));
+        assert!(
+            masses[0] < 1.0,
+            "Finished nodes should be lighter than the default mass"
+        );
+        assert!(masses[8] > 2.0, "Valence 8 should feel weighty");
+    }
+
+    #[test]
+    fn test_reduced_motion_disables_shake() {
+        let settings = Settings {
+            shake_intensity: 2.0,
+            reduced_motion: true,
+            ..Settings::default()
+        };
+        assert_eq!(settings.shake_scale(), 0.0);
+    }
+}
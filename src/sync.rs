@@ -0,0 +1,450 @@
+// sync.rs - cloud sync: profiles are kept on the server too, so progress follows a
+// player between devices and between the web and native builds
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    autosave::autosaving,
+    profile::{Profile, Profiles, SwitchProfile, persist_profiles, switch_profiles},
+    sync_rules::{FoundSolutions, SyncedSave, is_id, is_token, merge},
+};
+
+/// Real seconds between uploads of changed profiles
+const UPLOAD_INTERVAL: f32 = 10.0;
+
+pub struct SyncPlugin;
+
+impl Plugin for SyncPlugin {
+    fn build(&self, app: &mut App) {
+        // Without a server to talk to, profiles stay on the device
+        let Some(base_url) = transport::server_url() else {
+            return;
+        };
+
+        app.insert_resource(CloudSync::new(base_url)).add_systems(
+            Update,
+            (
+                (
+                    link_profile_key,
+                    fetch_active_profile,
+                    apply_synced_profiles,
+                )
+                    .chain()
+                    .before(switch_profiles),
+                upload_changed_profiles.after(persist_profiles),
+            )
+                .run_if(autosaving),
+        );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Startup, link_profile_from_env);
+    }
+}
+
+/// A finished request: the server's copy of a profile (None when it has none yet)
+#[derive(Debug)]
+struct Reply {
+    id: String,
+    result: Result<Option<Profile>, String>,
+}
+
+/// Resource: Keeps this device's profiles in step with `/api/save` on the server
+#[derive(Resource, Debug, Clone)]
+pub struct CloudSync {
+    /// Prefix for `/api/save` ("" for the page's own origin)
+    base_url: String,
+    /// Profiles fetched this session
+    fetched: HashSet<String>,
+    /// Each profile's `updated_at` as the server last saw it
+    synced: HashMap<String, u64>,
+    /// Real seconds of the last upload
+    last_upload: f32,
+    /// Finished requests, filled from outside the ECS
+    inbox: Arc<Mutex<Vec<Reply>>>,
+}
+
+impl CloudSync {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            fetched: HashSet::new(),
+            synced: HashMap::new(),
+            last_upload: 0.0,
+            inbox: Arc::default(),
+        }
+    }
+
+    /// Ask for the server's copy of a profile
+    fn fetch(&mut self, profile: &Profile) {
+        self.fetched.insert(profile.id.clone());
+        self.send(profile, None);
+    }
+
+    /// Send a profile; the server merges it with its copy and replies with the result
+    fn upload(&mut self, profile: &Profile) {
+        self.synced.insert(profile.id.clone(), profile.updated_at);
+        match serde_json::to_string(profile) {
+            Ok(body) => self.send(profile, Some(body)),
+            Err(err) => warn!("Can't upload profile {}: {}", profile.name, err),
+        }
+    }
+
+    /// Requests carry the profile's token; the first one to reach the server claims the
+    /// profile for it, and later ones without it are turned away
+    fn send(&self, profile: &Profile, body: Option<String>) {
+        let url = format!("{}/api/save/{}", self.base_url, profile.id);
+        let inbox = self.inbox.clone();
+        let id = profile.id.clone();
+        transport::request(url, Some(profile.token.clone()), body, move |result| {
+            let result = result.and_then(|body| {
+                body.map(|body| serde_json::from_str::<Profile>(&body))
+                    .transpose()
+                    .map_err(|err| err.to_string())
+            });
+            inbox.lock().unwrap().push(Reply { id, result });
+        });
+    }
+
+    fn take_delivered(&self) -> Vec<Reply> {
+        std::mem::take(&mut *self.inbox.lock().unwrap())
+    }
+}
+
+impl SyncedSave for Profile {
+    fn updated_at(&self) -> u64 {
+        self.updated_at
+    }
+
+    fn board(&self) -> Option<(&[usize; 9], &FoundSolutions)> {
+        self.board
+            .as_ref()
+            .map(|board| (&board.valences, &board.found))
+    }
+
+    fn board_mut(&mut self) -> Option<(&[usize; 9], &mut FoundSolutions)> {
+        self.board
+            .as_mut()
+            .map(|board| (&board.valences, &mut board.found))
+    }
+}
+
+/// A profile's sync key, "<id>:<token>": what another device needs to share it
+pub fn sync_key(profile: &Profile) -> String {
+    format!("{}:{}", profile.id, profile.token)
+}
+
+/// Split a sync key into its id and token, if it's well formed
+pub fn parse_sync_key(key: &str) -> Option<(&str, &str)> {
+    key.split_once(':')
+        .filter(|&(id, token)| is_id(id) && is_token(token))
+}
+
+/// System: Fetch the active profile's server copy the first time it plays this session
+fn fetch_active_profile(profiles: Res<Profiles>, mut sync: ResMut<CloudSync>) {
+    let profile = profiles.active();
+    if !sync.fetched.contains(&profile.id) {
+        sync.fetch(profile);
+    }
+}
+
+/// System: Merge server copies into the local profiles
+///
+/// When the active profile's board, level, settings, tour or hint tokens changed, it's
+/// switched to again, so play carries on from the merged copy.
+fn apply_synced_profiles(
+    mut sync: ResMut<CloudSync>,
+    mut profiles: ResMut<Profiles>,
+    mut switches: MessageWriter<SwitchProfile>,
+) {
+    for reply in sync.take_delivered() {
+        let mut remote = match reply.result {
+            Ok(Some(remote)) => remote,
+            Ok(None) => continue,
+            Err(err) => {
+                warn!("Cloud sync failed for profile {}: {}", reply.id, err);
+                // Upload it again next time
+                sync.synced.remove(&reply.id);
+                continue;
+            }
+        };
+        remote.id = reply.id;
+
+        let Some(local) = profiles
+            .book
+            .profiles
+            .iter()
+            .find(|local| local.id == remote.id)
+        else {
+            continue;
+        };
+        // The server only answered because our token matched; keep it whatever the copy says
+        remote.token = local.token.clone();
+        let merged = merge(local, &remote);
+        if merged == remote {
+            sync.synced.insert(remote.id.clone(), remote.updated_at);
+        } else {
+            // The server is missing something we have
+            sync.synced.remove(&remote.id);
+        }
+        if merged == *local {
+            continue;
+        }
+
+        let replay = merged.board != local.board
+            || merged.level != local.level
+            || merged.settings != local.settings
+            || merged.tour != local.tour
+            || merged.hints != local.hints;
+        info!(
+            "☁️ Synced {} from the server (level {})",
+            merged.name, merged.level
+        );
+        if let Some(index) = profiles.store_synced(merged)
+            && index == profiles.book.active
+            && replay
+        {
+            switches.write(SwitchProfile::To(index));
+        }
+    }
+}
+
+/// System: Every little while, send the profiles changed since the server last saw them
+fn upload_changed_profiles(
+    time: Res<Time<Real>>,
+    profiles: Res<Profiles>,
+    mut sync: ResMut<CloudSync>,
+) {
+    let now = time.elapsed_secs();
+    if now - sync.last_upload < UPLOAD_INTERVAL {
+        return;
+    }
+    sync.last_upload = now;
+
+    for profile in &profiles.book.profiles {
+        if sync.synced.get(&profile.id) != Some(&profile.updated_at) {
+            sync.upload(profile);
+        }
+    }
+}
+
+/// System: K shows the active profile's sync key; on the web, pasting another device's
+/// key there links this profile to it
+fn link_profile_key(keys: Res<ButtonInput<KeyCode>>, mut profiles: ResMut<Profiles>) {
+    if !keys.just_pressed(KeyCode::KeyK) {
+        return;
+    }
+    let key = sync_key(profiles.active());
+    info!(
+        "☁️ {}'s sync key: {} (use it on another device to share this profile)",
+        profiles.active().name,
+        key
+    );
+
+    if let Some(typed) = prompt_sync_key(&key) {
+        link(&mut profiles, typed.trim());
+    }
+}
+
+/// Ask for another device's sync key (the web build shows a prompt box)
+fn prompt_sync_key(key: &str) -> Option<String> {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    {
+        crate::web::prompt(
+            "This profile's sync key. Paste another device's to share its progress:",
+            key,
+        )
+    }
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    {
+        let _ = key;
+        None
+    }
+}
+
+/// System: Link the active profile to the sync key in `VALENCE_SYNC_PROFILE`, if set
+#[cfg(not(target_arch = "wasm32"))]
+fn link_profile_from_env(mut profiles: ResMut<Profiles>) {
+    if let Ok(key) = std::env::var("VALENCE_SYNC_PROFILE") {
+        link(&mut profiles, key.trim());
+    }
+}
+
+fn link(profiles: &mut Profiles, key: &str) {
+    if key == sync_key(profiles.active()) {
+        return;
+    }
+    let Some((id, token)) = parse_sync_key(key) else {
+        warn!(
+            "Not a sync key: {:?} (an id and a token, as K shows them)",
+            key
+        );
+        return;
+    };
+    info!("☁️ Linked {} to sync id {}", profiles.active().name, id);
+    profiles.link(id, token);
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub(crate) mod transport {
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Headers, RequestInit, Response};
+
+    /// The page's own server
+    pub fn server_url() -> Option<String> {
+        Some(String::new())
+    }
+
+    pub fn request(
+        url: String,
+        token: Option<String>,
+        body: Option<String>,
+        reply: impl FnOnce(Result<Option<String>, String>) + 'static,
+    ) {
+        wasm_bindgen_futures::spawn_local(async move {
+            reply(fetch(&url, token, body).await);
+        });
+    }
+
+    /// GET the URL, or PUT the body to it (with the token as a bearer token); Ok(None)
+    /// on a 404
+    async fn fetch(
+        url: &str,
+        token: Option<String>,
+        body: Option<String>,
+    ) -> Result<Option<String>, String> {
+        let window = web_sys::window().ok_or("no window")?;
+        let init = RequestInit::new();
+        let headers = Headers::new().map_err(|err| format!("{url}: {err:?}"))?;
+        if let Some(token) = token {
+            let _ = headers.set("Authorization", &format!("Bearer {token}"));
+        }
+        if let Some(body) = body {
+            init.set_method("PUT");
+            init.set_body(&JsValue::from_str(&body));
+            let _ = headers.set("Content-Type", "application/json");
+        }
+        init.set_headers(&headers);
+
+        let response = JsFuture::from(window.fetch_with_str_and_init(url, &init))
+            .await
+            .map_err(|err| format!("{url}: {err:?}"))?;
+        let response: Response = response
+            .dyn_into()
+            .map_err(|_| format!("{url}: not a Response"))?;
+        if response.status() == 404 {
+            return Ok(None);
+        }
+        if !response.ok() {
+            return Err(format!("{url}: HTTP {}", response.status()));
+        }
+
+        let text = response.text().map_err(|err| format!("{url}: {err:?}"))?;
+        JsFuture::from(text)
+            .await
+            .map_err(|err| format!("{url}: {err:?}"))?
+            .as_string()
+            .map(Some)
+            .ok_or_else(|| format!("{url}: body isn't text"))
+    }
+}
+
+#[cfg(all(feature = "cloud-sync", not(target_arch = "wasm32")))]
+pub(crate) mod transport {
+    /// Environment variable naming the server to sync with (e.g. https://valence-sdf.fly.dev)
+    const SYNC_URL_VAR: &str = "VALENCE_SYNC_URL";
+
+    pub fn server_url() -> Option<String> {
+        std::env::var(SYNC_URL_VAR)
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+    }
+
+    pub fn request(
+        url: String,
+        token: Option<String>,
+        body: Option<String>,
+        reply: impl FnOnce(Result<Option<String>, String>) + Send + 'static,
+    ) {
+        std::thread::spawn(move || reply(call(&url, token, body)));
+    }
+
+    /// GET the URL, or PUT the body to it (with the token as a bearer token); Ok(None)
+    /// on a 404
+    fn call(
+        url: &str,
+        token: Option<String>,
+        body: Option<String>,
+    ) -> Result<Option<String>, String> {
+        let authorization = token.map(|token| format!("Bearer {token}"));
+        let response = match body {
+            Some(body) => {
+                let mut put = ureq::put(url).header("Content-Type", "application/json");
+                if let Some(authorization) = &authorization {
+                    put = put.header("Authorization", authorization);
+                }
+                put.send(body)
+            }
+            None => {
+                let mut get = ureq::get(url);
+                if let Some(authorization) = &authorization {
+                    get = get.header("Authorization", authorization);
+                }
+                get.call()
+            }
+        };
+        match response {
+            Ok(mut response) => response
+                .body_mut()
+                .read_to_string()
+                .map(Some)
+                .map_err(|err| format!("{url}: {err}")),
+            Err(ureq::Error::StatusCode(404)) => Ok(None),
+            Err(err) => Err(format!("{url}: {err}")),
+        }
+    }
+}
+
+#[cfg(not(any(
+    all(feature = "wasm", target_arch = "wasm32"),
+    all(feature = "cloud-sync", not(target_arch = "wasm32"))
+)))]
+pub(crate) mod transport {
+    pub fn server_url() -> Option<String> {
+        None
+    }
+
+    pub fn request(
+        _url: String,
+        _token: Option<String>,
+        _body: Option<String>,
+        _reply: impl FnOnce(Result<Option<String>, String>),
+    ) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_keys_round_trip() {
+        let profile = Profile::new("Sam");
+        let key = sync_key(&profile);
+        assert_eq!(
+            parse_sync_key(&key),
+            Some((profile.id.as_str(), profile.token.as_str()))
+        );
+
+        // An id alone no longer links: the token is what lets the server hand the save over
+        assert_eq!(parse_sync_key(&profile.id), None);
+        assert_eq!(parse_sync_key(&format!("{}:short", profile.id)), None);
+    }
+}
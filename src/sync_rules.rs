@@ -0,0 +1,132 @@
+// sync_rules.rs - what the game and the server agree on about saved profiles: which ids
+// and tokens are well formed, and how two copies of a save settle. The server builds this
+// file into itself too (by path), so it sticks to std.
+
+/// Ids (profiles, players, rooms) are client-generated; keep them short so keys stay small
+pub const MAX_ID_LEN: usize = 32;
+
+/// Shortest and longest secret a profile's saves can be claimed with
+pub const TOKEN_LEN: std::ops::RangeInclusive<usize> = 16..=64;
+
+/// The solutions found on a board, each as its edges, sorted
+pub type FoundSolutions = Vec<Vec<[usize; 2]>>;
+
+/// Whether a string can be an id: 1-`MAX_ID_LEN` characters of [A-Za-z0-9_-]
+pub fn is_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Whether a string can be a profile's sync token: `TOKEN_LEN` characters of [A-Za-z0-9]
+pub fn is_token(token: &str) -> bool {
+    TOKEN_LEN.contains(&token.len()) && token.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// A copy of a profile, as far as settling conflicts between copies goes
+pub trait SyncedSave: Clone {
+    /// Unix milliseconds of the copy's last change
+    fn updated_at(&self) -> u64;
+
+    /// The board in progress: its puzzle's valences and the solutions found on it
+    fn board(&self) -> Option<(&[usize; 9], &FoundSolutions)>;
+
+    fn board_mut(&mut self) -> Option<(&[usize; 9], &mut FoundSolutions)>;
+}
+
+/// Settle two copies of a save: the later change wins, and solutions either one found on
+/// the board in progress are kept
+pub fn merge<S: SyncedSave>(ours: &S, theirs: &S) -> S {
+    let (newer, older) = if theirs.updated_at() > ours.updated_at() {
+        (theirs, ours)
+    } else {
+        (ours, theirs)
+    };
+
+    let mut merged = newer.clone();
+    if let (Some((valences, found)), Some((older_valences, older_found))) =
+        (merged.board_mut(), older.board())
+        && valences == older_valences
+    {
+        for solution in older_found {
+            if !found.contains(solution) {
+                found.push(solution.clone());
+            }
+        }
+        found.sort_unstable();
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Save {
+        updated_at: u64,
+        board: Option<([usize; 9], FoundSolutions)>,
+    }
+
+    impl SyncedSave for Save {
+        fn updated_at(&self) -> u64 {
+            self.updated_at
+        }
+
+        fn board(&self) -> Option<(&[usize; 9], &FoundSolutions)> {
+            self.board
+                .as_ref()
+                .map(|(valences, found)| (valences, found))
+        }
+
+        fn board_mut(&mut self) -> Option<(&[usize; 9], &mut FoundSolutions)> {
+            self.board
+                .as_mut()
+                .map(|(valences, found)| (&*valences, found))
+        }
+    }
+
+    const VALENCES: [usize; 9] = [2, 2, 0, 2, 2, 0, 0, 0, 0];
+
+    fn save(updated_at: u64, found: FoundSolutions) -> Save {
+        Save {
+            updated_at,
+            board: Some((VALENCES, found)),
+        }
+    }
+
+    #[test]
+    fn test_later_copy_wins_and_keeps_both_solutions() {
+        let older = save(1000, vec![vec![[1, 4]]]);
+        let newer = save(2000, vec![vec![[0, 1]]]);
+
+        for merged in [merge(&older, &newer), merge(&newer, &older)] {
+            assert_eq!(merged.updated_at, 2000);
+            assert_eq!(merged.board.unwrap().1, vec![vec![[0, 1]], vec![[1, 4]]]);
+        }
+    }
+
+    #[test]
+    fn test_solutions_on_another_puzzle_are_dropped() {
+        let mut older = save(1000, vec![vec![[1, 4]]]);
+        older.board.as_mut().unwrap().0 = [1, 1, 0, 0, 0, 0, 0, 0, 0];
+        let newer = save(2000, vec![vec![[0, 1]]]);
+
+        assert_eq!(merge(&older, &newer), newer);
+    }
+
+    #[test]
+    fn test_ids_and_tokens() {
+        assert!(is_id("3f9a0c21d4e5b678"));
+        assert!(is_id("game-42_b"));
+        assert!(!is_id(""));
+        assert!(!is_id(&"a".repeat(MAX_ID_LEN + 1)));
+        assert!(!is_id("../secrets"));
+
+        assert!(is_token("0123456789abcdef0123456789abcdef"));
+        assert!(!is_token("0123456789abcde"));
+        assert!(!is_token("0123456789abcdef-123"));
+    }
+}
@@ -0,0 +1,436 @@
+// telemetry.rs - opt-in anonymous gameplay events, batched to the server to tune difficulty
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    game::{
+        progression::{CompletionPolicy, ProgressionTracker},
+        session::PuzzleSession,
+    },
+    settings::Settings,
+    visual::interactions::InvalidMove,
+};
+
+/// Real seconds between batches
+const FLUSH_INTERVAL: f32 = 30.0;
+
+/// Events sent per request
+const MAX_BATCH: usize = 50;
+
+/// Events kept while offline before the oldest are dropped
+const MAX_QUEUED: usize = 500;
+
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TelemetryQueue>()
+            .init_resource::<LevelStats>()
+            .add_systems(Startup, restore_unsent.run_if(telemetry_enabled))
+            .add_systems(
+                Update,
+                (
+                    count_invalid_moves,
+                    note_solve,
+                    record_level_changes,
+                    record_abandonment,
+                    flush_telemetry,
+                )
+                    .chain()
+                    .run_if(telemetry_enabled),
+            );
+    }
+}
+
+/// What gets reported; nothing identifies the player beyond a per-run session id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    /// A level solved (its required solutions found) and left for the next
+    LevelComplete {
+        level: usize,
+        complexity: usize,
+        seconds: f32,
+    },
+    /// Rejected moves over a whole level
+    InvalidMoves { level: usize, count: u32 },
+    /// Player quit or left the tab mid-level (they may come back)
+    Abandoned {
+        level: usize,
+        edges_drawn: usize,
+        solutions_found: usize,
+        seconds: f32,
+    },
+}
+
+/// Resource: Events waiting to be sent, plus the batch currently in flight
+#[derive(Resource, Debug)]
+pub struct TelemetryQueue {
+    session: String,
+    pending: VecDeque<TelemetryEvent>,
+    in_flight: Vec<TelemetryEvent>,
+    /// Outcome of the in-flight request, filled from outside the ECS
+    outcome: Arc<Mutex<Option<bool>>>,
+    /// Send on the next flush instead of waiting out the interval
+    urgent: bool,
+}
+
+impl Default for TelemetryQueue {
+    fn default() -> Self {
+        Self {
+            session: format!("{:016x}", rand::random::<u64>()),
+            pending: VecDeque::new(),
+            in_flight: Vec::new(),
+            outcome: Arc::default(),
+            urgent: false,
+        }
+    }
+}
+
+impl TelemetryQueue {
+    pub fn push(&mut self, event: TelemetryEvent) {
+        if self.pending.len() == MAX_QUEUED {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(event);
+    }
+
+    /// Events not yet acknowledged by the server (queued or in flight)
+    pub fn len(&self) -> usize {
+        self.pending.len() + self.in_flight.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Start a batch if none is in flight; returns the request body
+    fn begin_batch(&mut self) -> Option<String> {
+        if !self.in_flight.is_empty() || self.pending.is_empty() {
+            return None;
+        }
+
+        let count = self.pending.len().min(MAX_BATCH);
+        self.in_flight = self.pending.drain(..count).collect();
+        *self.outcome.lock().unwrap() = None;
+
+        let body = serde_json::json!({
+            "session": self.session,
+            "events": self.in_flight,
+        });
+        Some(body.to_string())
+    }
+
+    /// Settle the in-flight batch: drop it once delivered, requeue it at the front otherwise
+    fn finish_batch(&mut self, delivered: bool) {
+        let batch = std::mem::take(&mut self.in_flight);
+        if delivered {
+            return;
+        }
+
+        for event in batch.into_iter().rev() {
+            self.pending.push_front(event);
+        }
+        self.pending.truncate(MAX_QUEUED);
+    }
+}
+
+/// Run condition: The player opted in
+pub fn telemetry_enabled(settings: Res<Settings>) -> bool {
+    settings.telemetry_opt_in
+}
+
+/// Resource: Tallies for the level being played
+#[derive(Resource, Default, Debug)]
+struct LevelStats {
+    /// 0 until the first level is seen
+    level: usize,
+    complexity: usize,
+    /// Real seconds when the level started
+    started: f32,
+    invalid_moves: u32,
+    /// The level's required solutions were found (skips and jumps leave it unset)
+    solved: bool,
+}
+
+/// System: Count rejected moves for the current level
+fn count_invalid_moves(
+    mut invalid_moves: MessageReader<InvalidMove>,
+    mut stats: ResMut<LevelStats>,
+) {
+    stats.invalid_moves += invalid_moves.read().count() as u32;
+}
+
+/// System: Mark the level solved once its required solutions are in
+///
+/// Runs before `record_level_changes`, so the solved board is seen even on the frame the
+/// tracker moves on (its replacement is only swapped in afterwards).
+fn note_solve(
+    session: Res<PuzzleSession>,
+    policy: Res<CompletionPolicy>,
+    mut stats: ResMut<LevelStats>,
+) {
+    stats.solved |= stats.level != 0 && policy.is_met(&session.progress());
+}
+
+/// System: Report the level left behind when the tracker moves on (a completion only if
+/// it was solved)
+fn record_level_changes(
+    time: Res<Time<Real>>,
+    tracker: Res<ProgressionTracker>,
+    mut stats: ResMut<LevelStats>,
+    mut queue: ResMut<TelemetryQueue>,
+) {
+    if !tracker.is_changed() || stats.level == tracker.current_level {
+        return;
+    }
+    let now = time.elapsed_secs();
+
+    if stats.level != 0 {
+        if stats.solved {
+            queue.push(TelemetryEvent::LevelComplete {
+                level: stats.level,
+                complexity: stats.complexity,
+                seconds: now - stats.started,
+            });
+        }
+        if stats.invalid_moves > 0 {
+            queue.push(TelemetryEvent::InvalidMoves {
+                level: stats.level,
+                count: stats.invalid_moves,
+            });
+        }
+    }
+
+    *stats = LevelStats {
+        level: tracker.current_level,
+        complexity: tracker.current_complexity(),
+        started: now,
+        invalid_moves: 0,
+        solved: false,
+    };
+}
+
+/// System: Report where the player left off when they quit or hide the tab
+///
+/// Sent right away and saved locally, since the page may not get another frame.
+fn record_abandonment(
+    time: Res<Time<Real>>,
+    session: Res<PuzzleSession>,
+    stats: Res<LevelStats>,
+    mut exits: MessageReader<AppExit>,
+    mut was_hidden: Local<bool>,
+    mut queue: ResMut<TelemetryQueue>,
+) {
+    let exiting = exits.read().count() > 0;
+    let hidden = transport::page_hidden();
+    let left = exiting || (hidden && !*was_hidden);
+    *was_hidden = hidden;
+    if !left || stats.level == 0 {
+        return;
+    }
+
+    queue.push(TelemetryEvent::Abandoned {
+        level: stats.level,
+        edges_drawn: session.edges().len(),
+        solutions_found: session.progress().solutions_found,
+        seconds: time.elapsed_secs() - stats.started,
+    });
+    queue.urgent = true;
+    transport::save_unsent(&queue);
+}
+
+/// System: Settle the last request and send the next batch when due and online
+///
+/// Failed batches go back on the queue, so events recorded offline go out once the
+/// connection returns.
+fn flush_telemetry(
+    time: Res<Time<Real>>,
+    mut queue: ResMut<TelemetryQueue>,
+    mut last_flush: Local<f32>,
+) {
+    let outcome = queue.outcome.lock().unwrap().take();
+    if let Some(delivered) = outcome {
+        queue.finish_batch(delivered);
+        // Keep the saved copy in step so a reload doesn't resend delivered events
+        transport::save_unsent(&queue);
+    }
+
+    let now = time.elapsed_secs();
+    if !queue.urgent && now - *last_flush < FLUSH_INTERVAL {
+        return;
+    }
+    if !transport::is_online() {
+        return;
+    }
+    let Some(body) = queue.begin_batch() else {
+        return;
+    };
+
+    *last_flush = now;
+    queue.urgent = false;
+    transport::send(body, queue.outcome.clone());
+}
+
+/// System: Pick up events saved by an earlier page that closed before sending them
+fn restore_unsent(mut queue: ResMut<TelemetryQueue>) {
+    for event in transport::take_unsent() {
+        queue.push(event);
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod transport {
+    use std::sync::{Arc, Mutex};
+
+    use bevy::prelude::*;
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{Headers, RequestInit, Response};
+
+    use super::{TelemetryEvent, TelemetryQueue};
+
+    /// Server route batches are posted to
+    const TELEMETRY_URL: &str = "/api/telemetry";
+
+    /// localStorage key for events that couldn't be sent before the page went away
+    const UNSENT_KEY: &str = "valence_sdf.telemetry_unsent";
+
+    pub fn page_hidden() -> bool {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .is_some_and(|document| document.hidden())
+    }
+
+    pub fn is_online() -> bool {
+        web_sys::window().is_none_or(|window| window.navigator().on_line())
+    }
+
+    pub fn send(body: String, outcome: Arc<Mutex<Option<bool>>>) {
+        let Some(window) = web_sys::window() else {
+            *outcome.lock().unwrap() = Some(false);
+            return;
+        };
+
+        let init = RequestInit::new();
+        init.set_method("POST");
+        init.set_body(&JsValue::from_str(&body));
+        // Lets the request finish if the tab is closing (web-sys has no setter for it)
+        let _ = js_sys::Reflect::set(&init, &JsValue::from_str("keepalive"), &JsValue::TRUE);
+        if let Ok(headers) = Headers::new() {
+            let _ = headers.set("Content-Type", "application/json");
+            init.set_headers(&headers);
+        }
+
+        let request = window.fetch_with_str_and_init(TELEMETRY_URL, &init);
+        wasm_bindgen_futures::spawn_local(async move {
+            let delivered = JsFuture::from(request)
+                .await
+                .ok()
+                .and_then(|response| response.dyn_into::<Response>().ok())
+                .is_some_and(|response| response.ok());
+            *outcome.lock().unwrap() = Some(delivered);
+        });
+    }
+
+    pub fn save_unsent(queue: &TelemetryQueue) {
+        let unsent: Vec<&TelemetryEvent> =
+            queue.in_flight.iter().chain(queue.pending.iter()).collect();
+        let Ok(json) = serde_json::to_string(&unsent) else {
+            return;
+        };
+
+        let saved = web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .map(|storage| storage.set_item(UNSENT_KEY, &json));
+        if !matches!(saved, Some(Ok(()))) {
+            warn!("Failed to save unsent telemetry");
+        }
+    }
+
+    pub fn take_unsent() -> Vec<TelemetryEvent> {
+        let Some(storage) =
+            web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+        else {
+            return Vec::new();
+        };
+        let saved = storage.get_item(UNSENT_KEY).ok().flatten();
+        let _ = storage.remove_item(UNSENT_KEY);
+
+        saved
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Native builds have no HTTP client; events stay queued (capped) and are never sent
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+mod transport {
+    use std::sync::{Arc, Mutex};
+
+    use super::{TelemetryEvent, TelemetryQueue};
+
+    pub fn page_hidden() -> bool {
+        false
+    }
+
+    pub fn is_online() -> bool {
+        true
+    }
+
+    pub fn send(_body: String, outcome: Arc<Mutex<Option<bool>>>) {
+        *outcome.lock().unwrap() = Some(false);
+    }
+
+    pub fn save_unsent(_queue: &TelemetryQueue) {}
+
+    pub fn take_unsent() -> Vec<TelemetryEvent> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid(count: u32) -> TelemetryEvent {
+        TelemetryEvent::InvalidMoves { level: 1, count }
+    }
+
+    #[test]
+    fn test_failed_batch_is_requeued_in_order() {
+        let mut queue = TelemetryQueue::default();
+        for count in 0..(MAX_BATCH as u32 + 5) {
+            queue.push(invalid(count));
+        }
+
+        let body = queue.begin_batch().unwrap();
+        assert!(body.contains(r#""kind":"invalid_moves""#));
+        assert_eq!(queue.in_flight.len(), MAX_BATCH);
+        // Only one batch in flight at a time
+        assert!(queue.begin_batch().is_none());
+
+        queue.finish_batch(false);
+        assert_eq!(queue.len(), MAX_BATCH + 5);
+        assert_eq!(queue.pending.front(), Some(&invalid(0)));
+
+        queue.begin_batch().unwrap();
+        queue.finish_batch(true);
+        assert_eq!(queue.len(), 5);
+        assert_eq!(queue.pending.front(), Some(&invalid(MAX_BATCH as u32)));
+    }
+
+    #[test]
+    fn test_queue_drops_oldest_when_full() {
+        let mut queue = TelemetryQueue::default();
+        for count in 0..(MAX_QUEUED as u32 + 1) {
+            queue.push(invalid(count));
+        }
+
+        assert_eq!(queue.len(), MAX_QUEUED);
+        assert_eq!(queue.pending.front(), Some(&invalid(1)));
+    }
+}
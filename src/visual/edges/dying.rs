@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::session::PuzzleSession,
+    graph::{Edge, NodeId},
+};
+
+/// How long a removed edge takes to shrink back into its origin node (seconds)
+const MELT_AWAY_DURATION: f32 = 0.35;
+
+/// Resource to track removed edges that are still animating out
+#[derive(Resource, Default)]
+pub struct DyingEdges {
+    pub(crate) edges: Vec<DyingEdge>,
+}
+
+/// An edge that was undone or reset, melting back into the node it was drawn from
+#[derive(Clone)]
+pub(crate) struct DyingEdge {
+    pub origin: NodeId, // Node the edge was drawn from (it shrinks back into this one)
+    pub target: NodeId, // Node the edge was drawn to
+    pub progress: f32,  // 0.0 = just removed (full length), 1.0 = gone
+}
+
+impl DyingEdge {
+    pub fn edge(&self) -> Edge {
+        Edge::new(self.origin, self.target)
+    }
+
+    /// Visible fraction of the edge (eased so it retracts quickly then slurps in)
+    pub fn remaining(&self) -> f32 {
+        let t = self.progress.clamp(0.0, 1.0);
+        1.0 - t * t
+    }
+}
+
+/// System: Detect edges that disappeared from the session and start their melt-away
+pub fn track_removed_edges(
+    session: Res<PuzzleSession>,
+    mut previous: Local<Vec<(Edge, NodeId)>>,
+    mut dying: ResMut<DyingEdges>,
+) {
+    if !session.is_changed() {
+        return;
+    }
+
+    let edges = session.edges();
+
+    // An edge drawn again while still melting replaces its ghost
+    dying.edges.retain(|d| !edges.contains(&d.edge()));
+
+    for &(edge, origin) in previous.iter() {
+        if !edges.contains(&edge) {
+            let target = edge.other_node(origin).unwrap_or(origin);
+            dying.edges.push(DyingEdge {
+                origin,
+                target,
+                progress: 0.0,
+            });
+        }
+    }
+
+    // Remember draw direction: the trail walks each edge from trail[i] to trail[i + 1]
+    *previous = session
+        .current_trail()
+        .windows(2)
+        .map(|pair| (Edge::new(pair[0], pair[1]), pair[0]))
+        .collect();
+}
+
+/// System: Advance melt-away animations and drop finished ones
+pub fn update_dying_edges(time: Res<Time>, mut dying: ResMut<DyingEdges>) {
+    let dt = time.delta_secs();
+
+    dying.edges.retain_mut(|d| {
+        d.progress += dt / MELT_AWAY_DURATION;
+        d.progress < 1.0
+    });
+}
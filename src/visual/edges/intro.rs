@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::{game::session::PuzzleSession, graph::Edge};
+
+/// How long a freshly drawn edge takes to fully melt into its nodes (seconds)
+const MELT_DURATION: f32 = 0.3;
+
+/// How long a new edge takes to grow from its start node to its end node (seconds)
+const GROW_DURATION: f32 = 0.18;
+
+/// Where a drawn edge's entrance has got to
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EdgeIntroProgress {
+    /// 0.0 = just drawn (hard seam), 1.0 = fully gooey
+    pub melt: f32,
+    /// 0.0 = just created (zero length), 1.0 = fully drawn
+    pub grow: f32,
+}
+
+impl EdgeIntroProgress {
+    const DONE: Self = Self {
+        melt: 1.0,
+        grow: 1.0,
+    };
+}
+
+/// Resource to track the entrance animations (melt-in and draw-in) of each drawn edge
+#[derive(Resource, Default)]
+pub struct EdgeIntro {
+    pub(crate) progress: HashMap<Edge, EdgeIntroProgress>,
+}
+
+impl EdgeIntro {
+    fn get(&self, edge: &Edge) -> EdgeIntroProgress {
+        self.progress
+            .get(edge)
+            .copied()
+            .unwrap_or(EdgeIntroProgress::DONE)
+    }
+
+    /// Blend multiplier for an edge (eased), 1.0 if the edge isn't tracked
+    pub fn blend_factor(&self, edge: &Edge) -> f32 {
+        let t = self.get(edge).melt;
+        // Ease-out quad so the goo forms quickly then settles
+        1.0 - (1.0 - t) * (1.0 - t)
+    }
+
+    /// Eased grow progress for an edge, 1.0 if the edge isn't tracked
+    pub fn grow_progress(&self, edge: &Edge) -> f32 {
+        let t = self.get(edge).grow;
+        // Ease-out cubic: shoots out of the start node, settles onto the end node
+        let x = 1.0 - t;
+        1.0 - x * x * x
+    }
+}
+
+/// System: Start the entrance animations for new edges and advance existing ones
+pub fn animate_edge_intro(
+    time: Res<Time>,
+    session: Res<PuzzleSession>,
+    mut intro: ResMut<EdgeIntro>,
+) {
+    let dt = time.delta_secs();
+
+    if session.is_changed() {
+        let edges = session.edges();
+
+        // Forget edges that were undone or reset
+        intro.progress.retain(|edge, _| edges.contains(edge));
+
+        // Newly drawn edges start un-melted and at zero length
+        for edge in edges.edges_in_order() {
+            intro.progress.entry(*edge).or_default();
+        }
+    }
+
+    for progress in intro.progress.values_mut() {
+        progress.melt = (progress.melt + dt / MELT_DURATION).min(1.0);
+        progress.grow = (progress.grow + dt / GROW_DURATION).min(1.0);
+    }
+}
@@ -1,4 +1,7 @@
+pub mod dying;
+pub mod intro;
 pub mod waves;
 
+pub use dying::{DyingEdges, track_removed_edges, update_dying_edges};
+pub use intro::{EdgeIntro, animate_edge_intro};
 pub use waves::{EdgeWaves, spawn_edge_waves, update_edge_waves};
-
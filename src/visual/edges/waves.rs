@@ -2,9 +2,15 @@ use bevy::prelude::*;
 
 use crate::{
     game::session::PuzzleSession,
-    graph::NodeId,
+    graph::{Edge, NodeId},
 };
 
+/// How many times a wave may hop to neighboring edges after the one it started on
+const MAX_WAVE_GENERATIONS: u32 = 3;
+
+/// Amplitude kept by each child wave when a wave propagates through a node
+const PROPAGATION_ATTENUATION: f32 = 0.6;
+
 /// Resource to track traveling tension waves on edges
 #[derive(Resource, Default)]
 pub struct EdgeWaves {
@@ -16,9 +22,33 @@ pub struct EdgeWaves {
 pub(crate) struct EdgeWave {
     pub from: NodeId,
     pub to: NodeId,
-    pub progress: f32,  // 0.0 = at 'from', 1.0 = at 'to'
-    pub amplitude: f32, // Wave strength (0.0 to 1.0)
-    pub direction: f32, // 0.0 = from→to, 1.0 = to→from
+    pub progress: f32,   // 0.0 = at 'from', 1.0 = at 'to'
+    pub amplitude: f32,  // Wave strength (0.0 to 1.0)
+    pub direction: f32,  // 0.0 = from→to, 1.0 = to→from
+    pub generation: u32, // 0 = spawned by a click, n = n hops through the graph
+}
+
+impl EdgeWave {
+    /// The node this wave is traveling toward
+    fn destination(&self) -> NodeId {
+        if self.direction < 0.5 {
+            self.to
+        } else {
+            self.from
+        }
+    }
+
+    /// Start a wave on `edge` traveling away from `node`
+    fn leaving(edge: &Edge, node: NodeId, amplitude: f32, generation: u32) -> Self {
+        EdgeWave {
+            from: edge.from,
+            to: edge.to,
+            progress: 0.0,
+            amplitude,
+            direction: if edge.from == node { 0.0 } else { 1.0 },
+            generation,
+        }
+    }
 }
 
 /// System: Spawn tension waves on edges when a node is clicked
@@ -44,6 +74,7 @@ pub fn spawn_edge_waves(session: Res<PuzzleSession>, mut edge_waves: ResMut<Edge
                 progress: 0.0,
                 amplitude: 1.0,
                 direction: 0.0, // from→to
+                generation: 0,
             });
         } else if edge.to == clicked_node {
             // Wave travels to→from (backwards)
@@ -53,22 +84,54 @@ pub fn spawn_edge_waves(session: Res<PuzzleSession>, mut edge_waves: ResMut<Edge
                 progress: 0.0,
                 amplitude: 1.0,
                 direction: 1.0, // to→from
+                generation: 0,
             });
         }
     }
 }
 
 /// System: Update traveling tension waves on edges
-pub fn update_edge_waves(time: Res<Time>, mut edge_waves: ResMut<EdgeWaves>) {
+///
+/// When a wave reaches the far node it splits into attenuated child waves on that
+/// node's other drawn edges, so a click ripples through the whole drawn structure.
+pub fn update_edge_waves(
+    time: Res<Time>,
+    session: Res<PuzzleSession>,
+    mut edge_waves: ResMut<EdgeWaves>,
+) {
     let dt = time.delta_secs();
+    let edges = session.edges();
+    let mut children = Vec::new();
 
     // Update all active waves
     edge_waves.waves.retain_mut(|wave| {
         wave.progress += dt * 2.0; // Speed of wave travel
         wave.amplitude *= 0.95_f32.powf(dt * 60.0); // Exponential decay
 
+        if wave.progress >= 1.0 {
+            // Wave arrived - propagate through the destination node (breadth-limited)
+            let child_amplitude = wave.amplitude * PROPAGATION_ATTENUATION;
+            if wave.generation < MAX_WAVE_GENERATIONS && child_amplitude > 0.01 {
+                let node = wave.destination();
+                let arrived_on = Edge::new(wave.from, wave.to);
+
+                for edge in edges.edges_in_order() {
+                    if *edge != arrived_on && edge.contains_node(node) {
+                        children.push(EdgeWave::leaving(
+                            edge,
+                            node,
+                            child_amplitude,
+                            wave.generation + 1,
+                        ));
+                    }
+                }
+            }
+            return false;
+        }
+
         // Keep wave if it's still active
-        wave.progress < 1.0 && wave.amplitude > 0.01
+        wave.amplitude > 0.01
     });
-}
 
+    edge_waves.waves.extend(children);
+}
@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+
+use crate::{
+    camera::{CameraShake, MainCamera},
+    visual::interactions::{InvalidMove, SolutionFound},
+};
+
+/// Shake added by an invalid move (subtle nudge)
+const INVALID_MOVE_TRAUMA: f32 = 0.3;
+
+/// Shake added by completing a solution (big punch)
+const SOLUTION_TRAUMA: f32 = 0.8;
+
+/// System: Shake the camera in response to move feedback
+pub fn punch_camera(
+    mut invalid_moves: MessageReader<InvalidMove>,
+    mut solutions: MessageReader<SolutionFound>,
+    mut cameras: Query<&mut CameraShake, With<MainCamera>>,
+) {
+    let trauma = invalid_moves.read().count() as f32 * INVALID_MOVE_TRAUMA
+        + solutions.read().count() as f32 * SOLUTION_TRAUMA;
+
+    if trauma <= 0.0 {
+        return;
+    }
+
+    for mut shake in &mut cameras {
+        shake.add_trauma(trauma);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod camera_punch;
+pub mod shockwave;
+pub mod slow_mo;
+
+pub use camera_punch::punch_camera;
+pub use shockwave::{Shockwave, trigger_shockwave, update_shockwave};
+pub use slow_mo::last_edge_slow_mo;
@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::visual::{
+    interactions::SolutionFound,
+    sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
+};
+
+/// How long the celebration shockwave lasts (seconds)
+const SHOCKWAVE_DURATION: f32 = 1.2;
+
+/// Fraction of the duration spent ramping up to full strength
+const SHOCKWAVE_ATTACK: f32 = 0.08;
+
+/// How far the ring travels before it fades out (world units)
+const SHOCKWAVE_MAX_RADIUS: f32 = 9.0;
+
+/// Resource driving the scene-wide shockwave fired by a new solution
+#[derive(Resource, Default)]
+pub struct Shockwave {
+    /// World position of the node that completed the solution
+    pub(crate) origin: Vec3,
+    /// Seconds since the shockwave fired, `None` when idle
+    pub(crate) elapsed: Option<f32>,
+}
+
+impl Shockwave {
+    /// Normalized time through the effect (0.0 = just fired, 1.0 = finished)
+    fn progress(&self) -> Option<f32> {
+        self.elapsed
+            .map(|elapsed| (elapsed / SHOCKWAVE_DURATION).clamp(0.0, 1.0))
+    }
+
+    /// Current ring radius (ease-out cubic: bursts outward, then slows)
+    pub fn radius(&self) -> f32 {
+        let Some(t) = self.progress() else {
+            return 0.0;
+        };
+        let x = 1.0 - t;
+        (1.0 - x * x * x) * SHOCKWAVE_MAX_RADIUS
+    }
+
+    /// Current glow boost of the ring: quick attack, quadratic release
+    pub fn strength(&self) -> f32 {
+        let Some(t) = self.progress() else {
+            return 0.0;
+        };
+        if t < SHOCKWAVE_ATTACK {
+            t / SHOCKWAVE_ATTACK
+        } else {
+            let release = (t - SHOCKWAVE_ATTACK) / (1.0 - SHOCKWAVE_ATTACK);
+            (1.0 - release) * (1.0 - release)
+        }
+    }
+}
+
+/// System: Fire a shockwave from the last node of every newly found solution
+pub fn trigger_shockwave(
+    mut solutions: MessageReader<SolutionFound>,
+    mut shockwave: ResMut<Shockwave>,
+) {
+    for solution in solutions.read() {
+        if solution.is_new {
+            shockwave.origin = solution.last_position;
+            shockwave.elapsed = Some(0.0);
+        }
+    }
+}
+
+/// System: Advance the shockwave envelope and push it into the scene uniform
+pub fn update_shockwave(
+    time: Res<Time>,
+    mut shockwave: ResMut<Shockwave>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+) {
+    if let Some(elapsed) = shockwave.elapsed.as_mut() {
+        *elapsed += time.delta_secs();
+        if *elapsed >= SHOCKWAVE_DURATION {
+            shockwave.elapsed = None;
+        }
+    }
+
+    // Idle and already cleared: nothing to upload
+    let cleared = materials
+        .get(&scene_handle.0)
+        .is_some_and(|m| m.data.shockwave_strength == 0.0);
+    if shockwave.elapsed.is_none() && cleared {
+        return;
+    }
+
+    let Some(material) = materials.get_mut(&scene_handle.0) else {
+        return;
+    };
+
+    material.data.shockwave_center = shockwave.origin;
+    material.data.shockwave_radius = shockwave.radius();
+    material.data.shockwave_strength = shockwave.strength();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(t: f32) -> Shockwave {
+        Shockwave {
+            origin: Vec3::ZERO,
+            elapsed: Some(t * SHOCKWAVE_DURATION),
+        }
+    }
+
+    #[test]
+    fn test_idle_shockwave_is_invisible() {
+        let shockwave = Shockwave::default();
+        assert_eq!(shockwave.radius(), 0.0);
+        assert_eq!(shockwave.strength(), 0.0);
+    }
+
+    #[test]
+    fn test_envelope_peaks_after_attack() {
+        assert!(at(0.0).strength() < 0.01);
+        assert!((at(SHOCKWAVE_ATTACK).strength() - 1.0).abs() < 1e-4);
+        assert!(at(0.5).strength() < at(SHOCKWAVE_ATTACK).strength());
+        assert!(at(1.0).strength() < 1e-4);
+    }
+
+    #[test]
+    fn test_ring_expands_monotonically() {
+        let radii: Vec<f32> = (0..=10).map(|i| at(i as f32 / 10.0).radius()).collect();
+        assert!(radii.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!((radii[10] - SHOCKWAVE_MAX_RADIUS).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::game::{clock::GameClock, session::PuzzleSession};
+
+/// Gameplay speed while the last edge is pending
+const SLOW_MO_SCALE: f32 = 0.5;
+
+/// How long the slow-mo holds before easing back (real seconds)
+const SLOW_MO_HOLD: f32 = 1.5;
+
+/// How quickly the time scale eases in and out (per real second)
+const SLOW_MO_EASE_SPEED: f32 = 4.0;
+
+/// Scale distance below which the ease snaps to its target
+const SCALE_EPSILON: f32 = 0.01;
+
+/// System: Briefly slow gameplay down when only the last edge is left to draw
+pub fn last_edge_slow_mo(
+    real_time: Res<Time<Real>>, // Virtual time is what we're scaling
+    session: Res<PuzzleSession>,
+    mut was_last_edge: Local<bool>,
+    mut hold_remaining: Local<f32>,
+    mut clock: ResMut<GameClock>,
+) {
+    let dt = real_time.delta_secs();
+    let is_last_edge = session.is_last_edge() && !session.current_trail().is_empty();
+
+    // Start the hold on entering the finale, cut it short if the finale ends (complete/undo)
+    if is_last_edge && !*was_last_edge {
+        *hold_remaining = SLOW_MO_HOLD;
+    } else if !is_last_edge {
+        *hold_remaining = 0.0;
+    }
+    *was_last_edge = is_last_edge;
+    *hold_remaining = (*hold_remaining - dt).max(0.0);
+
+    let target = if *hold_remaining > 0.0 {
+        SLOW_MO_SCALE
+    } else {
+        1.0
+    };
+    if clock.scale == target {
+        return; // Settled - leave the clock (and its change detection) alone
+    }
+
+    let next = clock.scale + (target - clock.scale) * (SLOW_MO_EASE_SPEED * dt).min(1.0);
+    clock.set_scale(if (target - next).abs() < SCALE_EPSILON {
+        target
+    } else {
+        next
+    });
+}
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::session::PuzzleSession,
+    visual::{
+        interactions::pointer::HoverState, nodes::GraphNode, physics::NodePhysics,
+        setup::SceneMetrics,
+    },
+};
+
+/// System: Valid next nodes gently "reach" toward a nearby cursor (the inverse of flee)
+pub fn node_hover_attract(
+    hover_state: Res<HoverState>,
+    session: Res<PuzzleSession>,
+    scene_metrics: Res<SceneMetrics>,
+    mut nodes: Query<(&GraphNode, &mut NodePhysics)>,
+) {
+    // Only reach when the cursor is actually near a node
+    if hover_state.hovered_node.is_none() {
+        return;
+    }
+    let Some(cursor_pos) = hover_state.cursor_world_pos else {
+        return;
+    };
+
+    let valid_nodes = session.valid_nodes();
+    let last_node = session.current_trail().last().copied();
+
+    // 🎯 SCALE FORCES BY SCENE METRICS
+    // Attraction reaches ~1 grid spacing and is much weaker than flee
+    let scale = scene_metrics.spacing;
+    let attract_range = scale * 1.0;
+    let attract_strength = scale * 0.6;
+    let min_distance = scale * 0.05;
+
+    for (graph_node, mut physics) in &mut nodes {
+        if !valid_nodes.contains(&graph_node.node_id) || Some(graph_node.node_id) == last_node {
+            continue;
+        }
+
+        let to_cursor = cursor_pos - physics.position;
+        let distance = to_cursor.length();
+
+        if distance > min_distance && distance < attract_range {
+            // Linear falloff: strongest up close, fades to nothing at the edge of range
+            let falloff = 1.0 - distance / attract_range;
+            physics.apply_force(to_cursor / distance * attract_strength * falloff);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+
+use crate::visual::interactions::{EdgeDrawn, InvalidMove};
+
+/// Valid edges in a row for each step up the multiplier
+const EDGES_PER_STEP: u32 = 5;
+
+/// The multiplier tops out here (one HUD digit)
+const MAX_MULTIPLIER: u32 = 9;
+
+/// Resource: Valid edges drawn in a row since the last invalid move
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Combo {
+    pub edges: u32,
+    /// Longest combo so far
+    pub best: u32,
+}
+
+impl Combo {
+    /// 1 to start with, one more for every `EDGES_PER_STEP` edges in the combo
+    pub fn multiplier(&self) -> u32 {
+        (1 + self.edges / EDGES_PER_STEP).min(MAX_MULTIPLIER)
+    }
+
+    /// `points` scaled by the multiplier (for timed or arcade scoring)
+    pub fn scored(&self, points: u32) -> u32 {
+        points * self.multiplier()
+    }
+
+    fn extend(&mut self, edges: u32) {
+        self.edges += edges;
+        self.best = self.best.max(self.edges);
+    }
+
+    /// End the combo, returning what it was worth (None if there wasn't one)
+    fn break_off(&mut self) -> Option<ComboEnded> {
+        if self.edges == 0 {
+            return None;
+        }
+        let ended = ComboEnded {
+            edges: self.edges,
+            multiplier: self.multiplier(),
+        };
+        self.edges = 0;
+        Some(ended)
+    }
+}
+
+/// Message sent when an invalid move ends a combo (a hook for scored modes)
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComboEnded {
+    pub edges: u32,
+    pub multiplier: u32,
+}
+
+/// System: Grow the combo with each edge the player draws and end it on an invalid move
+///
+/// Only drawn edges count: redoing an undone edge or taking a partner's trail doesn't.
+pub fn track_combo(
+    mut edges: MessageReader<EdgeDrawn>,
+    mut invalid_moves: MessageReader<InvalidMove>,
+    mut combo: ResMut<Combo>,
+    mut ended: MessageWriter<ComboEnded>,
+) {
+    let drawn = edges.read().count();
+    if drawn > 0 {
+        combo.extend(drawn as u32);
+    }
+
+    if invalid_moves.read().count() > 0
+        && let Some(broken) = combo.break_off()
+    {
+        info!(
+            "💥 Combo of {} edges ended (x{})",
+            broken.edges, broken.multiplier
+        );
+        ended.write(broken);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiplier_steps_up_and_tops_out() {
+        let mut combo = Combo::default();
+        assert_eq!(combo.multiplier(), 1);
+
+        combo.extend(EDGES_PER_STEP);
+        assert_eq!(combo.multiplier(), 2);
+        assert_eq!(combo.scored(10), 20);
+
+        combo.extend(EDGES_PER_STEP * 100);
+        assert_eq!(combo.multiplier(), MAX_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_breaking_keeps_the_best() {
+        let mut combo = Combo::default();
+        assert_eq!(combo.break_off(), None);
+
+        combo.extend(7);
+        assert_eq!(
+            combo.break_off(),
+            Some(ComboEnded {
+                edges: 7,
+                multiplier: 2
+            })
+        );
+        combo.extend(3);
+        assert_eq!(combo, Combo { edges: 3, best: 7 });
+    }
+}
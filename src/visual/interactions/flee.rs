@@ -4,26 +4,47 @@ use crate::{
     game::session::PuzzleSession,
     graph::NodeId,
     visual::{
-        nodes::GraphNode,
-        interactions::pointer::HoverState,
-        physics::NodePhysics,
+        interactions::pointer::HoverState, nodes::GraphNode, physics::NodePhysics,
         setup::SceneMetrics,
     },
 };
 
+/// How long the trigger node flees dramatically before calming down (seconds)
+const DRAMATIC_FLEE_DURATION: f32 = 1.5;
+
+/// How long the dramatic flee takes to decay into ambient flee (seconds)
+const DRAMATIC_FLEE_FADE: f32 = 0.5;
+
+/// Minimum time between flee activations; invalid clicks inside it don't restart flee
+const FLEE_COOLDOWN: f32 = 0.4;
+
 /// Resource to track if flee mode is currently active
 #[derive(Resource, Default)]
 pub struct FleeMode {
     pub active: bool,
     pub trigger_node: Option<NodeId>, // Which node triggered flee mode
     pub time_active: f32,             // How long we've been fleeing
+    pub cooldown: f32,                // Time left before flee can be restarted
 }
 
 impl FleeMode {
     pub fn activate(&mut self, node: NodeId) {
+        // Repeated invalid clicks during the cooldown just retarget the current flee
+        if self.active && self.cooldown > 0.0 {
+            self.update_trigger(node);
+            return;
+        }
+
         self.active = true;
         self.trigger_node = Some(node);
         self.time_active = 0.0;
+        self.cooldown = FLEE_COOLDOWN;
+    }
+
+    /// How dramatic the trigger node's flee is: 1.0 at first, fading to 0.0 (ambient)
+    pub fn dramatic_weight(&self) -> f32 {
+        let overtime = (self.time_active - DRAMATIC_FLEE_DURATION).max(0.0);
+        (1.0 - overtime / DRAMATIC_FLEE_FADE).max(0.0)
     }
 
     /// Update which node gets dramatic flee (called as cursor moves over invalid nodes)
@@ -63,18 +84,18 @@ pub fn node_hover_flee(
     // All distances and forces scale with grid spacing so they feel consistent
     // regardless of screen size or resolution
     let scale = scene_metrics.spacing;
-    
+
     // Scale ranges and forces relative to grid spacing
     // Dramatic flee affects ~2.67 grid spacings
     let dramatic_range = scale * 2.67;
-    let dramatic_strength = scale * 8.0;  // Reduced from 20.0
+    let dramatic_strength = scale * 8.0; // Reduced from 20.0
     let dramatic_min_offset = scale * 0.01;
-    
-    // Ambient flee affects ~1.33 grid spacings  
+
+    // Ambient flee affects ~1.33 grid spacings
     let ambient_range = scale * 1.33;
-    let ambient_strength = scale * 2.0;  // Reduced from 5.0
+    let ambient_strength = scale * 2.0; // Reduced from 5.0
     let ambient_min_offset = scale * 0.05;
-    
+
     let min_distance = scale * 0.01;
 
     // Apply flee forces
@@ -89,24 +110,49 @@ pub fn node_hover_flee(
         // Check if this is the node they tried to click
         let is_trigger = flee_mode.trigger_node == Some(graph_node.node_id);
 
-        if is_trigger {
-            // === DRAMATIC FLEE: The node they tried to add ===
-            if distance > min_distance && distance < dramatic_range {
-                let direction = to_node.normalize();
-                let flee_strength = dramatic_strength / (distance * distance + dramatic_min_offset);
-                physics.apply_force(direction * flee_strength);
-            }
+        if distance <= min_distance {
+            continue;
+        }
+        let direction = to_node / distance;
+
+        // === AMBIENT FLEE: Every invalid node ===
+        let ambient_force = if distance < ambient_range {
+            direction * ambient_strength / (distance * distance + ambient_min_offset)
         } else {
-            // === AMBIENT FLEE: Other invalid nodes ===
-            if distance > min_distance && distance < ambient_range {
-                let direction = to_node.normalize();
-                let flee_strength = ambient_strength / (distance * distance + ambient_min_offset);
-                physics.apply_force(direction * flee_strength);
-            }
+            Vec3::ZERO
+        };
+
+        // === DRAMATIC FLEE: The node they tried to add (decays into ambient over time) ===
+        let force = if is_trigger {
+            let dramatic_force = if distance < dramatic_range {
+                direction * dramatic_strength / (distance * distance + dramatic_min_offset)
+            } else {
+                Vec3::ZERO
+            };
+            ambient_force.lerp(dramatic_force, flee_mode.dramatic_weight())
+        } else {
+            ambient_force
+        };
+
+        if force != Vec3::ZERO {
+            physics.apply_force(force);
         }
     }
 }
 
+/// System: Advance flee timers (how long it's been active, cooldown before restarting)
+pub fn tick_flee_mode(time: Res<Time>, mut flee_mode: ResMut<FleeMode>) {
+    if !flee_mode.active && flee_mode.cooldown <= 0.0 {
+        return;
+    }
+
+    let dt = time.delta_secs();
+    if flee_mode.active {
+        flee_mode.time_active += dt;
+    }
+    flee_mode.cooldown = (flee_mode.cooldown - dt).max(0.0);
+}
+
 /// System: Update flee target based on cursor hover (runs every frame during flee)
 pub fn update_flee_target(
     hover_state: Res<HoverState>,
@@ -169,3 +215,36 @@ pub fn snap_back_from_flee(
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dramatic_flee_decays_after_timeout() {
+        let mut flee_mode = FleeMode::default();
+        flee_mode.activate(NodeId(4));
+        assert_eq!(flee_mode.dramatic_weight(), 1.0);
+
+        flee_mode.time_active = DRAMATIC_FLEE_DURATION + DRAMATIC_FLEE_FADE * 0.5;
+        assert!((flee_mode.dramatic_weight() - 0.5).abs() < 1e-5);
+
+        flee_mode.time_active = DRAMATIC_FLEE_DURATION + DRAMATIC_FLEE_FADE;
+        assert_eq!(flee_mode.dramatic_weight(), 0.0);
+    }
+
+    #[test]
+    fn test_repeat_clicks_during_cooldown_do_not_restart() {
+        let mut flee_mode = FleeMode::default();
+        flee_mode.activate(NodeId(4));
+        flee_mode.time_active = 0.2;
+
+        flee_mode.activate(NodeId(5));
+        assert_eq!(flee_mode.time_active, 0.2, "Flee timer should keep running");
+        assert_eq!(flee_mode.trigger_node, Some(NodeId(5)));
+
+        // Once the cooldown expires a new invalid click starts a fresh flee
+        flee_mode.cooldown = 0.0;
+        flee_mode.activate(NodeId(6));
+        assert_eq!(flee_mode.time_active, 0.0);
+    }
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{
+    graph::NodeId,
+    input::{PointerEventType, WorldPointerEvent},
+    visual::{nodes::GraphNode, physics::NodePhysics},
+};
+
+/// How close the pointer must be to a node to grab it (world units)
+const GRAB_RADIUS: f32 = 0.5;
+
+/// Resource to track a node the player is physically dragging (Shift + drag)
+#[derive(Resource, Default)]
+pub struct GrabState {
+    /// Node currently held, if any
+    pub grabbed: Option<NodeId>,
+    /// Where the held node should be (pointer position in world space)
+    pub target: Vec3,
+    /// A Shift press is down (on a node or not); its moves and release are the grab's
+    pub pressed: bool,
+    /// This frame's pointer events belong to a Shift press, so trail drawing skips them
+    pub holds_pointer: bool,
+}
+
+impl GrabState {
+    pub fn is_grabbing(&self) -> bool {
+        self.grabbed.is_some()
+    }
+}
+
+/// System: Grab a node with Shift + press, follow the pointer, let go on release
+///
+/// A Shift press and everything up to its release mark the frame as held, so they never
+/// start or extend a trail (even when the press missed every node).
+pub fn handle_node_grab(
+    mut pointer_events: MessageReader<WorldPointerEvent>,
+    keys: Res<ButtonInput<KeyCode>>,
+    nodes: Query<(&GraphNode, &NodePhysics)>,
+    mut grab_state: ResMut<GrabState>,
+) {
+    let events: Vec<WorldPointerEvent> = pointer_events.read().cloned().collect();
+    let modifier_held = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let shift_press = modifier_held
+        && events
+            .iter()
+            .any(|event| event.event_type == PointerEventType::Down);
+    // Set before the release below clears `pressed`, so the releasing frame stays held too
+    grab_state.holds_pointer = shift_press || grab_state.pressed;
+    if !grab_state.holds_pointer {
+        return;
+    }
+
+    for event in events {
+        let world_pos = event.world_pos;
+
+        match event.event_type {
+            PointerEventType::Down => {
+                grab_state.pressed = true;
+                grab_state.grabbed = nodes
+                    .iter()
+                    .filter(|(_, physics)| world_pos.distance(physics.position) < GRAB_RADIUS)
+                    .min_by(|(_, a), (_, b)| {
+                        let dist_a = world_pos.distance(a.position);
+                        let dist_b = world_pos.distance(b.position);
+                        dist_a.total_cmp(&dist_b)
+                    })
+                    .map(|(node, _)| node.node_id);
+                grab_state.target = world_pos;
+            }
+            PointerEventType::Move => {
+                grab_state.target = world_pos;
+            }
+            PointerEventType::Up => {
+                // Released: the rest spring pulls the node home on its own
+                grab_state.pressed = false;
+                grab_state.grabbed = None;
+            }
+        }
+    }
+}
+
+/// System: Pin the grabbed node to the pointer (runs in `FixedUpdate` after integration)
+pub fn hold_grabbed_node(
+    grab_state: Res<GrabState>,
+    mut nodes: Query<(&GraphNode, &mut NodePhysics)>,
+) {
+    let Some(grabbed) = grab_state.grabbed else {
+        return;
+    };
+
+    for (graph_node, mut physics) in &mut nodes {
+        if graph_node.node_id == grabbed {
+            physics.position = grab_state.target;
+            physics.velocity = Vec3::ZERO;
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// history.rs - undo/redo of the trail being drawn (keys, or the web build's buttons)
+
+use bevy::prelude::*;
+
+use crate::{
+    game::session::{PuzzleSession, SessionResult},
+    visual::{
+        interactions::pointer::{DragState, MoveFeedback, SolutionFound},
+        nodes::GraphNode,
+        physics::NodePhysics,
+    },
+};
+
+/// Message asking to step back or forward through the trail's move log
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryStep {
+    Undo,
+    Redo,
+}
+
+/// System: Z undoes the last edge; Y (or Shift+Z) redoes it
+pub fn history_keys(keys: Res<ButtonInput<KeyCode>>, mut steps: MessageWriter<HistoryStep>) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    if keys.just_pressed(KeyCode::KeyY) || (shift && keys.just_pressed(KeyCode::KeyZ)) {
+        steps.write(HistoryStep::Redo);
+    } else if keys.just_pressed(KeyCode::KeyZ) {
+        steps.write(HistoryStep::Undo);
+    }
+}
+
+/// System: Apply undo/redo requests to the session
+///
+/// A redone edge is an ordinary move again, so it grows back in with the draw-in
+/// animation, and a redo that finishes the puzzle counts as a solution.
+pub fn apply_history_steps(
+    mut steps: MessageReader<HistoryStep>,
+    nodes: Query<(&GraphNode, &NodePhysics)>,
+    mut session: ResMut<PuzzleSession>,
+    mut drag_state: ResMut<DragState>,
+    mut feedback: MoveFeedback,
+) {
+    for step in steps.read() {
+        match step {
+            HistoryStep::Undo => {
+                let Some(node) = session.undo() else {
+                    continue;
+                };
+                info!("↩️ Undid the edge to node {}", node);
+                // The pointer is usually still on it; don't let the drag redraw it at once
+                drag_state.held_off = Some(node);
+            }
+            HistoryStep::Redo => match session.redo() {
+                Some(SessionResult::Complete { is_new, .. }) => {
+                    let last_node = session.current_trail().last().copied();
+                    let last_position = nodes
+                        .iter()
+                        .find(|(graph_node, _)| Some(graph_node.node_id) == last_node)
+                        .map(|(_, physics)| physics.position)
+                        .unwrap_or_default();
+                    info!("↪️ Redo completed the puzzle");
+                    info!("Progress: {}", session.progress().display_string());
+                    feedback.solutions.write(SolutionFound {
+                        last_position,
+                        is_new,
+                    });
+
+                    // Auto-reset for next attempt, as a drawn completion does
+                    session.reset();
+                    drag_state.is_dragging = false;
+                }
+                Some(SessionResult::Invalid(err)) => warn!("Nothing to redo: {}", err),
+                Some(SessionResult::EdgeAdded(edge)) => {
+                    info!("↪️ Redid edge: {}-{}", edge.from.0, edge.to.0);
+                    drag_state.held_off = None;
+                }
+                Some(SessionResult::FirstNode(node)) => {
+                    info!("↪️ Restarted trail at node {}", node.0);
+                    drag_state.held_off = None;
+                }
+                None => {}
+            },
+        }
+    }
+}
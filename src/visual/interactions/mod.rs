@@ -1,7 +1,22 @@
+pub mod attract;
+pub mod combo;
 pub mod flee;
+pub mod grab;
+pub mod history;
 pub mod pointer;
+pub mod preview;
 pub mod trail_effects;
 
-pub use flee::{FleeMode, node_hover_flee, snap_back_from_flee, update_flee_target};
-pub use pointer::{DragState, HoverState, handle_pointer_input};
+pub use attract::node_hover_attract;
+pub use combo::{Combo, ComboEnded, track_combo};
+pub use flee::{
+    FleeMode, node_hover_flee, snap_back_from_flee, tick_flee_mode, update_flee_target,
+};
+pub use grab::{GrabState, handle_node_grab, hold_grabbed_node};
+pub use history::{HistoryStep, apply_history_steps, history_keys};
+pub use pointer::{
+    DragState, EdgeDrawn, HoverState, InvalidMove, SolutionFound, handle_pointer_input,
+    update_hover_state,
+};
+pub use preview::{PreviewEndpoint, update_preview_endpoint};
 pub use trail_effects::trigger_trail_effects;
@@ -1,65 +1,130 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::{
     camera::MainCamera,
     game::session::{PuzzleSession, SessionResult},
     graph::NodeId,
-    input::{PointerEvent, PointerEventType},
+    input::{PointerEventType, SmoothedCursor, WorldPointerEvent, window_to_world},
     visual::{
+        interactions::{flee::FleeMode, grab::GrabState},
         nodes::GraphNode,
         physics::NodePhysics,
-        interactions::flee::FleeMode,
     },
 };
 
 #[derive(Resource, Default)]
 pub struct DragState {
     pub is_dragging: bool,
+    /// Node whose edge was just undone: the drag won't redraw it until the pointer leaves
+    pub held_off: Option<NodeId>,
 }
 
+/// Message sent when the player completes a solution
+#[derive(Message, Debug, Clone)]
+pub struct SolutionFound {
+    /// World position of that node at the moment of completion
+    pub last_position: Vec3,
+    /// Whether this solution hadn't been found before
+    pub is_new: bool,
+}
+
+/// Message sent when the player tries a move the rules don't allow
+#[derive(Message, Debug, Clone)]
+pub struct InvalidMove;
+
+/// Message sent for each edge the player draws with their own trail (not for redo,
+/// bought hints or a partner's trail)
+#[derive(Message, Debug, Clone)]
+pub struct EdgeDrawn;
+
+/// Writers for the feedback messages produced by trail moves
+#[derive(SystemParam)]
+pub struct MoveFeedback<'w> {
+    pub(super) solutions: MessageWriter<'w, SolutionFound>,
+    pub(super) invalid_moves: MessageWriter<'w, InvalidMove>,
+    pub(super) edges: MessageWriter<'w, EdgeDrawn>,
+}
+
+/// How far ahead (seconds) hover looks along the smoothed cursor's motion to hide filter lag
+const CURSOR_PREDICTION: f32 = 0.02;
+
 #[derive(Resource, Default)]
 pub struct HoverState {
     pub hovered_node: Option<NodeId>,
     pub cursor_world_pos: Option<Vec3>,
 }
 
+/// System: Track the hovered node from the smoothed cursor
+pub fn update_hover_state(
+    cursor: Res<SmoothedCursor>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    nodes_query: Query<(&GraphNode, &NodePhysics)>,
+    mut hover_state: ResMut<HoverState>,
+) {
+    if !cursor.is_changed() {
+        return;
+    }
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+    let Some(world_pos) = cursor
+        .predicted(CURSOR_PREDICTION)
+        .and_then(|position| window_to_world(position, camera, camera_transform))
+    else {
+        return;
+    };
+
+    // Which node is closest to the cursor
+    let hovered_node = nodes_query
+        .iter()
+        .min_by(|(_, physics_a), (_, physics_b)| {
+            let dist_a = world_pos.distance(physics_a.position);
+            let dist_b = world_pos.distance(physics_b.position);
+            dist_a.total_cmp(&dist_b)
+        })
+        .filter(|(_, physics)| world_pos.distance(physics.position) < 1.0) // Only hover if within range
+        .map(|(node, _)| node.node_id);
+
+    hover_state.cursor_world_pos = Some(world_pos);
+    hover_state.hovered_node = hovered_node;
+}
+
 /// System: Handle pointer input for drawing trails
 pub fn handle_pointer_input(
-    mut pointer_events: MessageReader<PointerEvent>,
-    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut pointer_events: MessageReader<WorldPointerEvent>,
     nodes_query: Query<(&GraphNode, &NodePhysics)>,
     mut session: ResMut<PuzzleSession>,
     mut drag_state: ResMut<DragState>,
-    mut hover_state: ResMut<HoverState>,
     mut flee_mode: ResMut<FleeMode>,
+    grab_state: Res<GrabState>,
+    mut feedback: MoveFeedback,
 ) {
-    let Ok((camera, camera_transform)) = camera_query.single() else {
+    if grab_state.holds_pointer {
+        pointer_events.clear();
         return;
-    };
+    }
 
     for event in pointer_events.read() {
-        let Some(world_pos) = event.to_world_position(camera, camera_transform) else {
-            continue;
-        };
-
-        // Update hover state (which node is closest to cursor)
-        hover_state.cursor_world_pos = Some(world_pos);
-        hover_state.hovered_node = nodes_query
-            .iter()
-            .min_by(|(_, physics_a), (_, physics_b)| {
-                let dist_a = world_pos.distance(physics_a.position);
-                let dist_b = world_pos.distance(physics_b.position);
-                dist_a.partial_cmp(&dist_b).unwrap()
-            })
-            .filter(|(_, physics)| world_pos.distance(physics.position) < 1.0) // Only hover if within range
-            .map(|(node, _)| node.node_id);
+        let world_pos = event.world_pos;
 
         match event.event_type {
             PointerEventType::Down => {
+                drag_state.held_off = None;
+
                 // Check if we're clicking on a node to start dragging
                 for (graph_node, physics) in &nodes_query {
                     let distance = world_pos.distance(physics.position);
                     if distance < 0.5 {
+                        // Pressing the end of a trail left standing (resumed from a save,
+                        // or a partner's) carries on drawing it
+                        if session.current_trail().last() == Some(&graph_node.node_id) {
+                            info!("Picked up the trail at node {}", graph_node.node_id.0);
+                            drag_state.is_dragging = true;
+                            flee_mode.deactivate();
+                            break;
+                        }
+
                         match session.add_node(graph_node.node_id) {
                             SessionResult::FirstNode(node) => {
                                 info!("Started trail at node {}", node.0);
@@ -70,6 +135,7 @@ pub fn handle_pointer_input(
                                 info!("Added edge: {}-{}", edge.from.0, edge.to.0);
                                 drag_state.is_dragging = true;
                                 flee_mode.deactivate(); // Success - deactivate flee mode
+                                feedback.edges.write(EdgeDrawn);
                             }
                             SessionResult::Complete {
                                 solution: _,
@@ -81,6 +147,11 @@ pub fn handle_pointer_input(
                                     info!("Solution completed (already found)");
                                 }
                                 info!("Progress: {}", session.progress().display_string());
+                                feedback.edges.write(EdgeDrawn);
+                                feedback.solutions.write(SolutionFound {
+                                    last_position: physics.position,
+                                    is_new,
+                                });
 
                                 // Auto-reset for next attempt
                                 session.reset();
@@ -91,6 +162,7 @@ pub fn handle_pointer_input(
                             SessionResult::Invalid(err) => {
                                 warn!("❌ Invalid move attempted: {} - ACTIVATING FLEE MODE", err);
                                 flee_mode.activate(graph_node.node_id);
+                                feedback.invalid_moves.write(InvalidMove);
                             }
                         }
                         break;
@@ -107,12 +179,21 @@ pub fn handle_pointer_input(
                     for (graph_node, physics) in &nodes_query {
                         let distance = world_pos.distance(physics.position);
 
+                        // Leaving a node that was just undone lets it be drawn again
+                        if drag_state.held_off == Some(graph_node.node_id) {
+                            if distance >= 0.5 {
+                                drag_state.held_off = None;
+                            }
+                            continue;
+                        }
+
                         // Check if we're close to a node and it's not the last node we added
                         if distance < 0.5 && Some(graph_node.node_id) != last_node {
                             match session.add_node(graph_node.node_id) {
                                 SessionResult::EdgeAdded(edge) => {
                                     info!("Added edge: {}-{}", edge.from.0, edge.to.0);
                                     flee_mode.deactivate(); // Success - deactivate flee mode
+                                    feedback.edges.write(EdgeDrawn);
                                 }
                                 SessionResult::Complete {
                                     solution: _,
@@ -124,6 +205,11 @@ pub fn handle_pointer_input(
                                         info!("Solution completed (already found)");
                                     }
                                     info!("Progress: {}", session.progress().display_string());
+                                    feedback.edges.write(EdgeDrawn);
+                                    feedback.solutions.write(SolutionFound {
+                                        last_position: physics.position,
+                                        is_new,
+                                    });
 
                                     // Auto-reset for next attempt
                                     session.reset();
@@ -138,6 +224,7 @@ pub fn handle_pointer_input(
                                         err
                                     );
                                     flee_mode.activate(graph_node.node_id);
+                                    feedback.invalid_moves.write(InvalidMove);
                                 }
                                 _ => {}
                             }
@@ -150,6 +237,7 @@ pub fn handle_pointer_input(
             PointerEventType::Up => {
                 // Stop dragging and reset for next attempt
                 drag_state.is_dragging = false;
+                drag_state.held_off = None;
                 let trail_length = session.current_trail().len();
 
                 // Deactivate flee mode when user releases
@@ -165,4 +253,3 @@ pub fn handle_pointer_input(
         }
     }
 }
-
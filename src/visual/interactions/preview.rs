@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::session::PuzzleSession,
+    visual::{
+        interactions::pointer::{DragState, HoverState},
+        nodes::GraphNode,
+        physics::NodePhysics,
+    },
+};
+
+/// How quickly the preview endpoint is pulled onto a hovered node (per second)
+const SNAP_SPEED: f32 = 25.0;
+
+/// Distance below which the endpoint counts as settled on its target
+const SNAP_EPSILON: f32 = 1e-3;
+
+/// Resource: Where the preview edge from the trail head currently ends
+#[derive(Resource, Default)]
+pub struct PreviewEndpoint {
+    /// Eased endpoint, `None` while not dragging
+    pub position: Option<Vec3>,
+}
+
+/// System: Magnetically snap the preview edge onto the hovered node, else follow the cursor
+pub fn update_preview_endpoint(
+    time: Res<Time>,
+    session: Res<PuzzleSession>,
+    drag_state: Res<DragState>,
+    hover_state: Res<HoverState>,
+    nodes: Query<(&GraphNode, &NodePhysics)>,
+    mut preview: ResMut<PreviewEndpoint>,
+) {
+    let last_node = session.current_trail().last().copied();
+    let (true, Some(_), Some(cursor_pos)) = (
+        drag_state.is_dragging,
+        last_node,
+        hover_state.cursor_world_pos,
+    ) else {
+        if preview.position.is_some() {
+            preview.position = None;
+        }
+        return;
+    };
+
+    // Snap to the hovered node (not the one we're drawing from)
+    let target = hover_state
+        .hovered_node
+        .filter(|&node| Some(node) != last_node)
+        .and_then(|node| nodes.iter().find(|(n, _)| n.node_id == node))
+        .map(|(_, physics)| physics.render_position)
+        .unwrap_or(cursor_pos);
+
+    let Some(current) = preview.position else {
+        preview.position = Some(target);
+        return;
+    };
+
+    let next = current.lerp(target, (SNAP_SPEED * time.delta_secs()).min(1.0));
+    let next = if next.distance(target) < SNAP_EPSILON {
+        target
+    } else {
+        next
+    };
+    if next != current {
+        preview.position = Some(next);
+    }
+}
@@ -56,9 +56,6 @@ pub fn trigger_trail_effects(
             visual.ripple_phase = 0.0;
             visual.ripple_amplitude = 0.8; // Full strength ripple
 
-            // === TRIGGER GLOW on the added node ===
-            visual.glow = 1.0; // Full brightness glow (immediate)
-
             // === RUBBER BAND SNAP: Push node away from the edge ===
             if let Some(prev_pos) = prev_node_pos {
                 // Calculate direction away from previous node (along the edge)
@@ -79,4 +76,3 @@ pub fn trigger_trail_effects(
         // where it applies continuous flee forces while hovering
     }
 }
-
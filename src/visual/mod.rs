@@ -1,8 +1,12 @@
 pub mod edges;
+pub mod effects;
 pub mod interactions;
 pub mod nodes;
+pub mod particles;
 pub mod physics;
 pub mod plugin;
 pub mod sdf;
 pub mod setup;
+pub mod theme;
 pub mod ui;
+pub mod utils;
@@ -3,15 +3,26 @@ use bevy::prelude::*;
 use crate::{
     game::session::PuzzleSession,
     visual::{
-        nodes::{GraphNode, valence_to_color, components::NodeVisual},
+        interactions::FleeMode,
+        nodes::{GraphNode, components::NodeVisual},
         physics::NodePhysics,
+        theme::Theme,
     },
 };
 
+/// How fast spikes shoot out / retract (per second)
+const SPIKE_RISE_SPEED: f32 = 12.0;
+const SPIKE_FALL_SPEED: f32 = 6.0;
+
+/// Spike level for the node the player tried to add vs. other invalid nodes
+const TRIGGER_SPIKE: f32 = 1.0;
+const AMBIENT_SPIKE: f32 = 0.5;
+
 /// System: Update visual animation states (color transition, squeeze, ripple decay)
 pub fn update_node_visuals(
     time: Res<Time>,
     session: Res<PuzzleSession>,
+    theme: Res<Theme>,
     mut nodes: Query<(&GraphNode, &NodePhysics, &mut NodeVisual)>,
 ) {
     let dt = time.delta_secs();
@@ -21,23 +32,12 @@ pub fn update_node_visuals(
         let valence = valences.get(graph_node.node_id);
 
         // === Smooth Color Transition (Ease-Out) ===
-        let target_color = valence_to_color(valence);
-        
+        let target_color = theme.node_color(valence);
+
         // Fast exponential ease-out: starts very quick, slows near target
         // Higher value = faster transition (8.0 = ~0.125s, 12.0 = ~0.08s)
         visual.current_color = visual.current_color.lerp(target_color, dt * 8.0);
 
-        // === Glow Decay (rapid fade) ===
-        if visual.glow > 0.0 {
-            // Fast exponential decay for snappy feedback
-            visual.glow *= 0.95_f32.powf(dt * 60.0);  // Exponential decay
-            
-            // Clamp to zero when nearly invisible
-            if visual.glow < 0.01 {
-                visual.glow = 0.0;
-            }
-        }
-
         // === Squeeze from valence ===
         visual.target_squeeze = match valence {
             0 => 0.3,
@@ -74,3 +74,44 @@ pub fn update_node_visuals(
     }
 }
 
+/// System: Raise spikes on nodes that can't be added while flee mode is active
+pub fn update_node_spikes(
+    time: Res<Time>,
+    session: Res<PuzzleSession>,
+    flee_mode: Res<FleeMode>,
+    mut nodes: Query<(&GraphNode, &mut NodeVisual)>,
+) {
+    let dt = time.delta_secs();
+    let flee_nodes = if flee_mode.active {
+        session.nodes_to_flee()
+    } else {
+        Vec::new()
+    };
+
+    for (graph_node, mut visual) in &mut nodes {
+        let target = if !flee_nodes.contains(&graph_node.node_id) {
+            0.0
+        } else if flee_mode.trigger_node == Some(graph_node.node_id) {
+            TRIGGER_SPIKE
+        } else {
+            AMBIENT_SPIKE
+        };
+
+        if visual.spike == target {
+            continue; // Settled - don't trip change detection
+        }
+
+        // Snap out fast, retract a little slower
+        let speed = if target > visual.spike {
+            SPIKE_RISE_SPEED
+        } else {
+            SPIKE_FALL_SPEED
+        };
+        let step = speed * dt;
+        visual.spike = if (target - visual.spike).abs() <= step {
+            target
+        } else {
+            visual.spike + step * (target - visual.spike).signum()
+        };
+    }
+}
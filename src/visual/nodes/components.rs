@@ -11,12 +11,15 @@ pub struct NodeVisual {
     pub ripple_amplitude: f32,
     /// Target squeeze factor (for smooth interpolation)
     pub target_squeeze: f32,
-    
+
     /// Current display color (smoothly transitions when valence changes)
     pub current_color: Vec4,
-    
-    /// Glow intensity (0.0 = none, 1.0 = full glow) - multi-purpose effect
+
+    /// Glow intensity (0.0 = none, 1.0 = full glow) - combined from `NodeGlow` channels
     pub glow: f32,
+
+    /// Spike intensity (0.0 = smooth, 1.0 = fully spiky) - raised on invalid nodes while fleeing
+    pub spike: f32,
 }
 
 impl Default for NodeVisual {
@@ -28,7 +31,7 @@ impl Default for NodeVisual {
             target_squeeze: 0.0,
             current_color: Vec4::new(0.5, 0.5, 0.5, 1.0),
             glow: 0.0,
+            spike: 0.0,
         }
     }
 }
-
@@ -0,0 +1,293 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        director::Director, hints::HintEngine, progression::ProgressionTracker,
+        session::PuzzleSession,
+    },
+    graph::NodeId,
+    visual::{
+        interactions::{Combo, HoverState, SolutionFound},
+        nodes::{GraphNode, components::NodeVisual},
+    },
+};
+
+/// Steady glow on a hovered node that can be added next
+const HOVER_GLOW: f32 = 0.35;
+const HOVER_FADE_SPEED: f32 = 10.0;
+
+/// Per-frame decay of the flash on a freshly added node (at 60fps)
+const LAST_ADDED_DECAY: f32 = 0.95;
+
+/// Hinted node pulses between these levels
+const HINT_GLOW_MIN: f32 = 0.3;
+const HINT_GLOW_MAX: f32 = 0.8;
+const HINT_PULSE_SPEED: f32 = 4.0;
+const HINT_FADE_SPEED: f32 = 6.0;
+
+/// Steady glow on the two nodes left to connect during the last edge
+const FINALE_GLOW: f32 = 0.6;
+const FINALE_FADE_SPEED: f32 = 5.0;
+
+/// Trail nodes glow this much more for each step up the combo multiplier, up to the max
+const COMBO_GLOW_PER_STEP: f32 = 0.08;
+const COMBO_GLOW_MAX: f32 = 0.5;
+const COMBO_FADE_SPEED: f32 = 4.0;
+
+/// Per-frame decay of the whole-board flash on a solution (slower than a single add)
+const CELEBRATION_DECAY: f32 = 0.975;
+
+/// Channel levels below this snap to zero
+const GLOW_EPSILON: f32 = 0.01;
+
+/// Independent glow sources for a node - each is driven by its own system,
+/// and the brightest one is what the shader sees
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct NodeGlow {
+    /// Cursor is over this node and it's a valid next move
+    pub hover: f32,
+    /// Flash when the node is added to the trail
+    pub last_added: f32,
+    /// Pulse while the node is the current hint
+    pub hint: f32,
+    /// Whole-board flash when a solution is completed
+    pub celebration: f32,
+    /// Build-up on the last two nodes when only one edge is left
+    pub finale: f32,
+    /// Steady glow on the trail that deepens as the combo grows
+    pub combo: f32,
+}
+
+impl NodeGlow {
+    /// Final glow level sent to the shader
+    pub fn combined(&self) -> f32 {
+        self.hover
+            .max(self.last_added)
+            .max(self.hint)
+            .max(self.celebration)
+            .max(self.finale)
+            .max(self.combo)
+    }
+}
+
+/// Resource: Node currently highlighted as a hint (if any)
+#[derive(Resource, Default)]
+pub struct HintHighlight {
+    pub node: Option<NodeId>,
+}
+
+/// Move `current` toward `target` with exponential ease-out, snapping when close
+fn approach(current: f32, target: f32, speed: f32, dt: f32) -> f32 {
+    let next = current + (target - current) * (speed * dt).min(1.0);
+    if (target - next).abs() < GLOW_EPSILON {
+        target
+    } else {
+        next
+    }
+}
+
+/// Exponential per-frame decay (framerate independent), snapping to zero when faint
+fn decay(current: f32, per_frame: f32, dt: f32) -> f32 {
+    let next = current * per_frame.powf(dt * 60.0);
+    if next < GLOW_EPSILON { 0.0 } else { next }
+}
+
+/// System: Softly light up the hovered node when it's a valid next move
+pub fn glow_hovered_node(
+    time: Res<Time>,
+    hover_state: Res<HoverState>,
+    session: Res<PuzzleSession>,
+    mut nodes: Query<(&GraphNode, &mut NodeGlow)>,
+) {
+    let dt = time.delta_secs();
+    let valid_nodes = session.valid_nodes();
+
+    for (graph_node, mut glow) in &mut nodes {
+        let hovered = hover_state.hovered_node == Some(graph_node.node_id)
+            && valid_nodes.contains(&graph_node.node_id);
+        let target = if hovered { HOVER_GLOW } else { 0.0 };
+
+        let next = approach(glow.hover, target, HOVER_FADE_SPEED, dt);
+        if next != glow.hover {
+            glow.hover = next;
+        }
+    }
+}
+
+/// System: Flash the node that was just added to the trail
+pub fn glow_last_added(
+    time: Res<Time>,
+    session: Res<PuzzleSession>,
+    mut last_trail_length: Local<usize>,
+    mut nodes: Query<(&GraphNode, &mut NodeGlow)>,
+) {
+    let dt = time.delta_secs();
+    let trail = session.current_trail();
+
+    // Only flash when the trail grew (undo/reset shouldn't light anything up)
+    let added = if trail.len() > *last_trail_length {
+        trail.last().copied()
+    } else {
+        None
+    };
+    *last_trail_length = trail.len();
+
+    for (graph_node, mut glow) in &mut nodes {
+        if Some(graph_node.node_id) == added {
+            glow.last_added = 1.0; // Full brightness (immediate)
+        } else if glow.last_added > 0.0 {
+            glow.last_added = decay(glow.last_added, LAST_ADDED_DECAY, dt);
+        }
+    }
+}
+
+/// System: Show the next move toward a solution the player hasn't found, while a hint
+/// token has bought one or the director finds them stuck on the level
+pub fn show_hints(
+    director: Res<Director>,
+    tracker: Res<ProgressionTracker>,
+    session: Res<PuzzleSession>,
+    mut engine: ResMut<HintEngine>,
+    mut hint: ResMut<HintHighlight>,
+) {
+    let wanted = engine.bought(&session) || director.stuck_on(tracker.current_level);
+    let node = if wanted {
+        engine.next_node(&session)
+    } else {
+        None
+    };
+    if hint.node != node {
+        hint.node = node;
+    }
+}
+
+/// System: Pulse the hinted node
+pub fn glow_hint_node(
+    time: Res<Time>,
+    hint: Res<HintHighlight>,
+    mut nodes: Query<(&GraphNode, &mut NodeGlow)>,
+) {
+    let dt = time.delta_secs();
+    let pulse = 0.5 + 0.5 * (time.elapsed_secs() * HINT_PULSE_SPEED).sin();
+
+    for (graph_node, mut glow) in &mut nodes {
+        let next = if hint.node == Some(graph_node.node_id) {
+            HINT_GLOW_MIN + (HINT_GLOW_MAX - HINT_GLOW_MIN) * pulse
+        } else {
+            approach(glow.hint, 0.0, HINT_FADE_SPEED, dt)
+        };
+        if next != glow.hint {
+            glow.hint = next;
+        }
+    }
+}
+
+/// System: Light up the whole board when a solution is completed
+pub fn glow_on_solution(
+    time: Res<Time>,
+    mut solutions: MessageReader<SolutionFound>,
+    mut nodes: Query<&mut NodeGlow>,
+) {
+    let dt = time.delta_secs();
+    let celebrate = solutions.read().count() > 0;
+
+    for mut glow in &mut nodes {
+        if celebrate {
+            glow.celebration = 1.0;
+        } else if glow.celebration > 0.0 {
+            glow.celebration = decay(glow.celebration, CELEBRATION_DECAY, dt);
+        }
+    }
+}
+
+/// System: Light the two nodes left to connect when only the last edge remains
+pub fn glow_finale_nodes(
+    time: Res<Time>,
+    session: Res<PuzzleSession>,
+    mut nodes: Query<(&GraphNode, &mut NodeGlow)>,
+) {
+    let dt = time.delta_secs();
+    let is_last_edge = session.is_last_edge() && !session.current_trail().is_empty();
+    let valences = session.current_valences();
+
+    for (graph_node, mut glow) in &mut nodes {
+        let remaining = is_last_edge && valences.get(graph_node.node_id) > 0;
+        let target = if remaining { FINALE_GLOW } else { 0.0 };
+
+        let next = approach(glow.finale, target, FINALE_FADE_SPEED, dt);
+        if next != glow.finale {
+            glow.finale = next;
+        }
+    }
+}
+
+/// System: Deepen the trail's glow as the combo multiplier climbs
+pub fn glow_combo_trail(
+    time: Res<Time>,
+    combo: Res<Combo>,
+    session: Res<PuzzleSession>,
+    mut nodes: Query<(&GraphNode, &mut NodeGlow)>,
+) {
+    let dt = time.delta_secs();
+    let level = (COMBO_GLOW_PER_STEP * (combo.multiplier() - 1) as f32).min(COMBO_GLOW_MAX);
+    let trail = session.current_trail();
+
+    for (graph_node, mut glow) in &mut nodes {
+        let target = if trail.contains(&graph_node.node_id) {
+            level
+        } else {
+            0.0
+        };
+        let next = approach(glow.combo, target, COMBO_FADE_SPEED, dt);
+        if next != glow.combo {
+            glow.combo = next;
+        }
+    }
+}
+
+/// System: Fold all glow channels into the single value the shader uses
+pub fn combine_node_glow(mut nodes: Query<(&NodeGlow, &mut NodeVisual), Changed<NodeGlow>>) {
+    for (glow, mut visual) in &mut nodes {
+        let combined = glow.combined();
+        if visual.glow != combined {
+            visual.glow = combined;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_brightest_channel_wins() {
+        let glow = NodeGlow {
+            hover: 0.35,
+            last_added: 0.1,
+            hint: 0.6,
+            celebration: 0.0,
+            finale: 0.2,
+            combo: 0.4,
+        };
+        assert_eq!(glow.combined(), 0.6);
+        assert_eq!(NodeGlow::default().combined(), 0.0);
+    }
+
+    #[test]
+    fn test_approach_snaps_to_target() {
+        let mut value = 0.0;
+        for _ in 0..200 {
+            value = approach(value, HOVER_GLOW, HOVER_FADE_SPEED, 1.0 / 60.0);
+        }
+        assert_eq!(value, HOVER_GLOW);
+    }
+
+    #[test]
+    fn test_decay_reaches_zero() {
+        let mut value = 1.0;
+        for _ in 0..600 {
+            value = decay(value, CELEBRATION_DECAY, 1.0 / 60.0);
+        }
+        assert_eq!(value, 0.0);
+    }
+}
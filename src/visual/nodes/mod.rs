@@ -1,11 +1,16 @@
 pub mod animations;
 pub mod components;
+pub mod glow;
 
 use crate::graph::NodeId;
 use bevy::prelude::*;
 
-pub use animations::update_node_visuals;
+pub use animations::{update_node_spikes, update_node_visuals};
 pub use components::NodeVisual;
+pub use glow::{
+    HintHighlight, NodeGlow, combine_node_glow, glow_combo_trail, glow_finale_nodes,
+    glow_hint_node, glow_hovered_node, glow_last_added, glow_on_solution, show_hints,
+};
 
 #[derive(Component)]
 pub struct GraphNode {
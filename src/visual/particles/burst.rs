@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    game::rng::GameRng,
+    visual::{
+        interactions::SolutionFound,
+        nodes::{GraphNode, NodeVisual},
+        particles::point::{MAX_PARTICLES, SdfParticle},
+        physics::NodePhysics,
+        sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
+    },
+};
+
+/// How many particles each node throws out per burst
+const PARTICLES_PER_NODE: usize = 8;
+
+/// How long a particle lives (seconds)
+const PARTICLE_LIFETIME: f32 = 0.9;
+
+/// Downward pull on particles (world units / s²)
+const PARTICLE_GRAVITY: f32 = 2.5;
+
+/// Velocity lost per second to air drag (fraction)
+const PARTICLE_DRAG: f32 = 1.5;
+
+/// Radius of a freshly emitted particle (shrinks as it fades)
+const PARTICLE_RADIUS: f32 = 0.08;
+
+/// A single CPU-simulated particle
+#[derive(Clone, Debug)]
+pub(crate) struct Particle {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    pub color: Vec4,
+    pub age: f32, // Seconds since emission
+}
+
+impl Particle {
+    /// Fraction of the lifetime used up (0.0 = just emitted, 1.0 = dead)
+    fn life(&self) -> f32 {
+        (self.age / PARTICLE_LIFETIME).clamp(0.0, 1.0)
+    }
+
+    /// Shader representation: shrinks and fades quadratically over its life
+    fn to_sdf(&self) -> SdfParticle {
+        let fade = (1.0 - self.life()).powi(2);
+        SdfParticle {
+            position: self.position,
+            radius: PARTICLE_RADIUS * (0.25 + 0.75 * fade),
+            color: self.color.truncate().extend(fade),
+        }
+    }
+}
+
+/// Resource holding all live particles
+#[derive(Resource, Default)]
+pub struct ParticleSystem {
+    pub(crate) particles: Vec<Particle>,
+}
+
+impl ParticleSystem {
+    /// Throw a ring of particles out of a point, dropping the oldest if full
+    pub fn emit_burst(&mut self, origin: Vec3, color: Vec4, rng: &mut impl Rng) {
+        for i in 0..PARTICLES_PER_NODE {
+            // Evenly spaced angles with a little jitter so bursts don't look stamped
+            let angle = (i as f32 + rng.random_range(-0.3..0.3)) / PARTICLES_PER_NODE as f32
+                * std::f32::consts::TAU;
+            let speed = rng.random_range(2.0..4.0);
+
+            self.particles.push(Particle {
+                position: origin,
+                velocity: Vec3::new(angle.cos(), angle.sin(), 0.0) * speed
+                    + Vec3::Z * rng.random_range(0.0..0.5),
+                color,
+                age: 0.0,
+            });
+        }
+
+        let overflow = self.particles.len().saturating_sub(MAX_PARTICLES);
+        self.particles.drain(..overflow);
+    }
+
+    /// Advance motion and fade, removing dead particles
+    pub fn step(&mut self, dt: f32) {
+        let damping = (1.0 - PARTICLE_DRAG * dt).max(0.0);
+
+        self.particles.retain_mut(|p| {
+            p.velocity.y -= PARTICLE_GRAVITY * dt;
+            p.velocity *= damping;
+            p.position += p.velocity * dt;
+            p.age += dt;
+            p.age < PARTICLE_LIFETIME
+        });
+    }
+}
+
+/// System: Burst particles out of every node when a new solution is found
+pub fn spawn_solution_burst(
+    mut solutions: MessageReader<SolutionFound>,
+    nodes: Query<(&NodePhysics, &NodeVisual), With<GraphNode>>,
+    mut particles: ResMut<ParticleSystem>,
+    mut rng: ResMut<GameRng>,
+) {
+    for solution in solutions.read() {
+        if !solution.is_new {
+            continue;
+        }
+        for (physics, visual) in &nodes {
+            particles.emit_burst(physics.position, visual.current_color, &mut *rng);
+        }
+    }
+}
+
+/// System: Simulate particles
+pub fn update_particles(time: Res<Time>, mut particles: ResMut<ParticleSystem>) {
+    particles.step(time.delta_secs());
+}
+
+/// System: Copy live particles into the scene uniform
+pub fn sync_particles(
+    particles: Res<ParticleSystem>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+) {
+    // No particles alive and none left on the GPU: nothing to upload
+    let cleared = materials
+        .get(&scene_handle.0)
+        .is_some_and(|m| m.data.num_particles == 0);
+    if particles.particles.is_empty() && cleared {
+        return;
+    }
+
+    let Some(material) = materials.get_mut(&scene_handle.0) else {
+        return;
+    };
+
+    for (slot, particle) in material.data.particles.iter_mut().zip(&particles.particles) {
+        *slot = particle.to_sdf();
+    }
+    material.data.num_particles = particles.particles.len().min(MAX_PARTICLES) as u32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_is_capped() {
+        let mut system = ParticleSystem::default();
+        let mut rng = rand::rng();
+
+        for _ in 0..20 {
+            system.emit_burst(Vec3::ZERO, Vec4::ONE, &mut rng);
+        }
+
+        assert_eq!(system.particles.len(), MAX_PARTICLES);
+    }
+
+    #[test]
+    fn test_particles_fade_and_die() {
+        let mut system = ParticleSystem::default();
+        system.emit_burst(Vec3::ZERO, Vec4::ONE, &mut rand::rng());
+
+        system.step(PARTICLE_LIFETIME * 0.5);
+        let mid = system.particles[0].to_sdf();
+        assert!(mid.color.w < 1.0 && mid.color.w > 0.0);
+        assert!(mid.radius < PARTICLE_RADIUS);
+
+        system.step(PARTICLE_LIFETIME);
+        assert!(system.particles.is_empty());
+    }
+}
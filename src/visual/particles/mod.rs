@@ -0,0 +1,5 @@
+pub mod burst;
+pub mod point;
+
+pub use burst::{ParticleSystem, spawn_solution_burst, sync_particles, update_particles};
+pub use point::{MAX_PARTICLES, SdfParticle};
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::ShaderType;
+
+/// Maximum number of particles the scene uniform can hold
+pub const MAX_PARTICLES: usize = 72;
+
+/// A single glowing point in the scene
+#[derive(ShaderType, Debug, Clone, Copy, PartialEq)]
+pub struct SdfParticle {
+    pub position: Vec3,
+    pub radius: f32,
+
+    /// Glow color, alpha = current brightness
+    pub color: Vec4,
+}
+
+impl Default for SdfParticle {
+    fn default() -> Self {
+        SdfParticle {
+            position: Vec3::ZERO,
+            radius: 0.0,
+            color: Vec4::ZERO,
+        }
+    }
+}
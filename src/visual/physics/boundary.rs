@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::camera::CameraBounds;
+
+/// Width of the soft zone inside the wall where nodes start getting pushed back,
+/// as a multiple of the wall margin
+const SOFT_ZONE_FACTOR: f32 = 1.5;
+
+/// Wall for node centers: the camera bounds shrunk by `margin` on every side
+fn inner_bounds(bounds: &CameraBounds, margin: f32) -> (Vec2, Vec2) {
+    let min = Vec2::new(bounds.left + margin, bounds.bottom + margin);
+    let max = Vec2::new(bounds.right - margin, bounds.top - margin);
+    (min, max.max(min))
+}
+
+/// Soft push back toward the screen for a node inside the soft zone near a wall
+///
+/// Grows linearly from zero at the inner edge of the soft zone to `strength` at the wall.
+pub fn boundary_force(position: Vec3, bounds: &CameraBounds, margin: f32, strength: f32) -> Vec3 {
+    let (min, max) = inner_bounds(bounds, margin);
+    let soft = (margin * SOFT_ZONE_FACTOR).max(f32::EPSILON);
+    let p = position.truncate();
+
+    // Penetration into the soft zone on each side (0 = outside it, 1 = at the wall)
+    let from_min = ((min + Vec2::splat(soft) - p) / soft).clamp(Vec2::ZERO, Vec2::ONE);
+    let from_max = ((p - (max - Vec2::splat(soft))) / soft).clamp(Vec2::ZERO, Vec2::ONE);
+
+    ((from_min - from_max) * strength).extend(0.0)
+}
+
+/// Hard wall: keep a node's center inside the bounds, killing velocity into the wall
+pub fn clamp_to_bounds(
+    position: &mut Vec3,
+    velocity: &mut Vec3,
+    bounds: &CameraBounds,
+    margin: f32,
+) {
+    let (min, max) = inner_bounds(bounds, margin);
+
+    if position.x < min.x || position.x > max.x {
+        position.x = position.x.clamp(min.x, max.x);
+        velocity.x = 0.0;
+    }
+    if position.y < min.y || position.y > max.y {
+        position.y = position.y.clamp(min.y, max.y);
+        velocity.y = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> CameraBounds {
+        CameraBounds {
+            left: 0.0,
+            right: 4.0,
+            bottom: 0.0,
+            top: 8.0,
+        }
+    }
+
+    #[test]
+    fn test_no_force_in_the_middle() {
+        let force = boundary_force(Vec3::new(2.0, 4.0, 0.0), &bounds(), 0.3, 10.0);
+        assert_eq!(force, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_force_pushes_away_from_wall() {
+        let near_left = boundary_force(Vec3::new(0.4, 4.0, 0.0), &bounds(), 0.3, 10.0);
+        assert!(near_left.x > 0.0 && near_left.y == 0.0);
+
+        let near_top = boundary_force(Vec3::new(2.0, 7.6, 0.0), &bounds(), 0.3, 10.0);
+        assert!(near_top.y < 0.0 && near_top.x == 0.0);
+    }
+
+    #[test]
+    fn test_clamp_stops_at_wall() {
+        let mut position = Vec3::new(-1.0, 9.0, 0.0);
+        let mut velocity = Vec3::new(-2.0, 3.0, 1.0);
+
+        clamp_to_bounds(&mut position, &mut velocity, &bounds(), 0.3);
+
+        assert_eq!(position, Vec3::new(0.3, 7.7, 0.0));
+        assert_eq!(velocity, Vec3::new(0.0, 0.0, 1.0));
+    }
+}
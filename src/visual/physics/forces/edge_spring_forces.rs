@@ -1,8 +1,9 @@
 use bevy::prelude::*;
+use std::collections::HashMap;
 
 use crate::{
     game::session::PuzzleSession,
-    graph::NodeId,
+    graph::{Edge, NodeId},
     visual::{
         nodes::GraphNode,
         physics::{NodePhysics, PHYSICS},
@@ -10,35 +11,77 @@ use crate::{
     },
 };
 
+/// Relative stretch at which an edge reads as fully "white-hot"
+const MAX_VISUAL_STRETCH: f32 = 0.5;
+
+/// Resource holding how stretched each drawn edge is (for tension coloring)
+#[derive(Resource, Default)]
+pub struct EdgeTension {
+    /// Normalized tension per edge: 0.0 = at rest length (or compressed), 1.0 = max stretch
+    pub(crate) tension: HashMap<Edge, f32>,
+}
+
+impl EdgeTension {
+    /// Tension for an edge, 0.0 if it has no spring yet
+    pub fn get(&self, edge: &Edge) -> f32 {
+        self.tension.get(edge).copied().unwrap_or(0.0)
+    }
+}
+
 /// Spring forces between connected nodes (rubber band effect)
 pub fn apply_edge_spring_forces(
     scene_metrics: Res<SceneMetrics>,
     session: Res<PuzzleSession>,
+    mut edge_tension: ResMut<EdgeTension>,
     mut nodes: Query<(&GraphNode, &mut NodePhysics)>,
 ) {
     // 🎯 SCALE FORCES BY SCENE METRICS
     // Edge spring forces scale with grid spacing for consistency
     let scale = scene_metrics.spacing;
-    let edges = session.edges();
 
     // Collect all node data first to avoid borrow conflicts
-    let node_data: Vec<_> = nodes
+    let node_data: HashMap<NodeId, (Vec3, Vec3)> = nodes
         .iter()
-        .map(|(node, physics)| (node.node_id, physics.position, physics.rest_position))
+        .map(|(node, physics)| (node.node_id, (physics.position, physics.rest_position)))
         .collect();
 
-    // Calculate forces for each edge
-    let mut forces: Vec<(NodeId, Vec3)> = Vec::new();
+    edge_tension.tension.clear();
+    let forces = accumulate_spring_forces(
+        session.edges().edges_in_order(),
+        &node_data,
+        PHYSICS.edge_spring * scale,
+        scale,
+        &mut edge_tension.tension,
+    );
 
-    for edge in edges.edges_in_order() {
-        // Find the two nodes
-        let node_a_data = node_data.iter().find(|(id, _, _)| *id == edge.from);
-        let node_b_data = node_data.iter().find(|(id, _, _)| *id == edge.to);
+    // Apply the summed force to each node in a single pass
+    for (graph_node, mut physics) in &mut nodes {
+        if let Some(&force) = forces.get(&graph_node.node_id) {
+            physics.apply_force(force);
+        }
+    }
+}
 
-        let Some(&(_, pos_a, rest_a)) = node_a_data else {
+/// Sum the spring force on every node touched by an edge, recording per-edge tension
+///
+/// `node_data` maps each node to its (position, rest position); `stiffness` is the
+/// already-scaled spring constant. Public so the benches can drive it on grids larger
+/// than the 3×3 board.
+pub fn accumulate_spring_forces<'a>(
+    edges: impl IntoIterator<Item = &'a Edge>,
+    node_data: &HashMap<NodeId, (Vec3, Vec3)>,
+    stiffness: f32,
+    scale: f32,
+    tension: &mut HashMap<Edge, f32>,
+) -> HashMap<NodeId, Vec3> {
+    let mut forces: HashMap<NodeId, Vec3> = HashMap::new();
+
+    for edge in edges {
+        // Find the two nodes
+        let Some(&(pos_a, rest_a)) = node_data.get(&edge.from) else {
             continue;
         };
-        let Some(&(_, pos_b, rest_b)) = node_b_data else {
+        let Some(&(pos_b, rest_b)) = node_data.get(&edge.to) else {
             continue;
         };
 
@@ -51,25 +94,67 @@ pub fn apply_edge_spring_forces(
         }
 
         // Spring force: F = k * (current_length - rest_length)
-        // Scale spring constant so forces are consistent across resolutions
         let direction = (pos_b - pos_a) / current_length;
         let extension = current_length - rest_length;
-        let force_magnitude = PHYSICS.edge_spring * scale * extension;
+        let force = direction * stiffness * extension;
 
-        let force = direction * force_magnitude;
+        // Record relative stretch for the shader (compression doesn't glow)
+        if rest_length > scale * 0.001 {
+            let stretch = (extension / rest_length).max(0.0);
+            tension.insert(*edge, (stretch / MAX_VISUAL_STRETCH).min(1.0));
+        }
 
-        // Store forces to apply
-        forces.push((edge.from, force));
-        forces.push((edge.to, -force));
+        *forces.entry(edge.from).or_default() += force;
+        *forces.entry(edge.to).or_default() -= force;
     }
 
-    // Now apply all forces
-    for (node_id, force) in forces {
-        for (graph_node, mut physics) in &mut nodes {
-            if graph_node.node_id == node_id {
-                physics.apply_force(force);
-                break;
+    forces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `size`×`size` grid of nodes, each stretched 10% from rest, chained row by row
+    fn stretched_grid(size: usize) -> (HashMap<NodeId, (Vec3, Vec3)>, Vec<Edge>) {
+        let mut nodes = HashMap::new();
+        for row in 0..size {
+            for col in 0..size {
+                let rest = Vec3::new(col as f32, row as f32, 0.0);
+                nodes.insert(NodeId(row * size + col), (rest * 1.1, rest));
             }
         }
+
+        let edges = (0..size * size - 1)
+            .map(|i| Edge::new(NodeId(i), NodeId(i + 1)))
+            .collect();
+
+        (nodes, edges)
+    }
+
+    #[test]
+    fn test_forces_are_equal_and_opposite() {
+        let (nodes, edges) = stretched_grid(3);
+        let mut tension = HashMap::new();
+
+        let forces = accumulate_spring_forces(&edges[..1], &nodes, 2.0, 1.0, &mut tension);
+
+        let a = forces[&NodeId(0)];
+        let b = forces[&NodeId(1)];
+        assert!((a + b).length() < 1e-6);
+        assert!(a.x > 0.0, "Stretched spring pulls node 0 toward node 1");
+        assert!(tension[&edges[0]] > 0.0);
+    }
+
+    #[test]
+    fn test_shared_node_sums_forces() {
+        let (nodes, edges) = stretched_grid(3);
+        let mut tension = HashMap::new();
+
+        // Node 1 sits between two stretched springs pulling in opposite directions
+        let forces = accumulate_spring_forces(&edges[..2], &nodes, 2.0, 1.0, &mut tension);
+
+        assert_eq!(forces.len(), 3);
+        assert!(forces[&NodeId(1)].length() < 1e-5);
     }
 }
@@ -1,6 +1,5 @@
 pub mod edge_spring_forces;
 pub mod repulsion;
 
-pub use edge_spring_forces::apply_edge_spring_forces;
+pub use edge_spring_forces::{EdgeTension, apply_edge_spring_forces};
 pub use repulsion::apply_node_repulsion;
-
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::session::PuzzleSession, settings::Settings, visual::nodes::GraphNode,
+    visual::physics::NodePhysics,
+};
+
+/// System: Weigh nodes by their remaining valence (heavy when fresh, bouncy when nearly done)
+pub fn update_node_mass(
+    session: Res<PuzzleSession>,
+    settings: Res<Settings>,
+    mut nodes: Query<(&GraphNode, &mut NodePhysics)>,
+) {
+    if !session.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    let valences = session.current_valences();
+
+    for (graph_node, mut physics) in &mut nodes {
+        let mass = settings.mass_curve.mass(valences.get(graph_node.node_id));
+        if physics.mass != mass {
+            physics.mass = mass;
+        }
+    }
+}
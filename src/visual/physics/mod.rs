@@ -1,10 +1,15 @@
 // pub mod debug;  // Commented out - requires bevy_gizmos feature
+pub mod boundary;
 pub mod forces;
+pub mod mass;
 
 use bevy::prelude::*;
 
+use crate::{camera::GameCamera, visual::setup::SceneMetrics};
+
 // Re-export force systems for easy access
-pub use forces::{apply_edge_spring_forces, apply_node_repulsion};
+pub use forces::{EdgeTension, apply_edge_spring_forces, apply_node_repulsion};
+pub use mass::update_node_mass;
 
 pub mod presets {
     /// Gentle wobbly blobs
@@ -18,6 +23,16 @@ pub mod presets {
         repulsion_range: 2.0,     // Farther reach (unchanged)
     };
 
+    /// Stiff, barely damped springs - stress test for integrator stability
+    pub const BOUNCY: PhysicsPreset = PhysicsPreset {
+        damping: 0.98,
+        spring_stiffness: 40.0,
+        push_strength: 0.3,
+        edge_spring: 8.0,
+        repulsion_strength: 0.1,
+        repulsion_range: 2.0,
+    };
+
     #[derive(Debug, Clone, Copy)]
     pub struct PhysicsPreset {
         pub damping: f32,
@@ -32,6 +47,45 @@ pub mod presets {
 // Current active preset
 const PHYSICS: presets::PhysicsPreset = presets::GENTLE;
 
+/// Wall margin as a fraction of grid spacing (about one node radius)
+const BOUNDARY_MARGIN_FRACTION: f32 = 0.3;
+
+/// Soft-wall push at the wall itself, as a multiple of grid spacing
+const BOUNDARY_STRENGTH: f32 = 12.0;
+
+/// Rate of the fixed physics tick (the preset damping values are tuned per tick at this rate)
+pub const PHYSICS_TICK_HZ: f64 = 60.0;
+
+/// Numerical integration scheme for node motion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Integrator {
+    /// Explicit Euler: position uses the old velocity (gains energy with stiff springs)
+    Euler,
+    /// Semi-implicit (symplectic) Euler: position uses the updated velocity
+    #[default]
+    SemiImplicit,
+    /// Position Verlet, with the previous position implied by the current velocity
+    Verlet,
+}
+
+/// Fixed-timestep settings for the node simulation
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct PhysicsConfig {
+    /// Integration substeps per fixed tick (more = stiffer springs stay stable)
+    pub substeps: u32,
+    /// How velocity and position are advanced each substep
+    pub integrator: Integrator,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            substeps: 4,
+            integrator: Integrator::default(),
+        }
+    }
+}
+
 /// Physics state for a node
 #[derive(Component, Debug)]
 pub struct NodePhysics {
@@ -41,7 +95,7 @@ pub struct NodePhysics {
     pub velocity: Vec3,
     /// Accumulated forces this frame
     pub forces: Vec3,
-    /// Mass (affects acceleration)
+    /// Mass (affects acceleration) - follows remaining valence, see `update_node_mass`
     pub mass: f32,
     /// Damping factor (0.0 = full damping, 1.0 = no damping)
     pub damping: f32,
@@ -49,6 +103,10 @@ pub struct NodePhysics {
     pub rest_position: Vec3,
     /// Spring stiffness back to rest position
     pub spring_stiffness: f32,
+    /// Position at the start of the last physics tick (for render interpolation)
+    pub previous_position: Vec3,
+    /// Position to draw this frame, interpolated between physics ticks
+    pub render_position: Vec3,
 }
 
 impl Default for NodePhysics {
@@ -61,11 +119,31 @@ impl Default for NodePhysics {
             damping: PHYSICS.damping,
             rest_position: Vec3::ZERO,
             spring_stiffness: PHYSICS.spring_stiffness,
+            previous_position: Vec3::ZERO,
+            render_position: Vec3::ZERO,
         }
     }
 }
 
 impl NodePhysics {
+    /// A node resting at `position`
+    pub fn at_rest(position: Vec3) -> Self {
+        NodePhysics {
+            position,
+            rest_position: position,
+            previous_position: position,
+            render_position: position,
+            ..default()
+        }
+    }
+
+    /// Move instantly, without interpolating from the old position
+    pub fn teleport(&mut self, position: Vec3) {
+        self.position = position;
+        self.previous_position = position;
+        self.render_position = position;
+    }
+
     /// Apply a force to this node
     pub fn apply_force(&mut self, force: Vec3) {
         self.forces += force;
@@ -75,33 +153,188 @@ impl NodePhysics {
     pub fn apply_impulse(&mut self, impulse: Vec3) {
         self.velocity += impulse / self.mass;
     }
+
+    /// Advance one substep: spring back to rest plus constant external forces
+    pub fn integrate(
+        &mut self,
+        external_forces: Vec3,
+        dt: f32,
+        damping: f32,
+        integrator: Integrator,
+    ) {
+        // Spring force back to rest position (Hooke's law: F = -kx)
+        let displacement = self.position - self.rest_position;
+        let spring_force = -displacement * self.spring_stiffness;
+
+        // Calculate acceleration: F = ma → a = F/m
+        let acceleration = (external_forces + spring_force) / self.mass;
+
+        match integrator {
+            Integrator::Euler => {
+                self.position += self.velocity * dt;
+                self.velocity = (self.velocity + acceleration * dt) * damping;
+            }
+            Integrator::SemiImplicit => {
+                self.velocity = (self.velocity + acceleration * dt) * damping;
+                self.position += self.velocity * dt;
+            }
+            Integrator::Verlet => {
+                // x' = x + (x - x_prev) * damping + a * dt², with x - x_prev = v * dt
+                let step = self.velocity * dt * damping + acceleration * dt * dt;
+                self.position += step;
+                self.velocity = step / dt;
+            }
+        }
+    }
 }
 
-/// Core physics simulation system (integration loop)
-pub fn simulate_node_physics(time: Res<Time>, mut nodes: Query<&mut NodePhysics>) {
-    let dt = time.delta_secs();
+/// Core physics simulation system (integration loop, runs in `FixedUpdate`)
+pub fn simulate_node_physics(
+    time: Res<Time>,
+    config: Res<PhysicsConfig>,
+    game_camera: Res<GameCamera>,
+    scene_metrics: Res<SceneMetrics>,
+    mut nodes: Query<&mut NodePhysics>,
+) {
+    let substeps = config.substeps.max(1);
+    let dt = time.delta_secs() / substeps as f32;
+
+    // Invisible walls just inside the camera so fleeing nodes never leave the screen
+    let bounds = game_camera.bounds;
+    let margin = scene_metrics.spacing * BOUNDARY_MARGIN_FRACTION;
+    let wall_strength = scene_metrics.spacing * BOUNDARY_STRENGTH;
 
     for mut physics in &mut nodes {
-        // Spring force back to rest position (Hooke's law: F = -kx)
-        let displacement = physics.position - physics.rest_position;
-        let spring_force = -displacement * physics.spring_stiffness;
-        physics.apply_force(spring_force);
+        physics.previous_position = physics.position;
 
-        // Calculate acceleration: F = ma → a = F/m
-        let acceleration = physics.forces / physics.mass;
+        // Forces from other systems stay constant across the substeps of one tick
+        let external_forces = physics.forces
+            + boundary::boundary_force(physics.position, &bounds, margin, wall_strength);
 
-        // Update velocity (Euler integration)
-        physics.velocity += acceleration * dt;
+        // Preset damping is per tick: spread it evenly over the substeps
+        let damping = physics.damping.powf(1.0 / substeps as f32);
 
-        // Apply damping (exponential decay)
-        let damping = physics.damping;
-        physics.velocity *= damping;
+        for _ in 0..substeps {
+            physics.integrate(external_forces, dt, damping, config.integrator);
+        }
 
-        // Update position
-        let velocity = physics.velocity;
-        physics.position += velocity * dt;
+        // Hard wall in case the soft push wasn't enough (squash, don't escape)
+        let physics = &mut *physics;
+        boundary::clamp_to_bounds(
+            &mut physics.position,
+            &mut physics.velocity,
+            &bounds,
+            margin,
+        );
 
-        // Clear forces for next frame
+        // Clear forces for next tick
         physics.forces = Vec3::ZERO;
     }
 }
+
+/// System: Blend render positions between the last two physics ticks
+pub fn interpolate_node_positions(
+    fixed_time: Res<Time<Fixed>>,
+    mut nodes: Query<&mut NodePhysics>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for mut physics in &mut nodes {
+        let render_position = physics.previous_position.lerp(physics.position, alpha);
+        // Only write on change so resting nodes don't trip change detection
+        if physics.render_position != render_position {
+            physics.render_position = render_position;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Spring + kinetic energy of a node relative to its rest position
+    fn energy(physics: &NodePhysics) -> f32 {
+        let displacement = physics.position - physics.rest_position;
+        0.5 * physics.mass * physics.velocity.length_squared()
+            + 0.5 * physics.spring_stiffness * displacement.length_squared()
+    }
+
+    /// Pluck a BOUNCY node and let it ring for ten seconds at the fixed tick rate
+    fn ring_bouncy(integrator: Integrator) -> (f32, f32) {
+        let preset = presets::BOUNCY;
+        let mut physics = NodePhysics {
+            damping: preset.damping,
+            spring_stiffness: preset.spring_stiffness,
+            ..NodePhysics::at_rest(Vec3::ZERO)
+        };
+        physics.position = Vec3::new(0.5, 0.0, 0.0);
+        let initial = energy(&physics);
+
+        let dt = 1.0 / PHYSICS_TICK_HZ as f32;
+        let mut peak = initial;
+        for _ in 0..600 {
+            physics.integrate(Vec3::ZERO, dt, preset.damping, integrator);
+            peak = peak.max(energy(&physics));
+        }
+
+        (initial, peak.max(energy(&physics)))
+    }
+
+    #[test]
+    fn test_semi_implicit_stays_bounded_under_bouncy() {
+        let (initial, peak) = ring_bouncy(Integrator::SemiImplicit);
+        assert!(peak.is_finite());
+        // Symplectic Euler oscillates around the true energy but never runs away
+        assert!(peak < initial * 1.5, "energy grew from {initial} to {peak}");
+    }
+
+    #[test]
+    fn test_verlet_stays_bounded_under_bouncy() {
+        let (initial, peak) = ring_bouncy(Integrator::Verlet);
+        assert!(peak.is_finite());
+        assert!(peak < initial * 1.5, "energy grew from {initial} to {peak}");
+    }
+
+    #[test]
+    fn test_default_integrator_is_semi_implicit() {
+        assert_eq!(
+            PhysicsConfig::default().integrator,
+            Integrator::SemiImplicit
+        );
+    }
+
+    #[test]
+    fn test_teleport_skips_interpolation() {
+        let mut world = World::new();
+        let tick = Time::<Fixed>::from_hz(PHYSICS_TICK_HZ);
+        let mut frame = Time::<Virtual>::default();
+        frame.advance_by(tick.timestep().mul_f32(1.5));
+        world.insert_resource(tick);
+        world.insert_resource(frame);
+        world.init_resource::<Time>();
+        world.add_schedule(Schedule::new(bevy::app::FixedMain));
+        // One tick runs and half of the next is left over
+        bevy::time::run_fixed_main_schedule(&mut world);
+
+        let moving = world
+            .spawn(NodePhysics {
+                position: Vec3::X,
+                ..NodePhysics::at_rest(Vec3::ZERO)
+            })
+            .id();
+        let mut physics = NodePhysics {
+            position: Vec3::X,
+            ..NodePhysics::at_rest(Vec3::ZERO)
+        };
+        physics.teleport(Vec3::Y);
+        let teleported = world.spawn(physics).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(interpolate_node_positions);
+        schedule.run(&mut world);
+
+        let render = |entity| world.get::<NodePhysics>(entity).unwrap().render_position;
+        assert!(render(moving).abs_diff_eq(Vec3::X * 0.5, 1e-5));
+        assert_eq!(render(teleported), Vec3::Y);
+    }
+}
@@ -1,63 +1,382 @@
-use crate::game::{puzzle::setup_puzzle_library, session::PuzzleSession};
-use crate::visual::nodes::{GraphNode, NodeVisual, valence_to_color, update_node_visuals};
-use crate::visual::physics::{NodePhysics, simulate_node_physics, apply_edge_spring_forces, apply_node_repulsion};
+use crate::autosave::autosaving;
+use crate::camera::ease_camera_frame;
+use crate::editor::{not_editing, outside_editor};
+use crate::game::{
+    clock::not_paused,
+    director::{Director, adaptive_keys},
+    error::{AppError, no_app_error},
+    hints::{HintEngine, HintWallet, spend_hint_token},
+    progression::{CompletionPolicy, cycle_completion_policy},
+    puzzle::{NextCustomPuzzle, custom_keys, play_custom_puzzle, setup_puzzle_library},
+    rng::GameRng,
+    session::{NewPuzzle, PuzzleSession, announce_new_puzzle},
+    skip::{LevelPuzzles, PuzzleChange, change_puzzle, skip_keys},
+    tour::{LevelTour, track_served_puzzles},
+};
+use crate::net::not_spectating;
+use crate::visual::edges::{
+    DyingEdges, EdgeIntro, EdgeWaves, animate_edge_intro, spawn_edge_waves, track_removed_edges,
+    update_dying_edges, update_edge_waves,
+};
+use crate::visual::effects::{
+    Shockwave, last_edge_slow_mo, punch_camera, trigger_shockwave, update_shockwave,
+};
 use crate::visual::interactions::{
-    FleeMode, node_hover_flee, snap_back_from_flee, update_flee_target,
-    DragState, HoverState, handle_pointer_input,
-    trigger_trail_effects,
+    Combo, ComboEnded, DragState, EdgeDrawn, FleeMode, GrabState, HistoryStep, HoverState,
+    InvalidMove, PreviewEndpoint, SolutionFound, apply_history_steps, handle_node_grab,
+    handle_pointer_input, history_keys, hold_grabbed_node, node_hover_attract, node_hover_flee,
+    snap_back_from_flee, tick_flee_mode, track_combo, trigger_trail_effects, update_flee_target,
+    update_hover_state, update_preview_endpoint,
+};
+use crate::visual::nodes::{
+    GraphNode, HintHighlight, NodeVisual, combine_node_glow, glow_combo_trail, glow_finale_nodes,
+    glow_hint_node, glow_hovered_node, glow_last_added, glow_on_solution, show_hints,
+    update_node_spikes, update_node_visuals,
+};
+use crate::visual::particles::{
+    ParticleSystem, spawn_solution_burst, sync_particles, update_particles,
+};
+use crate::visual::physics::{
+    EdgeTension, NodePhysics, PHYSICS_TICK_HZ, PhysicsConfig, apply_edge_spring_forces,
+    apply_node_repulsion, interpolate_node_positions, simulate_node_physics, update_node_mass,
+};
+use crate::visual::sdf::background::update_background;
+use crate::visual::sdf::presentation::{toggle_presentation, update_presentation};
+use crate::visual::sdf::quality::{
+    FrameTimeMonitor, apply_quality, cycle_quality, monitor_frame_time,
+};
+use crate::visual::sdf::sync::{CylinderSlots, SdfSync, sdf_sync_systems};
+use crate::visual::setup::{
+    check_level_progression, frame_board, rebuild_board, retry_after_error, setup_puzzle,
+    setup_scene,
+};
+use crate::visual::theme::{Theme, apply_theme, cycle_theme};
+use crate::visual::ui::{
+    HudTransitionState, LevelSummary, LevelTally, Locale, ToastQueue, advance_toasts,
+    board_uncovered, cycle_transition_style, dismiss_level_summary, earn_hint_tokens,
+    fit_hud_to_view, spawn_hud, tally_level, toast_new_solutions, update_hud, watch_for_struggle,
 };
-use crate::visual::edges::waves::{EdgeWaves, spawn_edge_waves, update_edge_waves};
-use crate::visual::setup::{check_level_progression, setup_puzzle, setup_scene};
-use crate::visual::sdf::sync::update_sdf_scene;
-use crate::visual::ui::{spawn_hud, update_hud, HudTransitionState};
 use bevy::prelude::*;
 
 pub struct GraphPlugin;
 
+/// Pointer hover, grabs and trail edits
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputSet;
+
+/// Reactions to the frame's input and the per-frame side of node physics
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SimulationSet;
+
+/// Node and edge visuals, synced into the SDF scene material
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VisualSyncSet;
+
+/// The seven-segment HUD, laid over the current view
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HudSet;
+
 impl Plugin for GraphPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DragState>()
+        app.add_message::<SolutionFound>()
+            .add_message::<InvalidMove>()
+            .add_message::<EdgeDrawn>()
+            .add_message::<HistoryStep>()
+            .add_message::<PuzzleChange>()
+            .add_message::<ComboEnded>()
+            .add_message::<NewPuzzle>()
+            .add_message::<NextCustomPuzzle>()
+            .init_resource::<GameRng>()
+            .init_resource::<DragState>()
             .init_resource::<HoverState>()
+            .init_resource::<GrabState>()
+            .init_resource::<PreviewEndpoint>()
             .init_resource::<EdgeWaves>()
+            .init_resource::<EdgeIntro>()
+            .init_resource::<DyingEdges>()
+            .init_resource::<EdgeTension>()
+            .init_resource::<PhysicsConfig>()
+            .insert_resource(Time::<Fixed>::from_hz(PHYSICS_TICK_HZ))
             .init_resource::<FleeMode>()
+            .init_resource::<Combo>()
+            .init_resource::<Shockwave>()
+            .init_resource::<ParticleSystem>()
+            .init_resource::<Theme>()
+            .insert_resource(ClearColor(Theme::default().background))
+            .init_resource::<HintHighlight>()
             .init_resource::<HudTransitionState>()
+            .init_resource::<ToastQueue>()
+            .init_resource::<Locale>()
+            .init_resource::<LevelTally>()
+            .init_resource::<CompletionPolicy>()
+            .init_resource::<LevelPuzzles>()
+            .init_resource::<LevelTour>()
+            .init_resource::<Director>()
+            .init_resource::<HintWallet>()
+            .init_resource::<HintEngine>()
+            .init_resource::<FrameTimeMonitor>()
+            .init_resource::<CylinderSlots>()
             // Load puzzle library first, then set up initial puzzle and scene
             .add_systems(
                 Startup,
                 (setup_puzzle_library, setup_puzzle, setup_scene, spawn_hud).chain(),
             )
+            // Forces and integration run on a fixed tick so feel doesn't depend on framerate
             .add_systems(
-                Update,
+                FixedUpdate,
                 (
-                    handle_pointer_input,
-                    // Interaction effects
-                    trigger_trail_effects,
-                    spawn_edge_waves,
-                    // Physics forces
+                    update_node_mass,
                     apply_node_repulsion,
                     apply_edge_spring_forces,
-                    simulate_node_physics,
-                    update_flee_target, 
                     node_hover_flee,
-                    snap_back_from_flee,
-                    // Visual updates
-                    update_node_visuals,
-                    update_edge_waves,
-                    update_sdf_scene,
-                    snap_on_reset,
-                    // HUD updates (unified seven-segment display)
-                    update_hud,
-                    // Level progression (check for completion and advance)
-                    check_level_progression,
+                    node_hover_attract,
+                    simulate_node_physics,
+                    hold_grabbed_node,
                 )
                     .chain(),
+            )
+            // Each frame: read input, step the simulation, sync visuals, then lay the HUD over them
+            .configure_sets(
+                Update,
+                (InputSet, SimulationSet, VisualSyncSet, HudSet).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    update_hover_state.run_if(not_spectating),
+                    handle_node_grab
+                        .run_if(not_paused)
+                        .run_if(not_spectating)
+                        .run_if(not_editing)
+                        .run_if(no_app_error)
+                        .run_if(board_uncovered),
+                    handle_pointer_input
+                        .run_if(not_paused)
+                        .run_if(not_spectating)
+                        .run_if(not_editing)
+                        .run_if(no_app_error)
+                        .run_if(board_uncovered),
+                )
+                    .chain()
+                    .in_set(InputSet),
+            )
+            .add_systems(
+                Update,
+                (
+                    // Interaction effects, each reacting to this frame's input on its own
+                    (
+                        trigger_trail_effects,
+                        track_combo,
+                        spawn_edge_waves,
+                        trigger_shockwave,
+                        spawn_solution_burst,
+                        punch_camera,
+                        last_edge_slow_mo,
+                    ),
+                    // Physics (forces and integration live in FixedUpdate)
+                    (
+                        tick_flee_mode,
+                        update_flee_target,
+                        snap_back_from_flee,
+                        interpolate_node_positions,
+                    )
+                        .chain(),
+                )
+                    .chain()
+                    .in_set(SimulationSet),
+            )
+            .add_systems(
+                Update,
+                (
+                    // Everything the SDF uniform is built from
+                    (
+                        (
+                            (cycle_theme, apply_theme).chain(),
+                            update_node_visuals,
+                            update_node_spikes,
+                            (
+                                glow_hovered_node,
+                                glow_last_added,
+                                glow_hint_node,
+                                glow_on_solution,
+                                glow_finale_nodes,
+                                glow_combo_trail,
+                                combine_node_glow,
+                            )
+                                .chain(),
+                        )
+                            .chain(),
+                        update_preview_endpoint,
+                        update_edge_waves,
+                        animate_edge_intro,
+                        (track_removed_edges, update_dying_edges).chain(),
+                    )
+                        .before(SdfSync::Nodes),
+                    sdf_sync_systems(),
+                    // Other material inputs and effects, on top of this frame's sync
+                    (
+                        update_background,
+                        (toggle_presentation, update_presentation).chain(),
+                        (cycle_quality, monitor_frame_time, apply_quality).chain(),
+                        update_shockwave,
+                        (update_particles, sync_particles).chain(),
+                        snap_on_reset,
+                    )
+                        .after(SdfSync::Preview),
+                )
+                    .in_set(VisualSyncSet),
+            )
+            // HUD updates (unified seven-segment display, kept over the view)
+            .add_systems(
+                Update,
+                (
+                    cycle_transition_style.before(update_hud),
+                    (toast_new_solutions, advance_toasts)
+                        .chain()
+                        .before(update_hud),
+                    fit_hud_to_view,
+                    update_hud,
+                )
+                    .in_set(HudSet),
+            )
+            // Level progression (check for completion and advance; spectators
+            // follow the feed's level instead, and the editor holds it)
+            .add_systems(
+                Update,
+                check_level_progression
+                    .run_if(not_spectating)
+                    .run_if(outside_editor)
+                    .run_if(no_app_error)
+                    .after(HudSet),
+            )
+            // A new puzzle gets a fresh board before anything reads the nodes this frame
+            .add_systems(
+                Update,
+                (
+                    announce_new_puzzle.run_if(resource_exists_and_changed::<PuzzleSession>),
+                    rebuild_board,
+                )
+                    .chain()
+                    .before(InputSet),
+            )
+            // The camera frames wherever the nodes rest
+            .add_systems(Update, frame_board.before(ease_camera_frame))
+            // The Custom tab: C steps through player-made puzzles, on a board of our
+            // own (not in rooms, spectating, the daily puzzle or the editor)
+            .add_systems(
+                Update,
+                (custom_keys, play_custom_puzzle)
+                    .chain()
+                    .run_if(not_paused)
+                    .run_if(autosaving)
+                    .run_if(board_uncovered)
+                    .before(handle_pointer_input),
+            )
+            // N skips the level's puzzle for later; Shift+N shuffles in another
+            .add_systems(
+                Update,
+                (skip_keys, change_puzzle)
+                    .chain()
+                    .run_if(not_paused)
+                    .run_if(autosaving)
+                    .run_if(board_uncovered)
+                    .before(handle_pointer_input),
+            )
+            // Note each puzzle put up, so the level's others come before a repeat
+            .add_systems(
+                Update,
+                track_served_puzzles
+                    .run_if(autosaving)
+                    .after(check_level_progression),
+            )
+            // G switches how much of a level must be solved to move on
+            .add_systems(
+                Update,
+                cycle_completion_policy.before(check_level_progression),
+            )
+            // Adaptive difficulty (A toggles it): the director watches solo play for a
+            // struggle, then hints and eases off
+            .add_systems(
+                Update,
+                (
+                    adaptive_keys,
+                    watch_for_struggle
+                        .run_if(autosaving)
+                        .run_if(board_uncovered)
+                        .after(tally_level),
+                )
+                    .chain()
+                    .before(check_level_progression),
+            )
+            // Hints: H spends a token on one; finishing a level earns them
+            .add_systems(
+                Update,
+                (
+                    spend_hint_token
+                        .run_if(not_paused)
+                        .run_if(autosaving)
+                        .run_if(board_uncovered)
+                        .after(handle_pointer_input),
+                    show_hints,
+                )
+                    .chain()
+                    .after(watch_for_struggle)
+                    .before(glow_hint_node),
+            )
+            .add_systems(
+                Update,
+                earn_hint_tokens
+                    .run_if(resource_exists::<LevelSummary>)
+                    .after(check_level_progression),
+            )
+            // Undo/redo of the trail being drawn
+            .add_systems(
+                Update,
+                (history_keys, apply_history_steps)
+                    .chain()
+                    .run_if(not_paused)
+                    .run_if(not_spectating)
+                    .run_if(not_editing)
+                    .run_if(no_app_error)
+                    .run_if(board_uncovered)
+                    .before(handle_pointer_input),
+            )
+            // The level summary: tallies while playing, then the summary between levels
+            .add_systems(
+                Update,
+                (
+                    tally_level
+                        .run_if(board_uncovered)
+                        .after(handle_pointer_input),
+                    dismiss_level_summary.run_if(resource_exists::<LevelSummary>),
+                )
+                    .before(check_level_progression),
+            )
+            // The error screen: R reloads the library and restarts the level
+            .add_systems(
+                Update,
+                retry_after_error
+                    .run_if(resource_exists::<AppError>)
+                    .before(handle_pointer_input),
             );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(
+            Startup,
+            (
+                crate::game::puzzle::load_custom_puzzles
+                    .after(setup_puzzle_library)
+                    .before(setup_puzzle),
+                crate::visual::ui::locale::detect_locale,
+            ),
+        );
     }
 }
 
 /// Snap physics and colors back instantly when the board resets
 fn snap_on_reset(
     session: Res<PuzzleSession>,
+    theme: Res<Theme>,
     mut nodes: Query<(&GraphNode, &mut NodePhysics, &mut NodeVisual)>,
 ) {
     // Only trigger when session has changed (reset happened)
@@ -69,15 +388,15 @@ fn snap_on_reset(
     if session.current_trail().is_empty() {
         for (graph_node, mut physics, mut visual) in &mut nodes {
             // Snap position back to rest instantly
-            physics.position = physics.rest_position;
+            let rest_position = physics.rest_position;
+            physics.teleport(rest_position);
             physics.velocity = Vec3::ZERO;
             physics.forces = Vec3::ZERO;
 
             // Snap color back instantly
             let valence = session.current_valences().get(graph_node.node_id);
-            visual.current_color = valence_to_color(valence);
+            visual.current_color = theme.node_color(valence);
         }
         info!("Snapped all nodes back to rest!");
     }
 }
-
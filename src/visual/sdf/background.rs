@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::ShaderType;
+
+use crate::{
+    game::{error::AppError, progression::ProgressionTracker, puzzle::PuzzleLibrary},
+    visual::sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
+};
+
+/// How quickly the background palette fades to a new level's colors
+const PALETTE_FADE_SPEED: f32 = 1.5;
+
+/// Palette distance below which the fade counts as finished
+const PALETTE_EPSILON: f32 = 1e-4;
+
+/// Animated environment field rendered behind the board
+///
+/// The drift itself is driven by the view's `globals.time` in the shader, so this only
+/// needs rewriting while the palette is fading.
+#[derive(ShaderType, Debug, Clone)]
+pub struct SdfBackgroundUniform {
+    /// Overall strength of the environment layer (0 = flat base color)
+    pub intensity: f32,
+    /// Deep color at the bottom of the gradient
+    pub color_a: Vec4,
+    /// Lighter color at the top of the gradient
+    pub color_b: Vec4,
+}
+
+impl Default for SdfBackgroundUniform {
+    fn default() -> Self {
+        let (color_a, color_b) = background_palette(1, 1);
+        Self {
+            intensity: 1.0,
+            color_a,
+            color_b,
+        }
+    }
+}
+
+/// Pick a dark two-tone palette for a complexity level, out of the puzzle set's highest
+///
+/// Hue sweeps around the color wheel on a log scale so early levels (which change
+/// complexity quickly) still feel distinct from each other.
+pub fn background_palette(complexity: usize, max_complexity: usize) -> (Vec4, Vec4) {
+    let max_complexity = max_complexity.max(2) as f32;
+    let t = ((complexity.max(1) as f32).ln() / max_complexity.ln()).min(1.0);
+    let hue = (210.0 + t * 300.0) % 360.0;
+
+    let deep = Color::hsl(hue, 0.45, 0.06).to_srgba();
+    let light = Color::hsl((hue + 40.0) % 360.0, 0.55, 0.16).to_srgba();
+
+    (
+        Vec4::new(deep.red, deep.green, deep.blue, 1.0),
+        Vec4::new(light.red, light.green, light.blue, 1.0),
+    )
+}
+
+/// Dark red palette shown behind the board while an `AppError` is up
+pub fn error_palette() -> (Vec4, Vec4) {
+    let deep = Color::hsl(355.0, 0.6, 0.07).to_srgba();
+    let light = Color::hsl(15.0, 0.7, 0.2).to_srgba();
+
+    (
+        Vec4::new(deep.red, deep.green, deep.blue, 1.0),
+        Vec4::new(light.red, light.green, light.blue, 1.0),
+    )
+}
+
+/// System: Fade the background palette toward the current level's colors (or the error
+/// palette while something has failed)
+pub fn update_background(
+    time: Res<Time>,
+    tracker: Res<ProgressionTracker>,
+    library: Res<PuzzleLibrary>,
+    error: Option<Res<AppError>>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+) {
+    let (target_a, target_b) = match error {
+        Some(_) => error_palette(),
+        None => {
+            let complexity = tracker.current_complexity();
+            background_palette(complexity, library.max_complexity().unwrap_or(complexity))
+        }
+    };
+
+    // Leave the material untouched once the fade has settled (avoids a GPU re-upload)
+    let Some(current) = materials.get(&scene_handle.0).map(|m| &m.background) else {
+        return;
+    };
+    if current.color_a.distance(target_a) < PALETTE_EPSILON
+        && current.color_b.distance(target_b) < PALETTE_EPSILON
+    {
+        return;
+    }
+
+    let Some(material) = materials.get_mut(&scene_handle.0) else {
+        return;
+    };
+
+    let background = &mut material.background;
+    let fade = (time.delta_secs() * PALETTE_FADE_SPEED).min(1.0);
+    background.color_a = background.color_a.lerp(target_a, fade);
+    background.color_b = background.color_b.lerp(target_b, fade);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_is_dark() {
+        for complexity in [1, 20, 100, 500, 960] {
+            let (a, b) = background_palette(complexity, 960);
+            assert!(
+                a.truncate().max_element() < 0.2,
+                "Deep color should stay dark"
+            );
+            assert!(
+                b.truncate().max_element() < 0.4,
+                "Light color should stay subdued"
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_palette_reads_as_red() {
+        let (deep, light) = error_palette();
+        assert!(deep.x > deep.y && deep.x > deep.z);
+        assert!(light.x > light.y && light.x > light.z);
+    }
+
+    #[test]
+    fn test_palette_varies_with_complexity() {
+        let (easy, _) = background_palette(1, 960);
+        let (hard, _) = background_palette(960, 960);
+        assert!(easy.distance(hard) > 0.01);
+    }
+
+    #[test]
+    fn test_palette_sweep_spans_the_puzzle_set() {
+        // The hardest puzzle ends the sweep whatever the set's range
+        assert_eq!(background_palette(120, 120), background_palette(960, 960));
+        assert_ne!(background_palette(120, 120), background_palette(120, 960));
+    }
+}
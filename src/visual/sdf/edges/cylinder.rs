@@ -1,11 +1,17 @@
 use bevy::prelude::*;
-use bevy::render::render_resource::ShaderType;
+use bevy::render::render_resource::{ShaderSize, ShaderType};
+
+/// Maximum number of simultaneous tension waves rendered per edge
+pub const MAX_EDGE_WAVES: usize = 3;
+
+/// Wave phases with every slot inactive
+pub const NO_WAVES: Vec4 = Vec4::splat(-1.0);
 
 /// A cylinder connecting two spheres (edge)
-#[derive(ShaderType, Debug, Clone, Copy)]
+#[derive(ShaderType, Debug, Clone, Copy, PartialEq)]
 pub struct SdfCylinder {
+    // (vec3s align to 16 bytes on their own, in the shader as here)
     pub start: Vec3,
-    pub _padding1: f32,
     pub end: Vec3,
     pub radius: f32,
     pub color: Vec4,
@@ -14,24 +20,59 @@ pub struct SdfCylinder {
     pub node_a_idx: u32,
     pub node_b_idx: u32,
 
-    // Tension wave animation
-    pub wave_phase: f32,     // Where the wave is (0-1), -1 = no wave
-    pub wave_amplitude: f32, // Strength of squeeze
+    // Tension wave animation (up to MAX_EDGE_WAVES at once, xyz used, w unused)
+    pub wave_phases: Vec4,     // Where each wave is (0-1), -1 = no wave
+    pub wave_amplitudes: Vec4, // Strength of each squeeze
+
+    // Melt-in animation: multiplier on the scene blend smoothness (0 = hard seam, 1 = full goo)
+    pub blend: f32,
+
+    // Draw-in animation: fraction of the start→end length that is visible (0-1)
+    pub grow_progress: f32,
+
+    // Spring stretch (0 = relaxed, 1 = white-hot) for tension coloring
+    pub tension: f32,
+
+    // Position in draw order (0 = first edge of the trail, 1 = most recent) for the trail gradient
+    pub order_t: f32,
+
+    // Animated current along the edge: 1 = flows start→end, -1 = end→start, 0 = still
+    pub flow_direction: f32,
+}
+
+// The scene shader's cylinder array strides 128 bytes (checked field by field against
+// `sdf_scene.wgsl` in the material tests)
+const _: () = assert!(SdfCylinder::SHADER_SIZE.get() == 128);
+
+impl SdfCylinder {
+    /// A still, fully grown cylinder from `start` to `end`
+    pub fn new(start: Vec3, end: Vec3, radius: f32, color: Vec4) -> Self {
+        Self {
+            start,
+            end,
+            radius,
+            color,
+            ..default()
+        }
+    }
 }
 
 impl Default for SdfCylinder {
     fn default() -> Self {
         SdfCylinder {
             start: Vec3::ZERO,
-            _padding1: 0.0,
             end: Vec3::ZERO,
             radius: 0.1,
             color: Vec4::new(1.0, 1.0, 1.0, 1.0),
             node_a_idx: 0,
             node_b_idx: 0,
-            wave_phase: -1.0, // No wave by default
-            wave_amplitude: 0.0,
+            wave_phases: NO_WAVES, // No waves by default
+            wave_amplitudes: Vec4::ZERO,
+            blend: 1.0,
+            grow_progress: 1.0,
+            tension: 0.0,
+            order_t: 1.0,
+            flow_direction: 0.0,
         }
     }
 }
-
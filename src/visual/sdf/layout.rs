@@ -0,0 +1,245 @@
+//! WGSL uniform layout worked out from a shader's own struct declarations, so tests can
+//! check that the Rust `ShaderType`s line up with what the shader reads, field by field.
+//!
+//! Covers what the uniforms use: 32-bit scalars, `vecN<f32>`, fixed-size arrays and
+//! nested structs, with the uniform address space's rules on top of the plain WGSL
+//! ones (arrays and nested structs align to 16, array strides must be multiples of 16).
+
+/// Size and alignment of a WGSL type, in bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub size: u64,
+    pub align: u64,
+}
+
+fn round_up(n: u64, align: u64) -> u64 {
+    n.div_ceil(align) * align
+}
+
+/// Layout of `struct name { ... }` as declared in `source`
+pub fn struct_layout(source: &str, name: &str) -> Layout {
+    let fields = field_layouts(source, name);
+    let align = fields
+        .iter()
+        .map(|(_, _, field)| field.align)
+        .max()
+        .unwrap_or(1);
+    let end = fields
+        .last()
+        .map_or(0, |(_, offset, field)| offset + field.size);
+    Layout {
+        size: round_up(end, align),
+        align,
+    }
+}
+
+/// `(name, byte offset)` of each field of `struct name` in `source`, in declaration order
+pub fn field_offsets(source: &str, name: &str) -> Vec<(String, u64)> {
+    field_layouts(source, name)
+        .into_iter()
+        .map(|(name, offset, _)| (name, offset))
+        .collect()
+}
+
+/// Assert that a Rust uniform (its encase field offsets and size) matches `struct name`
+/// in `source`
+pub fn assert_matches_shader(source: &str, name: &str, offsets: &[u64], size: u64) {
+    let fields = field_offsets(source, name);
+    assert_eq!(
+        fields.len(),
+        offsets.len(),
+        "{name}: the shader declares {} fields, the Rust struct {}",
+        fields.len(),
+        offsets.len()
+    );
+    for ((field, shader_offset), offset) in fields.iter().zip(offsets) {
+        assert_eq!(shader_offset, offset, "{name}.{field}: offset");
+    }
+    assert_eq!(struct_layout(source, name).size, size, "{name}: size");
+}
+
+/// `assert_matches_shader` for a `ShaderType`, with its offsets and size from encase
+macro_rules! assert_uniform_matches {
+    ($source:expr, $ty:ty, $name:expr) => {
+        $crate::visual::sdf::layout::assert_matches_shader(
+            $source,
+            $name,
+            &<$ty as bevy::render::render_resource::ShaderType>::METADATA
+                .extra
+                .offsets,
+            <$ty as bevy::render::render_resource::ShaderSize>::SHADER_SIZE.get(),
+        )
+    };
+}
+pub(crate) use assert_uniform_matches;
+
+/// Each field of `struct name` with its offset and layout
+fn field_layouts(source: &str, name: &str) -> Vec<(String, u64, Layout)> {
+    let mut offset = 0;
+    struct_fields(source, name)
+        .into_iter()
+        .map(|(field, ty)| {
+            let layout = member_layout(source, &ty);
+            offset = round_up(offset, layout.align);
+            let placed = (field, offset, layout);
+            offset += layout.size;
+            placed
+        })
+        .collect()
+}
+
+/// Layout of a type used as a struct member or array element in a uniform: arrays and
+/// structs align to at least 16 there
+fn member_layout(source: &str, ty: &str) -> Layout {
+    let layout = type_layout(source, ty);
+    if is_scalar_or_vector(ty) {
+        layout
+    } else {
+        Layout {
+            align: round_up(layout.align, 16),
+            ..layout
+        }
+    }
+}
+
+fn is_scalar_or_vector(ty: &str) -> bool {
+    matches!(ty, "f32" | "u32" | "i32") || ty.starts_with("vec")
+}
+
+/// Layout of a type name as written in a field (`f32`, `vec3<f32>`, `array<T, N>`, or
+/// another struct in `source`)
+pub fn type_layout(source: &str, ty: &str) -> Layout {
+    let scalar = |size| Layout { size, align: size };
+    match ty {
+        "f32" | "u32" | "i32" => return scalar(4),
+        "vec2<f32>" | "vec2<u32>" | "vec2f" => return scalar(8),
+        "vec3<f32>" | "vec3<u32>" | "vec3f" => {
+            return Layout {
+                size: 12,
+                align: 16,
+            };
+        }
+        "vec4<f32>" | "vec4<u32>" | "vec4f" => return scalar(16),
+        _ => {}
+    }
+
+    if let Some(inner) = ty.strip_prefix("array<").and_then(|t| t.strip_suffix('>')) {
+        let (element, count) = inner
+            .rsplit_once(',')
+            .unwrap_or_else(|| panic!("array without a length: {ty}"));
+        let element = type_layout(source, element.trim());
+        let count: u64 = count
+            .trim()
+            .parse()
+            .unwrap_or_else(|_| panic!("array length isn't a literal: {ty}"));
+        let stride = round_up(element.size, element.align);
+        assert!(
+            stride.is_multiple_of(16),
+            "{ty}: a uniform array's stride must be a multiple of 16, not {stride}"
+        );
+        return Layout {
+            size: stride * count,
+            align: round_up(element.align, 16),
+        };
+    }
+
+    struct_layout(source, ty)
+}
+
+/// `(name, type)` of each field of `struct name` in `source`
+pub fn struct_fields(source: &str, name: &str) -> Vec<(String, String)> {
+    let header = format!("struct {name} {{");
+    let start = source
+        .find(&header)
+        .unwrap_or_else(|| panic!("no struct {name} in the shader"))
+        + header.len();
+    let body: String = source[start..]
+        .lines()
+        .map(|line| line.split("//").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = &body[..body.find('}').expect("unclosed struct")];
+
+    // Split on commas outside the angle brackets (`array<T, N>` has its own)
+    let mut fields = Vec::new();
+    let mut depth = 0;
+    let mut field = String::new();
+    for c in body.chars().chain([',']) {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                if let Some((name, ty)) = field.split_once(':') {
+                    fields.push((name.trim().to_string(), ty.trim().to_string()));
+                }
+                field.clear();
+                continue;
+            }
+            _ => {}
+        }
+        field.push(c);
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "
+        struct Inner {
+            a: vec3<f32>, // 12 bytes, aligned to 16
+            b: f32,
+        }
+        struct Outer {
+            count: u32,
+            items: array<Inner, 3>,
+            pos: vec2<f32>,
+        }
+        struct Flat {
+            a: u32,
+            b: vec2<f32>,
+            c: f32,
+        }
+        struct Odd {
+            pos: vec2<f32>, // 8-byte aligned, so 24 bytes in all
+            a: u32,
+            b: u32,
+            c: u32,
+        }
+        struct Bad {
+            items: array<Odd, 2>,
+        }
+    ";
+
+    #[test]
+    fn test_layout_follows_uniform_rules() {
+        assert_eq!(
+            struct_layout(SOURCE, "Inner"),
+            Layout {
+                size: 16,
+                align: 16
+            }
+        );
+        // count, padded to the array's 16, three 16-byte items, then pos padded out
+        assert_eq!(struct_layout(SOURCE, "Outer").size, 16 + 48 + 16);
+        assert_eq!(
+            field_offsets(SOURCE, "Outer"),
+            [
+                ("count".to_string(), 0),
+                ("items".to_string(), 16),
+                ("pos".to_string(), 64)
+            ]
+        );
+        assert_eq!(type_layout(SOURCE, "array<vec4<f32>, 4>").size, 64);
+
+        // Scalars and vectors only: no 16-byte rounding
+        assert_eq!(struct_layout(SOURCE, "Flat"), Layout { size: 24, align: 8 });
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple of 16")]
+    fn test_uneven_array_stride_is_rejected() {
+        struct_layout(SOURCE, "Bad");
+    }
+}
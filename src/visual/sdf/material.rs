@@ -3,8 +3,12 @@ use bevy::prelude::*;
 use bevy::render::render_resource::{AsBindGroup, ShaderType};
 use bevy::shader::ShaderRef;
 
+use crate::visual::particles::{MAX_PARTICLES, SdfParticle};
+use crate::visual::sdf::background::SdfBackgroundUniform;
 use crate::visual::sdf::edges::cylinder::SdfCylinder;
 use crate::visual::sdf::nodes::ellipsoid::SdfSphere;
+use crate::visual::sdf::quality::SdfQualityUniform;
+use crate::visual::theme::SdfThemeUniform;
 
 pub struct SdfMaterialPlugin;
 
@@ -15,14 +19,51 @@ impl Plugin for SdfMaterialPlugin {
 }
 
 /// All scene data in one uniform (with proper alignment)
-#[derive(ShaderType, Debug, Clone, Default)]
+#[derive(ShaderType, Debug, Clone)]
 pub struct SdfSceneUniform {
     pub num_spheres: u32,
     pub num_cylinders: u32,
-    pub _padding1: u32,
-    pub _padding2: u32,
+    /// Base smooth-minimum radius for blending nodes and edges (higher = gooier)
+    pub blend_smoothness: f32,
+    pub num_particles: u32,
+    /// Where the completion shockwave started (world space)
+    pub shockwave_center: Vec3,
+    /// Current radius of the shockwave ring (world units)
+    pub shockwave_radius: f32,
+    /// Glow boost of the ring (0 = no shockwave)
+    pub shockwave_strength: f32,
+    /// How the scene is drawn (see `ScenePresentation::shader_value`)
+    pub presentation: u32,
+    /// Vertical field of view of the orbit camera (radians)
+    pub orbit_fov: f32,
+    /// Where the orbit camera sits (orbit presentation only)
+    pub orbit_eye: Vec3,
+    /// What the orbit camera looks at
+    pub orbit_target: Vec3,
     pub spheres: [SdfSphere; 9],
     pub cylinders: [SdfCylinder; 17],
+    pub particles: [SdfParticle; MAX_PARTICLES],
+}
+
+impl Default for SdfSceneUniform {
+    fn default() -> Self {
+        Self {
+            num_spheres: 0,
+            num_cylinders: 0,
+            blend_smoothness: 0.0,
+            num_particles: 0,
+            shockwave_center: Vec3::ZERO,
+            shockwave_radius: 0.0,
+            shockwave_strength: 0.0,
+            presentation: 0,
+            orbit_fov: 0.0,
+            orbit_eye: Vec3::ZERO,
+            orbit_target: Vec3::ZERO,
+            spheres: Default::default(),
+            cylinders: Default::default(),
+            particles: [SdfParticle::default(); MAX_PARTICLES],
+        }
+    }
 }
 
 /// UV coordinates for each digit 0-8 in the atlas
@@ -54,6 +95,21 @@ pub struct SdfSceneMaterial {
     /// UV bounds for each digit (binding 3)
     #[uniform(3)]
     pub digit_uvs: DigitUvs,
+
+    /// Animated environment behind the board (binding 4)
+    #[uniform(4)]
+    pub background: SdfBackgroundUniform,
+
+    /// Active theme colors and lighting (binding 5)
+    #[uniform(5)]
+    pub theme: SdfThemeUniform,
+
+    /// Render quality preset (binding 6)
+    #[uniform(6)]
+    pub quality: SdfQualityUniform,
+
+    /// Let MSAA resolve the edges instead of blending them (set by the quality preset)
+    pub alpha_to_coverage: bool,
 }
 
 impl Material for SdfSceneMaterial {
@@ -62,10 +118,35 @@ impl Material for SdfSceneMaterial {
     }
 
     fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+        if self.alpha_to_coverage {
+            AlphaMode::AlphaToCoverage
+        } else {
+            AlphaMode::Blend
+        }
     }
 }
 
 /// Resource to store the handle to the scene material
 #[derive(Resource)]
 pub struct SceneMaterialHandle(pub Handle<SdfSceneMaterial>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visual::sdf::layout::assert_uniform_matches;
+
+    const SHADER: &str = include_str!("../../../assets/shaders/sdf_scene.wgsl");
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        // Nested structs first, so a mismatch points at the struct that moved
+        assert_uniform_matches!(SHADER, SdfSphere, "SdfSphere");
+        assert_uniform_matches!(SHADER, SdfCylinder, "SdfCylinder");
+        assert_uniform_matches!(SHADER, SdfParticle, "SdfParticle");
+        assert_uniform_matches!(SHADER, SdfSceneUniform, "SdfSceneUniform");
+        assert_uniform_matches!(SHADER, DigitUvs, "DigitUvs");
+        assert_uniform_matches!(SHADER, SdfBackgroundUniform, "SdfBackground");
+        assert_uniform_matches!(SHADER, SdfThemeUniform, "SdfTheme");
+        assert_uniform_matches!(SHADER, SdfQualityUniform, "SdfQuality");
+    }
+}
@@ -1,6 +1,11 @@
+pub mod background;
 pub mod edges;
+#[cfg(test)]
+pub(crate) mod layout;
 pub mod material;
 pub mod nodes;
 pub mod numbers;
+pub mod presentation;
+pub mod quality;
 pub mod seven_segment;
-pub mod sync;
\ No newline at end of file
+pub mod sync;
@@ -1,8 +1,8 @@
 use bevy::prelude::*;
-use bevy::render::render_resource::ShaderType;
+use bevy::render::render_resource::{ShaderSize, ShaderType};
 
 /// A single SDF sphere in the scene
-#[derive(ShaderType, Debug, Clone, Copy)]
+#[derive(ShaderType, Debug, Clone, Copy, PartialEq)]
 pub struct SdfSphere {
     pub center: Vec3,
     pub radius: f32,
@@ -14,8 +14,27 @@ pub struct SdfSphere {
     pub stretch_factor: f32,
     pub ripple_phase: f32,
     pub ripple_amplitude: f32,
-    pub spike_amount: f32,
+    /// Additive emission (0 = none, 1 = full flash)
+    pub glow: f32,
     pub digit_value: u32,
+    /// Spiky displacement for nodes that can't be added (0 = smooth, 1 = full spikes)
+    pub spike: f32,
+}
+
+// The scene shader's sphere array strides 80 bytes (checked field by field against
+// `sdf_scene.wgsl` in the material tests)
+const _: () = assert!(SdfSphere::SHADER_SIZE.get() == 80);
+
+impl SdfSphere {
+    /// A round, still sphere
+    pub fn new(center: Vec3, radius: f32, color: Vec4) -> Self {
+        Self {
+            center,
+            radius,
+            color,
+            ..default()
+        }
+    }
 }
 
 impl Default for SdfSphere {
@@ -28,9 +47,9 @@ impl Default for SdfSphere {
             stretch_factor: 1.0,
             ripple_phase: 0.0,
             ripple_amplitude: 0.0,
-            spike_amount: 0.0,
+            glow: 0.0,
             digit_value: 0,
+            spike: 0.0,
         }
     }
 }
-
@@ -44,9 +44,17 @@ impl DigitAtlas {
         // Load the texture
         let texture = asset_server.load("fonts/fredoka/fredoka-bold-digits.png");
 
-        // Parse the embedded JSON
-        let atlas_data: AtlasJson =
-            serde_json::from_str(DIGITS_JSON).expect("Failed to parse embedded digits.json");
+        // Parse the embedded JSON (without it the nodes just show no digits)
+        let atlas_data: AtlasJson = match serde_json::from_str(DIGITS_JSON) {
+            Ok(atlas_data) => atlas_data,
+            Err(err) => {
+                warn!("Failed to parse embedded digits.json: {}", err);
+                return Self {
+                    texture,
+                    digit_uvs: [[0.0; 4]; 9],
+                };
+            }
+        };
 
         let atlas_width = atlas_data.atlas.width;
         let atlas_height = atlas_data.atlas.height;
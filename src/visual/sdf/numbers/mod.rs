@@ -1,3 +1,3 @@
 pub mod atlas;
 
-pub use atlas::DigitAtlas;
\ No newline at end of file
+pub use atlas::DigitAtlas;
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::{
+    settings::{ScenePresentation, Settings},
+    visual::sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
+};
+
+/// Vertical field of view of the orbit camera (radians)
+const ORBIT_FOV: f32 = 0.7;
+
+/// How fast the orbit camera circles the board (radians per second)
+const ORBIT_SPEED: f32 = 0.25;
+
+/// How far the orbit camera leans off straight overhead (radians)
+const ORBIT_TILT: f32 = 0.75;
+
+/// Room around the blobs in the orbit view (1 = they just touch the frame)
+const ORBIT_MARGIN: f32 = 1.3;
+
+/// System: O switches between the flat board and the orbiting 3D view
+pub fn toggle_presentation(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if !keys.just_pressed(KeyCode::KeyO) {
+        return;
+    }
+    settings.presentation = match settings.presentation {
+        ScenePresentation::Flat => ScenePresentation::Orbit,
+        ScenePresentation::Orbit => ScenePresentation::Flat,
+    };
+    info!("🎥 Scene presentation: {:?}", settings.presentation);
+}
+
+/// Where the orbit camera sits to see a ball of blobs (`target`, `extent` across its
+/// widest radius) `yaw` radians around its circle
+pub fn orbit_eye(target: Vec3, extent: f32, yaw: f32) -> Vec3 {
+    let distance = extent * ORBIT_MARGIN / (ORBIT_FOV * 0.5).sin();
+    let direction = Quat::from_rotation_z(yaw) * Quat::from_rotation_x(ORBIT_TILT) * Vec3::Z;
+    target + direction * distance
+}
+
+/// System: Tell the scene shader how to present itself, and circle the orbit camera
+/// around the nodes
+///
+/// The orbit reads the node spheres already in the uniform, so both presentations draw
+/// from the same scene data. Flat, the material is left alone once it knows.
+pub fn update_presentation(
+    time: Res<Time<Real>>, // Keeps circling while gameplay is paused
+    settings: Res<Settings>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+) {
+    let presentation = settings.presentation.shader_value();
+    let settled = materials
+        .get(&scene_handle.0)
+        .is_some_and(|m| m.data.presentation == presentation);
+    if settled && settings.presentation == ScenePresentation::Flat {
+        return;
+    }
+
+    let Some(material) = materials.get_mut(&scene_handle.0) else {
+        return;
+    };
+    let data = &mut material.data;
+    data.presentation = presentation;
+    if settings.presentation == ScenePresentation::Flat {
+        return;
+    }
+
+    let spheres = &data.spheres[..data.num_spheres as usize];
+    if spheres.is_empty() {
+        return;
+    }
+    let target = spheres.iter().map(|sphere| sphere.center).sum::<Vec3>() / spheres.len() as f32;
+    let extent = spheres
+        .iter()
+        .map(|sphere| sphere.center.distance(target) + sphere.radius)
+        .fold(0.0, f32::max);
+
+    data.orbit_fov = ORBIT_FOV;
+    data.orbit_target = target;
+    data.orbit_eye = orbit_eye(target, extent, time.elapsed_secs() * ORBIT_SPEED);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orbit_circles_above_the_board_at_a_fixed_distance() {
+        let target = Vec3::new(2.0, 3.0, 0.0);
+        let eyes: Vec<Vec3> = (0..8)
+            .map(|step| orbit_eye(target, 1.5, step as f32 * 0.8))
+            .collect();
+
+        let distance = eyes[0].distance(target);
+        for eye in &eyes {
+            assert!((eye.distance(target) - distance).abs() < 1e-4);
+            assert!(eye.z > 0.0, "the camera stays above the board");
+            assert!((eye.z - eyes[0].z).abs() < 1e-4);
+        }
+        assert!(eyes[0].distance(eyes[1]) > 0.1, "the camera moves around");
+
+        // Wider scenes are seen from further back
+        assert!(orbit_eye(target, 3.0, 0.0).distance(target) > distance);
+    }
+}
@@ -0,0 +1,262 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::ShaderType;
+
+use crate::{
+    settings::{RenderQuality, Settings},
+    visual::{
+        sdf::{
+            edges::cylinder::MAX_EDGE_WAVES,
+            material::{SceneMaterialHandle, SdfSceneMaterial},
+            seven_segment::SevenSegmentMaterial,
+        },
+        ui::hud::HudMaterialHandle,
+    },
+};
+
+/// How far over the display's refresh interval a frame may run before it counts as slow
+const FRAME_BUDGET_SCALE: f32 = 1.5;
+
+/// Bounds on the measured refresh interval (seconds). Uncapped frames would otherwise set
+/// a hair-trigger budget, and a run of slow frames from the start could hide a real one.
+const REFRESH_SECS: std::ops::RangeInclusive<f32> = 1.0 / 60.0..=1.0 / 30.0;
+
+/// Slow frames in a row before the quality steps down (two to three seconds' worth)
+const SLOW_FRAMES_TO_DOWNGRADE: u32 = 90;
+
+/// Frames longer than this are hitches (a hidden tab, a shader compile), not load, and
+/// don't count either way
+const HITCH_SECS: f32 = 0.25;
+
+/// How much work the scene shader does per pixel
+#[derive(ShaderType, Debug, Clone, Copy, PartialEq)]
+pub struct SdfQualityUniform {
+    /// Raymarch iterations looking for a surface
+    pub march_steps: u32,
+    /// Ambient occlusion taps along the normal (0 = none)
+    pub ao_samples: u32,
+    /// Tension waves drawn per edge (of `MAX_EDGE_WAVES`)
+    pub wave_slots: u32,
+    /// 0 = plain gradient, 1 = + drifting noise and ripple warp, 2 = + distant blobs
+    pub background_detail: u32,
+    /// Silhouette fade width in pixels, from screen-space derivatives (0 = hard edges)
+    pub edge_softness: f32,
+}
+
+/// Narrowest fade Low keeps, so alpha-to-coverage has a partial alpha to turn into samples
+const MIN_EDGE_SOFTNESS: f32 = 0.5;
+
+impl SdfQualityUniform {
+    pub fn for_preset(quality: RenderQuality) -> Self {
+        match quality {
+            RenderQuality::Low => Self {
+                march_steps: 48,
+                ao_samples: 0,
+                wave_slots: 1,
+                background_detail: 0,
+                edge_softness: MIN_EDGE_SOFTNESS,
+            },
+            RenderQuality::Medium => Self {
+                march_steps: 80,
+                ao_samples: 2,
+                wave_slots: 2,
+                background_detail: 1,
+                edge_softness: 1.0,
+            },
+            RenderQuality::High => Self {
+                march_steps: 128,
+                ao_samples: 4,
+                wave_slots: MAX_EDGE_WAVES as u32,
+                background_detail: 2,
+                edge_softness: 1.5,
+            },
+        }
+    }
+}
+
+/// Whether a preset has MSAA resolve the plane edges (alpha-to-coverage) instead of
+/// blending them
+///
+/// Low keeps only a half-pixel fade and lets the hardware coverage spread it over the MSAA
+/// samples; the others blend, which keeps the blobs' translucency smooth rather than dithered.
+pub fn alpha_to_coverage(quality: RenderQuality) -> bool {
+    quality == RenderQuality::Low
+}
+
+impl Default for SdfQualityUniform {
+    fn default() -> Self {
+        Self::for_preset(RenderQuality::default())
+    }
+}
+
+/// Resource: Counts frames in a row that ran over budget, the budget following the
+/// display's refresh interval (the shortest frame seen, since vsync won't present faster)
+#[derive(Resource, Debug)]
+pub struct FrameTimeMonitor {
+    slow_frames: u32,
+    refresh_secs: f32,
+}
+
+impl Default for FrameTimeMonitor {
+    fn default() -> Self {
+        Self {
+            slow_frames: 0,
+            refresh_secs: *REFRESH_SECS.end(),
+        }
+    }
+}
+
+impl FrameTimeMonitor {
+    /// Longest a frame may take before it counts as slow (seconds)
+    pub fn budget(&self) -> f32 {
+        self.refresh_secs * FRAME_BUDGET_SCALE
+    }
+
+    /// Record a frame's length; true once enough slow frames have come in a row to step
+    /// the quality down (and the count starts over)
+    pub fn record(&mut self, frame_secs: f32) -> bool {
+        if frame_secs > HITCH_SECS {
+            return false;
+        }
+        self.refresh_secs = frame_secs
+            .clamp(*REFRESH_SECS.start(), *REFRESH_SECS.end())
+            .min(self.refresh_secs);
+        if frame_secs <= self.budget() {
+            self.slow_frames = 0;
+            return false;
+        }
+
+        self.slow_frames += 1;
+        if self.slow_frames < SLOW_FRAMES_TO_DOWNGRADE {
+            return false;
+        }
+        self.slow_frames = 0;
+        true
+    }
+}
+
+/// System: Q cycles the quality presets (picking one by hand turns auto-downgrade off)
+pub fn cycle_quality(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if !keys.just_pressed(KeyCode::KeyQ) {
+        return;
+    }
+    settings.quality = settings.quality.next();
+    settings.auto_quality = false;
+    info!("🖥️ Render quality: {:?}", settings.quality);
+}
+
+/// System: Step the quality down a preset when frames keep running over budget
+pub fn monitor_frame_time(
+    time: Res<Time<Real>>,
+    mut monitor: ResMut<FrameTimeMonitor>,
+    mut settings: ResMut<Settings>,
+) {
+    if !settings.auto_quality || !monitor.record(time.delta_secs()) {
+        return;
+    }
+    if let Some(lower) = settings.quality.lower() {
+        settings.quality = lower;
+        info!("🖥️ Frames running slow; render quality down to {:?}", lower);
+    }
+}
+
+/// System: Send the chosen preset to the scene and HUD shaders
+pub fn apply_quality(
+    settings: Res<Settings>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+    mut hud_materials: ResMut<Assets<SevenSegmentMaterial>>,
+    hud_handle: Option<Res<HudMaterialHandle>>,
+) {
+    let wanted = SdfQualityUniform::for_preset(settings.quality);
+    let coverage = alpha_to_coverage(settings.quality);
+
+    // Leave the materials untouched once they match (avoids a GPU re-upload)
+    if materials
+        .get(&scene_handle.0)
+        .is_some_and(|m| m.quality != wanted || m.alpha_to_coverage != coverage)
+        && let Some(material) = materials.get_mut(&scene_handle.0)
+    {
+        material.quality = wanted;
+        material.alpha_to_coverage = coverage;
+    }
+
+    let Some(hud_handle) = hud_handle else {
+        return;
+    };
+    if hud_materials.get(&hud_handle.0).is_some_and(|m| {
+        m.data.edge_softness != wanted.edge_softness || m.alpha_to_coverage != coverage
+    }) && let Some(material) = hud_materials.get_mut(&hud_handle.0)
+    {
+        material.data.edge_softness = wanted.edge_softness;
+        material.alpha_to_coverage = coverage;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLOW: f32 = 1.0 / 60.0 * FRAME_BUDGET_SCALE * 1.5;
+
+    #[test]
+    fn test_only_a_run_of_slow_frames_downgrades() {
+        let mut monitor = FrameTimeMonitor::default();
+        assert!(!monitor.record(1.0 / 60.0));
+        for _ in 1..SLOW_FRAMES_TO_DOWNGRADE {
+            assert!(!monitor.record(SLOW));
+        }
+        // One quick frame starts the count over
+        assert!(!monitor.record(1.0 / 60.0));
+        for _ in 1..SLOW_FRAMES_TO_DOWNGRADE {
+            assert!(!monitor.record(SLOW));
+        }
+        // Hitches don't break the run or add to it
+        assert!(!monitor.record(1.0));
+        assert!(monitor.record(SLOW));
+        assert!(!monitor.record(SLOW));
+    }
+
+    #[test]
+    fn test_budget_follows_the_refresh_interval() {
+        // A 30 Hz display's every frame is on time
+        let mut monitor = FrameTimeMonitor::default();
+        for _ in 0..SLOW_FRAMES_TO_DOWNGRADE * 2 {
+            assert!(!monitor.record(1.0 / 30.0));
+        }
+
+        // On a 60 Hz one, those frames are each a missed refresh
+        assert!(!monitor.record(1.0 / 60.0));
+        let downgraded = (0..SLOW_FRAMES_TO_DOWNGRADE).any(|_| monitor.record(1.0 / 30.0));
+        assert!(downgraded);
+
+        // Uncapped frames don't shrink the budget below a 60 Hz one
+        let mut monitor = FrameTimeMonitor::default();
+        monitor.record(1.0 / 500.0);
+        assert_eq!(monitor.budget(), 1.0 / 60.0 * FRAME_BUDGET_SCALE);
+    }
+
+    #[test]
+    fn test_presets_do_less_work_going_down() {
+        let [low, medium, high] = [
+            RenderQuality::Low,
+            RenderQuality::Medium,
+            RenderQuality::High,
+        ]
+        .map(SdfQualityUniform::for_preset);
+        for (less, more) in [(low, medium), (medium, high)] {
+            assert!(less.march_steps < more.march_steps);
+            assert!(less.ao_samples < more.ao_samples);
+            assert!(less.wave_slots <= more.wave_slots);
+            assert!(less.background_detail < more.background_detail);
+        }
+        // Low's narrow fade still leaves alpha-to-coverage an edge alpha to resolve
+        assert!(low.edge_softness > 0.0 && low.edge_softness < medium.edge_softness);
+        assert!(alpha_to_coverage(RenderQuality::Low));
+        for quality in [RenderQuality::Medium, RenderQuality::High] {
+            assert!(SdfQualityUniform::for_preset(quality).edge_softness > 0.0);
+            assert!(!alpha_to_coverage(quality));
+        }
+        assert_eq!(RenderQuality::High.lower(), Some(RenderQuality::Medium));
+        assert_eq!(RenderQuality::Low.lower(), None);
+    }
+}
@@ -16,6 +16,20 @@ pub enum Digit {
 }
 
 impl Digit {
+    /// Every digit, in order
+    pub const ALL: [Digit; 10] = [
+        Digit::Zero,
+        Digit::One,
+        Digit::Two,
+        Digit::Three,
+        Digit::Four,
+        Digit::Five,
+        Digit::Six,
+        Digit::Seven,
+        Digit::Eight,
+        Digit::Nine,
+    ];
+
     /// 7-segment bitmask, bits 0..6 correspond to SegmentId 0..6
     pub const fn mask(self) -> u8 {
         match self {
@@ -34,61 +48,122 @@ impl Digit {
 
     /// Convert a 7-segment mask back to a Digit
     pub fn from_mask(mask: u8) -> Option<Self> {
-        match mask {
-            0b0111111 => Some(Digit::Zero),
-            0b0000110 => Some(Digit::One),
-            0b1011011 => Some(Digit::Two),
-            0b1001111 => Some(Digit::Three),
-            0b1100110 => Some(Digit::Four),
-            0b1101101 => Some(Digit::Five),
-            0b1111101 => Some(Digit::Six),
-            0b0000111 => Some(Digit::Seven),
-            0b1111111 => Some(Digit::Eight),
-            0b1101111 => Some(Digit::Nine),
-            _ => None,
-        }
+        Self::ALL.into_iter().find(|digit| digit.mask() == mask)
     }
 
     /// Convert digit to its numeric value (0-9)
     pub const fn to_u8(self) -> u8 {
         self as u8
     }
+}
+
+impl TryFrom<u8> for Digit {
+    /// The value that isn't a single digit
+    type Error = u8;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        Self::ALL.get(n as usize).copied().ok_or(n)
+    }
+}
+
+/// Glyph bitmask for a minus sign (or dash): just the middle segment
+pub const MINUS_MASK: u32 = 0b1000000;
+
+/// Glyph mask bits past the seven segments: strokes and dots drawn on their own
+/// rather than flowing between segments (see `render_marks` in `seven_segment.wgsl`)
+pub const SLASH_MASK: u32 = 1 << 7;
+/// The two dots of a colon
+pub const COLON_MASK: u32 = 1 << 8;
+/// A dot on the baseline
+pub const PERIOD_MASK: u32 = 1 << 9;
+
+/// Glyph bitmask for a letter, digit or punctuation mark, in whichever case reads best
+/// (a dash for characters seven segments can't show, like M or W; blank for a space)
+///
+/// Bits 0..6 are the segments and the rest punctuation marks, so every glyph is
+/// drawn (and transitions) from its mask alone.
+pub const fn glyph_mask(c: char) -> u32 {
+    let segments = match c.to_ascii_lowercase() {
+        '0' => Digit::Zero.mask(),
+        '1' => Digit::One.mask(),
+        '2' => Digit::Two.mask(),
+        '3' => Digit::Three.mask(),
+        '4' => Digit::Four.mask(),
+        '5' | 's' => Digit::Five.mask(),
+        '6' => Digit::Six.mask(),
+        '7' => Digit::Seven.mask(),
+        '8' => Digit::Eight.mask(),
+        '9' => Digit::Nine.mask(),
+        'a' => 0b1110111,
+        'b' => 0b1111100,
+        'c' => 0b0111001,
+        'd' => 0b1011110,
+        'e' => 0b1111001,
+        'f' => 0b1110001,
+        'g' => 0b0111101,
+        'h' => 0b1110110,
+        'i' => 0b0110000,
+        'j' => 0b0011110,
+        'l' => 0b0111000,
+        'n' => 0b1010100,
+        'o' => 0b1011100,
+        'p' => 0b1110011,
+        'q' => 0b1100111,
+        'r' => 0b1010000,
+        't' => 0b1111000,
+        'u' => 0b0111110,
+        'y' => 0b1101110,
+        ' ' => 0,
+        '/' => return SLASH_MASK,
+        ':' => return COLON_MASK,
+        '.' => return PERIOD_MASK,
+        _ => return MINUS_MASK, // Also '-' itself
+    };
+    segments as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // /// Convert a numeric value to a Digit
-    // pub fn from_u8(n: u8) -> Option<Self> {
-    //     match n {
-    //         0 => Some(Digit::Zero),
-    //         1 => Some(Digit::One),
-    //         2 => Some(Digit::Two),
-    //         3 => Some(Digit::Three),
-    //         4 => Some(Digit::Four),
-    //         5 => Some(Digit::Five),
-    //         6 => Some(Digit::Six),
-    //         7 => Some(Digit::Seven),
-    //         8 => Some(Digit::Eight),
-    //         9 => Some(Digit::Nine),
-    //         _ => None,
-    //     }
-    // }
-    //
-    // /// Get an iterator over the active segments for this digit
-    // pub fn active_segments(self) -> impl Iterator<Item = SegmentId> {
-    //     let mask = self.mask();
-    //     (0u8..7).filter_map(move |i| {
-    //         if (mask & (1 << i)) != 0 {
-    //             Some(match i {
-    //                 0 => SegmentId::Top,
-    //                 1 => SegmentId::TopRight,
-    //                 2 => SegmentId::BottomRight,
-    //                 3 => SegmentId::Bottom,
-    //                 4 => SegmentId::BottomLeft,
-    //                 5 => SegmentId::TopLeft,
-    //                 6 => SegmentId::Middle,
-    //                 _ => unreachable!(),
-    //             })
-    //         } else {
-    //             None
-    //         }
-    //     })
-    // }
+    #[test]
+    fn test_digits_round_trip() {
+        for (n, digit) in Digit::ALL.into_iter().enumerate() {
+            assert_eq!(digit.to_u8() as usize, n);
+            assert_eq!(Digit::try_from(n as u8), Ok(digit));
+            assert_eq!(Digit::from_mask(digit.mask()), Some(digit));
+        }
+        for n in 10..=u8::MAX {
+            assert_eq!(Digit::try_from(n), Err(n));
+        }
+    }
+
+    #[test]
+    fn test_only_digit_masks_read_back() {
+        let digit_masks: Vec<u8> = Digit::ALL.iter().map(|digit| digit.mask()).collect();
+        for mask in 0..=u8::MAX {
+            assert_eq!(
+                Digit::from_mask(mask).is_some(),
+                digit_masks.contains(&mask),
+                "{mask:#010b}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_digits_and_punctuation_share_one_mask_space() {
+        for n in 0..10u8 {
+            let c = char::from(b'0' + n);
+            let digit = Digit::from_mask(glyph_mask(c) as u8).map(Digit::to_u8);
+            assert_eq!(digit, Some(n));
+        }
+
+        let marks = [SLASH_MASK, COLON_MASK, PERIOD_MASK];
+        for (c, mark) in ['/', ':', '.'].into_iter().zip(marks) {
+            assert_eq!(glyph_mask(c), mark);
+            assert_eq!(mark & 0b1111111, 0, "{c} overlaps the segments");
+        }
+        assert_eq!(glyph_mask('-'), MINUS_MASK);
+        assert_eq!(glyph_mask(' '), 0);
+    }
 }
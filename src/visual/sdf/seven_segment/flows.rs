@@ -0,0 +1,183 @@
+//! Which segments a digit's blobs flow between when it changes value.
+//!
+//! Mirrors `compute_flows` in `seven_segment.wgsl` (keep the two in step), so the
+//! shader's fixed flow budget can be checked on the CPU.
+
+use crate::settings::TransitionStyle;
+
+/// Flows the shader has room for per digit
+pub const MAX_FLOWS: usize = 16;
+
+/// Share of a segment's mass that an excitement spark carries
+const SPARK_SHARE: f32 = 0.2;
+
+/// Segments next to each one: 0=Top, 1=TopRight, 2=BottomRight, 3=Bottom,
+/// 4=BottomLeft, 5=TopLeft, 6=Middle (simplified)
+const ADJACENT: [[u32; 3]; 7] = [
+    [1, 5, 6],
+    [0, 2, 6],
+    [1, 3, 6],
+    [2, 4, 6],
+    [3, 5, 6],
+    [0, 4, 6],
+    [0, 1, 2],
+];
+
+/// One blob moving `share` of a segment from `from_seg` to `to_seg`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Flow {
+    pub from_seg: u32,
+    pub to_seg: u32,
+    pub share: f32,
+}
+
+/// Hops between two segments: 0 = same, 1 = adjacent, 2 = anywhere else
+pub fn segment_distance(from_seg: u32, to_seg: u32) -> u32 {
+    if from_seg == to_seg {
+        0
+    } else if ADJACENT[from_seg as usize].contains(&to_seg) {
+        1
+    } else {
+        2
+    }
+}
+
+/// Seeded pick in `0..count` (the shader's integer hash, salted per segment)
+pub fn pick(seed: u32, salt: u32, count: usize) -> usize {
+    let n = seed ^ salt.wrapping_mul(73);
+    let hashed = n.wrapping_mul(1103515245).wrapping_add(12345) & 0x7fffffff;
+    hashed as usize % count.max(1)
+}
+
+fn active(mask: u32, seg: u32) -> bool {
+    mask & (1 << seg) != 0
+}
+
+/// Every flow a `from_mask` → `to_mask` transition makes, uncapped
+///
+/// `seed` only matters for `TransitionStyle::Organic`.
+pub fn compute_flows(from_mask: u32, to_mask: u32, style: TransitionStyle, seed: u32) -> Vec<Flow> {
+    let mut flows = Vec::new();
+    let targets: Vec<u32> = (0..7).filter(|&seg| active(to_mask, seg)).collect();
+
+    // Disappearing segments: route to the nearest target segments
+    for from_seg in (0..7).filter(|&seg| active(from_mask, seg) && !active(to_mask, seg)) {
+        let Some(min_dist) = targets
+            .iter()
+            .map(|&to_seg| segment_distance(from_seg, to_seg))
+            .min()
+        else {
+            continue;
+        };
+        let nearest: Vec<u32> = targets
+            .iter()
+            .copied()
+            .filter(|&to_seg| segment_distance(from_seg, to_seg) == min_dist)
+            .collect();
+
+        if style == TransitionStyle::Organic {
+            let to_seg = nearest[pick(seed, from_seg, nearest.len())];
+            flows.push(Flow {
+                from_seg,
+                to_seg,
+                share: 1.0,
+            });
+        } else {
+            let share = 1.0 / nearest.len() as f32;
+            flows.extend(nearest.into_iter().map(|to_seg| Flow {
+                from_seg,
+                to_seg,
+                share,
+            }));
+        }
+    }
+
+    if style == TransitionStyle::Deterministic {
+        return flows;
+    }
+
+    // Sparks: a steady segment reaches out into each appearing one
+    let steady: Vec<u32> = (0..7)
+        .filter(|&seg| active(from_mask, seg) && active(to_mask, seg))
+        .collect();
+    for to_seg in (0..7).filter(|&seg| !active(from_mask, seg) && active(to_mask, seg)) {
+        let source = if style == TransitionStyle::Organic {
+            (!steady.is_empty()).then(|| steady[pick(seed, to_seg + 7, steady.len())])
+        } else {
+            // Closest steady segment, lowest index on ties
+            steady
+                .iter()
+                .copied()
+                .min_by_key(|&seg| segment_distance(seg, to_seg))
+        };
+        if let Some(from_seg) = source {
+            flows.push(Flow {
+                from_seg,
+                to_seg,
+                share: SPARK_SHARE,
+            });
+        }
+    }
+
+    flows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLES: [TransitionStyle; 3] = [
+        TransitionStyle::Deterministic,
+        TransitionStyle::Excitement,
+        TransitionStyle::Organic,
+    ];
+
+    #[test]
+    fn test_every_style_fits_the_flow_budget() {
+        // Every pair of masks, not just digits: the HUD shows letters too
+        for style in STYLES {
+            for seed in [0, 1, 0xdead_beef] {
+                for from_mask in 0..128 {
+                    for to_mask in 0..128 {
+                        let count = compute_flows(from_mask, to_mask, style, seed).len();
+                        assert!(
+                            count <= MAX_FLOWS,
+                            "{style:?} {from_mask:#09b} → {to_mask:#09b}: {count} flows"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_each_vanishing_segment_sends_its_whole_mass() {
+        // 8 → 0 loses only the middle, which splits evenly (or goes whole, organically)
+        for style in STYLES {
+            let flows = compute_flows(0b1111111, 0b0111111, style, 7);
+            let sent: f32 = flows
+                .iter()
+                .filter(|flow| flow.from_seg == 6)
+                .map(|flow| flow.share)
+                .sum();
+            assert!((sent - 1.0).abs() < 1e-6, "{style:?} sent {sent}");
+        }
+    }
+
+    #[test]
+    fn test_only_excitement_styles_spark() {
+        // 1 → 7 just gains the top
+        let sparks = |style| compute_flows(0b0000110, 0b0000111, style, 3).len();
+        assert_eq!(sparks(TransitionStyle::Deterministic), 0);
+        assert_eq!(sparks(TransitionStyle::Excitement), 1);
+        assert_eq!(sparks(TransitionStyle::Organic), 1);
+    }
+
+    #[test]
+    fn test_organic_flows_follow_the_seed() {
+        // 8 → 1: organic picks differ across seeds but repeat for the same one
+        let flows = |seed| compute_flows(0b1111111, 0b0000110, TransitionStyle::Organic, seed);
+        assert_eq!(flows(42), flows(42));
+        assert!((0..16).any(|seed| flows(seed) != flows(0)));
+    }
+}
@@ -8,9 +8,11 @@
 
 use bevy::pbr::{Material, MaterialPlugin};
 use bevy::prelude::*;
-use bevy::render::render_resource::{AsBindGroup, ShaderType};
+use bevy::render::render_resource::{AsBindGroup, ShaderSize, ShaderType};
 use bevy::shader::ShaderRef;
 
+use crate::settings::TransitionStyle;
+
 /// Plugin that registers the SevenSegmentMaterial for use in the HUD
 pub struct SevenSegmentMaterialPlugin;
 
@@ -20,16 +22,17 @@ impl Plugin for SevenSegmentMaterialPlugin {
     }
 }
 
-/// Maximum number of HUD instances (digits + slashes)
-pub const MAX_HUD_INSTANCES: usize = 12;
+/// Maximum number of HUD instances (glyphs and pips)
+pub const MAX_HUD_INSTANCES: usize = 128;
 
-/// A single HUD element instance (digit or slash)
+/// A single HUD element instance (a glyph or a pip)
 #[derive(Clone, Copy, Debug, ShaderType)]
 #[repr(C)]
 pub struct HudInstance {
-    /// Element kind: 0 = digit, 1 = slash
+    /// Element kind: 0 = glyph, 1 = pip
     pub kind: u32,
-    /// Current 7-segment bitmask (target for transitions)
+    /// Current glyph mask, segments and punctuation (target for transitions; see
+    /// `glyph_mask`)
     pub mask: u32,
     /// Previous mask (for animated transitions, from_mask → mask)
     pub from_mask: u32,
@@ -39,13 +42,20 @@ pub struct HudInstance {
     pub pos: Vec2,
     /// Scale multiplier for the element
     pub scale: f32,
-    /// Padding to reach 48 bytes (next multiple of 16 from 40)
-    pub _pad1: u32,
-    pub _pad2: u32,
-    pub _pad3: u32,
+    /// Seeds the organic flow style's picks (0 for the other styles)
+    pub flow_seed: u32,
+    /// `Easing::shader_value` for the traveling blobs (0 = a per-blob mix of curves)
+    pub easing: u32,
+    /// How much of each phase the segments' and blobs' starts are spread across
+    pub stagger: f32,
+    /// Padding to a 48-byte stride (uniform array elements stride in multiples of 16)
     pub _pad4: u32,
 }
 
+// Instances stride 48 bytes in the shader's array (checked field by field against
+// `seven_segment.wgsl` in the tests below)
+const _: () = assert!(HudInstance::SHADER_SIZE.get() == 48);
+
 impl Default for HudInstance {
     fn default() -> Self {
         Self {
@@ -55,9 +65,9 @@ impl Default for HudInstance {
             transition_progress: 1.0, // Default to "transition complete"
             pos: Vec2::ZERO,
             scale: 0.0,
-            _pad1: 0,
-            _pad2: 0,
-            _pad3: 0,
+            flow_seed: 0,
+            easing: 0,
+            stagger: 0.0,
             _pad4: 0,
         }
     }
@@ -67,8 +77,10 @@ impl Default for HudInstance {
 pub struct SevenSegmentData {
     pub time: f32,
     pub hud_count: u32,
-    pub _padding1: u32,
-    pub _padding2: u32,
+    /// Edge fade width in pixels (0 = the fixed world-space edge)
+    pub edge_softness: f32,
+    /// `TransitionStyle::shader_value`
+    pub transition_style: u32,
 
     pub hud: [HudInstance; MAX_HUD_INSTANCES],
 }
@@ -78,8 +90,8 @@ impl Default for SevenSegmentData {
         Self {
             time: 0.0,
             hud_count: 0,
-            _padding1: 0,
-            _padding2: 0,
+            edge_softness: 0.0,
+            transition_style: TransitionStyle::default().shader_value(),
             hud: [HudInstance::default(); MAX_HUD_INSTANCES],
         }
     }
@@ -89,6 +101,9 @@ impl Default for SevenSegmentData {
 pub struct SevenSegmentMaterial {
     #[uniform(0)]
     pub data: SevenSegmentData,
+
+    /// Let MSAA resolve the edges instead of blending them (set by the quality preset)
+    pub alpha_to_coverage: bool,
 }
 
 impl Material for SevenSegmentMaterial {
@@ -97,6 +112,24 @@ impl Material for SevenSegmentMaterial {
     }
 
     fn alpha_mode(&self) -> AlphaMode {
-        AlphaMode::Blend
+        if self.alpha_to_coverage {
+            AlphaMode::AlphaToCoverage
+        } else {
+            AlphaMode::Blend
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visual::sdf::layout::assert_uniform_matches;
+
+    const SHADER: &str = include_str!("../../../../assets/shaders/seven_segment.wgsl");
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_uniform_matches!(SHADER, HudInstance, "HudInstance");
+        assert_uniform_matches!(SHADER, SevenSegmentData, "SevenSegmentData");
     }
 }
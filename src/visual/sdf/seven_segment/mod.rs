@@ -1,8 +1,9 @@
 pub mod digit;
+pub mod flows;
 pub mod material;
 
 // Re-export commonly used types
-pub use digit::Digit;
+pub use digit::{COLON_MASK, Digit, MINUS_MASK, PERIOD_MASK, SLASH_MASK, glyph_mask};
 pub use material::{
     HudInstance, MAX_HUD_INSTANCES, SevenSegmentMaterial, SevenSegmentMaterialPlugin,
 };
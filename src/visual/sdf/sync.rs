@@ -1,47 +1,153 @@
+use bevy::ecs::schedule::ScheduleConfigs;
+use bevy::ecs::system::{ScheduleSystem, SystemParam};
 use bevy::prelude::*;
 
 use crate::{
     game::session::PuzzleSession,
+    graph::NodeId,
     visual::{
+        edges::{dying::DyingEdges, intro::EdgeIntro, waves::EdgeWaves},
+        interactions::{
+            pointer::{DragState, HoverState},
+            preview::PreviewEndpoint,
+        },
         nodes::{GraphNode, NodeVisual},
-        interactions::pointer::{HoverState, DragState},
-        physics::NodePhysics,
-        edges::waves::EdgeWaves,
+        physics::{EdgeTension, NodePhysics},
+        sdf::edges::cylinder::{MAX_EDGE_WAVES, NO_WAVES, SdfCylinder},
         sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
-        sdf::edges::cylinder::SdfCylinder,
     },
 };
 
-/// System: Update the unified SDF scene with all node and edge data
-/// 
-/// This syncs the ECS world state (physics, visuals, session) to the GPU shader uniforms.
-pub fn update_sdf_scene(
-    nodes: Query<(&GraphNode, &NodePhysics, &NodeVisual)>,
+/// Per-frame render data for every node, indexed by `NodeId::index()`
+///
+/// Built once per sync so edge endpoints are a direct index instead of a query scan.
+struct NodeLookup {
+    entries: Vec<Option<(Vec3, Vec4)>>, // (position, color)
+}
+
+impl NodeLookup {
+    fn build(nodes: impl Iterator<Item = (NodeId, Vec3, Vec4)>) -> Self {
+        let mut entries = Vec::new();
+        for (node_id, position, color) in nodes {
+            if entries.len() <= node_id.index() {
+                entries.resize(node_id.index() + 1, None);
+            }
+            entries[node_id.index()] = Some((position, color));
+        }
+        Self { entries }
+    }
+
+    /// Position and color of a node, if it exists in the scene
+    fn get(&self, node_id: NodeId) -> Option<(Vec3, Vec4)> {
+        self.entries.get(node_id.index()).copied().flatten()
+    }
+}
+
+/// Preview edge tints (alpha = how strongly they override the node color)
+const PREVIEW_VALID_COLOR: Vec4 = Vec4::new(0.2, 1.0, 0.35, 0.8);
+const PREVIEW_INVALID_COLOR: Vec4 = Vec4::new(1.0, 0.15, 0.1, 0.8);
+
+/// Cylinder slots in the uniform: up to 16 edges plus the preview/dying tail
+const MAX_CYLINDERS: usize = 17;
+
+/// Edges that fit while leaving room for the preview
+const EDGE_SLOTS: usize = MAX_CYLINDERS - 1;
+
+/// Ordered stages of the SDF uniform sync, so other systems can hook in between
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SdfSync {
+    /// Node spheres
+    Nodes,
+    /// Trail and dying edge cylinders
+    Edges,
+    /// The preview edge from the trail head to the cursor
+    Preview,
+}
+
+/// Resource: Which cylinder slot the preview owns, written by the edge sync
+#[derive(Resource, Default)]
+pub struct CylinderSlots {
+    /// Reserved while dragging from a trail head, `None` otherwise
+    pub preview: Option<usize>,
+}
+
+/// The three sync systems with their run conditions, in set order
+///
+/// Each one only runs when something it reads changed, and touching the material
+/// re-uploads the whole uniform, so entries are dirty-flagged before writing.
+pub fn sdf_sync_systems() -> ScheduleConfigs<ScheduleSystem> {
+    (
+        sync_sdf_nodes
+            .run_if(nodes_changed.or(resource_changed::<PuzzleSession>))
+            .in_set(SdfSync::Nodes),
+        sync_sdf_edges
+            .run_if(
+                nodes_changed
+                    .or(resource_changed::<PuzzleSession>)
+                    .or(resource_changed::<DragState>)
+                    .or(resource_changed::<EdgeWaves>)
+                    .or(resource_changed::<EdgeIntro>)
+                    .or(resource_changed::<DyingEdges>)
+                    .or(resource_changed::<EdgeTension>),
+            )
+            .in_set(SdfSync::Edges),
+        sync_sdf_preview
+            .run_if(|drag_state: Res<DragState>| drag_state.is_dragging)
+            .in_set(SdfSync::Preview),
+    )
+        .chain()
+        .into_configs()
+}
+
+/// Nodes that moved or changed how they look since the last sync
+type ChangedNodes<'w, 's> = Query<
+    'w,
+    's,
+    (),
+    (
+        With<GraphNode>,
+        Or<(Changed<NodePhysics>, Changed<NodeVisual>)>,
+    ),
+>;
+
+/// Run condition: Some node moved or changed how it looks
+pub fn nodes_changed(nodes: ChangedNodes) -> bool {
+    !nodes.is_empty()
+}
+
+/// System: Sync node physics and visuals into the scene's spheres
+pub fn sync_sdf_nodes(
+    nodes: Query<(&GraphNode, Ref<NodePhysics>, Ref<NodeVisual>)>,
     session: Res<PuzzleSession>,
-    hover_state: Res<HoverState>,
-    drag_state: Res<DragState>,
-    edge_waves: Res<EdgeWaves>,
     mut materials: ResMut<Assets<SdfSceneMaterial>>,
     scene_handle: Res<SceneMaterialHandle>,
 ) {
-    let Some(material) = materials.get_mut(&scene_handle.0) else {
+    let Some(current) = materials.get(&scene_handle.0) else {
         return;
     };
 
-    // Update all sphere positions and visuals
+    // Work on a copy so the material is only borrowed mutably if something is dirty
+    let mut spheres = current.data.spheres;
+
+    // Only nodes whose inputs changed (every node when the valences may have)
     for (graph_node, physics, visual) in &nodes {
-        let sphere = &mut material.data.spheres[graph_node.node_id.index()];
+        if !session.is_changed() && !physics.is_changed() && !visual.is_changed() {
+            continue;
+        }
+
+        let sphere = &mut spheres[graph_node.node_id.index()];
 
-        // Update position from physics
-        sphere.center = physics.position;
+        // Update position from physics (interpolated between ticks)
+        sphere.center = physics.render_position;
 
         sphere.color = visual.current_color;
 
         // Update visual effects
         sphere.ripple_phase = visual.ripple_phase;
         sphere.ripple_amplitude = visual.ripple_amplitude;
-        sphere.spike_amount = visual.glow; // Repurpose spike_amount for glow effect
-        
+        sphere.glow = visual.glow;
+        sphere.spike = visual.spike;
+
         // Update digit value from current valence
         let valence = session.current_valences().get(graph_node.node_id);
         sphere.digit_value = valence as u32;
@@ -65,89 +171,361 @@ pub fn update_sdf_scene(
         }
     }
 
-    // Update edge cylinders
+    // Dirty flags: compare against what the GPU already has
+    let dirty: Vec<usize> = (0..spheres.len())
+        .filter(|&i| spheres[i] != current.data.spheres[i])
+        .collect();
+    if dirty.is_empty() {
+        return;
+    }
+
+    let Some(material) = materials.get_mut(&scene_handle.0) else {
+        return;
+    };
+    for i in dirty {
+        material.data.spheres[i] = spheres[i];
+    }
+}
+
+/// The per-edge animation state the cylinders are built from
+#[derive(SystemParam)]
+pub struct EdgeEffects<'w> {
+    waves: Res<'w, EdgeWaves>,
+    intro: Res<'w, EdgeIntro>,
+    dying: Res<'w, DyingEdges>,
+    tension: Res<'w, EdgeTension>,
+}
+
+/// System: Sync the trail's edges and the dying edges into the scene's cylinders
+///
+/// While dragging, the slot after the trail is held for the preview (tucked into the
+/// trail head until `sync_sdf_preview` stretches it out), so it keeps priority over
+/// dying edges.
+pub fn sync_sdf_edges(
+    nodes: Query<(&GraphNode, &NodePhysics, &NodeVisual)>,
+    session: Res<PuzzleSession>,
+    drag_state: Res<DragState>,
+    effects: EdgeEffects,
+    mut slots: ResMut<CylinderSlots>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+) {
+    let Some(current) = materials.get(&scene_handle.0) else {
+        return;
+    };
+
+    let mut cylinders = current.data.cylinders;
+    let lookup = node_lookup(&nodes);
+
     let edges = session.edges();
-    let mut cylinder_count = edges.len();
-
-    for (i, edge) in edges.edges_in_order().iter().enumerate().take(16) {
-        // Save room for preview
-        // Find positions and colors of connected nodes
-        let start_data = nodes
-            .iter()
-            .find(|(node, _, _)| node.node_id == edge.from)
-            .map(|(_, physics, visual)| (physics.position, visual.current_color));
-
-        let end_data = nodes
-            .iter()
-            .find(|(node, _, _)| node.node_id == edge.to)
-            .map(|(_, physics, visual)| (physics.position, visual.current_color));
-
-        if let (Some((start, start_color)), Some((end, end_color))) = (start_data, end_data) {
+    let trail = session.current_trail();
+    let mut cylinder_count = edges.len().min(EDGE_SLOTS);
+
+    for (i, edge) in edges.edges_in_order().iter().enumerate().take(EDGE_SLOTS) {
+        // Positions and colors of connected nodes
+        if let (Some((start, start_color)), Some((end, end_color))) =
+            (lookup.get(edge.from), lookup.get(edge.to))
+        {
             // Blend the two node colors for a gradient effect
             let blended_color = (start_color + end_color) * 0.5;
 
-            // Find active wave for this edge
-            let mut wave_phase = -1.0; // -1.0 = no wave
-            let mut wave_amplitude = 0.0;
-
-            for wave in &edge_waves.waves {
-                if wave.from == edge.from && wave.to == edge.to {
-                    // Calculate wave position (0.0 to 1.0 along edge)
-                    wave_phase = if wave.direction < 0.5 {
-                        wave.progress // from→to
-                    } else {
-                        1.0 - wave.progress // to→from
-                    };
-                    wave_amplitude = wave.amplitude;
-                    break;
+            // Orient the cylinder in draw order so it grows out of the node the trail came from
+            // (edge i of the trail always runs trail[i] → trail[i + 1])
+            let drawn_backwards = trail.get(i) == Some(&edge.to);
+            let (start, end, node_a, node_b) = if drawn_backwards {
+                (end, start, edge.to, edge.from)
+            } else {
+                (start, end, edge.from, edge.to)
+            };
+
+            // Collect active waves for this edge (oldest first, so rapid clicks stack up)
+            let mut wave_phases = NO_WAVES;
+            let mut wave_amplitudes = Vec4::ZERO;
+
+            let edge_wave_iter = effects
+                .waves
+                .waves
+                .iter()
+                .filter(|wave| wave.from == edge.from && wave.to == edge.to)
+                .take(MAX_EDGE_WAVES);
+
+            for (slot, wave) in edge_wave_iter.enumerate() {
+                // Calculate wave position (0.0 to 1.0 along edge from→to)
+                let mut phase = if wave.direction < 0.5 {
+                    wave.progress // from→to
+                } else {
+                    1.0 - wave.progress // to→from
+                };
+                if drawn_backwards {
+                    phase = 1.0 - phase;
                 }
+                wave_phases[slot] = phase;
+                wave_amplitudes[slot] = wave.amplitude;
             }
 
-            material.data.cylinders[i] = SdfCylinder {
-                start,
-                _padding1: 0.0,
-                end,
-                radius: 0.08,                   // Thin connecting edges
-                color: blended_color,           // Gradient blend of connected nodes
-                node_a_idx: edge.from.0 as u32, // Track which nodes this connects
-                node_b_idx: edge.to.0 as u32,
-                wave_phase,     // Wave position
-                wave_amplitude, // Wave strength
+            // Thin connecting edges, colored with a gradient blend of their nodes
+            cylinders[i] = SdfCylinder {
+                node_a_idx: node_a.0 as u32, // Track which nodes this connects
+                node_b_idx: node_b.0 as u32,
+                wave_phases,     // Wave positions
+                wave_amplitudes, // Wave strengths
+                blend: effects.intro.blend_factor(edge),
+                grow_progress: effects.intro.grow_progress(edge),
+                tension: effects.tension.get(edge),
+                order_t: draw_order_t(i, edges.len()),
+                flow_direction: 1.0, // Already oriented in draw order, so flow runs toward the head
+                ..SdfCylinder::new(start, end, 0.08, blended_color)
             };
         }
     }
 
-    // Add preview cylinder from last node to cursor
-    if drag_state.is_dragging {
-        let trail = session.current_trail();
-        if let Some(&last_node_id) = trail.last() {
-            if let Some(cursor_pos) = hover_state.cursor_world_pos {
-                // Find last node data
-                if let Some((_, physics, visual)) = nodes
-                    .iter()
-                    .find(|(node, _, _)| node.node_id == last_node_id)
-                {
-                    let last_pos = physics.position;
-                    let last_color = visual.current_color;
-
-                    // Create preview cylinder (constant radius, no thick ends)
-                    material.data.cylinders[cylinder_count.min(16)] = SdfCylinder {
-                        start: last_pos,
-                        _padding1: 0.0,
-                        end: cursor_pos,
-                        radius: 0.08, // Same as regular edges
-                        color: last_color * Vec4::new(1.0, 1.0, 1.0, 0.5), // Semi-transparent
-                        node_a_idx: last_node_id.0 as u32,
-                        node_b_idx: last_node_id.0 as u32, // Same = preview (shader detects this)
-                        wave_phase: -1.0,                  // No wave on preview
-                        wave_amplitude: 0.0,
-                    };
-                    cylinder_count += 1;
-                }
-            }
+    // Hold the next slot for the preview, collapsed onto the trail head for now
+    let preview_slot = if drag_state.is_dragging
+        && let Some(&last_node_id) = trail.last()
+        && let Some((last_pos, last_color)) = lookup.get(last_node_id)
+    {
+        cylinders[cylinder_count] =
+            preview_cylinder(last_node_id, last_pos, last_pos, last_color.with_w(0.0));
+        cylinder_count += 1;
+        Some(cylinder_count - 1)
+    } else {
+        None
+    };
+    if slots.preview != preview_slot {
+        slots.preview = preview_slot;
+    }
+
+    // Removed edges melt back into their origin node while there's room left
+    for dying in &effects.dying.edges {
+        if cylinder_count >= MAX_CYLINDERS {
+            break;
         }
+
+        if let (Some((start, start_color)), Some((end, end_color))) =
+            (lookup.get(dying.origin), lookup.get(dying.target))
+        {
+            cylinders[cylinder_count] = SdfCylinder {
+                node_a_idx: dying.origin.0 as u32,
+                node_b_idx: dying.target.0 as u32,
+                grow_progress: dying.remaining(),
+                order_t: 1.0,         // Removed edges were the newest
+                flow_direction: -1.0, // Drains back into the origin node
+                ..SdfCylinder::new(start, end, 0.08, (start_color + end_color) * 0.5)
+            };
+            cylinder_count += 1;
+        }
+    }
+
+    let num_cylinders = cylinder_count as u32;
+
+    // Dirty flags: compare against what the GPU already has
+    let dirty: Vec<usize> = (0..cylinder_count)
+        .filter(|&i| cylinders[i] != current.data.cylinders[i])
+        .collect();
+    if dirty.is_empty() && num_cylinders == current.data.num_cylinders {
+        return;
     }
 
-    material.data.num_cylinders = cylinder_count.min(17) as u32;
+    let Some(material) = materials.get_mut(&scene_handle.0) else {
+        return;
+    };
+    for i in dirty {
+        material.data.cylinders[i] = cylinders[i];
+    }
+    material.data.num_cylinders = num_cylinders;
 }
 
+/// System: Stretch the preview edge from the trail head to the eased endpoint
+pub fn sync_sdf_preview(
+    nodes: Query<(&GraphNode, &NodePhysics, &NodeVisual)>,
+    session: Res<PuzzleSession>,
+    hover_state: Res<HoverState>,
+    preview: Res<PreviewEndpoint>,
+    slots: Res<CylinderSlots>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+) {
+    let (Some(slot), Some(&last_node_id), Some(preview_end)) = (
+        slots.preview,
+        session.current_trail().last(),
+        preview.position,
+    ) else {
+        return;
+    };
+    let Some((_, physics, visual)) = nodes.iter().find(|(n, ..)| n.node_id == last_node_id) else {
+        return;
+    };
+
+    // Tint green/red when hovering a node we'd connect to, plain node color otherwise
+    let preview_color = match hover_state.hovered_node.filter(|&n| n != last_node_id) {
+        Some(target) if session.can_add_node(target).is_ok() => PREVIEW_VALID_COLOR,
+        Some(_) => PREVIEW_INVALID_COLOR,
+        None => visual.current_color.with_w(0.0),
+    };
+    let cylinder = preview_cylinder(
+        last_node_id,
+        physics.render_position,
+        preview_end,
+        preview_color,
+    );
+
+    if materials
+        .get(&scene_handle.0)
+        .is_none_or(|current| current.data.cylinders[slot] == cylinder)
+    {
+        return;
+    }
+    if let Some(material) = materials.get_mut(&scene_handle.0) {
+        material.data.cylinders[slot] = cylinder;
+    }
+}
+
+/// Position and color of every node, for looking up edge endpoints
+fn node_lookup(nodes: &Query<(&GraphNode, &NodePhysics, &NodeVisual)>) -> NodeLookup {
+    NodeLookup::build(nodes.iter().map(|(graph_node, physics, visual)| {
+        (
+            graph_node.node_id,
+            physics.render_position,
+            visual.current_color,
+        )
+    }))
+}
+
+/// Preview cylinder from the trail head (constant radius, no thick ends)
+fn preview_cylinder(node: NodeId, start: Vec3, end: Vec3, color: Vec4) -> SdfCylinder {
+    SdfCylinder {
+        node_a_idx: node.0 as u32,
+        node_b_idx: node.0 as u32, // Same = preview (shader detects this)
+        order_t: 1.0,              // Preview is always the newest
+        flow_direction: 1.0,       // Keeps flowing out toward the cursor
+        ..SdfCylinder::new(start, end, 0.08, color)  // Same radius as regular edges
+    }
+}
+
+/// Where edge `index` sits in draw order, 0.0 = first edge, 1.0 = most recent
+fn draw_order_t(index: usize, edge_count: usize) -> f32 {
+    if edge_count <= 1 {
+        return 1.0; // A lone edge is the most recent one
+    }
+    index as f32 / (edge_count - 1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Valences;
+
+    /// A 3×3 board with the first edge of a trail drawn, ready to drag from node 8
+    fn scene_world() -> (World, Schedule) {
+        let mut world = World::new();
+
+        let mut session = PuzzleSession::new(Valences::from_array([0, 0, 0, 0, 0, 0, 0, 1, 1]), 1);
+        let _ = session.add_node(NodeId(7));
+        let _ = session.add_node(NodeId(8));
+
+        let mut materials = Assets::<SdfSceneMaterial>::default();
+        let handle = materials.add(SdfSceneMaterial::default());
+
+        world.insert_resource(session);
+        world.insert_resource(materials);
+        world.insert_resource(SceneMaterialHandle(handle));
+        world.init_resource::<HoverState>();
+        world.init_resource::<DragState>();
+        world.init_resource::<PreviewEndpoint>();
+        world.init_resource::<EdgeWaves>();
+        world.init_resource::<EdgeIntro>();
+        world.init_resource::<DyingEdges>();
+        world.init_resource::<EdgeTension>();
+        world.init_resource::<CylinderSlots>();
+
+        for index in 0..9 {
+            let rest = Vec3::new((index % 3) as f32, (index / 3) as f32, 0.0);
+            world.spawn((
+                GraphNode {
+                    node_id: NodeId(index),
+                },
+                NodePhysics::at_rest(rest),
+                NodeVisual::default(),
+            ));
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(sdf_sync_systems());
+        (world, schedule)
+    }
+
+    fn scene(world: &World) -> &SdfSceneMaterial {
+        let handle = &world.resource::<SceneMaterialHandle>().0;
+        world
+            .resource::<Assets<SdfSceneMaterial>>()
+            .get(handle)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sync_writes_spheres_and_edges() {
+        let (mut world, mut schedule) = scene_world();
+        schedule.run(&mut world);
+
+        let scene = scene(&world);
+        assert_eq!(scene.data.spheres[4].center, Vec3::new(1.0, 1.0, 0.0));
+        assert_eq!(scene.data.num_cylinders, 1);
+        assert_eq!(scene.data.cylinders[0].node_a_idx, 7); // Drawn from the trail's start
+        assert_eq!(scene.data.cylinders[0].node_b_idx, 8);
+    }
+
+    #[test]
+    fn test_preview_slot_follows_drag() {
+        let (mut world, mut schedule) = scene_world();
+        schedule.run(&mut world);
+
+        world.resource_mut::<DragState>().is_dragging = true;
+        world.resource_mut::<PreviewEndpoint>().position = Some(Vec3::new(5.0, 5.0, 0.0));
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<CylinderSlots>().preview, Some(1));
+        let preview = scene(&world).data.cylinders[1];
+        assert_eq!(scene(&world).data.num_cylinders, 2);
+        assert_eq!(preview.start, Vec3::new(2.0, 2.0, 0.0)); // Trail head, node 8
+        assert_eq!(preview.end, Vec3::new(5.0, 5.0, 0.0));
+        assert_eq!(preview.node_a_idx, preview.node_b_idx);
+
+        // Only the preview moves while the rest of the scene holds still
+        world.resource_mut::<PreviewEndpoint>().position = Some(Vec3::new(6.0, 5.0, 0.0));
+        schedule.run(&mut world);
+        assert_eq!(
+            scene(&world).data.cylinders[1].end,
+            Vec3::new(6.0, 5.0, 0.0)
+        );
+
+        world.resource_mut::<DragState>().is_dragging = false;
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<CylinderSlots>().preview, None);
+        assert_eq!(scene(&world).data.num_cylinders, 1);
+    }
+
+    #[test]
+    fn test_node_lookup_indexes_by_id() {
+        let lookup = NodeLookup::build(
+            [
+                (NodeId(4), Vec3::X, Vec4::ONE),
+                (NodeId(0), Vec3::Y, Vec4::ZERO),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(lookup.get(NodeId(4)), Some((Vec3::X, Vec4::ONE)));
+        assert_eq!(lookup.get(NodeId(0)), Some((Vec3::Y, Vec4::ZERO)));
+        assert_eq!(lookup.get(NodeId(2)), None); // Gap in the ids
+        assert_eq!(lookup.get(NodeId(24)), None); // Past the end (bigger grids)
+    }
+
+    #[test]
+    fn test_draw_order_spans_trail() {
+        assert_eq!(draw_order_t(0, 1), 1.0);
+        assert_eq!(draw_order_t(0, 5), 0.0);
+        assert_eq!(draw_order_t(2, 5), 0.5);
+        assert_eq!(draw_order_t(4, 5), 1.0);
+    }
+}
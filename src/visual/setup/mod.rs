@@ -1,6 +1,7 @@
 pub mod puzzle;
 pub mod scene;
 
-pub use puzzle::{check_level_progression, setup_puzzle};
-pub use scene::{setup_scene, SceneMetrics};
-
+pub use puzzle::{
+    PuzzleDraw, check_level_progression, retry_after_error, setup_puzzle, start_level,
+};
+pub use scene::{SceneMetrics, frame_board, rebuild_board, setup_scene};
@@ -1,54 +1,176 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
 use crate::game::{
-    progression::ProgressionTracker,
+    director::Director,
+    error::AppError,
+    progression::{CompletionPolicy, ProgressionTracker},
     puzzle::PuzzleLibrary,
+    rng::GameRng,
     session::PuzzleSession,
+    tour::LevelTour,
 };
+use crate::graph::Valences;
+use crate::visual::ui::{LevelEnd, LevelSummary};
+
+/// What a level's puzzle is drawn with, besides the library
+#[derive(SystemParam)]
+pub struct PuzzleDraw<'w> {
+    pub rng: ResMut<'w, GameRng>,
+    pub tour: ResMut<'w, LevelTour>,
+    pub director: Res<'w, Director>,
+}
 
 /// System: Setup the puzzle session from the library
 /// This runs after setup_puzzle_library, which loads the CSV data
-pub fn setup_puzzle(mut commands: Commands, library: Res<PuzzleLibrary>) {
-    let tracker = ProgressionTracker::default();
-    let complexity = tracker.current_complexity();
+///
+/// A tracker inserted before Startup (e.g. progress restored from browser storage) picks
+/// the starting level; otherwise play starts at level 1. Without a puzzle for that level
+/// the board starts empty under an `AppError`.
+pub fn setup_puzzle(
+    mut commands: Commands,
+    library: Res<PuzzleLibrary>,
+    mut draw: PuzzleDraw,
+    saved: Option<Res<ProgressionTracker>>,
+) {
+    let tracker = saved
+        .map(|saved| ProgressionTracker::at_level(saved.current_level))
+        .unwrap_or_default();
+
+    let session = match start_level(&library, &tracker, &mut draw.rng, &mut draw.tour) {
+        Ok(session) => session,
+        Err(reason) => {
+            commands.insert_resource(AppError::new(reason));
+            PuzzleSession::new(Valences::zeros(), 0)
+        }
+    };
+
+    commands.insert_resource(tracker);
+    commands.insert_resource(session);
+}
 
-    let config = library
-        .random_puzzle(complexity)
-        .expect("No puzzles available for starting level");
+/// A session on the tracker's level, on a puzzle its tour hasn't served yet
+pub fn start_level(
+    library: &PuzzleLibrary,
+    tracker: &ProgressionTracker,
+    rng: &mut GameRng,
+    tour: &mut LevelTour,
+) -> Result<PuzzleSession, String> {
+    let complexity = tracker.current_complexity();
+    let config = tour
+        .next_puzzle(library, tracker.current_level, complexity, false, rng)
+        .ok_or_else(|| {
+            format!(
+                "No puzzles available for level {} (complexity {})",
+                tracker.current_level, complexity
+            )
+        })?;
 
     info!(
-        "🎮 Level {}: complexity {}, {} solutions expected",
+        "🎮 Level {}: complexity {}, {} solutions expected (seed {})",
         tracker.current_level,
         config.complexity,
-        config.total_solutions
+        config.total_solutions,
+        rng.seed()
     );
 
-    let session = PuzzleSession::new(config.valences, config.total_solutions);
+    Ok(PuzzleSession::new(config.valences, config.total_solutions))
+}
 
-    commands.insert_resource(tracker);
-    commands.insert_resource(session);
+/// System: R reloads the puzzle library after an `AppError` and starts the level again
+pub fn retry_after_error(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut error: ResMut<AppError>,
+    mut library: ResMut<PuzzleLibrary>,
+    tracker: Res<ProgressionTracker>,
+    mut draw: PuzzleDraw,
+    mut session: ResMut<PuzzleSession>,
+) {
+    if !keys.just_pressed(KeyCode::KeyR) {
+        return;
+    }
+
+    let started = library.reload().and_then(|reloaded| {
+        crate::game::puzzle::log_library(&reloaded);
+        *library = reloaded;
+        start_level(&library, &tracker, &mut draw.rng, &mut draw.tour)
+    });
+
+    match started {
+        Ok(started) => {
+            info!("✓ Recovered, back to play");
+            *session = started;
+            commands.remove_resource::<AppError>();
+        }
+        Err(reason) => *error = AppError::new(reason),
+    }
 }
 
 /// System: Check for level completion and advance to next level
 /// This should run in the Update schedule
+///
+/// A finished level's summary goes up first (solo play only); the next level starts once
+/// it's dismissed.
 pub fn check_level_progression(
     mut commands: Commands,
     session: Res<PuzzleSession>,
     mut tracker: ResMut<ProgressionTracker>,
     library: Res<PuzzleLibrary>,
+    mut draw: PuzzleDraw,
+    policy: Res<CompletionPolicy>,
+    level_end: LevelEnd,
 ) {
-    // Only check when the session has changed (e.g., new solution found)
-    if !session.is_changed() {
+    // Only check when the session has changed (e.g., new solution found), the policy
+    // was switched or the summary is over
+    let summary_done = level_end.dismissed();
+    if !session.is_changed() && !policy.is_changed() && !summary_done {
         return;
     }
 
-    // Check if ALL solutions have been found for this puzzle
+    // Check if enough solutions have been found for this puzzle
     let progress = session.progress();
-    if !progress.is_complete() {
+    if !policy.is_met(&progress) {
+        if summary_done {
+            // The board was swapped out from under the summary (e.g. a friend's code)
+            commands.remove_resource::<LevelSummary>();
+        }
+        return;
+    }
+    if level_end.showing() {
         return;
     }
 
-    info!("🎉 Level {} complete! All solutions found!", tracker.current_level);
+    if !summary_done {
+        info!(
+            "🎉 Level {} complete! {} found",
+            tracker.current_level,
+            progress.display_string()
+        );
+
+        let par = LevelSummary::par_for(session.all_solutions().len(), *policy);
+        if let Some(summary) = level_end.summary(par) {
+            info!(
+                "📋 Level {}: {}s, {} attempts (par {}), {} invalid moves",
+                summary.level,
+                summary.seconds,
+                summary.attempts,
+                summary.par,
+                summary.invalid_moves
+            );
+            commands.insert_resource(summary);
+            return;
+        }
+    }
+    commands.remove_resource::<LevelSummary>();
+
+    // A level the player got stuck on is followed by one of the next level's easier puzzles
+    let gentle = draw.director.stuck_on(tracker.current_level);
+    if gentle {
+        info!(
+            "🧭 Easing off after a tough level {}",
+            tracker.current_level
+        );
+    }
 
     tracker.advance_level();
     let complexity = tracker.current_complexity();
@@ -57,7 +179,13 @@ pub fn check_level_progression(
         info!("🏆 You've completed all 217 levels! Starting over...");
     }
 
-    if let Some(config) = library.random_puzzle(complexity) {
+    if let Some(config) = draw.tour.next_puzzle(
+        &library,
+        tracker.current_level,
+        complexity,
+        gentle,
+        &mut *draw.rng,
+    ) {
         info!(
             "🎮 Level {}/{}: complexity {}, {} solutions expected",
             tracker.current_level,
@@ -75,4 +203,3 @@ pub fn check_level_progression(
         );
     }
 }
-
@@ -1,15 +1,16 @@
 use bevy::prelude::*;
 
 use crate::{
-    camera::GameCamera,
-    game::session::PuzzleSession,
-    graph::NodeId,
+    camera::{CameraBounds, GameCamera},
+    game::session::{NewPuzzle, PuzzleSession},
+    graph::{NodeId, Valences},
     visual::{
-        nodes::{GraphNode, NodeVisual, valence_to_color},
+        nodes::{GraphNode, NodeGlow, NodeVisual},
         physics::NodePhysics,
         sdf::material::{DigitUvs, SceneMaterialHandle, SdfSceneMaterial},
         sdf::nodes::ellipsoid::SdfSphere,
         sdf::numbers::DigitAtlas,
+        theme::Theme,
     },
 };
 
@@ -22,6 +23,12 @@ const PLANE_SIZE_SCALE: f32 = 1.5;
 /// Extra spacing divisor so nodes don't touch the region edges
 const SPACING_DENOMINATOR_OFFSET: f32 = 1.0;
 
+/// Default smooth-minimum radius for node/edge blending
+const BLEND_SMOOTHNESS: f32 = 0.15;
+
+/// Room the camera frame leaves around the outermost nodes, in grid spacings
+const FRAME_MARGIN_SPACINGS: f32 = 1.0;
+
 /// Resource to store scene metrics for physics scaling
 #[derive(Resource, Debug, Clone, Copy)]
 pub struct SceneMetrics {
@@ -35,6 +42,97 @@ impl SceneMetrics {
     }
 }
 
+/// Nodes per side of the board (`Valences` always holds a 3x3 grid)
+const GRID_SIZE: usize = 3;
+
+/// Where a board's nodes sit within the camera bounds, and how big they are
+struct BoardLayout {
+    grid_size: usize,
+    spacing: f32,
+    node_radius: f32,
+    /// Center of node 0, the bottom-left corner
+    start: Vec2,
+}
+
+impl BoardLayout {
+    fn new(bounds: &CameraBounds, grid_size: usize) -> Self {
+        let grid_region = bounds.region(0.0, 1.0, 0.0, 1.0, 0.0);
+
+        let available_width = grid_region.width();
+        let available_height = grid_region.height();
+
+        let spacing =
+            available_width.min(available_height) / (grid_size as f32 + SPACING_DENOMINATOR_OFFSET);
+        let node_radius = spacing * NODE_RADIUS_FRACTION_OF_SPACING;
+
+        let grid_width = (grid_size - 1) as f32 * spacing;
+        let grid_height = (grid_size - 1) as f32 * spacing;
+
+        // Center the grid horizontally, and sit it low enough to leave the top for the HUD
+        let start = Vec2::new(
+            grid_region.left + (grid_region.width() - grid_width) * 0.5,
+            grid_region.bottom + (grid_region.height() - grid_height) * 0.15,
+        );
+
+        Self {
+            grid_size,
+            spacing,
+            node_radius,
+            start,
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        self.grid_size * self.grid_size
+    }
+
+    /// Rest position of a node (the board is on the XY plane at z=0)
+    fn node_center(&self, node_id: NodeId) -> Vec3 {
+        let row = node_id.index() / self.grid_size;
+        let col = node_id.index() % self.grid_size;
+        (self.start + Vec2::new(col as f32, row as f32) * self.spacing).extend(0.0)
+    }
+}
+
+/// Spawn a node entity per grid cell at rest, and write its sphere into the material
+fn spawn_nodes(
+    commands: &mut Commands,
+    scene_material: &mut SdfSceneMaterial,
+    layout: &BoardLayout,
+    valences: &Valences,
+    theme: &Theme,
+) {
+    scene_material.data.num_spheres = layout.node_count() as u32;
+
+    for index in 0..layout.node_count() {
+        let node_id = NodeId(index);
+        let valence = valences.get(node_id);
+        let center = layout.node_center(node_id);
+        let color = theme.node_color(valence);
+
+        scene_material.data.spheres[index] = SdfSphere {
+            digit_value: valence as u32,
+            ..SdfSphere::new(center, layout.node_radius, color)
+        };
+
+        // Scale spring stiffness by spacing for resolution-independent physics
+        let mut physics = NodePhysics::at_rest(center);
+        physics.spring_stiffness *= layout.spacing;
+
+        commands.spawn((
+            GraphNode { node_id },
+            physics,
+            NodeVisual {
+                current_color: color,
+                ..default()
+            },
+            NodeGlow::default(),
+        ));
+
+        info!("Node {} at {} - valence: {}", node_id.0, center, valence);
+    }
+}
+
 pub fn setup_scene(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -42,35 +140,19 @@ pub fn setup_scene(
     asset_server: Res<AssetServer>,
     game_camera: Res<GameCamera>,
     session: Res<PuzzleSession>,
+    theme: Res<Theme>,
 ) {
     let grid_region = game_camera.bounds.region(0.0, 1.0, 0.0, 1.0, 0.0);
-
-    let grid_size = 3;
-    let grid_node_count = grid_size * grid_size;
-    let node_id_row_stride = grid_size;
-
-    let available_width = grid_region.width();
-    let available_height = grid_region.height();
-
-    let spacing =
-        available_width.min(available_height) / (grid_size as f32 + SPACING_DENOMINATOR_OFFSET);
-    let node_radius = spacing * NODE_RADIUS_FRACTION_OF_SPACING;
-
-    let grid_width = (grid_size - 1) as f32 * spacing;
-    let grid_height = (grid_size - 1) as f32 * spacing;
-
-    // Center the grid both horizontally and vertically
-    let start_x = grid_region.left + (grid_region.width() - grid_width) * 0.5;
-    let start_y = grid_region.bottom + (grid_region.height() - grid_height) * 0.15;
+    let layout = BoardLayout::new(&game_camera.bounds, GRID_SIZE);
 
     info!(
         "Scene setup: spacing={}, node_radius={}",
-        spacing, node_radius
+        layout.spacing, layout.node_radius
     );
     info!("Grid region: {:?}", grid_region);
 
     // Store scene metrics as a resource for physics scaling
-    commands.insert_resource(SceneMetrics::new(spacing));
+    commands.insert_resource(SceneMetrics::new(layout.spacing));
 
     let plane_size = grid_region.width().max(grid_region.height()) * PLANE_SIZE_SCALE;
     let plane_mesh = meshes.add(Plane3d::default().mesh().size(plane_size, plane_size));
@@ -81,62 +163,20 @@ pub fn setup_scene(
     };
 
     let mut scene_material = SdfSceneMaterial::default();
-    scene_material.data.num_spheres = grid_node_count as u32;
+    scene_material.data.blend_smoothness = BLEND_SMOOTHNESS;
     scene_material.digit_atlas = digit_atlas.texture.clone();
     scene_material.digit_uvs = digit_uvs;
+    scene_material.theme = theme.to_uniform();
 
     commands.insert_resource(digit_atlas);
 
-    let valences = session.current_valences();
-
-    for row in 0..grid_size {
-        for col in 0..grid_size {
-            let node_id = NodeId(row * node_id_row_stride + col);
-            let valence = valences.get(node_id);
-
-            let center = Vec3::new(
-                start_x + col as f32 * spacing,
-                start_y + row as f32 * spacing,
-                0.0, // Board is on XY plane at z=0
-            );
-
-            let color = valence_to_color(valence);
-
-            scene_material.data.spheres[node_id.index()] = SdfSphere {
-                center,
-                radius: node_radius,
-                color,
-                stretch_direction: Vec3::Y,
-                stretch_factor: 1.0,
-                ripple_phase: 0.0,
-                ripple_amplitude: 0.0,
-                spike_amount: 0.0,
-                digit_value: valence as u32,
-            };
-
-            // Scale spring stiffness by spacing for resolution-independent physics
-            let mut physics = NodePhysics {
-                position: center,
-                rest_position: center,
-                ..default()
-            };
-            physics.spring_stiffness *= spacing;
-
-            commands.spawn((
-                GraphNode { node_id },
-                physics,
-                NodeVisual {
-                    current_color: color,
-                    ..default()
-                },
-            ));
-
-            info!(
-                "Node {} at ({}, {}) - valence: {}",
-                node_id.0, row, col, valence
-            );
-        }
-    }
+    spawn_nodes(
+        &mut commands,
+        &mut scene_material,
+        &layout,
+        session.current_valences(),
+        &theme,
+    );
 
     let material_handle = materials.add(scene_material);
     commands.insert_resource(SceneMaterialHandle(material_handle.clone()));
@@ -154,3 +194,80 @@ pub fn setup_scene(
 
     info!("Unified SDF scene created!");
 }
+
+/// System: Swap the board over to a new puzzle: the old node entities go, the material's
+/// spheres are rewritten and its cylinders cleared, and fresh nodes spawn at rest with
+/// the new valences. The layout is worked out again, so a different grid size fits too
+pub fn rebuild_board(
+    mut commands: Commands,
+    mut puzzles: MessageReader<NewPuzzle>,
+    nodes: Query<Entity, With<GraphNode>>,
+    scene_handle: Option<Res<SceneMaterialHandle>>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    game_camera: Res<GameCamera>,
+    theme: Res<Theme>,
+) {
+    // Only the latest puzzle matters if several landed since the last frame
+    let Some(puzzle) = puzzles.read().last() else {
+        return;
+    };
+    let Some(scene_handle) = scene_handle else {
+        return;
+    };
+    let Some(scene_material) = materials.get_mut(&scene_handle.0) else {
+        return;
+    };
+
+    for entity in &nodes {
+        commands.entity(entity).despawn();
+    }
+
+    let layout = BoardLayout::new(&game_camera.bounds, GRID_SIZE);
+    scene_material.data.spheres = default();
+    scene_material.data.num_cylinders = 0;
+    spawn_nodes(
+        &mut commands,
+        scene_material,
+        &layout,
+        &puzzle.valences,
+        &theme,
+    );
+    commands.insert_resource(SceneMetrics::new(layout.spacing));
+
+    info!("Board rebuilt: {} nodes", layout.node_count());
+}
+
+/// System: Frame the camera on the nodes' rest positions, easing over whenever the layout
+/// changes (the first layout is framed straight away)
+pub fn frame_board(
+    nodes: Query<&NodePhysics, With<GraphNode>>,
+    metrics: Option<Res<SceneMetrics>>,
+    mut game_camera: ResMut<GameCamera>,
+    mut framed: Local<Option<Rect>>,
+) {
+    let Some(metrics) = metrics else {
+        return;
+    };
+    let Some(extents) = nodes
+        .iter()
+        .map(|physics| physics.rest_position.truncate())
+        .fold(None, |extents: Option<Rect>, point| {
+            Some(extents.map_or(Rect::from_corners(point, point), |rect| {
+                rect.union_point(point)
+            }))
+        })
+    else {
+        return;
+    };
+    if *framed == Some(extents) {
+        return;
+    }
+
+    let target = CameraBounds::framing(extents, metrics.spacing * FRAME_MARGIN_SPACINGS);
+    if framed.is_some() {
+        game_camera.frame_to(target);
+    } else {
+        game_camera.snap_frame_to(target);
+    }
+    *framed = Some(extents);
+}
@@ -0,0 +1,218 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::ShaderType;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::session::PuzzleSession,
+    visual::{
+        nodes::{GraphNode, NodeVisual, valence_to_color},
+        sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
+    },
+};
+
+/// Built-in looks the player can switch between
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    Neon,
+}
+
+impl ThemePreset {
+    /// The preset after this one (wraps around)
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreset::Dark => ThemePreset::Light,
+            ThemePreset::Light => ThemePreset::Neon,
+            ThemePreset::Neon => ThemePreset::Dark,
+        }
+    }
+}
+
+/// Active color scheme and lighting for the whole scene
+#[derive(Resource, Debug, Clone)]
+pub struct Theme {
+    pub preset: ThemePreset,
+    /// Clear color behind the SDF plane
+    pub background: Color,
+    /// Tint blended over the animated environment (w = blend amount)
+    pub background_tint: Vec4,
+    /// Node color for each valence 0-8
+    pub node_palette: [Vec4; 9],
+    /// Tint blended into edges (w = blend amount)
+    pub edge_tint: Vec4,
+    /// Multiplier on all additive glow (node flashes, rim light)
+    pub glow_intensity: f32,
+    /// Direction the key light shines from
+    pub light_direction: Vec3,
+    /// Brightness of the unlit side of surfaces
+    pub ambient: f32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_preset(ThemePreset::default())
+    }
+}
+
+impl Theme {
+    pub fn from_preset(preset: ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Dark => Self::dark(),
+            ThemePreset::Light => Self::light(),
+            ThemePreset::Neon => Self::neon(),
+        }
+    }
+
+    /// The original look: dark backdrop, saturated candy nodes
+    pub fn dark() -> Self {
+        Self {
+            preset: ThemePreset::Dark,
+            background: Color::srgb(0.02, 0.03, 0.05),
+            background_tint: Vec4::ZERO,
+            node_palette: std::array::from_fn(valence_to_color),
+            edge_tint: Vec4::ZERO,
+            glow_intensity: 1.0,
+            light_direction: Vec3::new(1.0, 1.0, 1.0),
+            ambient: 0.6,
+        }
+    }
+
+    /// Paper-like backdrop with deeper, ink-like node colors
+    pub fn light() -> Self {
+        Self {
+            preset: ThemePreset::Light,
+            background: Color::srgb(0.90, 0.91, 0.93),
+            background_tint: Vec4::new(0.86, 0.88, 0.91, 0.85),
+            node_palette: [
+                Vec4::new(0.55, 0.56, 0.60, 1.0), // Gray
+                Vec4::new(0.10, 0.65, 0.25, 1.0), // Green
+                Vec4::new(0.85, 0.65, 0.05, 1.0), // Amber
+                Vec4::new(0.15, 0.40, 0.85, 1.0), // Blue
+                Vec4::new(0.80, 0.12, 0.12, 1.0), // Red
+                Vec4::new(0.65, 0.20, 0.70, 1.0), // Magenta
+                Vec4::new(0.35, 0.35, 0.38, 1.0), // Charcoal (white would vanish)
+                Vec4::new(0.90, 0.45, 0.10, 1.0), // Orange
+                Vec4::new(0.45, 0.30, 0.85, 1.0), // Purple
+            ],
+            edge_tint: Vec4::new(0.2, 0.22, 0.28, 0.25),
+            glow_intensity: 0.5,
+            light_direction: Vec3::new(-0.5, 1.0, 1.0),
+            ambient: 0.8,
+        }
+    }
+
+    /// Black backdrop, electric colors, heavy glow
+    pub fn neon() -> Self {
+        Self {
+            preset: ThemePreset::Neon,
+            background: Color::BLACK,
+            background_tint: Vec4::new(0.02, 0.0, 0.05, 0.5),
+            node_palette: [
+                Vec4::new(0.20, 0.20, 0.30, 1.0), // Dim violet
+                Vec4::new(0.0, 1.0, 0.55, 1.0),   // Mint
+                Vec4::new(1.0, 1.0, 0.0, 1.0),    // Electric yellow
+                Vec4::new(0.0, 0.85, 1.0, 1.0),   // Cyan
+                Vec4::new(1.0, 0.0, 0.40, 1.0),   // Hot pink
+                Vec4::new(0.85, 0.0, 1.0, 1.0),   // Violet
+                Vec4::new(1.0, 1.0, 1.0, 1.0),    // White
+                Vec4::new(1.0, 0.45, 0.0, 1.0),   // Neon orange
+                Vec4::new(0.45, 0.25, 1.0, 1.0),  // Ultraviolet
+            ],
+            edge_tint: Vec4::new(0.0, 0.9, 1.0, 0.3),
+            glow_intensity: 1.8,
+            light_direction: Vec3::new(0.0, 1.0, 1.0),
+            ambient: 0.45,
+        }
+    }
+
+    /// Node color for a valence in this theme
+    pub fn node_color(&self, valence: usize) -> Vec4 {
+        self.node_palette[valence]
+    }
+
+    /// Shader-facing part of the theme
+    pub fn to_uniform(&self) -> SdfThemeUniform {
+        SdfThemeUniform {
+            light_direction: self.light_direction.normalize_or(Vec3::Z),
+            glow_intensity: self.glow_intensity,
+            background_tint: self.background_tint,
+            edge_tint: self.edge_tint,
+            ambient: self.ambient,
+        }
+    }
+}
+
+/// Theme parameters used by the scene shader
+#[derive(ShaderType, Debug, Clone)]
+pub struct SdfThemeUniform {
+    pub light_direction: Vec3,
+    pub glow_intensity: f32,
+    pub background_tint: Vec4,
+    pub edge_tint: Vec4,
+    pub ambient: f32,
+}
+
+impl Default for SdfThemeUniform {
+    fn default() -> Self {
+        Theme::default().to_uniform()
+    }
+}
+
+/// System: Cycle through theme presets with the T key
+pub fn cycle_theme(keys: Res<ButtonInput<KeyCode>>, mut theme: ResMut<Theme>) {
+    if keys.just_pressed(KeyCode::KeyT) {
+        *theme = Theme::from_preset(theme.preset.next());
+        info!("🎨 Theme: {:?}", theme.preset);
+    }
+}
+
+/// System: Push theme changes into the clear color, scene uniform and node colors
+pub fn apply_theme(
+    theme: Res<Theme>,
+    session: Res<PuzzleSession>,
+    mut clear_color: ResMut<ClearColor>,
+    mut materials: ResMut<Assets<SdfSceneMaterial>>,
+    scene_handle: Res<SceneMaterialHandle>,
+    mut nodes: Query<(&GraphNode, &mut NodeVisual)>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+
+    clear_color.0 = theme.background;
+
+    if let Some(material) = materials.get_mut(&scene_handle.0) {
+        material.theme = theme.to_uniform();
+    }
+
+    // Snap nodes to the new palette so the switch reads as instant
+    let valences = session.current_valences();
+    for (graph_node, mut visual) in &mut nodes {
+        visual.current_color = theme.node_color(valences.get(graph_node.node_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dark_matches_original_palette() {
+        let theme = Theme::dark();
+        for valence in 0..9 {
+            assert_eq!(theme.node_color(valence), valence_to_color(valence));
+        }
+    }
+
+    #[test]
+    fn test_presets_cycle_back() {
+        let start = ThemePreset::Dark;
+        assert_eq!(start.next().next().next(), start);
+        assert_eq!(
+            Theme::from_preset(ThemePreset::Neon).preset,
+            ThemePreset::Neon
+        );
+    }
+}
@@ -1,16 +1,50 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::RngCore;
 
 use crate::{
-    camera::{CameraBounds, GameCamera},
-    game::{progression::ProgressionTracker, session::PuzzleSession},
+    camera::GameCamera,
+    game::{
+        error::AppError, hints::HintWallet, progression::ProgressionTracker, rng::GameRng,
+        session::PuzzleSession, skip::LevelPuzzles,
+    },
+    leaderboard::LeaderboardView,
+    net::RaceState,
+    score::Score,
+    settings::{Settings, TransitionStyle},
+    visual::interactions::Combo,
     visual::sdf::seven_segment::{Digit, HudInstance, MAX_HUD_INSTANCES, SevenSegmentMaterial},
+    visual::utils::Easing,
 };
 
 use super::{
     hud_builder::build_instances_for_group,
-    number_group::{HudStyle, level_group, progress_group},
+    locale::Locale,
+    number_group::{
+        HudGroup, HudStyle, combo_group, error_groups, hint_tokens_group, level_group,
+        opponent_group, progress_group, remaining_edges_group, score_group, skipped_group,
+    },
+    profile_select::ProfileSelect,
+    summary::LevelSummary,
+    toast::{TOAST_SLOTS, ToastQueue},
 };
 
+/// Level-summary and profile-select digits are bigger than the corner counters
+const SUMMARY_STYLE: HudStyle = HudStyle {
+    digit_scale: 0.4,
+    digit_spacing: 0.5,
+    slash_spacing: 0.0,
+};
+
+/// Leaderboard rows are smaller, so four columns fit across
+const LEADERBOARD_STYLE: HudStyle = HudStyle {
+    digit_scale: 0.18,
+    digit_spacing: 0.5,
+    slash_spacing: 0.0,
+};
+
+/// Instance slots the counters and overlays share (the rest are the toasts')
+const HUD_BUDGET: usize = MAX_HUD_INSTANCES - TOAST_SLOTS;
+
 /// Resource to store the handle to the HUD material
 #[derive(Resource)]
 pub struct HudMaterialHandle(pub Handle<SevenSegmentMaterial>);
@@ -22,6 +56,8 @@ pub struct HudTransitionState {
     pub prev_instances: Vec<HudInstance>,
     /// Transition duration in seconds
     pub transition_duration: f32,
+    /// Groups hidden last frame for want of instance slots (warned about once)
+    pub evicted: usize,
 }
 
 impl Default for HudTransitionState {
@@ -29,6 +65,7 @@ impl Default for HudTransitionState {
         Self {
             prev_instances: Vec::new(),
             transition_duration: 0.8, // 800ms transitions
+            evicted: 0,
         }
     }
 }
@@ -36,14 +73,27 @@ impl Default for HudTransitionState {
 /// Categorizes the type of transition occurring in the HUD
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TransitionType {
-    /// No changes detected
-    None,
     /// Level advanced (only animate increasing digits, skip found counter reset)
     LevelAdvance,
     /// Progress changed within same level (animate all changed digits)
     ProgressChange,
 }
 
+impl TransitionType {
+    /// Blob easing and how far segments' starts cascade: a slow-landing sweep when
+    /// the level advances, a gentler ripple for progress within it
+    fn feel(self) -> (Easing, f32) {
+        match self {
+            Self::LevelAdvance => (Easing::OutCubic, 0.5),
+            Self::ProgressChange => (Easing::InOutQuad, 0.3),
+        }
+    }
+}
+
+/// Marker for the plane the HUD is drawn on (hidden in photo mode)
+#[derive(Component)]
+pub struct HudPlane;
+
 /// Spawn the unified HUD plane
 pub fn spawn_hud(
     mut commands: Commands,
@@ -73,27 +123,165 @@ pub fn spawn_hud(
         Transform::from_xyz(cx, cy, 0.5)
             .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
         Name::new("HUD Plane"),
+        HudPlane,
     ));
 
     info!("✨ Unified HUD plane spawned!");
 }
 
+/// System: Keep the HUD plane over the camera's view as it zooms and pans
+pub fn fit_hud_to_view(
+    game_camera: Res<GameCamera>,
+    mut planes: Query<&mut Transform, With<HudPlane>>,
+) {
+    if !game_camera.is_changed() {
+        return;
+    }
+
+    let center = game_camera.view().center();
+    for mut transform in &mut planes {
+        transform.translation = center.extend(transform.translation.z);
+        transform.scale = Vec3::splat(game_camera.scale);
+    }
+}
+
+/// System: D cycles how the HUD digits flow between values
+pub fn cycle_transition_style(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if !keys.just_pressed(KeyCode::KeyD) {
+        return;
+    }
+    settings.transition_style = settings.transition_style.next();
+    info!("🔢 Digit transitions: {:?}", settings.transition_style);
+}
+
+/// Game state the HUD displays
+#[derive(SystemParam)]
+pub struct HudSources<'w> {
+    tracker: Res<'w, ProgressionTracker>,
+    session: Res<'w, PuzzleSession>,
+    puzzles: Res<'w, LevelPuzzles>,
+    wallet: Res<'w, HintWallet>,
+    combo: Res<'w, Combo>,
+    /// Present with the score plugin
+    score: Option<Res<'w, Score>>,
+    /// Present with the net plugin; shown only while racing an opponent
+    race: Option<Res<'w, RaceState>>,
+    /// Present between levels
+    summary: Option<Res<'w, LevelSummary>>,
+    /// Present while a profile is being picked
+    profile_select: Option<Res<'w, ProfileSelect>>,
+    /// Present while the leaderboard is open
+    leaderboard: Option<Res<'w, LeaderboardView>>,
+    /// Present while something has failed (the error screen)
+    error: Option<Res<'w, AppError>>,
+    toasts: Res<'w, ToastQueue>,
+    locale: Res<'w, Locale>,
+}
+
+/// How new digit transitions flow: the player's style, and the seeds organic flows draw
+#[derive(SystemParam)]
+pub struct HudFlow<'w> {
+    settings: Res<'w, Settings>,
+    rng: ResMut<'w, GameRng>,
+}
+
 /// Update the HUD material with current game state and animate transitions
 pub fn update_hud(
-    time: Res<Time>,
-    tracker: Res<ProgressionTracker>,
-    session: Res<PuzzleSession>,
+    time: Res<Time<Real>>, // Keeps animating while gameplay is paused
+    sources: HudSources,
     game_camera: Res<GameCamera>,
     hud_handle: Res<HudMaterialHandle>,
+    mut flow: HudFlow,
     mut transition_state: ResMut<HudTransitionState>,
     mut materials: ResMut<Assets<SevenSegmentMaterial>>,
 ) {
     let Some(material) = materials.get_mut(&hud_handle.0) else {
         return;
     };
+    let HudSources {
+        tracker,
+        session,
+        puzzles,
+        wallet,
+        combo,
+        score,
+        race,
+        summary,
+        profile_select,
+        leaderboard,
+        error,
+        toasts,
+        locale,
+    } = sources;
 
     // 1. Build current instances from game state
-    let current_instances = build_current_instances(&game_camera.bounds, &tracker, &session);
+    let opponent_found = race
+        .as_ref()
+        .and_then(|race| race.opponent_found(tracker.current_level));
+    let view = game_camera.view();
+    let mut groups = corner_groups(
+        &tracker,
+        &session,
+        &locale,
+        SideCounts {
+            skipped: puzzles.skipped_count(tracker.current_level),
+            hint_tokens: wallet.tokens as usize,
+            multiplier: combo.multiplier() as usize,
+            score: score.map(|score| score.shown() as usize),
+            opponent_found,
+        },
+    );
+
+    // The level summary's tallies count up in their own rows (flowing like any digit)
+    // (grouped for the locale, but centered or tabled, so never mirrored)
+    let overlay = |group: HudGroup, style| {
+        let tokens = locale.format(group.tokens);
+        PlacedGroup::new(HudGroup { tokens, ..group }, style, HudPriority::Overlay)
+    };
+    if let Some(summary) = summary.filter(|summary| !summary.dismissed) {
+        let elapsed = time.elapsed_secs() - summary.shown_at;
+        groups.extend(
+            summary
+                .groups(elapsed)
+                .into_iter()
+                .map(|group| overlay(group, SUMMARY_STYLE)),
+        );
+    }
+    if let Some(select) = &profile_select {
+        groups.push(overlay(select.group(), SUMMARY_STYLE));
+    }
+    if let Some(leaderboard) = &leaderboard {
+        groups.extend(
+            leaderboard
+                .groups()
+                .into_iter()
+                .map(|group| overlay(group, LEADERBOARD_STYLE)),
+        );
+    }
+
+    if let Some(error) = &error {
+        groups.extend(
+            error_groups(&error.reason)
+                .into_iter()
+                .map(|group| overlay(group, LEADERBOARD_STYLE)),
+        );
+    }
+
+    // Whatever doesn't fit gives way by priority, whole groups at a time
+    let dropped = evict_to_fit(&mut groups, HUD_BUDGET);
+    if dropped != transition_state.evicted {
+        if dropped > 0 {
+            warn!(
+                "🔢 HUD over its {} instance slots: {} low-priority group(s) hidden",
+                HUD_BUDGET, dropped
+            );
+        }
+        transition_state.evicted = dropped;
+    }
+    let mut current_instances = Vec::new();
+    for placed in &groups {
+        build_instances_for_group(&view, &placed.group, placed.style, &mut current_instances);
+    }
 
     // 2. Detect transition type (level advance vs normal progress)
     let progress = session.progress();
@@ -105,7 +293,7 @@ pub fn update_hud(
     };
 
     // 3. Apply transitions to instances (each digit computes its own flows in shader)
-    let animated_instances = apply_transitions(
+    let mut animated_instances = apply_transitions(
         current_instances,
         &transition_state.prev_instances,
         transition_type,
@@ -113,8 +301,36 @@ pub fn update_hud(
         &transition_state,
     );
 
+    // Transitions that just started take their type's feel (and a fresh organic seed)
+    let (easing, stagger) = transition_type.feel();
+    for inst in animated_instances
+        .iter_mut()
+        .filter(|inst| inst.kind == 0 && inst.transition_progress == 0.0)
+    {
+        inst.easing = easing.shader_value();
+        inst.stagger = stagger;
+        if flow.settings.transition_style == TransitionStyle::Organic {
+            inst.flow_seed = flow.rng.next_u32();
+        }
+    }
+
+    // The race pip pulses on its own clock, whenever the opponent scores
+    if let Some(race) = &race {
+        let pulse = race.pulse(time.elapsed_secs());
+        for inst in animated_instances.iter_mut().filter(|inst| inst.kind == 1) {
+            inst.transition_progress = pulse;
+        }
+    }
+
     // 4. Update material
-    update_material(material, &animated_instances, time.elapsed_secs());
+    let toast_instances = toasts.instances(&view);
+    update_material(
+        material,
+        &animated_instances,
+        &toast_instances,
+        time.elapsed_secs(),
+    );
+    material.data.transition_style = flow.settings.transition_style.shader_value();
 
     // 5. Store for next frame
     transition_state.prev_instances = animated_instances;
@@ -131,28 +347,101 @@ pub fn update_hud(
     }
 }
 
-/// Build HUD instances from current game state
-fn build_current_instances(
-    bounds: &CameraBounds,
+/// The smaller counts around the level and progress, each shown only when it applies
+struct SideCounts {
+    skipped: usize,
+    hint_tokens: usize,
+    /// Combo multiplier (shown from 2 up)
+    multiplier: usize,
+    /// Arcade score, as it reads while rolling up
+    score: Option<usize>,
+    opponent_found: Option<usize>,
+}
+
+/// Which HUD groups give way first when they don't all fit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum HudPriority {
+    /// The side counts around the corners
+    Side,
+    /// The summary, profile select and leaderboard
+    Overlay,
+    /// The level and progress
+    Core,
+}
+
+/// A group with the style it's drawn in and how much it matters
+struct PlacedGroup {
+    group: HudGroup,
+    style: HudStyle,
+    priority: HudPriority,
+}
+
+impl PlacedGroup {
+    fn new(group: HudGroup, style: HudStyle, priority: HudPriority) -> Self {
+        Self {
+            group,
+            style,
+            priority,
+        }
+    }
+}
+
+/// Drop the lowest-priority groups (the latest first among equals) until the rest fit
+/// in `budget` instance slots; returns how many were dropped
+fn evict_to_fit(groups: &mut Vec<PlacedGroup>, budget: usize) -> usize {
+    let mut total: usize = groups.iter().map(|placed| placed.group.slots()).sum();
+    let mut dropped = 0;
+    while total > budget {
+        let Some((index, _)) = groups
+            .iter()
+            .enumerate()
+            .rev()
+            .min_by_key(|(_, placed)| placed.priority)
+        else {
+            break;
+        };
+        total -= groups.remove(index).group.slots();
+        dropped += 1;
+    }
+    dropped
+}
+
+/// The level, progress and side counts, in slot order (on the locale's sides)
+fn corner_groups(
     tracker: &ProgressionTracker,
     session: &PuzzleSession,
-) -> Vec<HudInstance> {
+    locale: &Locale,
+    counts: SideCounts,
+) -> Vec<PlacedGroup> {
+    let SideCounts {
+        skipped,
+        hint_tokens,
+        multiplier,
+        score,
+        opponent_found,
+    } = counts;
     let style = HudStyle::default();
     let progress = session.progress();
 
-    let groups = [
-        level_group(tracker.current_level),
-        progress_group(
+    let core = |group| PlacedGroup::new(locale.place(group), style, HudPriority::Core);
+    let side = |group| PlacedGroup::new(locale.place(group), style, HudPriority::Side);
+
+    let mut groups = vec![
+        core(level_group(tracker.current_level)),
+        core(progress_group(
             progress.solutions_found,
             progress.total_solutions.unwrap_or(0),
-        ),
+        )),
     ];
-
-    let mut instances = Vec::new();
-    for group in &groups {
-        build_instances_for_group(bounds, group, style, &mut instances);
-    }
-    instances
+    // Last, so the groups above keep their instance slots for transitions
+    groups.extend((skipped > 0).then(|| side(skipped_group(skipped))));
+    groups.extend((hint_tokens > 0).then(|| side(hint_tokens_group(hint_tokens))));
+    groups.extend(opponent_found.map(|found| side(opponent_group(found))));
+    groups.extend(score.map(|score| side(score_group(score))));
+    groups.extend((multiplier > 1).then(|| side(combo_group(multiplier))));
+    // Counts down (each change flowing like any digit) as the attempt's edges are drawn
+    groups.push(side(remaining_edges_group(session.remaining_edges())));
+    groups
 }
 
 /// Apply transition logic to instances based on transition type
@@ -160,11 +449,10 @@ fn apply_transitions(
     mut current: Vec<HudInstance>,
     previous: &[HudInstance],
     transition_type: TransitionType,
-    time: &Time,
+    time: &Time<Real>,
     state: &HudTransitionState,
 ) -> Vec<HudInstance> {
     match transition_type {
-        TransitionType::None => current,
         TransitionType::LevelAdvance => {
             animate_increasing_digits(&mut current, previous, time, state);
             current
@@ -180,7 +468,7 @@ fn apply_transitions(
 fn animate_increasing_digits(
     current: &mut [HudInstance],
     previous: &[HudInstance],
-    time: &Time,
+    time: &Time<Real>,
     state: &HudTransitionState,
 ) {
     for (inst, prev) in current.iter_mut().zip(previous.iter()) {
@@ -191,13 +479,20 @@ fn animate_increasing_digits(
         if prev.transition_progress < 1.0 {
             // Continue existing transition
             inst.from_mask = prev.from_mask;
+            inst.flow_seed = prev.flow_seed;
+            inst.easing = prev.easing;
+            inst.stagger = prev.stagger;
             inst.transition_progress =
                 (prev.transition_progress + time.delta_secs() / state.transition_duration).min(1.0);
         } else if inst.mask != prev.mask {
-            let curr_val = Digit::from_mask(inst.mask as u8)
+            let curr_val = u8::try_from(inst.mask)
+                .ok()
+                .and_then(Digit::from_mask)
                 .map(|d| d.to_u8())
                 .unwrap_or(255);
-            let prev_val = Digit::from_mask(prev.mask as u8)
+            let prev_val = u8::try_from(prev.mask)
+                .ok()
+                .and_then(Digit::from_mask)
                 .map(|d| d.to_u8())
                 .unwrap_or(255);
 
@@ -218,7 +513,7 @@ fn animate_increasing_digits(
 fn animate_all_changed(
     current: &mut [HudInstance],
     previous: &[HudInstance],
-    time: &Time,
+    time: &Time<Real>,
     state: &HudTransitionState,
 ) {
     for (inst, prev) in current.iter_mut().zip(previous.iter()) {
@@ -229,6 +524,9 @@ fn animate_all_changed(
         if prev.transition_progress < 1.0 {
             // Continue existing transition
             inst.from_mask = prev.from_mask;
+            inst.flow_seed = prev.flow_seed;
+            inst.easing = prev.easing;
+            inst.stagger = prev.stagger;
             inst.transition_progress =
                 (prev.transition_progress + time.delta_secs() / state.transition_duration).min(1.0);
         } else if inst.mask != prev.mask {
@@ -240,15 +538,59 @@ fn animate_all_changed(
 }
 
 /// Update the material with animated instances
-fn update_material(material: &mut SevenSegmentMaterial, instances: &[HudInstance], time: f32) {
-    // Update instances
-    let count = instances.len().min(MAX_HUD_INSTANCES);
-    material.data.hud_count = count as u32;
+fn update_material(
+    material: &mut SevenSegmentMaterial,
+    instances: &[HudInstance],
+    toasts: &[HudInstance],
+    time: f32,
+) {
+    // Update instances, keeping the last slots for toasts
+    let instances = &instances[..instances.len().min(MAX_HUD_INSTANCES - TOAST_SLOTS)];
+    let toasts = &toasts[..toasts.len().min(TOAST_SLOTS)];
+    material.data.hud_count = (instances.len() + toasts.len()) as u32;
     material.data.hud = [HudInstance::default(); MAX_HUD_INSTANCES];
-    for (i, inst) in instances.iter().enumerate().take(MAX_HUD_INSTANCES) {
+    for (i, inst) in instances.iter().chain(toasts).enumerate() {
         material.data.hud[i] = *inst;
     }
 
     // Update time
     material.data.time = time;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visual::ui::number_group::tokens_for_number;
+
+    fn placed(digits: usize, priority: HudPriority) -> PlacedGroup {
+        let mut group = level_group(0);
+        group.tokens = tokens_for_number(10usize.pow(digits as u32 - 1));
+        PlacedGroup::new(group, HudStyle::default(), priority)
+    }
+
+    #[test]
+    fn test_overflow_evicts_lowest_priority_groups_first() {
+        let mut groups = vec![
+            placed(3, HudPriority::Core),
+            placed(2, HudPriority::Side),
+            placed(3, HudPriority::Overlay),
+            placed(2, HudPriority::Side),
+        ];
+
+        // Everything fits: nothing goes
+        assert_eq!(evict_to_fit(&mut groups, 10), 0);
+        assert_eq!(groups.len(), 4);
+
+        // One slot short: the later side count gives way
+        assert_eq!(evict_to_fit(&mut groups, 9), 1);
+        let priorities: Vec<_> = groups.iter().map(|placed| placed.priority).collect();
+        assert_eq!(
+            priorities,
+            vec![HudPriority::Core, HudPriority::Side, HudPriority::Overlay]
+        );
+
+        // Down to the core counters alone
+        assert_eq!(evict_to_fit(&mut groups, 3), 2);
+        assert_eq!(groups[0].priority, HudPriority::Core);
+    }
+}
@@ -3,27 +3,27 @@
 use bevy::prelude::*;
 
 use crate::camera::CameraBounds;
-use crate::visual::sdf::seven_segment::{Digit, HudInstance};
+use crate::visual::sdf::seven_segment::{Digit, HudInstance, MINUS_MASK, SLASH_MASK, glyph_mask};
 
 use super::number_group::*;
 
 /// Convert anchor coordinates to world position
-/// 
+///
 /// Returns Vec2 in world XY plane where the HUD element should be positioned
 pub fn anchor_world(bounds: &CameraBounds, anchor: HudAnchor) -> Vec2 {
     let w = bounds.width();
     let h = bounds.height();
-    
+
     // Calculate positions with padding
     let x0 = bounds.left + w * anchor.padding;
     let x1 = bounds.right - w * anchor.padding;
     let y0 = bounds.bottom + h * anchor.padding;
     let y1 = bounds.top - h * anchor.padding;
-    
+
     // Interpolate based on anchor (h: 0=left, 1=right, v: 0=bottom, 1=top)
     let x = x0 + (x1 - x0) * anchor.h;
     let y = y0 + (y1 - y0) * anchor.v;
-    
+
     // Return world position directly - shader uses: p = vec2(world_x, world_y)
     Vec2::new(x, y)
 }
@@ -35,7 +35,9 @@ fn group_width(tokens: &[HudToken], digit_w: f32, gap: f32, slash_extra: f32) ->
         if i > 0 {
             w += gap;
         }
-        w += digit_w;
+        if *token != HudToken::Gap {
+            w += digit_w;
+        }
         if matches!(token, HudToken::Slash) {
             w += slash_extra;
         }
@@ -46,7 +48,7 @@ fn group_width(tokens: &[HudToken], digit_w: f32, gap: f32, slash_extra: f32) ->
 /// Build HUD instances from a number group
 ///
 /// Converts a `HudGroup` into a list of `HudInstance` structs for the shader.
-/// 
+///
 /// Position convention: all positions are CENTERS of tokens in world XZ space.
 pub fn build_instances_for_group(
     bounds: &CameraBounds,
@@ -60,9 +62,10 @@ pub fn build_instances_for_group(
 
     // Get anchor position in world space
     let anchor = anchor_world(bounds, group.anchor);
-    
-    // Calculate dimensions
-    let digit_w = style.digit_scale;
+
+    // Calculate dimensions (styles are sized for the whole board; a zoomed-in view
+    // shrinks them to keep the same size on screen)
+    let digit_w = style.digit_scale * bounds.height() / CameraBounds::from_fixed_aspect().height();
     let gap = style.digit_spacing * digit_w;
     let slash_extra = style.slash_spacing * digit_w;
     let total_w = group_width(&group.tokens, digit_w, gap, slash_extra);
@@ -70,9 +73,11 @@ pub fn build_instances_for_group(
     // Calculate starting X based on justification
     // - Left: anchor is at CENTER of first token
     // - Right: anchor is at CENTER of last token
+    // - Center: anchor is at the middle of the whole group
     let start_x = match group.justify {
         HudJustify::Left => anchor.x,
         HudJustify::Right => anchor.x - total_w + digit_w,
+        HudJustify::Center => anchor.x - (total_w - digit_w) * 0.5,
     };
 
     // Place each token, treating x as CENTER
@@ -80,35 +85,29 @@ pub fn build_instances_for_group(
     for token in &group.tokens {
         let (kind, mask) = match token {
             HudToken::Digit(d) => {
-                // Convert usize to Digit enum
-                let digit = match d {
-                    0 => Digit::Zero,
-                    1 => Digit::One,
-                    2 => Digit::Two,
-                    3 => Digit::Three,
-                    4 => Digit::Four,
-                    5 => Digit::Five,
-                    6 => Digit::Six,
-                    7 => Digit::Seven,
-                    8 => Digit::Eight,
-                    9 => Digit::Nine,
-                    _ => Digit::Eight, // Default to 8 for invalid digits
-                };
+                let digit = Digit::try_from(*d).unwrap_or(Digit::Eight); // 8 for invalid digits
                 (0u32, digit.mask() as u32)
             }
-            HudToken::Slash => (1u32, 0u32), // Slash doesn't use mask
+            HudToken::Slash => (0u32, SLASH_MASK), // A glyph like any other
+            HudToken::Pip => (1u32, 0u32),         // The pip pulses instead of flowing
+            HudToken::Minus => (0u32, MINUS_MASK), // The middle segment alone
+            HudToken::Letter(c) => (0u32, glyph_mask(*c)), // Segments or punctuation
+            HudToken::Gap => {
+                x += gap; // Nothing drawn, just one more gap's room
+                continue;
+            }
         };
 
         out.push(HudInstance {
             kind,
             mask,
-            from_mask: mask,  // Initially, no transition (from == to)
-            transition_progress: 1.0,  // Fully transitioned
+            from_mask: mask,          // Initially, no transition (from == to)
+            transition_progress: 1.0, // Fully transitioned
             pos: Vec2::new(x, anchor.y),
             scale: digit_w,
-            _pad1: 0,
-            _pad2: 0,
-            _pad3: 0,
+            flow_seed: 0,
+            easing: 0,
+            stagger: 0.0,
             _pad4: 0,
         });
 
@@ -0,0 +1,210 @@
+//! Locale-aware HUD layout: thousands grouping for numbers and mirrored anchoring for
+//! right-to-left languages.
+//!
+//! Digits stay in reading order either way (numbers read left to right in RTL scripts
+//! too); only which corner a group sits in swaps.
+
+use bevy::prelude::*;
+
+use super::number_group::{HudAnchor, HudGroup, HudJustify, HudToken};
+
+/// Languages written right to left (primary subtags)
+const RTL_LANGUAGES: [&str; 6] = ["ar", "dv", "fa", "he", "ps", "ur"];
+
+/// How the digits of long numbers are grouped
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigitGrouping {
+    /// 12345
+    #[default]
+    None,
+    /// 12 345, in threes from the right
+    Thousands,
+}
+
+/// Resource: How HUD numbers are formatted and which way the HUD reads
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Locale {
+    pub grouping: DigitGrouping,
+    /// Mirror the HUD so the level sits top-right and progress top-left
+    pub right_to_left: bool,
+}
+
+impl Locale {
+    /// The locale for a BCP 47 language tag such as `en-US` or `he-IL`
+    pub fn for_language(tag: &str) -> Self {
+        let language = tag
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Self {
+            grouping: DigitGrouping::Thousands,
+            right_to_left: RTL_LANGUAGES.contains(&language.as_str()),
+        }
+    }
+
+    /// The token between digit groups: a blank gap for every language until there's
+    /// a comma glyph to go with the period
+    fn separator(&self) -> HudToken {
+        HudToken::Gap
+    }
+
+    /// Group every run of digits in `tokens` (runs of four or more split in threes
+    /// from the right, so 1000 reads 1 000)
+    pub fn format(&self, tokens: Vec<HudToken>) -> Vec<HudToken> {
+        if self.grouping == DigitGrouping::None {
+            return tokens;
+        }
+
+        let mut formatted = Vec::with_capacity(tokens.len() + tokens.len() / 3);
+        let mut rest = tokens.as_slice();
+        while let Some(first) = rest.first() {
+            let run = rest
+                .iter()
+                .take_while(|token| matches!(token, HudToken::Digit(_)))
+                .count();
+            if run == 0 {
+                formatted.push(*first);
+                rest = &rest[1..];
+                continue;
+            }
+            for (i, digit) in rest[..run].iter().enumerate() {
+                if i > 0 && run >= 4 && (run - i) % 3 == 0 {
+                    formatted.push(self.separator());
+                }
+                formatted.push(*digit);
+            }
+            rest = &rest[run..];
+        }
+        formatted
+    }
+
+    /// `group` formatted, and moved to the mirrored side for right-to-left locales
+    pub fn place(&self, group: HudGroup) -> HudGroup {
+        let mut group = HudGroup {
+            tokens: self.format(group.tokens),
+            ..group
+        };
+        if self.right_to_left {
+            group.anchor = mirror_anchor(group.anchor);
+            group.justify = mirror_justify(group.justify);
+        }
+        group
+    }
+}
+
+/// The desktop's language tag from the POSIX locale variables (`LC_ALL`, then
+/// `LC_MESSAGES`, then `LANG`), passing over unset ones and the `C`/`POSIX` defaults
+#[cfg(not(target_arch = "wasm32"))]
+fn language_from(var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !matches!(value.as_str(), "" | "C" | "POSIX") && !value.starts_with("C."))
+}
+
+/// System: Format the HUD for the desktop's language (the browser build asks the page)
+#[cfg(not(target_arch = "wasm32"))]
+pub fn detect_locale(mut locale: ResMut<Locale>) {
+    let Some(language) = language_from(|name| std::env::var(name).ok()) else {
+        return;
+    };
+    *locale = Locale::for_language(&language);
+    if locale.right_to_left {
+        info!("🔢 Mirroring the HUD for {}", language);
+    }
+}
+
+/// The same anchor from the other side of the screen
+pub fn mirror_anchor(anchor: HudAnchor) -> HudAnchor {
+    HudAnchor {
+        h: 1.0 - anchor.h,
+        ..anchor
+    }
+}
+
+/// Justification that keeps a mirrored group growing away from its edge
+pub fn mirror_justify(justify: HudJustify) -> HudJustify {
+    match justify {
+        HudJustify::Left => HudJustify::Right,
+        HudJustify::Right => HudJustify::Left,
+        HudJustify::Center => HudJustify::Center,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visual::ui::number_group::{level_group, tokens_for_number, tokens_for_text};
+
+    const GROUPED: Locale = Locale {
+        grouping: DigitGrouping::Thousands,
+        right_to_left: false,
+    };
+
+    #[test]
+    fn test_long_numbers_group_in_threes() {
+        let digits = |s: &str| {
+            s.chars()
+                .map(|c| match c {
+                    ' ' => HudToken::Gap,
+                    c => HudToken::Digit(c.to_digit(10).unwrap() as u8),
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(GROUPED.format(tokens_for_number(999)), digits("999"));
+        assert_eq!(GROUPED.format(tokens_for_number(1000)), digits("1 000"));
+        assert_eq!(
+            GROUPED.format(tokens_for_number(1234567)),
+            digits("1 234 567")
+        );
+        assert_eq!(
+            Locale::default().format(tokens_for_number(1234567)),
+            digits("1234567")
+        );
+    }
+
+    #[test]
+    fn test_grouping_leaves_other_tokens_alone() {
+        let mut tokens = tokens_for_text("pb");
+        tokens.extend(tokens_for_number(12345));
+        let formatted = GROUPED.format(tokens);
+        assert_eq!(formatted[..2], tokens_for_text("pb"));
+        assert_eq!(formatted[3], HudToken::Digit(2));
+        assert_eq!(formatted[4], HudToken::Gap);
+    }
+
+    #[test]
+    fn test_rtl_languages_mirror_the_corners() {
+        assert!(!Locale::for_language("en-US").right_to_left);
+        assert!(Locale::for_language("he-IL").right_to_left);
+        assert!(Locale::for_language("AR").right_to_left);
+
+        let placed = Locale::for_language("fa").place(level_group(12));
+        let level = level_group(12);
+        assert_eq!(placed.anchor.h, 1.0 - level.anchor.h);
+        assert_eq!(placed.justify, mirror_justify(level.justify));
+        assert_eq!(placed.tokens, level.tokens);
+    }
+
+    #[test]
+    fn test_desktop_language_skips_the_c_locale() {
+        let env = |vars: &'static [(&str, &str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        assert_eq!(
+            language_from(env(&[("LC_ALL", "C"), ("LANG", "he_IL.UTF-8")])),
+            Some("he_IL.UTF-8".to_string())
+        );
+        assert_eq!(
+            language_from(env(&[("LC_MESSAGES", "fa_IR"), ("LANG", "en_US")])),
+            Some("fa_IR".to_string())
+        );
+        assert_eq!(language_from(env(&[("LANG", "C.UTF-8")])), None);
+        assert!(Locale::for_language("he_IL.UTF-8").right_to_left);
+    }
+}
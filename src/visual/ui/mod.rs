@@ -1,5 +1,41 @@
 pub mod hud;
 pub mod hud_builder;
+pub mod locale;
 pub mod number_group;
+pub mod odometer;
+pub mod profile_select;
+pub mod summary;
+pub mod toast;
 
-pub use hud::{spawn_hud, update_hud, HudTransitionState};
+use bevy::prelude::*;
+
+use crate::{
+    leaderboard::LeaderboardView,
+    photo::PhotoMode,
+    settings::{ScenePresentation, Settings},
+};
+
+pub use hud::{HudTransitionState, cycle_transition_style, fit_hud_to_view, spawn_hud, update_hud};
+pub use locale::Locale;
+pub use profile_select::{ProfileSelect, choose_profile, offer_profile_select};
+pub use summary::{
+    LevelEnd, LevelSummary, LevelTally, dismiss_level_summary, earn_hint_tokens, tally_level,
+    watch_for_struggle,
+};
+pub use toast::{ToastQueue, advance_toasts, toast_new_solutions};
+
+/// Run condition: Nothing covers the board (the level summary, the profile select, the
+/// leaderboard, photo mode or the orbiting 3D view), so gameplay input reaches it
+pub fn board_uncovered(
+    summary: Option<Res<LevelSummary>>,
+    select: Option<Res<ProfileSelect>>,
+    leaderboard: Option<Res<LeaderboardView>>,
+    photo: Option<Res<PhotoMode>>,
+    settings: Res<Settings>,
+) -> bool {
+    summary.is_none()
+        && select.is_none()
+        && leaderboard.is_none()
+        && photo.is_none()
+        && settings.presentation == ScenePresentation::Flat
+}
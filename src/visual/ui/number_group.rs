@@ -11,6 +11,8 @@ pub enum HudJustify {
     Left,
     /// Right-justify: last token ends at anchor point, extends left
     Right,
+    /// Center the group on the anchor point
+    Center,
 }
 
 /// Anchor point for positioning a HUD group on the screen
@@ -52,6 +54,14 @@ pub enum HudToken {
     Digit(u8),
     /// A forward slash separator
     Slash,
+    /// A round dot leading a side count (the opponent's pulses when they score)
+    Pip,
+    /// A minus sign, drawn as a digit's middle segment
+    Minus,
+    /// A letter drawn in seven segments, as close as they get (see `glyph_mask`)
+    Letter(char),
+    /// A narrow blank between digit groups (see `Locale::format`); takes no instance
+    Gap,
 }
 
 /// A group of HUD tokens positioned together
@@ -65,6 +75,16 @@ pub struct HudGroup {
     pub tokens: Vec<HudToken>,
 }
 
+impl HudGroup {
+    /// Shader instances the group draws (gaps only take up room)
+    pub fn slots(&self) -> usize {
+        self.tokens
+            .iter()
+            .filter(|token| **token != HudToken::Gap)
+            .count()
+    }
+}
+
 /// Convert a number into a sequence of digit tokens.
 ///
 /// # Examples
@@ -86,6 +106,61 @@ pub fn tokens_for_number(mut n: usize) -> Vec<HudToken> {
     digits.into_iter().map(HudToken::Digit).collect()
 }
 
+/// Convert a number into exactly `width` digit tokens, zero-padded on the left.
+///
+/// Keeps a counter's digits in the same instance slots while it counts up; the highest
+/// digits are dropped if the number doesn't fit.
+pub fn tokens_for_padded_number(n: usize, width: usize) -> Vec<HudToken> {
+    let mut tokens = tokens_for_number(n);
+    if tokens.len() > width {
+        tokens.drain(..tokens.len() - width);
+    }
+    let mut padded = vec![HudToken::Digit(0); width - tokens.len()];
+    padded.extend(tokens);
+    padded
+}
+
+/// Convert a signed number into a sign slot followed by its digit tokens.
+///
+/// The sign slot is a minus for negative numbers and blank otherwise, so a value
+/// crossing zero keeps its digits in the same instance slots while the minus grows in
+/// or melts away in place.
+///
+/// # Examples
+/// ```ignore
+/// assert_eq!(tokens_for_signed_number(-50), vec![HudToken::Minus, HudToken::Digit(5), HudToken::Digit(0)]);
+/// assert_eq!(tokens_for_signed_number(7), vec![HudToken::Letter(' '), HudToken::Digit(7)]);
+/// ```
+pub fn tokens_for_signed_number(n: isize) -> Vec<HudToken> {
+    let sign = if n < 0 {
+        HudToken::Minus
+    } else {
+        HudToken::Letter(' ')
+    };
+    let mut tokens = vec![sign];
+    tokens.extend(tokens_for_number(n.unsigned_abs()));
+    tokens
+}
+
+/// Convert seconds into a clock reading, `m:ss` (minutes run past 59 rather than
+/// rolling into hours).
+///
+/// # Examples
+/// ```ignore
+/// assert_eq!(tokens_for_clock(75), tokens_for_text("1:15"));
+/// ```
+pub fn tokens_for_clock(seconds: usize) -> Vec<HudToken> {
+    let mut tokens = tokens_for_number(seconds / 60);
+    tokens.push(HudToken::Letter(':'));
+    tokens.extend(tokens_for_padded_number(seconds % 60, 2));
+    tokens
+}
+
+/// Convert text into letter tokens, one per character.
+pub fn tokens_for_text(text: &str) -> Vec<HudToken> {
+    text.chars().map(HudToken::Letter).collect()
+}
+
 /// Create a HUD group for displaying the current level number.
 ///
 /// Positioned at the top-left with left justification.
@@ -129,10 +204,321 @@ pub fn progress_group(found: usize, total: usize) -> HudGroup {
     }
 }
 
+/// Create a HUD group for displaying the race opponent's solution count.
+///
+/// Positioned under the progress group with right justification, led by a pip.
+///
+/// # Arguments
+/// * `found` - Number of solutions the opponent has found (0-95)
+pub fn opponent_group(found: usize) -> HudGroup {
+    let mut tokens = vec![HudToken::Pip];
+    tokens.extend(tokens_for_number(found));
+    HudGroup {
+        anchor: HudAnchor {
+            h: 1.0,
+            v: 0.89, // One digit height below the progress group
+            padding: 0.05,
+        },
+        justify: HudJustify::Right,
+        tokens,
+    }
+}
+
+/// Create a HUD group for the puzzles skipped on this level, waiting to come back.
+///
+/// Positioned under the level group with left justification, led by a pip.
+///
+/// # Arguments
+/// * `skipped` - Number of skipped puzzles (1-99)
+pub fn skipped_group(skipped: usize) -> HudGroup {
+    let mut tokens = vec![HudToken::Pip];
+    tokens.extend(tokens_for_number(skipped));
+    HudGroup {
+        anchor: HudAnchor {
+            h: 0.0,
+            v: 0.89, // One digit height below the level group
+            padding: 0.05,
+        },
+        justify: HudJustify::Left,
+        tokens,
+    }
+}
+
+/// Create a HUD group for the hint tokens in the wallet.
+///
+/// Positioned in the bottom-left corner with left justification, led by a pip.
+///
+/// # Arguments
+/// * `tokens` - Hint tokens to spend
+pub fn hint_tokens_group(tokens: usize) -> HudGroup {
+    let mut group_tokens = vec![HudToken::Pip];
+    group_tokens.extend(tokens_for_number(tokens));
+    HudGroup {
+        anchor: HudAnchor {
+            h: 0.0,
+            v: 0.01, // Bottom edge, clear of the board
+            padding: 0.05,
+        },
+        justify: HudJustify::Left,
+        tokens: group_tokens,
+    }
+}
+
+/// Create a HUD group for the arcade score.
+///
+/// Centered along the top edge, where it rolls up as points come in.
+///
+/// # Arguments
+/// * `points` - The score as it currently reads
+pub fn score_group(points: usize) -> HudGroup {
+    HudGroup {
+        anchor: HudAnchor {
+            h: 0.5,
+            v: 0.99,
+            padding: 0.05,
+        },
+        justify: HudJustify::Center,
+        tokens: tokens_for_number(points),
+    }
+}
+
+/// Create a HUD group for the combo multiplier.
+///
+/// Centered under the score, led by a pip.
+///
+/// # Arguments
+/// * `multiplier` - Current combo multiplier (2-9)
+pub fn combo_group(multiplier: usize) -> HudGroup {
+    let mut tokens = vec![HudToken::Pip];
+    tokens.extend(tokens_for_number(multiplier));
+    HudGroup {
+        anchor: HudAnchor {
+            h: 0.5,
+            v: 0.89, // Under the score
+            padding: 0.05,
+        },
+        justify: HudJustify::Center,
+        tokens,
+    }
+}
+
+/// Create a HUD group for the edges left to draw in the current attempt.
+///
+/// Positioned in the bottom-right corner with right justification, so the ones digit
+/// stays put as it counts down.
+///
+/// # Arguments
+/// * `remaining` - Edges left to draw (0-20)
+pub fn remaining_edges_group(remaining: usize) -> HudGroup {
+    HudGroup {
+        anchor: HudAnchor {
+            h: 1.0,
+            v: 0.01, // Bottom edge, clear of the board
+            padding: 0.05,
+        },
+        justify: HudJustify::Right,
+        tokens: tokens_for_number(remaining),
+    }
+}
+
+/// Create a HUD group for one row of the level-complete summary.
+///
+/// Rows stack down from the upper middle of the screen, above the board, centered.
+///
+/// # Arguments
+/// * `shown` - The value as it currently reads while counting up
+/// * `width` - Digits to show (the final value's), so the row doesn't shift as it counts
+/// * `row` - 0 for the first row
+pub fn tally_group(shown: usize, width: usize, row: usize) -> HudGroup {
+    HudGroup {
+        anchor: HudAnchor {
+            h: 0.5,
+            v: 0.8 - 0.12 * row as f32,
+            padding: 0.05,
+        },
+        justify: HudJustify::Center,
+        tokens: tokens_for_padded_number(shown, width),
+    }
+}
+
+/// Create a HUD group for the leaderboard's heading: which view it is, and the page.
+///
+/// Centered above the rows.
+///
+/// # Arguments
+/// * `label` - The view's name (a few letters)
+/// * `page` - Page number from 1, for views that page
+pub fn leaderboard_heading_group(label: &str, page: Option<usize>) -> HudGroup {
+    let mut tokens = tokens_for_text(label);
+    if let Some(page) = page {
+        tokens.push(HudToken::Pip);
+        tokens.extend(tokens_for_number(page));
+    }
+    HudGroup {
+        anchor: HudAnchor {
+            h: 0.5,
+            v: 0.86,
+            padding: 0.05,
+        },
+        justify: HudJustify::Center,
+        tokens,
+    }
+}
+
+/// One leaderboard row, as a column group each so the columns line up
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LeaderboardRow {
+    /// Rank from 1
+    pub rank: usize,
+    /// This device's own entry (its rank is led by a pip)
+    pub mine: bool,
+    /// A few characters naming the player
+    pub tag: String,
+    pub solutions: usize,
+    pub seconds: usize,
+}
+
+/// Create the HUD groups for a leaderboard row: rank, player tag, solutions and seconds.
+///
+/// Rows stack down from under the heading; numbers are right-justified in their columns.
+///
+/// # Arguments
+/// * `entry` - The row's contents
+/// * `row` - 0 for the first row
+pub fn leaderboard_row_groups(entry: &LeaderboardRow, row: usize) -> [HudGroup; 4] {
+    let v = 0.76 - 0.1 * row as f32;
+    let column = |h: f32, justify: HudJustify, tokens: Vec<HudToken>| HudGroup {
+        anchor: HudAnchor {
+            h,
+            v,
+            padding: 0.05,
+        },
+        justify,
+        tokens,
+    };
+
+    let mut rank = Vec::new();
+    if entry.mine {
+        rank.push(HudToken::Pip);
+    }
+    rank.extend(tokens_for_number(entry.rank));
+    [
+        column(0.22, HudJustify::Right, rank),
+        column(0.3, HudJustify::Left, tokens_for_text(&entry.tag)),
+        column(0.7, HudJustify::Right, tokens_for_number(entry.solutions)),
+        column(1.0, HudJustify::Right, tokens_for_number(entry.seconds)),
+    ]
+}
+
+/// Create a HUD group for the profile select: the profiles' numbers in a centered row,
+/// the highlighted one led by a pip.
+///
+/// # Arguments
+/// * `count` - Profiles to show (1-9)
+/// * `highlighted` - Index of the highlighted profile
+pub fn profile_group(count: usize, highlighted: usize) -> HudGroup {
+    let mut tokens = Vec::new();
+    for index in 0..count {
+        if index == highlighted {
+            tokens.push(HudToken::Pip);
+        }
+        tokens.extend(tokens_for_number(index + 1));
+    }
+    HudGroup {
+        anchor: HudAnchor {
+            h: 0.5,
+            v: 0.8,
+            padding: 0.05,
+        },
+        justify: HudJustify::Center,
+        tokens,
+    }
+}
+
+/// Characters in one row of the error screen's message
+const ERROR_LINE: usize = 16;
+
+/// Rows of the message shown (a longer one is cut short)
+const ERROR_LINES: usize = 5;
+
+/// Create the HUD groups for the error screen: an "error" heading, the reason word-wrapped
+/// below it, and the key to retry.
+///
+/// Centered rows; the reason is spelled as far as seven segments go (see `glyph_mask`).
+///
+/// # Arguments
+/// * `reason` - What failed, as `AppError` stores it
+pub fn error_groups(reason: &str) -> Vec<HudGroup> {
+    let row = |text: &str, v: f32| HudGroup {
+        anchor: HudAnchor {
+            h: 0.5,
+            v,
+            padding: 0.05,
+        },
+        justify: HudJustify::Center,
+        tokens: tokens_for_text(text),
+    };
+
+    let lines = wrap(reason, ERROR_LINE);
+    let shown = lines.len().min(ERROR_LINES);
+    let mut groups = vec![row("error", 0.8)];
+    groups.extend(
+        lines[..shown]
+            .iter()
+            .enumerate()
+            .map(|(index, line)| row(line, 0.7 - 0.07 * index as f32)),
+    );
+    groups.push(row("r retry", 0.7 - 0.07 * (shown as f32 + 1.0)));
+    groups
+}
+
+/// `text` in rows of at most `width` characters, broken between words where it can
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        if word.is_empty() {
+            continue;
+        }
+        if !line.is_empty() && line.chars().count() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.extend(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_message_wraps_between_words() {
+        assert_eq!(
+            wrap("No puzzles available for level 12", 16),
+            ["No puzzles", "available for", "level 12"]
+        );
+        assert_eq!(wrap("abcdefghij k", 4), ["abcd", "efgh", "ij k"]);
+
+        let groups = error_groups("No puzzles available for level 12");
+        assert_eq!(groups.len(), 1 + 3 + 1);
+        assert_eq!(groups[0].tokens, tokens_for_text("error"));
+        assert_eq!(groups[4].tokens, tokens_for_text("r retry"));
+    }
+
     #[test]
     fn test_tokens_for_number() {
         assert_eq!(tokens_for_number(0), vec![HudToken::Digit(0)]);
@@ -146,4 +532,70 @@ mod tests {
             vec![HudToken::Digit(2), HudToken::Digit(1), HudToken::Digit(7)]
         );
     }
+
+    #[test]
+    fn test_tokens_for_clock() {
+        let clock = |s: &str| -> Vec<HudToken> {
+            s.chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(d) => HudToken::Digit(d as u8),
+                    None => HudToken::Letter(c),
+                })
+                .collect()
+        };
+        assert_eq!(tokens_for_clock(0), clock("0:00"));
+        assert_eq!(tokens_for_clock(75), clock("1:15"));
+        assert_eq!(tokens_for_clock(3600), clock("60:00"));
+    }
+
+    #[test]
+    fn test_tokens_for_signed_number() {
+        assert_eq!(
+            tokens_for_signed_number(-50),
+            vec![HudToken::Minus, HudToken::Digit(5), HudToken::Digit(0)]
+        );
+        assert_eq!(
+            tokens_for_signed_number(50),
+            vec![
+                HudToken::Letter(' '),
+                HudToken::Digit(5),
+                HudToken::Digit(0)
+            ]
+        );
+        assert_eq!(
+            tokens_for_signed_number(0),
+            vec![HudToken::Letter(' '), HudToken::Digit(0)]
+        );
+    }
+
+    #[test]
+    fn test_leaderboard_row_marks_the_players_own_rank() {
+        let mut entry = LeaderboardRow {
+            rank: 12,
+            mine: false,
+            tag: "a3f0".to_string(),
+            solutions: 3,
+            seconds: 95,
+        };
+        let [rank, tag, ..] = leaderboard_row_groups(&entry, 1);
+        assert_eq!(rank.tokens, vec![HudToken::Digit(1), HudToken::Digit(2)]);
+        assert_eq!(tag.tokens.len(), 4);
+        assert_eq!(tag.tokens[0], HudToken::Letter('a'));
+
+        entry.mine = true;
+        let [rank, ..] = leaderboard_row_groups(&entry, 1);
+        assert_eq!(rank.tokens[0], HudToken::Pip);
+    }
+
+    #[test]
+    fn test_tokens_for_padded_number() {
+        assert_eq!(
+            tokens_for_padded_number(7, 3),
+            vec![HudToken::Digit(0), HudToken::Digit(0), HudToken::Digit(7)]
+        );
+        assert_eq!(
+            tokens_for_padded_number(1234, 2),
+            vec![HudToken::Digit(3), HudToken::Digit(4)]
+        );
+    }
 }
@@ -0,0 +1,140 @@
+//! Odometer-style counting: a number steps through every value on the way to a new
+//! one (9 → 12 reads 10 and 11 as it goes), so multi-digit counters roll over the way
+//! a mechanical counter does instead of jumping.
+
+/// How fast an odometer rolls
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RollSpeed {
+    /// Values stepped per second
+    pub per_sec: f32,
+    /// Longest a roll may take; bigger gaps step faster to finish in time
+    pub max_secs: f32,
+}
+
+impl RollSpeed {
+    /// Steps per second to cover a gap of `gap` values
+    fn rate(self, gap: u32) -> f32 {
+        self.per_sec
+            .max(gap as f32 / self.max_secs.max(f32::EPSILON))
+    }
+
+    /// The value `elapsed` seconds into a roll from `from` to `to`
+    pub fn value_at(self, from: u32, to: u32, elapsed: f32) -> u32 {
+        let gap = from.abs_diff(to);
+        let steps = ((elapsed.max(0.0) * self.rate(gap)).floor() as u32).min(gap);
+        if to >= from {
+            from + steps
+        } else {
+            from - steps
+        }
+    }
+}
+
+/// A shown value that rolls toward its target one step at a time
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Odometer {
+    shown: u32,
+    target: u32,
+    /// Steps per second for the roll under way
+    rate: f32,
+    /// Progress toward the next step
+    partial: f32,
+}
+
+impl Odometer {
+    /// The value as it reads right now
+    pub fn shown(&self) -> u32 {
+        self.shown
+    }
+
+    /// Whether it's still rolling toward its target
+    pub fn rolling(&self) -> bool {
+        self.shown != self.target
+    }
+
+    /// Roll toward `target` from wherever it reads now
+    pub fn roll_to(&mut self, target: u32, speed: RollSpeed) {
+        if target != self.target {
+            self.target = target;
+            self.rate = speed.rate(self.shown.abs_diff(target));
+        }
+    }
+
+    /// Step along by `dt` seconds' worth of values
+    pub fn tick(&mut self, dt: f32) {
+        if !self.rolling() {
+            self.partial = 0.0;
+            return;
+        }
+
+        self.partial += dt * self.rate;
+        let steps = (self.partial.floor() as u32).min(self.shown.abs_diff(self.target));
+        self.partial -= steps as f32;
+        if self.target > self.shown {
+            self.shown += steps;
+        } else {
+            self.shown -= steps;
+        }
+        if !self.rolling() {
+            self.partial = 0.0; // Don't bank overshoot for the next roll
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEED: RollSpeed = RollSpeed {
+        per_sec: 10.0,
+        max_secs: 1.0,
+    };
+
+    #[test]
+    fn test_rolls_through_every_value() {
+        let mut odometer = Odometer::default();
+        odometer.roll_to(9, SPEED);
+        odometer.tick(1.0);
+        assert_eq!(odometer.shown(), 9);
+
+        // 9 → 12 reads 10 and 11 on the way
+        odometer.roll_to(12, SPEED);
+        let mut seen = vec![odometer.shown()];
+        while odometer.rolling() {
+            odometer.tick(0.05);
+            if seen.last() != Some(&odometer.shown()) {
+                seen.push(odometer.shown());
+            }
+        }
+        assert_eq!(seen, vec![9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn test_rolls_down_and_stops_at_the_target() {
+        let mut odometer = Odometer::default();
+        odometer.roll_to(5, SPEED);
+        odometer.tick(10.0);
+        assert_eq!(odometer.shown(), 5);
+
+        odometer.roll_to(2, SPEED);
+        odometer.tick(0.15);
+        assert_eq!(odometer.shown(), 4);
+        odometer.tick(10.0);
+        assert_eq!(odometer.shown(), 2);
+        assert!(!odometer.rolling());
+    }
+
+    #[test]
+    fn test_big_gaps_finish_in_time() {
+        let mut odometer = Odometer::default();
+        odometer.roll_to(500, SPEED);
+        odometer.tick(0.5);
+        assert_eq!(odometer.shown(), 250);
+        odometer.tick(0.5);
+        assert_eq!(odometer.shown(), 500);
+
+        assert_eq!(SPEED.value_at(0, 500, 0.5), 250);
+        assert_eq!(SPEED.value_at(3, 0, 0.15), 2);
+        assert_eq!(SPEED.value_at(0, 4, 60.0), 4);
+    }
+}
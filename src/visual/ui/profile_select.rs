@@ -0,0 +1,128 @@
+//! Profile select: on a shared device, the profiles' numbers line up on the HUD at
+//! startup and the player picks theirs before the board takes input.
+
+use bevy::prelude::*;
+
+use crate::{
+    input::{PointerEventType, WorldPointerEvent},
+    profile::{Profiles, SwitchProfile},
+};
+
+use super::number_group::{HudGroup, profile_group};
+
+/// Number keys pick a profile directly (profile 1 is Digit1)
+const DIGIT_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Resource: Present while a profile is being picked
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSelect {
+    /// Index of the profile a tap or Enter would pick
+    pub highlighted: usize,
+    /// Profiles to pick from
+    pub count: usize,
+}
+
+impl ProfileSelect {
+    /// Move the highlight `step` places, wrapping around
+    pub fn step(&mut self, step: isize) {
+        let count = self.count.max(1) as isize;
+        self.highlighted = (self.highlighted as isize + step).rem_euclid(count) as usize;
+    }
+
+    /// The HUD row: profile numbers, the highlighted one led by a pip
+    pub fn group(&self) -> HudGroup {
+        profile_group(self.count, self.highlighted)
+    }
+}
+
+/// System: Offer the profile select when more than one profile is kept
+pub fn offer_profile_select(mut commands: Commands, profiles: Res<Profiles>) {
+    let count = profiles.book.profiles.len();
+    if count < 2 {
+        return;
+    }
+
+    info!(
+        "👥 {} profiles: press 1-{} (or ←/→ and Enter) to pick one",
+        count, count
+    );
+    for (index, profile) in profiles.book.profiles.iter().enumerate() {
+        info!(
+            "  {}: {} (level {})",
+            index + 1,
+            profile.name,
+            profile.level
+        );
+    }
+    commands.insert_resource(ProfileSelect {
+        highlighted: profiles.book.active,
+        count,
+    });
+}
+
+/// System: Pick a profile with its number key, or ←/→ then a tap or Space/Enter
+///
+/// Presses over the select screen are drained, so gameplay doesn't act on them.
+pub fn choose_profile(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut pointer_events: ResMut<Messages<WorldPointerEvent>>,
+    mut select: ResMut<ProfileSelect>,
+    profiles: Res<Profiles>,
+    mut switches: MessageWriter<SwitchProfile>,
+) {
+    let tapped = pointer_events
+        .drain()
+        .any(|event| event.event_type == PointerEventType::Down);
+
+    if keys.just_pressed(KeyCode::ArrowLeft) {
+        select.step(-1);
+    }
+    if keys.just_pressed(KeyCode::ArrowRight) {
+        select.step(1);
+    }
+
+    let numbered = DIGIT_KEYS
+        .iter()
+        .take(select.count)
+        .position(|key| keys.just_pressed(*key));
+    let confirmed = (tapped || keys.any_just_pressed([KeyCode::Space, KeyCode::Enter]))
+        .then_some(select.highlighted);
+    let Some(chosen) = numbered.or(confirmed) else {
+        return;
+    };
+
+    commands.remove_resource::<ProfileSelect>();
+    if chosen == profiles.book.active {
+        info!("👤 Playing as {}", profiles.active().name);
+    } else {
+        switches.write(SwitchProfile::To(chosen));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_wraps_around() {
+        let mut select = ProfileSelect {
+            highlighted: 0,
+            count: 3,
+        };
+        select.step(-1);
+        assert_eq!(select.highlighted, 2);
+        select.step(1);
+        assert_eq!(select.highlighted, 0);
+    }
+}
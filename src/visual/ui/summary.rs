@@ -0,0 +1,330 @@
+//! Level-complete summary: the level's time, attempts (against par) and invalid moves
+//! count up on the HUD before the next level starts.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    game::{
+        director::{Director, Struggle},
+        hints::{HintWallet, tokens_earned},
+        progression::{CompletionPolicy, ProgressionTracker},
+        session::PuzzleSession,
+    },
+    input::{PointerEventType, WorldPointerEvent},
+    net::{RoomLink, Spectator},
+    settings::Settings,
+    visual::interactions::InvalidMove,
+};
+
+use super::{
+    number_group::{HudGroup, HudToken, tally_group, tokens_for_number},
+    odometer::RollSpeed,
+};
+
+/// Real seconds the tallies take to count up
+const COUNT_UP_SECS: f32 = 1.5;
+
+/// Tallies roll through every value on the way up, finishing within the count-up
+const TALLY_ROLL: RollSpeed = RollSpeed {
+    per_sec: 12.0,
+    max_secs: COUNT_UP_SECS,
+};
+
+/// Real seconds the counted tallies stay up before the next level starts by itself
+const HOLD_SECS: f32 = 2.5;
+
+/// The summary's rows, top to bottom: seconds, attempts (against par), invalid moves
+const SECONDS_ROW: usize = 0;
+const ATTEMPTS_ROW: usize = 1;
+const INVALID_MOVES_ROW: usize = 2;
+
+/// Resource: Tallies for the level being played
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct LevelTally {
+    /// Level the tallies belong to (0 until the first level is seen)
+    pub level: usize,
+    /// Gameplay seconds (pauses don't count)
+    pub seconds: f32,
+    /// Trails started
+    pub attempts: u32,
+    /// Nodes added to trails
+    pub moves: u32,
+    pub invalid_moves: u32,
+    /// Trail length last frame, to spot new attempts
+    trail_len: usize,
+}
+
+impl LevelTally {
+    /// Share of the level's moves that were invalid
+    pub fn invalid_rate(&self) -> f32 {
+        let tries = self.moves + self.invalid_moves;
+        if tries == 0 {
+            0.0
+        } else {
+            self.invalid_moves as f32 / tries as f32
+        }
+    }
+}
+
+/// Resource: Present while a finished level's summary is up
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct LevelSummary {
+    pub level: usize,
+    pub seconds: u32,
+    pub attempts: u32,
+    /// Fewest attempts the level could have taken (see `LevelSummary::new`)
+    pub par: u32,
+    pub invalid_moves: u32,
+    /// Real seconds when it went up
+    pub shown_at: f32,
+    /// Set when it's over (skipped or timed out); `check_level_progression` moves on then
+    pub dismissed: bool,
+}
+
+impl LevelSummary {
+    /// A summary of `tally`, against `par` (see `par_for`)
+    pub fn new(tally: &LevelTally, par: u32, shown_at: f32) -> Self {
+        Self {
+            level: tally.level,
+            seconds: tally.seconds.round() as u32,
+            attempts: tally.attempts,
+            par,
+            invalid_moves: tally.invalid_moves,
+            shown_at,
+            dismissed: false,
+        }
+    }
+
+    /// Fewest attempts a puzzle with `solutions` solutions (as the solver counts them)
+    /// can take under `policy`
+    ///
+    /// Every attempt finishes at most one solution, so par is one attempt per solution the
+    /// policy requires.
+    pub fn par_for(solutions: usize, policy: CompletionPolicy) -> u32 {
+        policy.required(solutions) as u32
+    }
+
+    /// Seconds, attempts and invalid moves, in display order
+    pub fn tallies(&self) -> [u32; 3] {
+        [self.seconds, self.attempts, self.invalid_moves]
+    }
+
+    /// The tallies as they read `elapsed` real seconds in, rolling up odometer-style
+    pub fn counted(&self, elapsed: f32) -> [u32; 3] {
+        self.tallies()
+            .map(|tally| TALLY_ROLL.value_at(0, tally, elapsed))
+    }
+
+    /// Whether it has counted up and been held long enough to move on by itself
+    pub fn timed_out(&self, elapsed: f32) -> bool {
+        elapsed >= COUNT_UP_SECS + HOLD_SECS
+    }
+
+    /// The HUD rows `elapsed` real seconds in (attempts read "attempts/par")
+    pub fn groups(&self, elapsed: f32) -> Vec<HudGroup> {
+        let shown = self.counted(elapsed);
+        let tallies = self.tallies();
+        let row =
+            |row: usize| tally_group(shown[row] as usize, tallies[row].to_string().len(), row);
+
+        let mut attempts = row(ATTEMPTS_ROW);
+        attempts.tokens.push(HudToken::Slash);
+        attempts.tokens.extend(tokens_for_number(self.par as usize));
+        vec![row(SECONDS_ROW), attempts, row(INVALID_MOVES_ROW)]
+    }
+}
+
+/// The summary between levels, as `check_level_progression` sees it
+#[derive(SystemParam)]
+pub struct LevelEnd<'w> {
+    time: Res<'w, Time<Real>>,
+    tally: Res<'w, LevelTally>,
+    summary: Option<Res<'w, LevelSummary>>,
+    room: Option<Res<'w, RoomLink>>,
+    spectator: Option<Res<'w, Spectator>>,
+}
+
+impl LevelEnd<'_> {
+    /// A summary is up and still counting or holding
+    pub fn showing(&self) -> bool {
+        self.summary
+            .as_ref()
+            .is_some_and(|summary| !summary.dismissed)
+    }
+
+    /// The summary is over; the next level can start
+    pub fn dismissed(&self) -> bool {
+        self.summary
+            .as_ref()
+            .is_some_and(|summary| summary.dismissed)
+    }
+
+    /// A summary for the level just finished, against `par`
+    ///
+    /// None on shared boards: rooms and spectators move on together, at once.
+    pub fn summary(&self, par: u32) -> Option<LevelSummary> {
+        if self.summary.is_some() || self.room.is_some() || self.spectator.is_some() {
+            return None;
+        }
+        Some(LevelSummary::new(
+            &self.tally,
+            par,
+            self.time.elapsed_secs(),
+        ))
+    }
+}
+
+/// System: Count the level's playing time, attempts and invalid moves
+pub fn tally_level(
+    time: Res<Time>,
+    tracker: Res<ProgressionTracker>,
+    session: Res<PuzzleSession>,
+    mut invalid_moves: MessageReader<InvalidMove>,
+    mut tally: ResMut<LevelTally>,
+) {
+    if tally.level != tracker.current_level {
+        *tally = LevelTally {
+            level: tracker.current_level,
+            ..default()
+        };
+    }
+
+    tally.seconds += time.delta_secs();
+    tally.invalid_moves += invalid_moves.read().count() as u32;
+
+    let trail_len = session.current_trail().len();
+    if trail_len > 0 && tally.trail_len == 0 {
+        tally.attempts += 1;
+    }
+    tally.moves += trail_len.saturating_sub(tally.trail_len) as u32;
+    tally.trail_len = trail_len;
+}
+
+/// System: Have the director judge the level's tallies, with adaptive difficulty on
+pub fn watch_for_struggle(
+    settings: Res<Settings>,
+    tally: Res<LevelTally>,
+    session: Res<PuzzleSession>,
+    mut director: ResMut<Director>,
+) {
+    if !settings.adaptive {
+        return;
+    }
+
+    let struggle = Struggle {
+        seconds: tally.seconds,
+        invalid_moves: tally.invalid_moves,
+        invalid_rate: tally.invalid_rate(),
+        solutions_found: session.progress().solutions_found,
+    };
+    if director.assess(tally.level, &struggle) {
+        info!(
+            "🧭 Stuck on level {} ({:.0}s, {:.0}% invalid moves); hints on",
+            tally.level,
+            struggle.seconds,
+            struggle.invalid_rate * 100.0
+        );
+    }
+}
+
+/// System: Bank hint tokens for the finished level, with more for extra solutions
+pub fn earn_hint_tokens(
+    summary: Res<LevelSummary>,
+    session: Res<PuzzleSession>,
+    policy: Res<CompletionPolicy>,
+    mut wallet: ResMut<HintWallet>,
+) {
+    if !summary.is_added() {
+        return;
+    }
+    let progress = session.progress();
+    let required = policy.required(progress.total_solutions.unwrap_or(0));
+    wallet.tokens += tokens_earned(progress.solutions_found, required);
+    info!(
+        "💡 Level {} done: {} hint tokens now",
+        summary.level, wallet.tokens
+    );
+}
+
+/// System: End the summary on a tap or Space/Enter, or once it has been up long enough
+///
+/// Presses over the summary are drained, so gameplay doesn't act on them once it resumes.
+pub fn dismiss_level_summary(
+    time: Res<Time<Real>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut pointer_events: ResMut<Messages<WorldPointerEvent>>,
+    mut summary: ResMut<LevelSummary>,
+) {
+    let tapped = pointer_events
+        .drain()
+        .any(|event| event.event_type == PointerEventType::Down);
+    if summary.dismissed {
+        return;
+    }
+
+    let skipped = tapped || keys.any_just_pressed([KeyCode::Space, KeyCode::Enter]);
+    if skipped || summary.timed_out(time.elapsed_secs() - summary.shown_at) {
+        info!("📋 Level {} summary done", summary.level);
+        summary.dismissed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> LevelSummary {
+        LevelSummary::new(
+            &LevelTally {
+                level: 3,
+                seconds: 41.6,
+                attempts: 5,
+                moves: 30,
+                invalid_moves: 12,
+                trail_len: 0,
+            },
+            4,
+            10.0,
+        )
+    }
+
+    #[test]
+    fn test_tallies_count_up_then_hold() {
+        let summary = summary();
+        assert_eq!(summary.counted(0.0), [0, 0, 0]);
+
+        let halfway = summary.counted(COUNT_UP_SECS * 0.5);
+        assert!(halfway[0] > 0 && halfway[0] < 42);
+
+        assert_eq!(summary.counted(COUNT_UP_SECS), [42, 5, 12]);
+        assert_eq!(summary.counted(COUNT_UP_SECS + HOLD_SECS), [42, 5, 12]);
+        assert!(!summary.timed_out(COUNT_UP_SECS));
+        assert!(summary.timed_out(COUNT_UP_SECS + HOLD_SECS));
+    }
+
+    #[test]
+    fn test_rows_keep_their_width_while_counting() {
+        let summary = summary();
+        let widths = |elapsed| {
+            summary
+                .groups(elapsed)
+                .iter()
+                .map(|group| group.tokens.len())
+                .collect::<Vec<_>>()
+        };
+        // Attempts read "5/4"
+        assert_eq!(widths(0.0), vec![2, 3, 2]);
+        assert_eq!(widths(COUNT_UP_SECS), vec![2, 3, 2]);
+    }
+
+    #[test]
+    fn test_invalid_rate_counts_against_every_move() {
+        assert_eq!(LevelTally::default().invalid_rate(), 0.0);
+        let tally = LevelTally {
+            moves: 6,
+            invalid_moves: 2,
+            ..default()
+        };
+        assert_eq!(tally.invalid_rate(), 0.25);
+    }
+}
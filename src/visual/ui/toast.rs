@@ -0,0 +1,215 @@
+//! Toasts: short seven-segment messages ("found", "pb") that rise in from below the
+//! board, hold a moment, then melt away through the segment flow animation.
+//!
+//! They draw in their own instance slots at the end of the HUD material, so they
+//! never take a slot (or a transition) from the counters.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    camera::CameraBounds,
+    visual::{interactions::SolutionFound, sdf::seven_segment::HudInstance, utils::Easing},
+};
+
+use super::{
+    hud_builder::build_instances_for_group,
+    number_group::{HudAnchor, HudGroup, HudJustify, HudStyle, tokens_for_text},
+};
+
+/// Instance slots kept for toasts (the longest message they show)
+pub const TOAST_SLOTS: usize = 8;
+
+/// Toasts waiting beyond this many are dropped rather than shown late
+const MAX_PENDING: usize = 3;
+
+/// Real seconds a toast takes to slide down into place
+const SLIDE_SECS: f32 = 0.35;
+
+/// Real seconds it holds still
+const HOLD_SECS: f32 = 1.2;
+
+/// Real seconds it takes to melt away
+const MELT_SECS: f32 = 0.8;
+
+/// Where a toast slides in from and comes to rest (HUD anchor heights): bottom center,
+/// a row above the bottom counters, where no HUD group sits (the score and combo hold
+/// the top center, the level summary the middle)
+const START_V: f32 = -0.15;
+const REST_V: f32 = 0.12;
+
+/// Toast letters sit between the corner counters and the summary's digits in size
+const TOAST_STYLE: HudStyle = HudStyle {
+    digit_scale: 0.3,
+    digit_spacing: 0.5,
+    slash_spacing: 0.0,
+};
+
+/// Resource: Toasts waiting to show, and the one showing now
+#[derive(Resource, Debug, Default)]
+pub struct ToastQueue {
+    pending: VecDeque<String>,
+    /// Text showing and real seconds it's been up
+    showing: Option<(String, f32)>,
+}
+
+impl ToastQueue {
+    /// Queue a message (cut to `TOAST_SLOTS` characters; see `glyph_mask` for what
+    /// seven segments can spell)
+    pub fn push(&mut self, text: &str) {
+        if self.pending.len() >= MAX_PENDING {
+            return;
+        }
+        self.pending
+            .push_back(text.chars().take(TOAST_SLOTS).collect());
+    }
+
+    /// Nothing showing or waiting
+    pub fn is_idle(&self) -> bool {
+        self.showing.is_none() && self.pending.is_empty()
+    }
+
+    /// The message showing now
+    pub fn showing(&self) -> Option<&str> {
+        self.showing.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    /// Age the toast showing by `dt`, moving on to the next once it has melted
+    pub fn tick(&mut self, dt: f32) {
+        if let Some((_, age)) = &mut self.showing {
+            *age += dt;
+            if *age < SLIDE_SECS + HOLD_SECS + MELT_SECS {
+                return;
+            }
+        }
+        self.showing = self.pending.pop_front().map(|text| (text, 0.0));
+    }
+
+    /// The showing toast's instances, placed in `bounds`
+    pub fn instances(&self, bounds: &CameraBounds) -> Vec<HudInstance> {
+        let Some((text, age)) = &self.showing else {
+            return Vec::new();
+        };
+        let (v, melt) = toast_motion(*age);
+
+        let group = HudGroup {
+            anchor: HudAnchor {
+                h: 0.5,
+                v,
+                padding: 0.05,
+            },
+            justify: HudJustify::Center,
+            tokens: tokens_for_text(text),
+        };
+        let mut instances = Vec::new();
+        build_instances_for_group(bounds, &group, TOAST_STYLE, &mut instances);
+
+        // Melting is a transition to blank, cascading letter by letter
+        if melt > 0.0 {
+            for inst in &mut instances {
+                inst.from_mask = inst.mask;
+                inst.mask = 0;
+                inst.transition_progress = melt;
+                inst.easing = Easing::InOutQuad.shader_value();
+                inst.stagger = 0.3;
+            }
+        }
+        instances.truncate(TOAST_SLOTS);
+        instances
+    }
+}
+
+/// Anchor height and melt progress of a toast `age` real seconds in
+fn toast_motion(age: f32) -> (f32, f32) {
+    let slide = Easing::OutCubic.apply(age / SLIDE_SECS);
+    let melt = ((age - SLIDE_SECS - HOLD_SECS) / MELT_SECS).clamp(0.0, 1.0);
+    (START_V + (REST_V - START_V) * slide, melt)
+}
+
+/// System: Toast each solution the player hadn't found before
+pub fn toast_new_solutions(
+    mut solutions: MessageReader<SolutionFound>,
+    mut toasts: ResMut<ToastQueue>,
+) {
+    for solution in solutions.read() {
+        if solution.is_new {
+            toasts.push("found");
+        }
+    }
+}
+
+/// System: Move the toasts along (real time, so they finish while paused)
+pub fn advance_toasts(time: Res<Time<Real>>, mut toasts: ResMut<ToastQueue>) {
+    if toasts.is_idle() {
+        return;
+    }
+    toasts.tick(time.delta_secs());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts_show_one_after_another() {
+        let mut toasts = ToastQueue::default();
+        toasts.push("found");
+        toasts.push("pb");
+        assert!(toasts.showing().is_none());
+
+        toasts.tick(0.0);
+        assert_eq!(toasts.showing(), Some("found"));
+
+        toasts.tick(SLIDE_SECS + HOLD_SECS);
+        assert_eq!(toasts.showing(), Some("found"));
+        toasts.tick(MELT_SECS);
+        assert_eq!(toasts.showing(), Some("pb"));
+
+        toasts.tick(SLIDE_SECS + HOLD_SECS + MELT_SECS);
+        assert!(toasts.is_idle());
+    }
+
+    #[test]
+    fn test_toasts_slide_in_hold_then_melt() {
+        assert_eq!(toast_motion(0.0), (START_V, 0.0));
+        for resting in [SLIDE_SECS, SLIDE_SECS + HOLD_SECS] {
+            let (v, melt) = toast_motion(resting);
+            assert!((v - REST_V).abs() < 1e-6);
+            assert_eq!(melt, 0.0);
+        }
+
+        let (_, melting) = toast_motion(SLIDE_SECS + HOLD_SECS + MELT_SECS * 0.5);
+        assert!(melting > 0.0 && melting < 1.0);
+    }
+
+    #[test]
+    fn test_resting_toast_clears_the_hud_groups() {
+        use crate::visual::ui::number_group::{
+            combo_group, hint_tokens_group, remaining_edges_group, score_group,
+        };
+
+        // Counter rows sit 0.1 apart; a toast keeps at least that from each of them
+        for group in [
+            score_group(0),
+            combo_group(2),
+            hint_tokens_group(1),
+            remaining_edges_group(3),
+        ] {
+            assert!((group.anchor.v - REST_V).abs() >= 0.1, "{:?}", group.anchor);
+        }
+    }
+
+    #[test]
+    fn test_long_or_piled_up_toasts_are_cut() {
+        let mut toasts = ToastQueue::default();
+        toasts.push("achievement");
+        for _ in 0..MAX_PENDING {
+            toasts.push("pb");
+        }
+        assert_eq!(toasts.pending.len(), MAX_PENDING);
+
+        toasts.tick(0.0);
+        assert_eq!(toasts.showing().map(str::len), Some(TOAST_SLOTS));
+    }
+}
@@ -0,0 +1,87 @@
+//! Easing curves and staggering, mirrored by the HUD shader (`ease` and `staggered`
+//! in `seven_segment.wgsl`; keep the two in step).
+
+/// An easing curve from 0.0 to 1.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Slow start
+    InCubic,
+    /// Slow end
+    OutCubic,
+    /// Slow start and end
+    InOutQuad,
+}
+
+impl Easing {
+    /// Eased value of `t` (clamped to 0.0..=1.0)
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::InCubic => t * t * t,
+            Self::OutCubic => 1.0 - (1.0 - t).powi(3),
+            Self::InOutQuad if t < 0.5 => 2.0 * t * t,
+            Self::InOutQuad => 1.0 - 2.0 * (1.0 - t).powi(2),
+        }
+    }
+
+    /// The shader's easing id (0 is left for its per-blob mix of curves)
+    pub fn shader_value(self) -> u32 {
+        match self {
+            Self::Linear => 1,
+            Self::InCubic => 2,
+            Self::OutCubic => 3,
+            Self::InOutQuad => 4,
+        }
+    }
+}
+
+/// Progress of `slot` out of `slots` when their starts are spread across the first
+/// `stagger` of the run, so slot 0 leads and every slot still finishes at 1.0
+pub fn staggered(progress: f32, slot: u32, slots: u32, stagger: f32) -> f32 {
+    let stagger = stagger.clamp(0.0, 0.95);
+    let delay = stagger * slot as f32 / slots.saturating_sub(1).max(1) as f32;
+    ((progress - delay) / (1.0 - stagger)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easings_span_zero_to_one() {
+        for easing in [
+            Easing::Linear,
+            Easing::InCubic,
+            Easing::OutCubic,
+            Easing::InOutQuad,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert!(easing.apply(0.25) <= easing.apply(0.75));
+        }
+        assert!(Easing::InCubic.apply(0.5) < 0.5);
+        assert!(Easing::OutCubic.apply(0.5) > 0.5);
+        assert_eq!(Easing::InOutQuad.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_stagger_cascades_and_finishes_together() {
+        // No stagger: every slot moves in lockstep
+        assert_eq!(staggered(0.4, 0, 4, 0.0), 0.4);
+        assert_eq!(staggered(0.4, 3, 4, 0.0), 0.4);
+
+        // Earlier slots lead, the last waits out the whole stagger
+        let early = staggered(0.3, 0, 4, 0.5);
+        let late = staggered(0.3, 3, 4, 0.5);
+        assert!(early > late);
+        assert_eq!(late, 0.0);
+
+        for slot in 0..4 {
+            assert_eq!(staggered(1.0, slot, 4, 0.5), 1.0);
+        }
+        // A lone slot starts right away
+        assert_eq!(staggered(0.5, 0, 1, 0.5), 1.0);
+    }
+}
@@ -0,0 +1,711 @@
+// web.rs - browser integration for the wasm build (`--features wasm`)
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use wasm_bindgen::{JsCast, JsValue, closure::Closure};
+use web_sys::{
+    AddEventListenerOptions, Blob, BlobPropertyBag, DeviceMotionEvent, Event, Headers,
+    HtmlAnchorElement, RequestInit, Storage, Url,
+};
+
+use crate::{
+    camera::CameraTilt,
+    challenge::Challenge,
+    game::{
+        clock::GameClock,
+        progression::ProgressionTracker,
+        puzzle::{
+            JsonText, NextCustomPuzzle, PuzzleLibrary, RemotePuzzleSource, import_custom_puzzles,
+            play_custom_puzzle, receive_remote_puzzles, request_remote_puzzles,
+            setup_puzzle_library,
+        },
+        rng::GameRng,
+        session::PuzzleSession,
+        skip::{PuzzleChange, change_puzzle},
+    },
+    graph::Valences,
+    net::{RoomLink, RoomMode, Spectator},
+    profile::load_profiles,
+    settings::Settings,
+    visual::{
+        interactions::{
+            HistoryStep, SolutionFound, apply_history_steps, handle_pointer_input,
+            pointer::DragState,
+        },
+        setup::{check_level_progression, setup_puzzle},
+        ui::Locale,
+    },
+};
+
+/// The canvas in index.html the game renders into
+pub const CANVAS_SELECTOR: &str = "#valence-canvas";
+
+/// localStorage key that held the player's level before profiles
+const LEVEL_KEY: &str = "valence_sdf.level";
+
+/// localStorage key holding imported custom puzzles (as `{"puzzle": [...]}` JSON)
+const CUSTOM_PUZZLES_KEY: &str = "valence_sdf.custom_puzzles";
+
+/// localStorage key holding the anonymous id daily scores are posted under
+const PLAYER_KEY: &str = "valence_sdf.player";
+
+/// Query flag that opens the shared puzzle of the day (`/?daily`)
+const DAILY_QUERY: &str = "daily";
+
+/// Level the daily puzzle is drawn from; everyone gets the same one for a given date
+const DAILY_LEVEL: usize = 30;
+
+/// Query parameter that turns anonymous telemetry on or off (`?telemetry=on`)
+const TELEMETRY_QUERY: &str = "telemetry";
+
+/// localStorage key remembering the telemetry opt-in
+const TELEMETRY_KEY: &str = "valence_sdf.telemetry";
+
+/// Query parameter naming a shared-board room to join (`?room=<id>`)
+const ROOM_QUERY: &str = "room";
+
+/// Query parameter naming a room to race in (`?race=<id>`)
+const RACE_QUERY: &str = "race";
+
+/// Query parameter naming a room to watch without playing (`?spectate=<id>`)
+const SPECTATE_QUERY: &str = "spectate";
+
+/// Query parameter holding a friend's puzzle code to play (`?puzzle=<code>`)
+const PUZZLE_QUERY: &str = "puzzle";
+
+/// Level every race starts from, so racers meet on the same puzzles
+const RACE_LEVEL: usize = 10;
+
+/// Mirrors `DragState::is_dragging` for the canvas touch listener (runs outside the ECS)
+static DRAG_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Latest gravity reading from the phone's motion sensor, as f32 bits (the devicemotion
+/// listener runs outside the ECS)
+static MOTION_X: AtomicU32 = AtomicU32::new(0);
+static MOTION_Y: AtomicU32 = AtomicU32::new(0);
+static MOTION_SEEN: AtomicBool = AtomicBool::new(false);
+
+/// Whether the page is hidden, kept by a visibilitychange listener (runs outside the ECS)
+static PAGE_HIDDEN: AtomicBool = AtomicBool::new(false);
+
+/// Standard gravity (m/s²): a full tip of the phone moves a gravity axis this much
+const GRAVITY: f32 = 9.81;
+
+/// How much of a full tip leans the camera all the way
+const MOTION_TILT_RANGE: f32 = 0.3;
+
+/// The undo/redo, skip and Custom tab buttons in index.html
+const UNDO_BUTTON_SELECTOR: &str = "#undo-button";
+const REDO_BUTTON_SELECTOR: &str = "#redo-button";
+const SKIP_BUTTON_SELECTOR: &str = "#skip-button";
+const CUSTOM_BUTTON_SELECTOR: &str = "#custom-button";
+
+/// Button taps waiting for the next frame (the click listeners run outside the ECS)
+static UNDO_TAPS: AtomicUsize = AtomicUsize::new(0);
+static REDO_TAPS: AtomicUsize = AtomicUsize::new(0);
+static SKIP_TAPS: AtomicUsize = AtomicUsize::new(0);
+static CUSTOM_TAPS: AtomicUsize = AtomicUsize::new(0);
+
+pub struct WebPlugin;
+
+impl Plugin for WebPlugin {
+    fn build(&self, app: &mut App) {
+        // Puzzles from the server's catalog top up the embedded library
+        app.insert_resource(RemotePuzzleSource::new(""))
+            .add_systems(
+                PreStartup,
+                (
+                    start_daily_run,
+                    restore_telemetry_choice,
+                    join_room,
+                    accept_puzzle_link,
+                )
+                    .chain()
+                    .after(load_profiles),
+            )
+            .add_systems(
+                Startup,
+                (
+                    block_touch_scroll,
+                    listen_buttons,
+                    listen_device_motion,
+                    listen_visibility,
+                    detect_locale,
+                ),
+            )
+            .add_systems(Update, forward_history_taps.before(apply_history_steps))
+            .add_systems(Update, forward_skip_taps.before(change_puzzle))
+            .add_systems(Update, forward_custom_taps.before(play_custom_puzzle))
+            .add_systems(Startup, pin_daily_puzzle.after(setup_puzzle))
+            .add_systems(
+                Startup,
+                restore_custom_puzzles
+                    .after(setup_puzzle_library)
+                    .before(setup_puzzle),
+            )
+            .add_systems(
+                Update,
+                (
+                    sync_drag_lock,
+                    forward_device_tilt,
+                    pause_when_hidden,
+                    share_puzzle_code,
+                    paste_custom_puzzles,
+                ),
+            )
+            .add_systems(Update, (request_remote_puzzles, receive_remote_puzzles))
+            .add_systems(
+                Update,
+                report_daily_score
+                    .after(handle_pointer_input)
+                    .before(check_level_progression),
+            );
+    }
+}
+
+/// Resource: Present while playing the daily puzzle (`/?daily`)
+#[derive(Resource, Debug)]
+pub struct DailyRun {
+    /// UTC date the puzzle belongs to, "YYYY-MM-DD"
+    date: String,
+    player: String,
+    /// The day's puzzle, and the (real) time it went up, once it's on the board
+    puzzle: Option<(Valences, Duration)>,
+    solutions_found: u32,
+    /// Set once the daily puzzle is left; later puzzles aren't scored
+    finished: bool,
+}
+
+/// Render into the page's canvas and follow its CSS size
+///
+/// The browser's resizes then arrive as `WindowResized`, which `GameCamera` already
+/// letterboxes to the portrait aspect.
+pub fn configure_window(window: Window) -> Window {
+    Window {
+        canvas: Some(CANVAS_SELECTOR.into()),
+        fit_canvas_to_parent: true,
+        ..window
+    }
+}
+
+/// Offer `contents` to the player as a file download (a blob URL on a clicked link)
+pub fn download(filename: &str, contents: &[u8], mime: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime);
+    let url = match Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .and_then(|blob| Url::create_object_url_with_blob(&blob))
+    {
+        Ok(url) => url,
+        Err(err) => {
+            warn!("Failed to prepare {} for download: {:?}", filename, err);
+            return;
+        }
+    };
+
+    if let Ok(link) = document
+        .create_element("a")
+        .map(|element| element.unchecked_into::<HtmlAnchorElement>())
+    {
+        link.set_href(&url);
+        link.set_download(filename);
+        link.click();
+    }
+    // The URL isn't revoked: doing it right after the click can cancel the download
+}
+
+/// Ask the player for text in the browser's prompt box (None if they cancel)
+pub fn prompt(message: &str, default: &str) -> Option<String> {
+    web_sys::window()?
+        .prompt_with_message_and_default(message, default)
+        .ok()
+        .flatten()
+}
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// A value kept in localStorage on an earlier visit
+pub fn read_storage(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok().flatten()
+}
+
+/// Keep a value in localStorage for later visits
+pub fn write_storage(key: &str, value: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or_else(|| "localStorage is unavailable".to_string())?
+        .set_item(key, value)
+        .map_err(|err| format!("{:?}", err))
+}
+
+/// The level saved before profiles existed, removed once read (it moves into a profile)
+pub fn take_legacy_level() -> Option<usize> {
+    let storage = local_storage()?;
+    let level = storage
+        .get_item(LEVEL_KEY)
+        .ok()
+        .flatten()?
+        .parse::<usize>()
+        .ok();
+    let _ = storage.remove_item(LEVEL_KEY);
+    level
+}
+
+/// System: Bring back custom puzzles imported on an earlier visit
+fn restore_custom_puzzles(mut library: ResMut<PuzzleLibrary>) {
+    if let Some(json) =
+        local_storage().and_then(|storage| storage.get_item(CUSTOM_PUZZLES_KEY).ok().flatten())
+    {
+        import_custom_puzzles(&mut library, &JsonText(json));
+    }
+}
+
+/// System: I opens a box for pasting custom puzzles as JSON, kept for later visits
+fn paste_custom_puzzles(keys: Res<ButtonInput<KeyCode>>, mut library: ResMut<PuzzleLibrary>) {
+    if !keys.just_pressed(KeyCode::KeyI) {
+        return;
+    }
+    let Some(json) = prompt(
+        r#"Paste custom puzzles: {"puzzle": [{"name": "...", "valences": [9 numbers]}]}"#,
+        "",
+    ) else {
+        return;
+    };
+    if import_custom_puzzles(&mut library, &JsonText(json)) > 0 {
+        save_custom_puzzles(&library);
+    }
+}
+
+/// Keep the library's custom puzzles in localStorage for later visits
+pub fn save_custom_puzzles(library: &PuzzleLibrary) {
+    let puzzles: Vec<_> = library
+        .custom_puzzles()
+        .iter()
+        .map(|entry| serde_json::json!({ "name": entry.name, "valences": entry.valences }))
+        .collect();
+    let json = serde_json::json!({ "puzzle": puzzles }).to_string();
+    if !matches!(
+        local_storage().map(|storage| storage.set_item(CUSTOM_PUZZLES_KEY, &json)),
+        Some(Ok(()))
+    ) {
+        warn!("Failed to save custom puzzles to localStorage");
+    }
+}
+
+/// System: Stop touch drags from scrolling/bouncing the page while a trail is drawn
+///
+/// Needs a non-passive listener, which Bevy's own canvas handlers don't install.
+fn block_touch_scroll() {
+    let Some(canvas) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.query_selector(CANVAS_SELECTOR).ok().flatten())
+    else {
+        warn!(
+            "Canvas {} not found; touch scrolling stays enabled",
+            CANVAS_SELECTOR
+        );
+        return;
+    };
+
+    let on_touch_move = Closure::<dyn FnMut(Event)>::new(|event: Event| {
+        if DRAG_ACTIVE.load(Ordering::Relaxed) {
+            event.prevent_default();
+        }
+    });
+
+    let options = AddEventListenerOptions::new();
+    options.set_passive(false);
+    let registered = canvas.add_event_listener_with_callback_and_add_event_listener_options(
+        "touchmove",
+        on_touch_move.as_ref().unchecked_ref(),
+        &options,
+    );
+    if registered.is_err() {
+        warn!("Failed to register touchmove listener");
+    }
+
+    // The listener lives as long as the page
+    on_touch_move.forget();
+}
+
+/// System: Count taps on the undo/redo, skip and Custom buttons for `forward_history_taps`,
+/// `forward_skip_taps` and `forward_custom_taps`
+fn listen_buttons() {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    for (selector, taps) in [
+        (UNDO_BUTTON_SELECTOR, &UNDO_TAPS),
+        (REDO_BUTTON_SELECTOR, &REDO_TAPS),
+        (SKIP_BUTTON_SELECTOR, &SKIP_TAPS),
+        (CUSTOM_BUTTON_SELECTOR, &CUSTOM_TAPS),
+    ] {
+        let Some(button) = document.query_selector(selector).ok().flatten() else {
+            warn!("Button {} not found; use the keyboard instead", selector);
+            continue;
+        };
+
+        let on_click = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+            taps.fetch_add(1, Ordering::Relaxed);
+        });
+        if button
+            .add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())
+            .is_err()
+        {
+            warn!("Failed to register click listener on {}", selector);
+        }
+
+        // The listener lives as long as the page
+        on_click.forget();
+    }
+}
+
+/// System: Record the phone's gravity reading for `forward_device_tilt`
+///
+/// Desktop browsers never fire devicemotion, so the cursor keeps driving the tilt there.
+fn listen_device_motion() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let on_motion = Closure::<dyn FnMut(Event)>::new(|event: Event| {
+        let Some(gravity) = event
+            .dyn_ref::<DeviceMotionEvent>()
+            .and_then(|motion| motion.acceleration_including_gravity())
+        else {
+            return;
+        };
+        let (Some(x), Some(y)) = (gravity.x(), gravity.y()) else {
+            return;
+        };
+        MOTION_X.store((x as f32).to_bits(), Ordering::Relaxed);
+        MOTION_Y.store((y as f32).to_bits(), Ordering::Relaxed);
+        MOTION_SEEN.store(true, Ordering::Relaxed);
+    });
+    if window
+        .add_event_listener_with_callback("devicemotion", on_motion.as_ref().unchecked_ref())
+        .is_err()
+    {
+        warn!("Failed to register devicemotion listener");
+    }
+
+    // The listener lives as long as the page
+    on_motion.forget();
+}
+
+/// System: Lean the perspective camera with the phone, relative to how it was first held
+fn forward_device_tilt(
+    settings: Res<Settings>,
+    mut tilt: ResMut<CameraTilt>,
+    mut held: Local<Option<Vec2>>,
+) {
+    if !MOTION_SEEN.load(Ordering::Relaxed) || !settings.perspective || settings.reduced_motion {
+        return;
+    }
+    let gravity = Vec2::new(
+        f32::from_bits(MOTION_X.load(Ordering::Relaxed)),
+        f32::from_bits(MOTION_Y.load(Ordering::Relaxed)),
+    );
+    let held = *held.get_or_insert(gravity);
+
+    // Gravity shifts toward the edge tipped down; the board leans that way
+    let target =
+        (-(gravity - held) / (GRAVITY * MOTION_TILT_RANGE)).clamp(Vec2::NEG_ONE, Vec2::ONE);
+    tilt.set_if_neq(CameraTilt {
+        target,
+        from_motion: true,
+        ..*tilt
+    });
+}
+
+/// System: Turn button taps into undo/redo steps
+fn forward_history_taps(mut steps: MessageWriter<HistoryStep>) {
+    for _ in 0..UNDO_TAPS.swap(0, Ordering::Relaxed) {
+        steps.write(HistoryStep::Undo);
+    }
+    for _ in 0..REDO_TAPS.swap(0, Ordering::Relaxed) {
+        steps.write(HistoryStep::Redo);
+    }
+}
+
+/// System: Turn skip button taps into puzzle skips
+fn forward_skip_taps(mut changes: MessageWriter<PuzzleChange>) {
+    for _ in 0..SKIP_TAPS.swap(0, Ordering::Relaxed) {
+        changes.write(PuzzleChange::Skip);
+    }
+}
+
+/// System: Turn Custom button taps into requests for the next custom puzzle
+fn forward_custom_taps(mut next: MessageWriter<NextCustomPuzzle>) {
+    for _ in 0..CUSTOM_TAPS.swap(0, Ordering::Relaxed) {
+        next.write(NextCustomPuzzle);
+    }
+}
+
+/// System: Publish drag state to the touch listener
+fn sync_drag_lock(drag: Res<DragState>) {
+    if drag.is_changed() {
+        DRAG_ACTIVE.store(drag.is_dragging, Ordering::Relaxed);
+    }
+}
+
+/// System: Record the page's visibility for `pause_when_hidden` as it changes
+fn listen_visibility() {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    PAGE_HIDDEN.store(document.hidden(), Ordering::Relaxed);
+
+    let watched = document.clone();
+    let on_change = Closure::<dyn FnMut(Event)>::new(move |_: Event| {
+        PAGE_HIDDEN.store(watched.hidden(), Ordering::Relaxed);
+    });
+    if document
+        .add_event_listener_with_callback("visibilitychange", on_change.as_ref().unchecked_ref())
+        .is_err()
+    {
+        warn!("Failed to register visibilitychange listener");
+    }
+
+    // The listener lives as long as the page
+    on_change.forget();
+}
+
+/// System: Pause gameplay while the tab is hidden, resuming only if we paused it
+///
+/// Browsers throttle hidden tabs to a frame or so per second; pausing keeps physics from
+/// taking huge steps in the background.
+fn pause_when_hidden(mut clock: ResMut<GameClock>, mut paused_by_us: Local<bool>) {
+    let hidden = PAGE_HIDDEN.load(Ordering::Relaxed);
+
+    if hidden && !clock.paused {
+        clock.paused = true;
+        *paused_by_us = true;
+    } else if !hidden && *paused_by_us {
+        clock.paused = false;
+        *paused_by_us = false;
+    }
+}
+
+/// System: Format the HUD for the browser's language (grouping, right-to-left)
+fn detect_locale(mut locale: ResMut<Locale>) {
+    let Some(language) = web_sys::window().and_then(|window| window.navigator().language()) else {
+        return;
+    };
+    *locale = Locale::for_language(&language);
+    if locale.right_to_left {
+        info!("🔢 Mirroring the HUD for {}", language);
+    }
+}
+
+/// System: Switch to the puzzle of the day when the page was opened with `?daily`
+///
+/// Seeding `GameRng` from the date gives every player the same puzzle and symmetry.
+/// Runs after `load_profiles` so the daily level wins over the profile's.
+fn start_daily_run(mut commands: Commands) {
+    if query_param(DAILY_QUERY).is_none() {
+        return;
+    }
+
+    let date: String = js_sys::Date::new_0().to_iso_string().into();
+    let date = date[..10].to_string();
+    let seed = date.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    info!("📅 Daily puzzle for {}", date);
+    commands.insert_resource(GameRng::from_seed(seed));
+    commands.insert_resource(ProgressionTracker::at_level(DAILY_LEVEL));
+    commands.insert_resource(DailyRun {
+        date,
+        player: player_id(),
+        puzzle: None,
+        solutions_found: 0,
+        finished: false,
+    });
+}
+
+/// System: Note which puzzle the daily run put up, and when, so only its solutions are
+/// posted and timed from its start
+fn pin_daily_puzzle(
+    session: Res<PuzzleSession>,
+    time: Res<Time<Real>>,
+    daily: Option<ResMut<DailyRun>>,
+) {
+    if let Some(mut daily) = daily {
+        daily.puzzle = Some((session.puzzle_valences().clone(), time.elapsed()));
+    }
+}
+
+/// Value of a page URL query parameter ("" for a bare flag like `?daily`)
+fn query_param(name: &str) -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|param| match param.split_once('=') {
+            Some((key, value)) if key == name => Some(value.to_string()),
+            None if param == name => Some(String::new()),
+            _ => None,
+        })
+}
+
+/// System: Apply the telemetry opt-in, remembering `?telemetry=on|off` across visits
+fn restore_telemetry_choice(mut settings: ResMut<Settings>) {
+    let storage = local_storage();
+    match query_param(TELEMETRY_QUERY).as_deref() {
+        Some("on") => {
+            let _ = storage.as_ref().map(|s| s.set_item(TELEMETRY_KEY, "on"));
+        }
+        Some("off") => {
+            let _ = storage.as_ref().map(|s| s.remove_item(TELEMETRY_KEY));
+        }
+        _ => {}
+    }
+
+    settings.telemetry_opt_in = storage
+        .and_then(|storage| storage.get_item(TELEMETRY_KEY).ok().flatten())
+        .is_some_and(|value| value == "on");
+    if settings.telemetry_opt_in {
+        info!("📊 Anonymous telemetry enabled (?telemetry=off to stop)");
+    }
+}
+
+/// System: Queue the board from `?puzzle=<code>` (see `crate::challenge`)
+fn accept_puzzle_link(mut challenge: ResMut<Challenge>) {
+    if let Some(code) = query_param(PUZZLE_QUERY) {
+        challenge.accept(&code);
+    }
+}
+
+/// System: L shows the code to share in a paste box, which also takes a friend's code
+fn share_puzzle_code(
+    keys: Res<ButtonInput<KeyCode>>,
+    session: Res<PuzzleSession>,
+    mut challenge: ResMut<Challenge>,
+) {
+    if !keys.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+
+    let code = challenge.share_code(&session);
+    if let Some(pasted) = prompt(
+        "Copy this code to challenge a friend, or paste theirs to play it:",
+        &code,
+    ) && pasted.trim() != code
+    {
+        challenge.accept(&pasted);
+    }
+}
+
+/// System: Join the room named by `?room=<id>` (shared board), `?race=<id>` (race) or
+/// `?spectate=<id>` (watch a shared board)
+///
+/// Co-op players draw on one puzzle, whichever was in the room first. Racers all start
+/// from `RACE_LEVEL` and get the same puzzle at each level. Spectators only follow.
+fn join_room(mut commands: Commands) {
+    let joined = [
+        (ROOM_QUERY, RoomMode::Coop),
+        (RACE_QUERY, RoomMode::Race),
+        (SPECTATE_QUERY, RoomMode::Spectate),
+    ]
+    .into_iter()
+    .find_map(|(name, mode)| query_param(name).map(|room| (room, mode)));
+    let Some((room, mode)) = joined else {
+        return;
+    };
+    if !crate::sync_rules::is_id(&room) {
+        warn!("Ignoring room {:?}: use up to 32 of [A-Za-z0-9_-]", room);
+        return;
+    }
+
+    info!("🔗 Joining room {} ({:?})", room, mode);
+    match mode {
+        RoomMode::Race => commands.insert_resource(ProgressionTracker::at_level(RACE_LEVEL)),
+        RoomMode::Spectate => commands.insert_resource(Spectator),
+        RoomMode::Coop => {}
+    }
+    commands.insert_resource(RoomLink::connect(room, mode));
+}
+
+/// Anonymous, stable-per-browser id for the leaderboard
+pub(crate) fn player_id() -> String {
+    let storage = local_storage();
+    if let Some(id) = storage
+        .as_ref()
+        .and_then(|storage| storage.get_item(PLAYER_KEY).ok().flatten())
+    {
+        return id;
+    }
+
+    let random = || (js_sys::Math::random() * u32::MAX as f64) as u32;
+    let id = format!("p{:08x}{:08x}", random(), random());
+    if let Some(storage) = storage {
+        let _ = storage.set_item(PLAYER_KEY, &id);
+    }
+    id
+}
+
+/// System: Post each new solution to the daily puzzle to the server leaderboard
+///
+/// Runs before `check_level_progression` so the solve that finishes the level is still
+/// scored against the daily puzzle. Times run from when the puzzle went up.
+fn report_daily_score(
+    mut solutions: MessageReader<SolutionFound>,
+    session: Res<PuzzleSession>,
+    daily: Option<ResMut<DailyRun>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut daily) = daily else {
+        solutions.clear();
+        return;
+    };
+    let Some((puzzle, started)) = daily.puzzle.clone() else {
+        solutions.clear();
+        return;
+    };
+    if daily.finished || *session.puzzle_valences() != puzzle {
+        daily.finished = true;
+        solutions.clear();
+        return;
+    }
+
+    let new_solutions = solutions.read().filter(|found| found.is_new).count() as u32;
+    if new_solutions == 0 {
+        return;
+    }
+    daily.solutions_found += new_solutions;
+
+    let body = serde_json::json!({
+        "player": daily.player,
+        "time_ms": time.elapsed().saturating_sub(started).as_millis() as u64,
+        "solutions_found": daily.solutions_found,
+    });
+    post_json(&format!("/api/daily/{}/scores", daily.date), &body);
+}
+
+/// Fire-and-forget JSON POST to the game's own server
+fn post_json(url: &str, body: &serde_json::Value) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_body(&JsValue::from_str(&body.to_string()));
+    if let Ok(headers) = Headers::new() {
+        let _ = headers.set("Content-Type", "application/json");
+        init.set_headers(&headers);
+    }
+
+    // Scores are best-effort; a failed request only shows up in the browser console
+    let _ = window.fetch_with_str_and_init(url, &init);
+}
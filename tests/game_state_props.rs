@@ -0,0 +1,134 @@
+//! Property tests for `GameState`: random puzzles driven by random move sequences must
+//! keep the valence/edge bookkeeping consistent no matter what the player tries.
+
+use proptest::prelude::*;
+
+use valence_sdf::graph::{Edge, GameState, MoveResult, NodeId, Valences};
+
+#[derive(Debug, Clone, Copy)]
+enum Move {
+    Add(usize),
+    Undo,
+    Reset,
+}
+
+fn valences_strategy() -> impl Strategy<Value = Valences> {
+    prop::array::uniform9(0usize..=4).prop_map(Valences::from_array)
+}
+
+fn move_strategy() -> impl Strategy<Value = Move> {
+    // Mostly adds so trails actually get long; undo/reset often enough to interleave
+    prop_oneof![
+        8 => (0usize..9).prop_map(Move::Add),
+        2 => Just(Move::Undo),
+        1 => Just(Move::Reset),
+    ]
+}
+
+fn apply(state: &mut GameState, mv: Move) {
+    match mv {
+        Move::Add(index) => {
+            state.add_node(NodeId(index));
+        }
+        Move::Undo => {
+            state.pop_node();
+        }
+        Move::Reset => state.reset(),
+    }
+}
+
+/// Everything observable about a state, for before/after comparisons
+fn snapshot(state: &GameState) -> (Valences, Vec<NodeId>, Vec<Edge>) {
+    (
+        state.valences().clone(),
+        state.current_trail().to_vec(),
+        state.edges().edges_in_order().to_vec(),
+    )
+}
+
+fn assert_invariants(state: &GameState) -> Result<(), TestCaseError> {
+    let puzzle = state.puzzle_valences();
+
+    for index in 0..9 {
+        let node = NodeId(index);
+        let remaining = state.valence(node);
+        let initial = puzzle.get(node);
+        prop_assert!(
+            remaining <= initial,
+            "node {index} went above its puzzle valence"
+        );
+        // Every drawn edge at a node spends exactly one unit of its valence
+        prop_assert_eq!(initial - remaining, state.edge_degree(node));
+    }
+
+    let spent = state.puzzle_total_valence() - state.total_remaining_valence();
+    prop_assert_eq!(spent % 2, 0);
+    prop_assert_eq!(state.edge_count(), spent / 2);
+
+    // The trail is the edges plus its starting node
+    let trail = state.current_trail().len();
+    prop_assert_eq!(state.edge_count(), trail.saturating_sub(1));
+
+    // ...and the state's own check agrees
+    prop_assert_eq!(state.verify_invariants(), Ok(()));
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn valence_bookkeeping_holds_after_every_move(
+        valences in valences_strategy(),
+        moves in prop::collection::vec(move_strategy(), 0..64),
+    ) {
+        let mut state = GameState::new(valences);
+        assert_invariants(&state)?;
+
+        for mv in moves {
+            apply(&mut state, mv);
+            assert_invariants(&state)?;
+        }
+    }
+
+    #[test]
+    fn undo_is_inverse_of_add(
+        valences in valences_strategy(),
+        moves in prop::collection::vec(move_strategy(), 0..32),
+        next in 0usize..9,
+    ) {
+        let mut state = GameState::new(valences);
+        for mv in moves {
+            apply(&mut state, mv);
+        }
+
+        let before = snapshot(&state);
+        match state.add_node(NodeId(next)) {
+            MoveResult::Invalid(_) => {
+                // Rejected moves must not touch anything
+                prop_assert_eq!(snapshot(&state), before);
+            }
+            _ => {
+                state.pop_node();
+                prop_assert_eq!(snapshot(&state), before);
+            }
+        }
+    }
+
+    #[test]
+    fn reset_restores_puzzle_valences(
+        valences in valences_strategy(),
+        moves in prop::collection::vec(move_strategy(), 0..64),
+    ) {
+        let mut state = GameState::new(valences.clone());
+        for mv in moves {
+            apply(&mut state, mv);
+        }
+
+        state.reset();
+
+        prop_assert_eq!(state.valences(), &valences);
+        prop_assert_eq!(state.puzzle_valences(), &valences);
+        prop_assert!(state.current_trail().is_empty());
+        prop_assert_eq!(state.edge_count(), 0);
+    }
+}
@@ -0,0 +1,280 @@
+//! Golden image tests: known SDF scene and HUD states rendered to a texture and compared
+//! with the PNGs in `tests/goldens/`, catching shader and uniform-layout regressions.
+//!
+//! Native only and needs a GPU adapter, so they sit behind the `golden` feature:
+//! `cargo test --features golden --test golden`. A missing golden fails the test;
+//! `UPDATE_GOLDENS=1` writes them all (first run, or after an intended visual change),
+//! and the PNGs are committed with the change.
+#![cfg(feature = "golden")]
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::asset::RenderAssetUsages;
+use bevy::camera::{RenderTarget, ScalingMode};
+use bevy::image::{BevyDefault, CompressedImageFormats, ImageSampler, ImageType};
+use bevy::prelude::*;
+use bevy::render::render_resource::TextureFormat;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+
+use valence_sdf::{
+    camera::CameraBounds,
+    visual::{
+        sdf::edges::cylinder::SdfCylinder,
+        sdf::material::{SdfMaterialPlugin, SdfSceneMaterial},
+        sdf::nodes::ellipsoid::SdfSphere,
+        sdf::seven_segment::{
+            HudInstance, SevenSegmentMaterial, SevenSegmentMaterialPlugin, glyph_mask,
+        },
+        theme::Theme,
+        ui::hud_builder::build_instances_for_group,
+        ui::number_group::{HudStyle, level_group, progress_group},
+    },
+};
+
+/// Rendered size: the game's 9:16 frame, small enough to keep the goldens light
+const WIDTH: u32 = 180;
+const HEIGHT: u32 = 320;
+
+/// A channel may drift this far (out of 255) before its pixel counts as changed
+const CHANNEL_TOLERANCE: u8 = 8;
+
+/// Share of pixels allowed to change (driver and rasterizer noise along edges)
+const PIXEL_TOLERANCE: f32 = 0.005;
+
+/// Frames to wait for shaders to compile and the capture to come back
+const MAX_FRAMES: usize = 600;
+
+/// The material plugins on the renderer, with no window and a clock stopped at zero
+/// so time-driven effects (breathing, the background) hold still
+fn render_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .build()
+            .disable::<WinitPlugin>()
+            .set(WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..default()
+            }),
+    )
+    .add_plugins((SdfMaterialPlugin, SevenSegmentMaterialPlugin))
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    app
+}
+
+/// A camera over the game's frame rendering into a fresh target image
+fn spawn_camera(app: &mut App) -> Handle<Image> {
+    let target = Image::new_target_texture(WIDTH, HEIGHT, TextureFormat::bevy_default());
+    let target = app.world_mut().resource_mut::<Assets<Image>>().add(target);
+
+    let center = CameraBounds::from_fixed_aspect().center();
+    app.world_mut().spawn((
+        Camera3d::default(),
+        Camera {
+            target: RenderTarget::Image(target.clone().into()),
+            clear_color: ClearColorConfig::Custom(Color::BLACK),
+            ..default()
+        },
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: CameraBounds::from_fixed_aspect().height(),
+            },
+            near: -1000.0,
+            far: 1000.0,
+            ..OrthographicProjection::default_3d()
+        }),
+        Transform::from_xyz(center.x, center.y, 10.0).looking_at(center.extend(0.0), Vec3::Y),
+    ));
+    target
+}
+
+/// A plane covering the frame, turned from XZ to face the camera at depth `z`
+fn frame_plane(app: &mut App, z: f32) -> (Mesh3d, Transform) {
+    let bounds = CameraBounds::from_fixed_aspect();
+    let mesh = Plane3d::default()
+        .mesh()
+        .size(bounds.width(), bounds.height());
+    let mesh = app.world_mut().resource_mut::<Assets<Mesh>>().add(mesh);
+    let center = bounds.center();
+    (
+        Mesh3d(mesh),
+        Transform::from_xyz(center.x, center.y, z)
+            .with_rotation(Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+    )
+}
+
+/// Render frames until a screenshot of `target` comes back
+fn capture(app: &mut App, target: Handle<Image>, golden: &str) -> Image {
+    let captured = Arc::new(Mutex::new(None));
+    let slot = captured.clone();
+    app.world_mut().spawn(Screenshot::image(target)).observe(
+        move |capture: On<ScreenshotCaptured>| {
+            *slot.lock().unwrap() = Some(capture.image.clone());
+        },
+    );
+
+    for _ in 0..MAX_FRAMES {
+        app.update();
+        if let Some(image) = captured.lock().unwrap().take() {
+            return image;
+        }
+    }
+    panic!("no capture for {golden} after {MAX_FRAMES} frames (is there a GPU adapter?)");
+}
+
+fn updating_goldens() -> bool {
+    std::env::var_os("UPDATE_GOLDENS").is_some()
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/goldens")
+        .join(format!("{name}.png"))
+}
+
+/// Compare a capture with its golden, or write the golden under `UPDATE_GOLDENS`
+fn assert_matches_golden(app: &mut App, target: Handle<Image>, name: &str) {
+    let path = golden_path(name);
+    let rendered = capture(app, target, name);
+    let rendered = rendered.try_into_dynamic().unwrap().to_rgba8();
+    if updating_goldens() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        rendered.save(&path).unwrap();
+        eprintln!("wrote golden {}", path.display());
+        return;
+    }
+    assert!(
+        path.exists(),
+        "{name}: no golden at {} (UPDATE_GOLDENS=1 writes it; commit it with the change)",
+        path.display()
+    );
+
+    let golden = Image::from_buffer(
+        &std::fs::read(&path).unwrap(),
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    )
+    .unwrap();
+    let golden = golden.try_into_dynamic().unwrap().to_rgba8();
+    assert_eq!(golden.dimensions(), rendered.dimensions(), "{name}: size");
+
+    let changed = golden
+        .pixels()
+        .zip(rendered.pixels())
+        .filter(|(a, b)| {
+            a.0.iter()
+                .zip(b.0.iter())
+                .any(|(a, b)| a.abs_diff(*b) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    let share = changed as f32 / (WIDTH * HEIGHT) as f32;
+    assert!(
+        share <= PIXEL_TOLERANCE,
+        "{name}: {:.2}% of pixels differ from {} (UPDATE_GOLDENS=1 if that's intended)",
+        share * 100.0,
+        path.display()
+    );
+}
+
+#[test]
+fn test_sdf_scene_matches_golden() {
+    let mut app = render_app();
+    let target = spawn_camera(&mut app);
+
+    // Three nodes, two joined by an edge: blending, node colors and the digit path
+    let theme = Theme::default();
+    let bounds = CameraBounds::from_fixed_aspect();
+    let center = bounds.center().extend(0.0);
+    let mut material = SdfSceneMaterial {
+        theme: theme.to_uniform(),
+        ..default()
+    };
+    material.data.blend_smoothness = 0.15;
+    material.data.num_spheres = 3;
+    for (i, (offset, valence)) in [(-1.2, 2), (0.0, 1), (1.2, 3)].into_iter().enumerate() {
+        material.data.spheres[i] = SdfSphere {
+            center: center + Vec3::X * offset,
+            radius: 0.35,
+            color: theme.node_color(valence),
+            digit_value: valence as u32,
+            ..default()
+        };
+    }
+    material.data.num_cylinders = 1;
+    material.data.cylinders[0] = SdfCylinder {
+        start: material.data.spheres[0].center,
+        end: material.data.spheres[1].center,
+        radius: 0.08,
+        color: theme.node_color(2),
+        node_a_idx: 0,
+        node_b_idx: 1,
+        ..default()
+    };
+
+    let material = app
+        .world_mut()
+        .resource_mut::<Assets<SdfSceneMaterial>>()
+        .add(material);
+    let plane = frame_plane(&mut app, 0.0);
+    app.world_mut().spawn((plane, MeshMaterial3d(material)));
+
+    assert_matches_golden(&mut app, target, "sdf_scene");
+}
+
+#[test]
+fn test_hud_transitions_match_golden() {
+    let mut app = render_app();
+    let target = spawn_camera(&mut app);
+
+    // The corner counters as the game lays them out
+    let bounds = CameraBounds::from_fixed_aspect();
+    let mut instances = Vec::new();
+    for group in [level_group(12), progress_group(3, 40)] {
+        build_instances_for_group(&bounds, &group, HudStyle::default(), &mut instances);
+    }
+
+    // Glyphs frozen partway through a transition, one per animation phase, then the
+    // punctuation marks mid-swap
+    let transitions = [('3', '8'), ('1', '7'), ('9', '0'), ('5', '2'), ('6', '4')];
+    for (i, (from, to)) in transitions.into_iter().enumerate() {
+        instances.push(HudInstance {
+            mask: glyph_mask(to),
+            from_mask: glyph_mask(from),
+            transition_progress: 0.1 + 0.2 * i as f32,
+            pos: Vec2::new(0.6 + 0.8 * i as f32, 4.0),
+            scale: 0.4,
+            ..default()
+        });
+    }
+    for (i, (from, to)) in [(':', '.'), ('/', ':')].into_iter().enumerate() {
+        instances.push(HudInstance {
+            mask: glyph_mask(to),
+            from_mask: glyph_mask(from),
+            transition_progress: 0.5,
+            pos: Vec2::new(1.6 + 1.2 * i as f32, 2.5),
+            scale: 0.4,
+            ..default()
+        });
+    }
+
+    let mut material = SevenSegmentMaterial::default();
+    material.data.hud_count = instances.len() as u32;
+    material.data.hud[..instances.len()].copy_from_slice(&instances);
+    let material = app
+        .world_mut()
+        .resource_mut::<Assets<SevenSegmentMaterial>>()
+        .add(material);
+    let plane = frame_plane(&mut app, 0.5);
+    app.world_mut().spawn((plane, MeshMaterial3d(material)));
+
+    assert_matches_golden(&mut app, target, "hud_transitions");
+}
@@ -0,0 +1,854 @@
+//! Headless integration tests: the full game app on `MinimalPlugins`, driven by scripted
+//! pointer events (no window, GPU or render world).
+
+use std::time::Duration;
+
+use bevy::camera::ScalingMode;
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::{CursorMoved, WindowResized};
+
+use valence_sdf::{
+    camera::{CameraPlugin, CameraShake, MainCamera},
+    editor::{Editor, EditorPlugin},
+    event_log::{EventLog, EventLogPlugin},
+    game::{
+        clock::ClockPlugin,
+        director::Director,
+        error::AppError,
+        hints::HintWallet,
+        progression::{CompletionPolicy, ProgressionTracker},
+        puzzle::{CustomPuzzle, NextCustomPuzzle, PuzzleLibrary},
+        rng::GameRng,
+        session::PuzzleSession,
+        session::SessionResult,
+        skip::{LevelPuzzles, PuzzleChange},
+        tour::LevelTour,
+    },
+    graph::{NodeId, Valences},
+    input::{InputPlugin, PointerEventType, WorldPointerEvent},
+    net::{BoardState, NetPlugin, Replay, ReplayFrame, Spectator},
+    photo::{PhotoMode, PhotoPlugin},
+    profile::{Profile, ProfileBook, ProfilePlugin, Profiles},
+    score::{Score, ScorePlugin},
+    settings::{Settings, SettingsPlugin},
+    telemetry::{TelemetryPlugin, TelemetryQueue},
+    visual::{
+        interactions::{Combo, FleeMode, GrabState, HistoryStep},
+        nodes::{GraphNode, HintHighlight},
+        physics::NodePhysics,
+        plugin::GraphPlugin,
+        sdf::material::{SceneMaterialHandle, SdfSceneMaterial},
+        sdf::seven_segment::{Digit, SevenSegmentMaterial},
+        ui::{
+            LevelSummary, LevelTally, ProfileSelect,
+            hud::{HudMaterialHandle, HudPlane},
+        },
+    },
+};
+
+/// Build the game without any rendering and run Startup
+fn headless_app() -> App {
+    let mut app = unstarted_app();
+    app.update();
+    app
+}
+
+/// The headless game before Startup, for tests that add to it first
+fn unstarted_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        bevy::input::InputPlugin,
+    ))
+    // Window messages the input/camera systems listen for
+    .add_message::<CursorMoved>()
+    .add_message::<WindowResized>()
+    // Asset storage the scene and HUD write their uniforms into (no GPU upload)
+    .init_asset::<Mesh>()
+    .init_asset::<Image>()
+    .init_asset::<Shader>()
+    .init_asset::<SdfSceneMaterial>()
+    .init_asset::<SevenSegmentMaterial>()
+    // The camera plugin gives meshes their visibility in the real game
+    .register_required_components::<Mesh3d, Visibility>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        1.0 / 60.0,
+    )))
+    .insert_resource(GameRng::from_seed(7))
+    .add_plugins((
+        SettingsPlugin,
+        ClockPlugin,
+        CameraPlugin,
+        InputPlugin,
+        EventLogPlugin,
+        GraphPlugin,
+        NetPlugin,
+    ));
+    app
+}
+
+fn session(app: &App) -> &PuzzleSession {
+    app.world().resource::<PuzzleSession>()
+}
+
+fn node_position(app: &mut App, node: NodeId) -> Vec3 {
+    let mut nodes = app.world_mut().query::<(&GraphNode, &NodePhysics)>();
+    nodes
+        .iter(app.world())
+        .find(|(graph_node, _)| graph_node.node_id == node)
+        .map(|(_, physics)| physics.position)
+        .expect("node should be spawned")
+}
+
+/// Send one pointer event at a node and run a frame
+fn pointer_at(app: &mut App, event_type: PointerEventType, node: NodeId) {
+    let world_pos = node_position(app, node);
+    app.world_mut().write_message(WorldPointerEvent {
+        world_pos,
+        event_type,
+        id: 0,
+    });
+    app.update();
+}
+
+/// Press and release a key, a frame each
+fn tap_key(app: &mut App, key_code: KeyCode, key: &str) {
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world_mut().write_message(KeyboardInput {
+            key_code,
+            logical_key: Key::Character(key.into()),
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+}
+
+/// Depth-first search for a trail that completes the current puzzle
+fn find_solution_trail(session: &PuzzleSession) -> Vec<NodeId> {
+    fn search(session: &PuzzleSession, trail: &mut Vec<NodeId>) -> bool {
+        for index in 0..9 {
+            let node = NodeId(index);
+            let mut next = session.clone();
+            match next.add_node(node) {
+                SessionResult::Complete { .. } => {
+                    trail.push(node);
+                    return true;
+                }
+                SessionResult::FirstNode(_) | SessionResult::EdgeAdded(_) => {
+                    trail.push(node);
+                    if search(&next, trail) {
+                        return true;
+                    }
+                    trail.pop();
+                }
+                SessionResult::Invalid(_) => {}
+            }
+        }
+        false
+    }
+
+    let mut trail = Vec::new();
+    assert!(search(session, &mut trail), "puzzle should be solvable");
+    trail
+}
+
+/// Drag through a full trail: press on the first node, move through the rest, release
+fn drag_trail(app: &mut App, trail: &[NodeId]) {
+    pointer_at(app, PointerEventType::Down, trail[0]);
+    for &node in &trail[1..] {
+        pointer_at(app, PointerEventType::Move, node);
+    }
+    pointer_at(app, PointerEventType::Up, *trail.last().unwrap());
+}
+
+/// Tap through the summary that finishing a level puts up
+fn dismiss_summary(app: &mut App) {
+    assert!(
+        app.world().contains_resource::<LevelSummary>(),
+        "a finished level should show its summary"
+    );
+    pointer_at(app, PointerEventType::Down, NodeId(4));
+}
+
+#[test]
+fn startup_uploads_puzzle_to_scene_uniform() {
+    let app = headless_app();
+
+    let handle = &app.world().resource::<SceneMaterialHandle>().0;
+    let material = app
+        .world()
+        .resource::<Assets<SdfSceneMaterial>>()
+        .get(handle)
+        .expect("scene material should exist");
+
+    assert_eq!(material.data.num_spheres, 9);
+    assert_eq!(material.data.num_cylinders, 0);
+
+    let valences = session(&app).current_valences();
+    for index in 0..9 {
+        assert_eq!(
+            material.data.spheres[index].digit_value,
+            valences.get(NodeId(index)) as u32,
+            "sphere {index} should show its valence"
+        );
+    }
+}
+
+#[test]
+fn drawing_an_edge_updates_session_and_uniform() {
+    let mut app = headless_app();
+
+    // Level 1 is a single edge, so advance to a puzzle with room for a partial trail
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    dismiss_summary(&mut app);
+    let trail = find_solution_trail(session(&app));
+    assert!(trail.len() > 2, "level 2 should need more than one edge");
+
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    pointer_at(&mut app, PointerEventType::Move, trail[1]);
+
+    assert_eq!(session(&app).current_trail(), &trail[..2]);
+    assert_eq!(session(&app).edges().len(), 1);
+
+    let handle = &app.world().resource::<SceneMaterialHandle>().0;
+    let material = app
+        .world()
+        .resource::<Assets<SdfSceneMaterial>>()
+        .get(handle)
+        .unwrap();
+
+    // One drawn edge (plus possibly the preview edge following the pointer)
+    assert!(material.data.num_cylinders >= 1);
+    let valences = session(&app).current_valences();
+    for &node in &trail[..2] {
+        assert_eq!(
+            material.data.spheres[node.index()].digit_value,
+            valences.get(node) as u32
+        );
+    }
+}
+
+#[test]
+fn completing_a_level_advances_progression_and_hud() {
+    let mut app = headless_app();
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        1
+    );
+
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    app.update();
+
+    // The summary holds the level until it's dismissed
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        1
+    );
+    let summary = app.world().resource::<LevelSummary>();
+    // Level 1's single solution: one attempt is par
+    assert_eq!((summary.level, summary.attempts, summary.par), (1, 1, 1));
+    dismiss_summary(&mut app);
+    assert!(!app.world().contains_resource::<LevelSummary>());
+
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        2
+    );
+    assert_eq!(session(&app).progress().solutions_found, 0);
+
+    // The level number leads the HUD instances (the HUD catches up a frame later)
+    app.update();
+    let handle = &app.world().resource::<HudMaterialHandle>().0;
+    let hud = app
+        .world()
+        .resource::<Assets<SevenSegmentMaterial>>()
+        .get(handle)
+        .unwrap();
+    assert!(hud.data.hud_count > 0);
+    assert_eq!(hud.data.hud[0].mask, Digit::Two.mask() as u32);
+}
+
+#[test]
+fn invalid_press_activates_flee_and_is_logged() {
+    let mut app = headless_app();
+
+    // Any node with no valence left is an invalid start
+    let empty = (0..9)
+        .map(NodeId)
+        .find(|&node| session(&app).current_valences().get(node) == 0)
+        .expect("level 1 has empty nodes");
+
+    pointer_at(&mut app, PointerEventType::Down, empty);
+
+    assert!(session(&app).current_trail().is_empty());
+    assert!(app.world().resource::<FleeMode>().active);
+    let log = app.world().resource::<EventLog>();
+    assert!(log.recent(1).any(|entry| entry.message == "Invalid move"));
+}
+
+#[test]
+fn spectator_follows_replay_and_ignores_pointer() {
+    let mut app = headless_app();
+
+    // A recording of someone at level 5, one node into this puzzle
+    let trail = find_solution_trail(session(&app));
+    let mut theirs = session(&app).clone();
+    theirs.add_node(trail[0]);
+    let board = BoardState::capture(&theirs, &ProgressionTracker::at_level(5));
+    let line = serde_json::to_string(&ReplayFrame { at: 0.0, board }).unwrap();
+    app.insert_resource(Replay::parse(&line).unwrap())
+        .insert_resource(Spectator);
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        5
+    );
+    assert_eq!(session(&app).current_trail(), &trail[..1]);
+
+    // The board is read-only
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    pointer_at(&mut app, PointerEventType::Move, trail[1]);
+    assert_eq!(session(&app).current_trail(), &trail[..1]);
+
+    // Shake is turned up for the session only, not in the saved settings
+    let shake_boost = |app: &mut App| {
+        let mut cameras = app
+            .world_mut()
+            .query_filtered::<&CameraShake, With<MainCamera>>();
+        cameras.single(app.world()).unwrap().boost
+    };
+    assert!(shake_boost(&mut app) > 1.0);
+    assert_eq!(
+        app.world().resource::<Settings>().shake_intensity,
+        Settings::default().shake_intensity
+    );
+    app.world_mut().remove_resource::<Spectator>();
+    app.update();
+    assert_eq!(shake_boost(&mut app), 1.0);
+}
+
+#[test]
+fn app_error_blocks_play_until_retry() {
+    let mut app = headless_app();
+    let trail = find_solution_trail(session(&app));
+
+    app.insert_resource(AppError::new("Simulated library failure"));
+    app.update();
+
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    assert!(session(&app).current_trail().is_empty());
+    pointer_at(&mut app, PointerEventType::Up, trail[0]);
+
+    // R reloads the library and restarts the level
+    app.world_mut().write_message(KeyboardInput {
+        key_code: KeyCode::KeyR,
+        logical_key: Key::Character("r".into()),
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+    assert!(app.world().get_resource::<AppError>().is_none());
+
+    let trail = find_solution_trail(session(&app));
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    assert_eq!(session(&app).current_trail(), &trail[..1]);
+}
+
+#[test]
+fn undo_and_redo_step_through_the_drawn_trail() {
+    let mut app = unstarted_app();
+    app.add_plugins(ScorePlugin);
+    app.update();
+
+    // Level 2 has room for a partial trail
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    dismiss_summary(&mut app);
+    let trail = find_solution_trail(session(&app));
+    let combo = app.world().resource::<Combo>().edges;
+
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    let points = app.world().resource::<Score>().points;
+    pointer_at(&mut app, PointerEventType::Move, trail[1]);
+    assert_eq!(session(&app).current_trail(), &trail[..2]);
+    assert_eq!(app.world().resource::<Combo>().edges, combo + 1);
+    let scored = app.world().resource::<Score>().points - points;
+    assert!(scored > 0);
+
+    // The pointer is still on the undone node; it stays undone until the pointer leaves
+    app.world_mut().write_message(HistoryStep::Undo);
+    app.update();
+    assert_eq!(session(&app).current_trail(), &trail[..1]);
+    pointer_at(&mut app, PointerEventType::Move, trail[1]);
+    assert_eq!(session(&app).current_trail(), &trail[..1]);
+
+    app.world_mut().write_message(HistoryStep::Redo);
+    app.update();
+    assert_eq!(session(&app).current_trail(), &trail[..2]);
+    assert_eq!(session(&app).edges().len(), 1);
+    // Redoing the edge doesn't count towards the combo or the score again
+    assert_eq!(app.world().resource::<Combo>().edges, combo + 1);
+    assert_eq!(app.world().resource::<Score>().points, points + scored);
+}
+
+#[test]
+fn custom_puzzles_stay_out_of_shared_boards() {
+    let mut app = headless_app();
+    let custom = [2, 2, 0, 0, 2, 0, 0, 0, 0];
+    app.world_mut()
+        .resource_mut::<PuzzleLibrary>()
+        .merge_custom(&[CustomPuzzle {
+            name: None,
+            valences: custom,
+        }])
+        .unwrap();
+
+    // Watching someone else's board, the Custom tab doesn't swap it out
+    app.insert_resource(Spectator);
+    let watched = session(&app).puzzle_valences().clone();
+    app.world_mut().write_message(NextCustomPuzzle);
+    app.update();
+    assert_eq!(session(&app).puzzle_valences(), &watched);
+
+    app.world_mut().remove_resource::<Spectator>();
+    app.world_mut().write_message(NextCustomPuzzle);
+    app.update();
+    assert_eq!(
+        session(&app).puzzle_valences(),
+        &Valences::from_array(custom)
+    );
+}
+
+#[test]
+fn editor_stays_shut_on_a_watched_board() {
+    let mut app = headless_app();
+    app.add_plugins(EditorPlugin).insert_resource(Spectator);
+
+    tap_key(&mut app, KeyCode::KeyB, "b");
+    assert!(!app.world().contains_resource::<Editor>());
+
+    app.world_mut().remove_resource::<Spectator>();
+    tap_key(&mut app, KeyCode::KeyB, "b");
+    assert!(app.world().contains_resource::<Editor>());
+}
+
+#[test]
+fn skipping_puts_up_another_puzzle_on_the_same_level() {
+    let mut app = headless_app();
+    let skipped = session(&app).puzzle_valences().clone();
+
+    app.world_mut().write_message(PuzzleChange::Skip);
+    app.update();
+
+    assert_ne!(session(&app).puzzle_valences(), &skipped);
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        1
+    );
+    assert_eq!(app.world().resource::<LevelPuzzles>().skipped_count(1), 1);
+    // Both puzzles count as served on the level's tour
+    assert_eq!(app.world().resource::<LevelTour>().tried(1).len(), 2);
+}
+
+#[test]
+fn a_new_puzzle_rebuilds_the_board() {
+    let mut app = headless_app();
+    let nodes = |app: &mut App| {
+        let mut nodes = app.world_mut().query::<(Entity, &NodePhysics)>();
+        nodes
+            .iter(app.world())
+            .map(|(entity, physics)| (entity, physics.rest_position))
+            .collect::<Vec<_>>()
+    };
+    let old_nodes = nodes(&mut app);
+
+    app.world_mut().write_message(PuzzleChange::Skip);
+    app.update();
+    app.update();
+
+    // Fresh nodes at the same spots, showing the new puzzle
+    let new_nodes = nodes(&mut app);
+    assert_eq!(new_nodes.len(), 9);
+    for (entity, rest) in &new_nodes {
+        assert!(old_nodes.iter().all(|(old, _)| old != entity));
+        assert!(old_nodes.iter().any(|(_, old_rest)| old_rest == rest));
+    }
+
+    let handle = &app.world().resource::<SceneMaterialHandle>().0;
+    let material = app
+        .world()
+        .resource::<Assets<SdfSceneMaterial>>()
+        .get(handle)
+        .unwrap();
+    assert_eq!(material.data.num_spheres, 9);
+    assert_eq!(material.data.num_cylinders, 0);
+    let valences = session(&app).puzzle_valences();
+    for index in 0..9 {
+        assert_eq!(
+            material.data.spheres[index].digit_value,
+            valences.get(NodeId(index)) as u32
+        );
+    }
+}
+
+#[test]
+fn adaptive_difficulty_hints_once_the_player_is_stuck() {
+    let mut app = headless_app();
+    app.world_mut().resource_mut::<LevelTally>().seconds = 600.0;
+    app.update();
+    assert!(
+        app.world().resource::<HintHighlight>().node.is_none(),
+        "adaptive difficulty is off by default"
+    );
+
+    app.world_mut().resource_mut::<Settings>().adaptive = true;
+    app.update();
+    assert!(app.world().resource::<Director>().stuck_on(1));
+    let hinted = app
+        .world()
+        .resource::<HintHighlight>()
+        .node
+        .expect("a stuck player should get a hint");
+
+    // The hint is a good first move: following it keeps a solution in reach
+    pointer_at(&mut app, PointerEventType::Down, hinted);
+    assert_eq!(session(&app).current_trail(), &[hinted]);
+    assert!(app.world().resource::<HintHighlight>().node.is_some());
+}
+
+#[test]
+fn hint_token_buys_one_move() {
+    let mut app = headless_app();
+
+    // Finishing level 1 earns the token
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    dismiss_summary(&mut app);
+    assert_eq!(app.world().resource::<HintWallet>().tokens, 1);
+
+    app.world_mut().write_message(KeyboardInput {
+        key_code: KeyCode::KeyH,
+        logical_key: Key::Character("h".into()),
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+    assert_eq!(app.world().resource::<HintWallet>().tokens, 0);
+    let hinted = app
+        .world()
+        .resource::<HintHighlight>()
+        .node
+        .expect("a token should show the next move");
+
+    // Playing it uses up the hint
+    pointer_at(&mut app, PointerEventType::Down, hinted);
+    assert!(app.world().resource::<HintHighlight>().node.is_none());
+}
+
+#[test]
+fn autosaved_board_resumes_mid_level() {
+    let path =
+        std::env::temp_dir().join(format!("valence_sdf_autosave_{}.json", std::process::id()));
+
+    // Play on to a level with more than one solution
+    let mut app = headless_app();
+    while session(&app).progress().total_solutions < Some(2) {
+        let trail = find_solution_trail(session(&app));
+        drag_trail(&mut app, &trail);
+        dismiss_summary(&mut app);
+    }
+
+    // Closed one solution and one edge into it
+    let mut saved = session(&app).clone();
+    let trail = find_solution_trail(&saved);
+    for &node in &trail {
+        saved.add_node(node);
+    }
+    saved.reset();
+    saved.add_node(trail[0]);
+    saved.add_node(trail[1]);
+    let level = app.world().resource::<ProgressionTracker>().current_level;
+    let board = BoardState::capture(&saved, &ProgressionTracker::at_level(level));
+    let mut book = ProfileBook::default();
+    book.active_mut().level = level;
+    book.active_mut().board = Some(board.clone());
+    std::fs::write(&path, serde_json::to_string(&book).unwrap()).unwrap();
+
+    let mut app = unstarted_app();
+    app.insert_resource(Profiles::at(path.to_string_lossy()))
+        .add_plugins(ProfilePlugin);
+    app.update();
+
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        level
+    );
+    assert_eq!(session(&app).found_solutions(), saved.found_solutions());
+    assert_eq!(session(&app).current_trail(), &trail[..2]);
+
+    app.update();
+    let handle = &app.world().resource::<SceneMaterialHandle>().0;
+    let material = app
+        .world()
+        .resource::<Assets<SdfSceneMaterial>>()
+        .get(handle)
+        .unwrap();
+    assert!(material.data.num_cylinders >= 1);
+    let valences = session(&app).current_valences();
+    for &node in &trail[..2] {
+        assert_eq!(
+            material.data.spheres[node.index()].digit_value,
+            valences.get(node) as u32
+        );
+    }
+
+    // Pressing the trail's end carries on drawing, and the save follows along
+    pointer_at(&mut app, PointerEventType::Down, trail[1]);
+    pointer_at(&mut app, PointerEventType::Move, trail[2]);
+    assert_eq!(session(&app).current_trail(), &trail[..3]);
+
+    let resaved = ProfileBook::parse(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let resaved = resaved.active().board.clone().unwrap();
+    assert_eq!(resaved.trail.len(), 3);
+    assert_eq!(resaved.found, board.found);
+}
+
+#[test]
+fn profile_select_switches_to_the_chosen_profile() {
+    let path =
+        std::env::temp_dir().join(format!("valence_sdf_profiles_{}.json", std::process::id()));
+    let mut book = ProfileBook::default();
+    let mut second = Profile::new("Player 2");
+    second.level = 3;
+    second.settings.completion = CompletionPolicy::FirstSolution;
+    book.profiles.push(second);
+    std::fs::write(&path, serde_json::to_string(&book).unwrap()).unwrap();
+
+    let mut app = unstarted_app();
+    app.insert_resource(Profiles::at(path.to_string_lossy()))
+        .add_plugins(ProfilePlugin);
+    app.update();
+    assert!(app.world().contains_resource::<ProfileSelect>());
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        1
+    );
+
+    app.world_mut().write_message(KeyboardInput {
+        key_code: KeyCode::Digit2,
+        logical_key: Key::Character("2".into()),
+        state: ButtonState::Pressed,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+
+    assert!(!app.world().contains_resource::<ProfileSelect>());
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        3
+    );
+    assert_eq!(
+        *app.world().resource::<CompletionPolicy>(),
+        CompletionPolicy::FirstSolution
+    );
+
+    let saved = ProfileBook::parse(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(saved.active, 1);
+    assert_eq!(
+        saved.active().board.as_ref().map(|board| board.level),
+        Some(3)
+    );
+}
+
+#[test]
+fn finishing_a_level_scores_and_records_a_high_score() {
+    let path = std::env::temp_dir().join(format!("valence_sdf_scores_{}.json", std::process::id()));
+    std::fs::write(
+        &path,
+        serde_json::to_string(&ProfileBook::default()).unwrap(),
+    )
+    .unwrap();
+
+    let mut app = unstarted_app();
+    app.insert_resource(Profiles::at(path.to_string_lossy()))
+        .add_plugins((ProfilePlugin, ScorePlugin));
+    app.update();
+
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    app.update();
+    assert!(app.world().contains_resource::<LevelSummary>());
+
+    let score = *app.world().resource::<Score>();
+    assert_eq!(score.level, 1);
+    // Every edge, plus the new solution's bonus
+    assert!(score.points > 10 * (trail.len() as u32 - 1));
+
+    let saved = ProfileBook::parse(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let best: Vec<(usize, u32)> = saved
+        .high_scores
+        .table(CompletionPolicy::default())
+        .map(|entry| (entry.level, entry.points))
+        .collect();
+    assert_eq!(best, vec![(1, score.points)]);
+}
+
+#[test]
+fn photo_mode_hides_the_hud_and_frames_the_board() {
+    let mut app = unstarted_app();
+    app.add_plugins(PhotoPlugin);
+    app.update();
+    let view_height = |app: &mut App| {
+        let mut cameras = app
+            .world_mut()
+            .query_filtered::<&Projection, With<MainCamera>>();
+        match cameras.single(app.world()).unwrap() {
+            Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical { viewport_height },
+                ..
+            }) => *viewport_height,
+            _ => panic!("the game camera is orthographic, with a fixed height"),
+        }
+    };
+    let framed = view_height(&mut app);
+    let hud_visibility = |app: &mut App| {
+        let mut hud = app
+            .world_mut()
+            .query_filtered::<&Visibility, With<HudPlane>>();
+        *hud.single(app.world()).unwrap()
+    };
+
+    tap_key(&mut app, KeyCode::KeyF, "f");
+    assert!(app.world().contains_resource::<PhotoMode>());
+    assert_eq!(hud_visibility(&mut app), Visibility::Hidden);
+
+    tap_key(&mut app, KeyCode::Equal, "=");
+    assert!(view_height(&mut app) < framed);
+
+    // The board doesn't take taps while framing a shot
+    let trail = find_solution_trail(session(&app));
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    assert!(session(&app).current_trail().is_empty());
+
+    tap_key(&mut app, KeyCode::KeyF, "f");
+    assert!(!app.world().contains_resource::<PhotoMode>());
+    assert_eq!(view_height(&mut app), framed);
+    assert_eq!(hud_visibility(&mut app), Visibility::Inherited);
+}
+
+#[test]
+fn photo_mode_waits_for_the_summary() {
+    let mut app = headless_app();
+    app.add_plugins(PhotoPlugin);
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    app.update();
+    assert!(app.world().contains_resource::<LevelSummary>());
+
+    tap_key(&mut app, KeyCode::KeyF, "f");
+    assert!(!app.world().contains_resource::<PhotoMode>());
+
+    dismiss_summary(&mut app);
+    tap_key(&mut app, KeyCode::KeyF, "f");
+    assert!(app.world().contains_resource::<PhotoMode>());
+}
+
+#[test]
+fn first_solution_policy_moves_on_after_one_solution() {
+    let mut app = headless_app();
+    while session(&app).progress().total_solutions < Some(2) {
+        let trail = find_solution_trail(session(&app));
+        drag_trail(&mut app, &trail);
+        dismiss_summary(&mut app);
+    }
+    let level = app.world().resource::<ProgressionTracker>().current_level;
+
+    app.insert_resource(CompletionPolicy::FirstSolution);
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    dismiss_summary(&mut app);
+
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        level + 1
+    );
+}
+
+#[test]
+fn telemetry_reports_only_solved_levels() {
+    let mut app = unstarted_app();
+    app.add_plugins(TelemetryPlugin);
+    app.update();
+    app.world_mut().resource_mut::<Settings>().telemetry_opt_in = true;
+    app.update();
+
+    // Jumping to another level (a link, a profile switch) isn't finishing this one
+    app.insert_resource(ProgressionTracker::at_level(5));
+    app.update();
+    assert!(app.world().resource::<TelemetryQueue>().is_empty());
+
+    let trail = find_solution_trail(session(&app));
+    drag_trail(&mut app, &trail);
+    dismiss_summary(&mut app);
+    app.update();
+    assert_eq!(
+        app.world().resource::<ProgressionTracker>().current_level,
+        6
+    );
+    assert_eq!(app.world().resource::<TelemetryQueue>().len(), 1);
+}
+
+#[test]
+fn shift_drag_grabs_a_node_without_drawing() {
+    let mut app = headless_app();
+    let trail = find_solution_trail(session(&app));
+    let shift = |state| KeyboardInput {
+        key_code: KeyCode::ShiftLeft,
+        logical_key: Key::Shift,
+        state,
+        text: None,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    };
+
+    app.world_mut().write_message(shift(ButtonState::Pressed));
+    app.update();
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    assert_eq!(app.world().resource::<GrabState>().grabbed, Some(trail[0]));
+    // The grab leaves the press in place for every other reader
+    assert!(
+        !app.world()
+            .resource::<Messages<WorldPointerEvent>>()
+            .is_empty()
+    );
+    pointer_at(&mut app, PointerEventType::Move, trail[1]);
+    // Let go of Shift mid-drag: the press is still the grab's, through to its release
+    app.world_mut().write_message(shift(ButtonState::Released));
+    pointer_at(&mut app, PointerEventType::Up, trail[1]);
+    app.update();
+    assert!(!app.world().resource::<GrabState>().is_grabbing());
+    assert!(session(&app).current_trail().is_empty());
+
+    // Plain presses draw again
+    pointer_at(&mut app, PointerEventType::Down, trail[0]);
+    assert_eq!(session(&app).current_trail(), &trail[..1]);
+}